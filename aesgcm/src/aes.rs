@@ -0,0 +1,564 @@
+//! Constant-time bitsliced AES-128 and AES-256.
+//!
+//! Four blocks are processed in parallel, spread over eight 64-bit words. The
+//! S-box is computed with the Boyar–Peralta circuit, so there are no secret
+//! dependent table lookups or branches. This follows the `aes_ct64`
+//! implementation of [BearSSL](https://bearssl.org/constanttime.html).
+
+/// The AES block length.
+pub(crate) const BLOCK_LEN: usize = 16;
+
+/// The number of blocks that are encrypted in parallel.
+pub(crate) const PARALLEL_BLOCKS: usize = 4;
+
+const RCON: [u32; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// Four blocks in bitsliced representation.
+type State = [u64; 8];
+
+/// The bitsliced S-box, applied to all 16 bytes of the 4 blocks at once.
+fn sub_bytes(q: &mut State) {
+    let x0 = q[7];
+    let x1 = q[6];
+    let x2 = q[5];
+    let x3 = q[4];
+    let x4 = q[3];
+    let x5 = q[2];
+    let x6 = q[1];
+    let x7 = q[0];
+
+    // Top linear transformation.
+    let y14 = x3 ^ x5;
+    let y13 = x0 ^ x6;
+    let y9 = x0 ^ x3;
+    let y8 = x0 ^ x5;
+    let t0 = x1 ^ x2;
+    let y1 = t0 ^ x7;
+    let y4 = y1 ^ x3;
+    let y12 = y13 ^ y14;
+    let y2 = y1 ^ x0;
+    let y5 = y1 ^ x6;
+    let y3 = y5 ^ y8;
+    let t1 = x4 ^ y12;
+    let y15 = t1 ^ x5;
+    let y20 = t1 ^ x1;
+    let y6 = y15 ^ x7;
+    let y10 = y15 ^ t0;
+    let y11 = y20 ^ y9;
+    let y7 = x7 ^ y11;
+    let y17 = y10 ^ y11;
+    let y19 = y10 ^ y8;
+    let y16 = t0 ^ y11;
+    let y21 = y13 ^ y16;
+    let y18 = x0 ^ y16;
+
+    // Non-linear section.
+    let t2 = y12 & y15;
+    let t3 = y3 & y6;
+    let t4 = t3 ^ t2;
+    let t5 = y4 & x7;
+    let t6 = t5 ^ t2;
+    let t7 = y13 & y16;
+    let t8 = y5 & y1;
+    let t9 = t8 ^ t7;
+    let t10 = y2 & y7;
+    let t11 = t10 ^ t7;
+    let t12 = y9 & y11;
+    let t13 = y14 & y17;
+    let t14 = t13 ^ t12;
+    let t15 = y8 & y10;
+    let t16 = t15 ^ t12;
+    let t17 = t4 ^ t14;
+    let t18 = t6 ^ t16;
+    let t19 = t9 ^ t14;
+    let t20 = t11 ^ t16;
+    let t21 = t17 ^ y20;
+    let t22 = t18 ^ y19;
+    let t23 = t19 ^ y21;
+    let t24 = t20 ^ y18;
+
+    let t25 = t21 ^ t22;
+    let t26 = t21 & t23;
+    let t27 = t24 ^ t26;
+    let t28 = t25 & t27;
+    let t29 = t28 ^ t22;
+    let t30 = t23 ^ t24;
+    let t31 = t22 ^ t26;
+    let t32 = t31 & t30;
+    let t33 = t32 ^ t24;
+    let t34 = t23 ^ t33;
+    let t35 = t27 ^ t33;
+    let t36 = t24 & t35;
+    let t37 = t36 ^ t34;
+    let t38 = t27 ^ t36;
+    let t39 = t29 & t38;
+    let t40 = t25 ^ t39;
+
+    let t41 = t40 ^ t37;
+    let t42 = t29 ^ t33;
+    let t43 = t29 ^ t40;
+    let t44 = t33 ^ t37;
+    let t45 = t42 ^ t41;
+    let z0 = t44 & y15;
+    let z1 = t37 & y6;
+    let z2 = t33 & x7;
+    let z3 = t43 & y16;
+    let z4 = t40 & y1;
+    let z5 = t29 & y7;
+    let z6 = t42 & y11;
+    let z7 = t45 & y17;
+    let z8 = t41 & y10;
+    let z9 = t44 & y12;
+    let z10 = t37 & y3;
+    let z11 = t33 & y4;
+    let z12 = t43 & y13;
+    let z13 = t40 & y5;
+    let z14 = t29 & y2;
+    let z15 = t42 & y9;
+    let z16 = t45 & y14;
+    let z17 = t41 & y8;
+
+    // Bottom linear transformation.
+    let t46 = z15 ^ z16;
+    let t47 = z10 ^ z11;
+    let t48 = z5 ^ z13;
+    let t49 = z9 ^ z10;
+    let t50 = z2 ^ z12;
+    let t51 = z2 ^ z5;
+    let t52 = z7 ^ z8;
+    let t53 = z0 ^ z3;
+    let t54 = z6 ^ z7;
+    let t55 = z16 ^ z17;
+    let t56 = z12 ^ t48;
+    let t57 = t50 ^ t53;
+    let t58 = z4 ^ t46;
+    let t59 = z3 ^ t54;
+    let t60 = t46 ^ t57;
+    let t61 = z14 ^ t57;
+    let t62 = t52 ^ t58;
+    let t63 = t49 ^ t58;
+    let t64 = z4 ^ t59;
+    let t65 = t61 ^ t62;
+    let t66 = z1 ^ t63;
+    let s0 = t59 ^ t63;
+    let s6 = t56 ^ !t62;
+    let s7 = t48 ^ !t60;
+    let t67 = t64 ^ t65;
+    let s3 = t53 ^ t66;
+    let s4 = t51 ^ t66;
+    let s5 = t47 ^ t65;
+    let s1 = t64 ^ !s3;
+    let s2 = t55 ^ !t67;
+
+    q[7] = s0;
+    q[6] = s1;
+    q[5] = s2;
+    q[4] = s3;
+    q[3] = s4;
+    q[2] = s5;
+    q[1] = s6;
+    q[0] = s7;
+}
+
+/// The affine transformation of the inverse S-box, as bitsliced code.
+fn inv_affine(q: &mut State) {
+    let [q0, q1, q2, q3, q4, q5, q6, q7] = *q;
+    let (q0, q1, q5, q6) = (!q0, !q1, !q5, !q6);
+
+    q[7] = q1 ^ q4 ^ q6;
+    q[6] = q0 ^ q3 ^ q5;
+    q[5] = q7 ^ q2 ^ q4;
+    q[4] = q6 ^ q1 ^ q3;
+    q[3] = q5 ^ q0 ^ q2;
+    q[2] = q4 ^ q7 ^ q1;
+    q[1] = q3 ^ q6 ^ q0;
+    q[0] = q2 ^ q5 ^ q7;
+}
+
+/// The bitsliced inverse S-box. The S-box is an inversion followed by an
+/// affine transformation, so the inverse is obtained by wrapping it in the
+/// inverse affine transformation.
+fn inv_sub_bytes(q: &mut State) {
+    inv_affine(q);
+    sub_bytes(q);
+    inv_affine(q);
+}
+
+#[inline(always)]
+fn swap_bits(q: &mut State, x: usize, y: usize, cl: u64, ch: u64, s: u32) {
+    let a = q[x];
+    let b = q[y];
+    q[x] = (a & cl) | ((b & cl) << s);
+    q[y] = ((a & ch) >> s) | (b & ch);
+}
+
+/// Convert between the interleaved and the bitsliced representation. This is
+/// an involution.
+fn ortho(q: &mut State) {
+    const CL2: u64 = 0x5555555555555555;
+    const CH2: u64 = 0xAAAAAAAAAAAAAAAA;
+    const CL4: u64 = 0x3333333333333333;
+    const CH4: u64 = 0xCCCCCCCCCCCCCCCC;
+    const CL8: u64 = 0x0F0F0F0F0F0F0F0F;
+    const CH8: u64 = 0xF0F0F0F0F0F0F0F0;
+
+    swap_bits(q, 0, 1, CL2, CH2, 1);
+    swap_bits(q, 2, 3, CL2, CH2, 1);
+    swap_bits(q, 4, 5, CL2, CH2, 1);
+    swap_bits(q, 6, 7, CL2, CH2, 1);
+
+    swap_bits(q, 0, 2, CL4, CH4, 2);
+    swap_bits(q, 1, 3, CL4, CH4, 2);
+    swap_bits(q, 4, 6, CL4, CH4, 2);
+    swap_bits(q, 5, 7, CL4, CH4, 2);
+
+    swap_bits(q, 0, 4, CL8, CH8, 4);
+    swap_bits(q, 1, 5, CL8, CH8, 4);
+    swap_bits(q, 2, 6, CL8, CH8, 4);
+    swap_bits(q, 3, 7, CL8, CH8, 4);
+}
+
+/// Spread the four little-endian words of a block over two 64-bit words.
+fn interleave_in(w: &[u32]) -> (u64, u64) {
+    let mut x = [w[0] as u64, w[1] as u64, w[2] as u64, w[3] as u64];
+    for x in x.iter_mut() {
+        *x |= *x << 16;
+        *x &= 0x0000FFFF0000FFFF;
+        *x |= *x << 8;
+        *x &= 0x00FF00FF00FF00FF;
+    }
+
+    (x[0] | (x[2] << 8), x[1] | (x[3] << 8))
+}
+
+/// The inverse of [`interleave_in`].
+fn interleave_out(q0: u64, q1: u64) -> [u32; 4] {
+    let mut x = [
+        q0 & 0x00FF00FF00FF00FF,
+        q1 & 0x00FF00FF00FF00FF,
+        (q0 >> 8) & 0x00FF00FF00FF00FF,
+        (q1 >> 8) & 0x00FF00FF00FF00FF,
+    ];
+    for x in x.iter_mut() {
+        *x |= *x >> 8;
+        *x &= 0x0000FFFF0000FFFF;
+    }
+
+    [
+        x[0] as u32 | (x[0] >> 16) as u32,
+        x[1] as u32 | (x[1] >> 16) as u32,
+        x[2] as u32 | (x[2] >> 16) as u32,
+        x[3] as u32 | (x[3] >> 16) as u32,
+    ]
+}
+
+fn add_round_key(q: &mut State, round_key: &State) {
+    for (q, k) in q.iter_mut().zip(round_key.iter()) {
+        *q ^= k;
+    }
+}
+
+fn shift_rows(q: &mut State) {
+    for x in q.iter_mut() {
+        *x = (*x & 0x000000000000FFFF)
+            | ((*x & 0x00000000FFF00000) >> 4)
+            | ((*x & 0x00000000000F0000) << 12)
+            | ((*x & 0x0000FF0000000000) >> 8)
+            | ((*x & 0x000000FF00000000) << 8)
+            | ((*x & 0xF000000000000000) >> 12)
+            | ((*x & 0x0FFF000000000000) << 4);
+    }
+}
+
+fn mix_columns(q: &mut State) {
+    let [q0, q1, q2, q3, q4, q5, q6, q7] = *q;
+    let [r0, r1, r2, r3, r4, r5, r6, r7] = q.map(|x| x.rotate_right(16));
+
+    q[0] = q7 ^ r7 ^ r0 ^ (q0 ^ r0).rotate_right(32);
+    q[1] = q0 ^ r0 ^ q7 ^ r7 ^ r1 ^ (q1 ^ r1).rotate_right(32);
+    q[2] = q1 ^ r1 ^ r2 ^ (q2 ^ r2).rotate_right(32);
+    q[3] = q2 ^ r2 ^ q7 ^ r7 ^ r3 ^ (q3 ^ r3).rotate_right(32);
+    q[4] = q3 ^ r3 ^ q7 ^ r7 ^ r4 ^ (q4 ^ r4).rotate_right(32);
+    q[5] = q4 ^ r4 ^ r5 ^ (q5 ^ r5).rotate_right(32);
+    q[6] = q5 ^ r5 ^ r6 ^ (q6 ^ r6).rotate_right(32);
+    q[7] = q6 ^ r6 ^ r7 ^ (q7 ^ r7).rotate_right(32);
+}
+
+fn inv_shift_rows(q: &mut State) {
+    for x in q.iter_mut() {
+        *x = (*x & 0x000000000000FFFF)
+            | ((*x & 0x000000000FFF0000) << 4)
+            | ((*x & 0x00000000F0000000) >> 12)
+            | ((*x & 0x000000FF00000000) << 8)
+            | ((*x & 0x0000FF0000000000) >> 8)
+            | ((*x & 0x000F000000000000) << 12)
+            | ((*x & 0xFFF0000000000000) >> 4);
+    }
+}
+
+fn inv_mix_columns(q: &mut State) {
+    let [q0, q1, q2, q3, q4, q5, q6, q7] = *q;
+    let [r0, r1, r2, r3, r4, r5, r6, r7] = q.map(|x| x.rotate_right(16));
+
+    q[0] = q5 ^ q6 ^ q7 ^ r0 ^ r5 ^ r7 ^ (q0 ^ q5 ^ q6 ^ r0 ^ r5).rotate_right(32);
+    q[1] = q0 ^ q5 ^ r0 ^ r1 ^ r5 ^ r6 ^ r7 ^ (q1 ^ q5 ^ q7 ^ r1 ^ r5 ^ r6).rotate_right(32);
+    q[2] = q0 ^ q1 ^ q6 ^ r1 ^ r2 ^ r6 ^ r7 ^ (q0 ^ q2 ^ q6 ^ r2 ^ r6 ^ r7).rotate_right(32);
+    q[3] = q0
+        ^ q1
+        ^ q2
+        ^ q5
+        ^ q6
+        ^ r0
+        ^ r2
+        ^ r3
+        ^ r5
+        ^ (q0 ^ q1 ^ q3 ^ q5 ^ q6 ^ q7 ^ r0 ^ r3 ^ r5 ^ r7).rotate_right(32);
+    q[4] = q1
+        ^ q2
+        ^ q3
+        ^ q5
+        ^ r1
+        ^ r3
+        ^ r4
+        ^ r5
+        ^ r6
+        ^ r7
+        ^ (q1 ^ q2 ^ q4 ^ q5 ^ q7 ^ r1 ^ r4 ^ r5 ^ r6).rotate_right(32);
+    q[5] = q2
+        ^ q3
+        ^ q4
+        ^ q6
+        ^ r2
+        ^ r4
+        ^ r5
+        ^ r6
+        ^ r7
+        ^ (q2 ^ q3 ^ q5 ^ q6 ^ r2 ^ r5 ^ r6 ^ r7).rotate_right(32);
+    q[6] =
+        q3 ^ q4 ^ q5 ^ q7 ^ r3 ^ r5 ^ r6 ^ r7 ^ (q3 ^ q4 ^ q6 ^ q7 ^ r3 ^ r6 ^ r7).rotate_right(32);
+    q[7] = q4 ^ q5 ^ q6 ^ r4 ^ r6 ^ r7 ^ (q4 ^ q5 ^ q7 ^ r4 ^ r7).rotate_right(32);
+}
+
+/// Apply the S-box to each byte of `x`.
+fn sub_word(x: u32) -> u32 {
+    let mut q = [0u64; 8];
+    q[0] = x as u64;
+    ortho(&mut q);
+    sub_bytes(&mut q);
+    ortho(&mut q);
+    q[0] as u32
+}
+
+/// Expand a 16 byte AES-128 or 32 byte AES-256 key into little-endian round
+/// key words. Returns the words and the number of rounds.
+pub(crate) fn expand_key(key: &[u8]) -> ([u32; 60], usize) {
+    debug_assert!(key.len() == 16 || key.len() == 32);
+
+    let nk = key.len() / 4;
+    let rounds = nk + 6;
+    let nkf = (rounds + 1) * 4;
+
+    let mut words = [0u32; 60];
+    for (word, chunk) in words.iter_mut().zip(key.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut tmp = words[nk - 1];
+    let mut j = 0;
+    let mut k = 0;
+    for i in nk..nkf {
+        if j == 0 {
+            tmp = sub_word(tmp.rotate_right(8)) ^ RCON[k];
+        } else if nk > 6 && j == 4 {
+            tmp = sub_word(tmp);
+        }
+        tmp ^= words[i - nk];
+        words[i] = tmp;
+
+        j += 1;
+        if j == nk {
+            j = 0;
+            k += 1;
+        }
+    }
+
+    (words, rounds)
+}
+
+/// An expanded AES key in bitsliced representation.
+pub(crate) struct Aes {
+    round_keys: [State; 15],
+    rounds: usize,
+}
+
+impl Aes {
+    /// Expand a 16 byte AES-128 or 32 byte AES-256 key.
+    pub(crate) fn new(key: &[u8]) -> Self {
+        let (words, rounds) = expand_key(key);
+        let nkf = (rounds + 1) * 4;
+
+        // The round keys are the same for all four blocks.
+        let mut round_keys = [[0u64; 8]; 15];
+        for (round_key, w) in round_keys.iter_mut().zip(words[..nkf].chunks_exact(4)) {
+            let (a, b) = interleave_in(w);
+            *round_key = [a, a, a, a, b, b, b, b];
+            ortho(round_key);
+        }
+
+        Self { round_keys, rounds }
+    }
+
+    /// Encrypt four blocks in place.
+    pub(crate) fn encrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        let mut q = load_blocks(blocks);
+
+        add_round_key(&mut q, &self.round_keys[0]);
+        for round_key in &self.round_keys[1..self.rounds] {
+            sub_bytes(&mut q);
+            shift_rows(&mut q);
+            mix_columns(&mut q);
+            add_round_key(&mut q, round_key);
+        }
+        sub_bytes(&mut q);
+        shift_rows(&mut q);
+        add_round_key(&mut q, &self.round_keys[self.rounds]);
+
+        store_blocks(q, blocks);
+    }
+
+    /// Decrypt four blocks in place.
+    pub(crate) fn decrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        let mut q = load_blocks(blocks);
+
+        add_round_key(&mut q, &self.round_keys[self.rounds]);
+        for round_key in self.round_keys[1..self.rounds].iter().rev() {
+            inv_shift_rows(&mut q);
+            inv_sub_bytes(&mut q);
+            add_round_key(&mut q, round_key);
+            inv_mix_columns(&mut q);
+        }
+        inv_shift_rows(&mut q);
+        inv_sub_bytes(&mut q);
+        add_round_key(&mut q, &self.round_keys[0]);
+
+        store_blocks(q, blocks);
+    }
+}
+
+/// Convert four blocks into the bitsliced representation.
+fn load_blocks(blocks: &[[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) -> State {
+    let mut q = [0u64; 8];
+    for (i, block) in blocks.iter().enumerate() {
+        let mut w = [0u32; 4];
+        for (w, chunk) in w.iter_mut().zip(block.chunks_exact(4)) {
+            *w = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        (q[i], q[i + 4]) = interleave_in(&w);
+    }
+    ortho(&mut q);
+    q
+}
+
+/// The inverse of [`load_blocks`].
+fn store_blocks(mut q: State, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+    ortho(&mut q);
+    for (i, block) in blocks.iter_mut().enumerate() {
+        let w = interleave_out(q[i], q[i + 4]);
+        for (chunk, w) in block.chunks_exact_mut(4).zip(w.iter()) {
+            chunk.copy_from_slice(&w.to_le_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The S-box computed from its definition: inversion in GF(2^8) followed
+    /// by the affine transformation.
+    fn sbox_reference(x: u8) -> u8 {
+        fn gmul(mut a: u8, mut b: u8) -> u8 {
+            let mut p = 0;
+            while b != 0 {
+                if b & 1 == 1 {
+                    p ^= a;
+                }
+                let carry = a & 0x80;
+                a <<= 1;
+                if carry != 0 {
+                    a ^= 0x1b;
+                }
+                b >>= 1;
+            }
+            p
+        }
+
+        // x^254 = x^-1
+        let mut inv = 1;
+        for _ in 0..254 {
+            inv = gmul(inv, x);
+        }
+
+        inv ^ inv.rotate_left(1)
+            ^ inv.rotate_left(2)
+            ^ inv.rotate_left(3)
+            ^ inv.rotate_left(4)
+            ^ 0x63
+    }
+
+    #[test]
+    fn sbox() {
+        for x in 0..=255u8 {
+            let word = u32::from_le_bytes([x, x, x, x]);
+            let expected = sbox_reference(x);
+            assert_eq!(sub_word(word), u32::from_le_bytes([expected; 4]), "{x}");
+        }
+    }
+
+    #[test]
+    fn inv_sbox() {
+        for x in 0..=255u8 {
+            let mut q = [0u64; 8];
+            q[0] = u32::from_le_bytes([sbox_reference(x); 4]) as u64;
+            ortho(&mut q);
+            inv_sub_bytes(&mut q);
+            ortho(&mut q);
+            assert_eq!(q[0] as u32, u32::from_le_bytes([x; 4]), "{x}");
+        }
+    }
+
+    fn encrypt_block(key: &[u8], block: &str) -> [u8; 16] {
+        let aes = Aes::new(key);
+        let block: [u8; 16] = hex::decode(block).unwrap().try_into().unwrap();
+        let mut blocks = [[0u8; 16], block, [0xffu8; 16], block];
+        aes.encrypt_blocks(&mut blocks);
+
+        // All lanes are independent.
+        assert_eq!(blocks[1], blocks[3]);
+
+        // Decryption inverts encryption.
+        let mut decrypted = blocks;
+        aes.decrypt_blocks(&mut decrypted);
+        assert_eq!(decrypted, [[0u8; 16], block, [0xffu8; 16], block]);
+
+        blocks[1]
+    }
+
+    #[test]
+    fn fips197() {
+        // FIPS 197, Appendix C.1
+        let key = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            encrypt_block(&key, "00112233445566778899aabbccddeeff").to_vec(),
+            hex::decode("69c4e0d86a7b0430d8cdb78070b4c55a").unwrap()
+        );
+
+        // FIPS 197, Appendix C.3
+        let key = hex::decode("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+            .unwrap();
+        assert_eq!(
+            encrypt_block(&key, "00112233445566778899aabbccddeeff").to_vec(),
+            hex::decode("8ea2b7ca516745bfeafc49904b496089").unwrap()
+        );
+    }
+}
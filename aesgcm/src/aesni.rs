@@ -0,0 +1,156 @@
+//! AES and POLYVAL using the AES-NI and PCLMULQDQ instructions.
+//!
+//! The key schedule is shared with the bitsliced implementation. Callers
+//! have to make sure that the CPU supports the instructions before using
+//! anything in this module.
+
+#![allow(unsafe_code)]
+
+use core::arch::x86_64::*;
+
+use crate::aes::{expand_key, BLOCK_LEN, PARALLEL_BLOCKS};
+use crate::block_cipher::BlockCipher;
+use crate::gcm_siv::UniversalHash;
+
+/// An expanded AES key.
+pub(crate) struct Aes {
+    round_keys: [[u8; BLOCK_LEN]; 15],
+    rounds: usize,
+}
+
+impl BlockCipher for Aes {
+    fn new(key: &[u8]) -> Self {
+        let (words, rounds) = expand_key(key);
+
+        let mut round_keys = [[0u8; BLOCK_LEN]; 15];
+        for (round_key, w) in round_keys
+            .iter_mut()
+            .zip(words[..(rounds + 1) * 4].chunks_exact(4))
+        {
+            for (chunk, w) in round_key.chunks_exact_mut(4).zip(w.iter()) {
+                chunk.copy_from_slice(&w.to_le_bytes());
+            }
+        }
+
+        Self { round_keys, rounds }
+    }
+
+    fn encrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        // SAFETY: Only used after checking for AES-NI support.
+        unsafe { encrypt_blocks(self, blocks) }
+    }
+
+    fn decrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        // SAFETY: Only used after checking for AES-NI support.
+        unsafe { decrypt_blocks(self, blocks) }
+    }
+}
+
+#[target_feature(enable = "aes")]
+unsafe fn encrypt_blocks(aes: &Aes, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+    let round_key = |i: usize| _mm_loadu_si128(aes.round_keys[i].as_ptr() as *const __m128i);
+
+    let mut state = [_mm_setzero_si128(); PARALLEL_BLOCKS];
+    let key = round_key(0);
+    for (state, block) in state.iter_mut().zip(blocks.iter()) {
+        *state = _mm_xor_si128(_mm_loadu_si128(block.as_ptr() as *const __m128i), key);
+    }
+    for round in 1..aes.rounds {
+        let key = round_key(round);
+        for state in state.iter_mut() {
+            *state = _mm_aesenc_si128(*state, key);
+        }
+    }
+    let key = round_key(aes.rounds);
+    for (state, block) in state.iter().zip(blocks.iter_mut()) {
+        _mm_storeu_si128(
+            block.as_mut_ptr() as *mut __m128i,
+            _mm_aesenclast_si128(*state, key),
+        );
+    }
+}
+
+/// Decrypt with the equivalent inverse cipher, which needs the inverse
+/// MixColumns applied to the inner round keys.
+#[target_feature(enable = "aes")]
+unsafe fn decrypt_blocks(aes: &Aes, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+    let round_key = |i: usize| _mm_loadu_si128(aes.round_keys[i].as_ptr() as *const __m128i);
+
+    let mut state = [_mm_setzero_si128(); PARALLEL_BLOCKS];
+    let key = round_key(aes.rounds);
+    for (state, block) in state.iter_mut().zip(blocks.iter()) {
+        *state = _mm_xor_si128(_mm_loadu_si128(block.as_ptr() as *const __m128i), key);
+    }
+    for round in (1..aes.rounds).rev() {
+        let key = _mm_aesimc_si128(round_key(round));
+        for state in state.iter_mut() {
+            *state = _mm_aesdec_si128(*state, key);
+        }
+    }
+    let key = round_key(0);
+    for (state, block) in state.iter().zip(blocks.iter_mut()) {
+        _mm_storeu_si128(
+            block.as_mut_ptr() as *mut __m128i,
+            _mm_aesdeclast_si128(*state, key),
+        );
+    }
+}
+
+/// POLYVAL with the hash key `h` and the accumulator `y`.
+pub(crate) struct Polyval {
+    h: [u8; BLOCK_LEN],
+    y: [u8; BLOCK_LEN],
+}
+
+impl UniversalHash for Polyval {
+    fn new(h: &[u8; BLOCK_LEN]) -> Self {
+        Self {
+            h: *h,
+            y: [0u8; BLOCK_LEN],
+        }
+    }
+
+    fn update_blocks(&mut self, blocks: &[u8]) {
+        // SAFETY: Only used after checking for PCLMULQDQ support.
+        unsafe { polyval_update(self, blocks) }
+    }
+
+    fn finish(self) -> [u8; BLOCK_LEN] {
+        self.y
+    }
+}
+
+/// Compute `a * b * x^-128` modulo `x^128 + x^127 + x^126 + x^121 + 1`.
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn dot(a: __m128i, b: __m128i) -> __m128i {
+    let poly = _mm_set_epi64x(0xc200000000000000u64 as i64, 1);
+
+    // Schoolbook multiplication into `hi:lo`.
+    let mut lo = _mm_clmulepi64_si128::<0x00>(a, b);
+    let mut hi = _mm_clmulepi64_si128::<0x11>(a, b);
+    let mid = _mm_xor_si128(
+        _mm_clmulepi64_si128::<0x01>(a, b),
+        _mm_clmulepi64_si128::<0x10>(a, b),
+    );
+    lo = _mm_xor_si128(lo, _mm_slli_si128::<8>(mid));
+    hi = _mm_xor_si128(hi, _mm_srli_si128::<8>(mid));
+
+    // Montgomery reduction of the low half, one 64-bit word at a time.
+    for _ in 0..2 {
+        let t = _mm_clmulepi64_si128::<0x10>(lo, poly);
+        lo = _mm_xor_si128(_mm_shuffle_epi32::<0x4e>(lo), t);
+    }
+
+    _mm_xor_si128(hi, lo)
+}
+
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn polyval_update(polyval: &mut Polyval, blocks: &[u8]) {
+    let h = _mm_loadu_si128(polyval.h.as_ptr() as *const __m128i);
+    let mut y = _mm_loadu_si128(polyval.y.as_ptr() as *const __m128i);
+    for block in blocks.chunks_exact(BLOCK_LEN) {
+        let x = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+        y = dot(_mm_xor_si128(y, x), h);
+    }
+    _mm_storeu_si128(polyval.y.as_mut_ptr() as *mut __m128i, y);
+}
@@ -0,0 +1,82 @@
+//! The AES block cipher with runtime selection of the implementation.
+
+use crate::aes::{BLOCK_LEN, PARALLEL_BLOCKS};
+
+/// An AES implementation.
+pub(crate) trait BlockCipher {
+    /// Expand a 16 byte AES-128 or 32 byte AES-256 key.
+    fn new(key: &[u8]) -> Self;
+
+    /// Encrypt four blocks in place.
+    fn encrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]);
+
+    /// Decrypt four blocks in place.
+    fn decrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]);
+
+    /// Encrypt a single block in place.
+    fn encrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        let mut blocks = [*block; PARALLEL_BLOCKS];
+        self.encrypt_blocks(&mut blocks);
+        *block = blocks[0];
+    }
+
+    /// Decrypt a single block in place.
+    fn decrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        let mut blocks = [*block; PARALLEL_BLOCKS];
+        self.decrypt_blocks(&mut blocks);
+        *block = blocks[0];
+    }
+}
+
+impl BlockCipher for crate::aes::Aes {
+    fn new(key: &[u8]) -> Self {
+        crate::aes::Aes::new(key)
+    }
+
+    fn encrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        crate::aes::Aes::encrypt_blocks(self, blocks)
+    }
+
+    fn decrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        crate::aes::Aes::decrypt_blocks(self, blocks)
+    }
+}
+
+/// AES using AES-NI if the CPU supports it and the portable, constant-time
+/// implementation otherwise.
+///
+/// The cipher only lives on the stack for a single operation, so the size
+/// difference between the variants is not a concern.
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Cipher {
+    Portable(crate::aes::Aes),
+    #[cfg(target_arch = "x86_64")]
+    AesNi(crate::aesni::Aes),
+}
+
+impl BlockCipher for Cipher {
+    fn new(key: &[u8]) -> Self {
+        #[cfg(target_arch = "x86_64")]
+        if libcrux_platform::aes_ni_support() {
+            return Self::AesNi(crate::aesni::Aes::new(key));
+        }
+
+        Self::Portable(crate::aes::Aes::new(key))
+    }
+
+    fn encrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        match self {
+            Self::Portable(aes) => aes.encrypt_blocks(blocks),
+            #[cfg(target_arch = "x86_64")]
+            Self::AesNi(aes) => aes.encrypt_blocks(blocks),
+        }
+    }
+
+    fn decrypt_blocks(&self, blocks: &mut [[u8; BLOCK_LEN]; PARALLEL_BLOCKS]) {
+        match self {
+            Self::Portable(aes) => aes.decrypt_blocks(blocks),
+            #[cfg(target_arch = "x86_64")]
+            Self::AesNi(aes) => aes.decrypt_blocks(blocks),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! AES-CMAC (NIST SP 800-38B, RFC 4493).
+
+use crate::aes::BLOCK_LEN;
+use crate::block_cipher::BlockCipher;
+use crate::CmacError;
+
+/// Multiply by `x` in GF(2^128) with the CMAC polynomial, in constant time.
+fn dbl(block: &[u8; BLOCK_LEN]) -> [u8; BLOCK_LEN] {
+    let v = u128::from_be_bytes(*block);
+    let mask = 0u128.wrapping_sub(v >> 127);
+    ((v << 1) ^ (mask & 0x87)).to_be_bytes()
+}
+
+fn xor_into(x: &mut [u8; BLOCK_LEN], y: &[u8]) {
+    for (x, y) in x.iter_mut().zip(y.iter()) {
+        *x ^= y;
+    }
+}
+
+pub(crate) fn mac(cipher: &impl BlockCipher, msg: &[u8]) -> [u8; BLOCK_LEN] {
+    // The subkeys (RFC 4493, Section 2.3).
+    let mut l = [0u8; BLOCK_LEN];
+    cipher.encrypt_block(&mut l);
+    let k1 = dbl(&l);
+    let k2 = dbl(&k1);
+
+    // The last block is processed separately. It is a complete block unless
+    // the message is empty or not a multiple of the block length.
+    let complete = !msg.is_empty() && msg.len().is_multiple_of(BLOCK_LEN);
+    let last_len = if complete {
+        BLOCK_LEN
+    } else {
+        msg.len() % BLOCK_LEN
+    };
+    let (msg, last) = msg.split_at(msg.len() - last_len);
+
+    let mut x = [0u8; BLOCK_LEN];
+    for block in msg.chunks_exact(BLOCK_LEN) {
+        xor_into(&mut x, block);
+        cipher.encrypt_block(&mut x);
+    }
+
+    xor_into(&mut x, last);
+    if complete {
+        xor_into(&mut x, &k1);
+    } else {
+        x[last.len()] ^= 0x80;
+        xor_into(&mut x, &k2);
+    }
+    cipher.encrypt_block(&mut x);
+
+    x
+}
+
+pub(crate) fn verify(
+    cipher: &impl BlockCipher,
+    msg: &[u8],
+    tag: &[u8; BLOCK_LEN],
+) -> Result<(), CmacError> {
+    let expected = mac(cipher, msg);
+    let mut diff = 0u8;
+    for (a, b) in expected.iter().zip(tag.iter()) {
+        diff |= a ^ b;
+    }
+
+    if core::hint::black_box(diff) == 0 {
+        Ok(())
+    } else {
+        Err(CmacError::InvalidTag)
+    }
+}
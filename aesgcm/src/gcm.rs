@@ -0,0 +1,170 @@
+//! The GCM mode (NIST SP 800-38D) with 96-bit nonces.
+
+use crate::aes::{Aes, BLOCK_LEN, PARALLEL_BLOCKS};
+use crate::ghash::GHash;
+use crate::{AeadError, NONCE_LEN, TAG_LEN};
+
+/// The maximum plaintext length of 2^39 - 256 bits.
+const MAX_PTXT_LEN: u64 = (1 << 36) - 32;
+
+/// The maximum associated data length of 2^64 - 1 bits.
+const MAX_AAD_LEN: u64 = (1 << 61) - 1;
+
+fn counter_block(nonce: &[u8; NONCE_LEN], counter: u32) -> [u8; BLOCK_LEN] {
+    let mut block = [0u8; BLOCK_LEN];
+    block[..NONCE_LEN].copy_from_slice(nonce);
+    block[NONCE_LEN..].copy_from_slice(&counter.to_be_bytes());
+    block
+}
+
+/// An expanded key together with the hash key `H`.
+pub(crate) struct Gcm {
+    aes: Aes,
+    h: [u8; BLOCK_LEN],
+}
+
+impl Gcm {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        let aes = Aes::new(key);
+        let mut blocks = [[0u8; BLOCK_LEN]; PARALLEL_BLOCKS];
+        aes.encrypt_blocks(&mut blocks);
+
+        Self { aes, h: blocks[0] }
+    }
+
+    /// XOR `buffer` with the key stream starting at counter 2.
+    fn ctr(&self, nonce: &[u8; NONCE_LEN], buffer: &mut [u8]) {
+        let mut counter = 2u32;
+        for chunk in buffer.chunks_mut(BLOCK_LEN * PARALLEL_BLOCKS) {
+            let mut blocks = [[0u8; BLOCK_LEN]; PARALLEL_BLOCKS];
+            for block in blocks.iter_mut() {
+                *block = counter_block(nonce, counter);
+                counter = counter.wrapping_add(1);
+            }
+            self.aes.encrypt_blocks(&mut blocks);
+
+            for (b, k) in chunk.iter_mut().zip(blocks.iter().flatten()) {
+                *b ^= k;
+            }
+        }
+    }
+
+    /// Compute the tag over `aad` and the ciphertext `ctxt`.
+    fn tag(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], ctxt: &[u8]) -> [u8; TAG_LEN] {
+        let mut ghash = GHash::new(&self.h);
+        ghash.update_padded(aad);
+        ghash.update_padded(ctxt);
+        let mut tag = ghash.finish(aad.len(), ctxt.len());
+
+        let mut blocks = [[0u8; BLOCK_LEN]; PARALLEL_BLOCKS];
+        blocks[0] = counter_block(nonce, 1);
+        self.aes.encrypt_blocks(&mut blocks);
+        for (t, k) in tag.iter_mut().zip(blocks[0].iter()) {
+            *t ^= k;
+        }
+
+        tag
+    }
+
+    pub(crate) fn encrypt_in_place(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &mut [u8; TAG_LEN],
+    ) -> Result<(), AeadError> {
+        if buffer.len() as u64 > MAX_PTXT_LEN {
+            return Err(AeadError::PlaintextTooLarge);
+        }
+        if aad.len() as u64 > MAX_AAD_LEN {
+            return Err(AeadError::AadTooLarge);
+        }
+
+        self.ctr(nonce, buffer);
+        *tag = self.tag(nonce, aad, buffer);
+
+        Ok(())
+    }
+
+    pub(crate) fn decrypt_in_place(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; TAG_LEN],
+    ) -> Result<(), AeadError> {
+        if buffer.len() as u64 > MAX_PTXT_LEN {
+            return Err(AeadError::CiphertextTooLarge);
+        }
+        if aad.len() as u64 > MAX_AAD_LEN {
+            return Err(AeadError::AadTooLarge);
+        }
+
+        let expected_tag = self.tag(nonce, aad, buffer);
+        let mut diff = 0u8;
+        for (a, b) in expected_tag.iter().zip(tag.iter()) {
+            diff |= a ^ b;
+        }
+        if diff != 0 {
+            return Err(AeadError::InvalidCiphertext);
+        }
+
+        self.ctr(nonce, buffer);
+
+        Ok(())
+    }
+}
+
+pub(crate) fn encrypt(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    ptxt: &[u8],
+    ctxt: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+) -> Result<(), AeadError> {
+    if ctxt.len() < ptxt.len() {
+        return Err(AeadError::CiphertextTooShort);
+    }
+    let ctxt = &mut ctxt[..ptxt.len()];
+    ctxt.copy_from_slice(ptxt);
+
+    encrypt_in_place(key, nonce, aad, ctxt, tag)
+}
+
+pub(crate) fn decrypt(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    ctxt: &[u8],
+    tag: &[u8; TAG_LEN],
+    ptxt: &mut [u8],
+) -> Result<(), AeadError> {
+    if ptxt.len() < ctxt.len() {
+        return Err(AeadError::PlaintextTooShort);
+    }
+    let ptxt = &mut ptxt[..ctxt.len()];
+    ptxt.copy_from_slice(ctxt);
+
+    decrypt_in_place(key, nonce, aad, ptxt, tag).inspect_err(|_| ptxt.fill(0))
+}
+
+pub(crate) fn encrypt_in_place(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    buffer: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+) -> Result<(), AeadError> {
+    Gcm::new(key).encrypt_in_place(nonce, aad, buffer, tag)
+}
+
+pub(crate) fn decrypt_in_place(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    buffer: &mut [u8],
+    tag: &[u8; TAG_LEN],
+) -> Result<(), AeadError> {
+    Gcm::new(key).decrypt_in_place(nonce, aad, buffer, tag)
+}
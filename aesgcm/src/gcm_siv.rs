@@ -0,0 +1,387 @@
+//! The nonce misuse-resistant AES-GCM-SIV mode (RFC 8452).
+//!
+//! The mode is generic over the AES and POLYVAL implementations so that the
+//! same code drives the portable and the AES-NI backend.
+
+use core::marker::PhantomData;
+
+use crate::aes::{BLOCK_LEN, PARALLEL_BLOCKS};
+use crate::block_cipher::BlockCipher;
+use crate::{AeadError, NONCE_LEN, TAG_LEN};
+
+/// The maximum plaintext length of 2^36 bytes.
+const MAX_PTXT_LEN: u64 = 1 << 36;
+
+/// The maximum associated data length of 2^36 bytes.
+const MAX_AAD_LEN: u64 = 1 << 36;
+
+/// A POLYVAL implementation.
+pub(crate) trait UniversalHash {
+    /// Initialize the hash with the key `h`.
+    fn new(h: &[u8; BLOCK_LEN]) -> Self;
+
+    /// Process `blocks`, whose length must be a multiple of the block length.
+    fn update_blocks(&mut self, blocks: &[u8]);
+
+    /// Return the hash of all blocks processed so far.
+    fn finish(self) -> [u8; BLOCK_LEN];
+}
+
+/// Process `data`, padding the last block with zeros.
+fn update_padded(hash: &mut impl UniversalHash, data: &[u8]) {
+    let full = data.len() - data.len() % BLOCK_LEN;
+    hash.update_blocks(&data[..full]);
+
+    let rest = &data[full..];
+    if !rest.is_empty() {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..rest.len()].copy_from_slice(rest);
+        hash.update_blocks(&block);
+    }
+}
+
+/// Compare two tags in constant time.
+fn tags_equal(a: &[u8; TAG_LEN], b: &[u8; TAG_LEN]) -> bool {
+    let mut diff = 0u8;
+    for (a, b) in a.iter().zip(b.iter()) {
+        diff |= a ^ b;
+    }
+    core::hint::black_box(diff) == 0
+}
+
+/// The expanded key-generating key.
+pub(crate) struct GcmSiv<C, H> {
+    key_generating_key: C,
+    key_len: usize,
+    _hash: PhantomData<H>,
+}
+
+impl<C: BlockCipher, H: UniversalHash> GcmSiv<C, H> {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        Self {
+            key_generating_key: C::new(key),
+            key_len: key.len(),
+            _hash: PhantomData,
+        }
+    }
+
+    /// Derive the per-nonce message authentication and encryption keys
+    /// (RFC 8452, Section 4).
+    fn derive_keys(&self, nonce: &[u8; NONCE_LEN]) -> (H, C) {
+        let mut blocks = [[0u8; BLOCK_LEN]; 2 * PARALLEL_BLOCKS];
+        for (i, block) in blocks.iter_mut().enumerate() {
+            block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+        }
+
+        // Two blocks for the authentication key and one block for every
+        // eight bytes of the encryption key.
+        let needed = 2 + self.key_len / 8;
+        for chunk in blocks
+            .chunks_exact_mut(PARALLEL_BLOCKS)
+            .take(needed.div_ceil(PARALLEL_BLOCKS))
+        {
+            self.key_generating_key
+                .encrypt_blocks(chunk.try_into().unwrap());
+        }
+
+        let mut auth_key = [0u8; BLOCK_LEN];
+        auth_key[..8].copy_from_slice(&blocks[0][..8]);
+        auth_key[8..].copy_from_slice(&blocks[1][..8]);
+
+        let mut enc_key = [0u8; 32];
+        for (key, block) in enc_key[..self.key_len]
+            .chunks_exact_mut(8)
+            .zip(blocks[2..].iter())
+        {
+            key.copy_from_slice(&block[..8]);
+        }
+
+        (H::new(&auth_key), C::new(&enc_key[..self.key_len]))
+    }
+
+    /// Compute the tag over `aad` and the plaintext `ptxt`.
+    fn tag(hash: H, cipher: &C, nonce: &[u8; NONCE_LEN], aad: &[u8], ptxt: &[u8]) -> [u8; TAG_LEN] {
+        let mut hash = hash;
+        update_padded(&mut hash, aad);
+        update_padded(&mut hash, ptxt);
+
+        let mut length_block = [0u8; BLOCK_LEN];
+        length_block[..8].copy_from_slice(&((aad.len() as u64) * 8).to_le_bytes());
+        length_block[8..].copy_from_slice(&((ptxt.len() as u64) * 8).to_le_bytes());
+        hash.update_blocks(&length_block);
+
+        let mut s = hash.finish();
+        for (s, n) in s.iter_mut().zip(nonce.iter()) {
+            *s ^= n;
+        }
+        s[15] &= 0x7f;
+
+        let mut blocks = [[0u8; BLOCK_LEN]; PARALLEL_BLOCKS];
+        blocks[0] = s;
+        cipher.encrypt_blocks(&mut blocks);
+
+        blocks[0]
+    }
+
+    /// XOR `buffer` with the key stream for the initial counter block
+    /// derived from `tag`.
+    fn ctr(cipher: &C, tag: &[u8; TAG_LEN], buffer: &mut [u8]) {
+        let mut counter_block = *tag;
+        counter_block[15] |= 0x80;
+        let mut counter = u32::from_le_bytes(counter_block[..4].try_into().unwrap());
+
+        for chunk in buffer.chunks_mut(BLOCK_LEN * PARALLEL_BLOCKS) {
+            let mut blocks = [counter_block; PARALLEL_BLOCKS];
+            for block in blocks.iter_mut() {
+                block[..4].copy_from_slice(&counter.to_le_bytes());
+                counter = counter.wrapping_add(1);
+            }
+            cipher.encrypt_blocks(&mut blocks);
+
+            for (b, k) in chunk.iter_mut().zip(blocks.iter().flatten()) {
+                *b ^= k;
+            }
+        }
+    }
+
+    pub(crate) fn encrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        tag: &mut [u8; TAG_LEN],
+    ) -> Result<(), AeadError> {
+        if ctxt.len() < ptxt.len() {
+            return Err(AeadError::CiphertextTooShort);
+        }
+        let ctxt = &mut ctxt[..ptxt.len()];
+        ctxt.copy_from_slice(ptxt);
+
+        self.encrypt_in_place(nonce, aad, ctxt, tag)
+    }
+
+    pub(crate) fn decrypt(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        ctxt: &[u8],
+        tag: &[u8; TAG_LEN],
+        ptxt: &mut [u8],
+    ) -> Result<(), AeadError> {
+        if ptxt.len() < ctxt.len() {
+            return Err(AeadError::PlaintextTooShort);
+        }
+        let ptxt = &mut ptxt[..ctxt.len()];
+        ptxt.copy_from_slice(ctxt);
+
+        self.decrypt_in_place(nonce, aad, ptxt, tag)
+    }
+
+    pub(crate) fn encrypt_in_place(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &mut [u8; TAG_LEN],
+    ) -> Result<(), AeadError> {
+        if buffer.len() as u64 > MAX_PTXT_LEN {
+            return Err(AeadError::PlaintextTooLarge);
+        }
+        if aad.len() as u64 > MAX_AAD_LEN {
+            return Err(AeadError::AadTooLarge);
+        }
+
+        let (hash, cipher) = self.derive_keys(nonce);
+        *tag = Self::tag(hash, &cipher, nonce, aad, buffer);
+        Self::ctr(&cipher, tag, buffer);
+
+        Ok(())
+    }
+
+    pub(crate) fn decrypt_in_place(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; TAG_LEN],
+    ) -> Result<(), AeadError> {
+        if buffer.len() as u64 > MAX_PTXT_LEN {
+            return Err(AeadError::CiphertextTooLarge);
+        }
+        if aad.len() as u64 > MAX_AAD_LEN {
+            return Err(AeadError::AadTooLarge);
+        }
+
+        // The tag is computed over the plaintext, so it has to be decrypted
+        // before it can be authenticated.
+        let (hash, cipher) = self.derive_keys(nonce);
+        Self::ctr(&cipher, tag, buffer);
+        let expected_tag = Self::tag(hash, &cipher, nonce, aad, buffer);
+
+        if !tags_equal(&expected_tag, tag) {
+            buffer.fill(0);
+            return Err(AeadError::InvalidCiphertext);
+        }
+
+        Ok(())
+    }
+}
+
+/// The portable, constant-time implementation.
+pub(crate) type Portable = GcmSiv<crate::aes::Aes, crate::polyval::Polyval>;
+
+/// The implementation using AES-NI and PCLMULQDQ.
+#[cfg(target_arch = "x86_64")]
+pub(crate) type AesNi = GcmSiv<crate::aesni::Aes, crate::aesni::Polyval>;
+
+/// Encrypt with the AES-NI implementation if the CPU supports it and the
+/// portable implementation otherwise.
+pub(crate) fn encrypt(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    ptxt: &[u8],
+    ctxt: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+) -> Result<(), AeadError> {
+    #[cfg(target_arch = "x86_64")]
+    if libcrux_platform::aes_ni_support() {
+        return AesNi::new(key).encrypt(nonce, aad, ptxt, ctxt, tag);
+    }
+
+    Portable::new(key).encrypt(nonce, aad, ptxt, ctxt, tag)
+}
+
+/// Decrypt with the AES-NI implementation if the CPU supports it and the
+/// portable implementation otherwise.
+pub(crate) fn decrypt(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    ctxt: &[u8],
+    tag: &[u8; TAG_LEN],
+    ptxt: &mut [u8],
+) -> Result<(), AeadError> {
+    #[cfg(target_arch = "x86_64")]
+    if libcrux_platform::aes_ni_support() {
+        return AesNi::new(key).decrypt(nonce, aad, ctxt, tag, ptxt);
+    }
+
+    Portable::new(key).decrypt(nonce, aad, ctxt, tag, ptxt)
+}
+
+/// Encrypt `buffer` in place with the AES-NI implementation if the CPU
+/// supports it and the portable implementation otherwise.
+pub(crate) fn encrypt_in_place(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    buffer: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+) -> Result<(), AeadError> {
+    #[cfg(target_arch = "x86_64")]
+    if libcrux_platform::aes_ni_support() {
+        return AesNi::new(key).encrypt_in_place(nonce, aad, buffer, tag);
+    }
+
+    Portable::new(key).encrypt_in_place(nonce, aad, buffer, tag)
+}
+
+/// Decrypt `buffer` in place with the AES-NI implementation if the CPU
+/// supports it and the portable implementation otherwise.
+pub(crate) fn decrypt_in_place(
+    key: &[u8],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    buffer: &mut [u8],
+    tag: &[u8; TAG_LEN],
+) -> Result<(), AeadError> {
+    #[cfg(target_arch = "x86_64")]
+    if libcrux_platform::aes_ni_support() {
+        return AesNi::new(key).decrypt_in_place(nonce, aad, buffer, tag);
+    }
+
+    Portable::new(key).decrypt_in_place(nonce, aad, buffer, tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polyval<H: UniversalHash>() -> [u8; BLOCK_LEN] {
+        // RFC 8452, Appendix A
+        let h = hex::decode("25629347589242761d31f826ba4b757b").unwrap();
+        let x = hex::decode("4f4f95668c83dfb6401762bb2d01a262d1a24ddd2721d006bbe45f20d3c9f362")
+            .unwrap();
+
+        let mut hash = H::new(h.as_slice().try_into().unwrap());
+        hash.update_blocks(&x);
+        hash.finish()
+    }
+
+    /// Encrypt and decrypt with every length up to a few blocks.
+    fn round_trips<C: BlockCipher, H: UniversalHash>(key: &[u8]) -> [u8; TAG_LEN] {
+        let siv = GcmSiv::<C, H>::new(key);
+        let nonce = [7u8; NONCE_LEN];
+        let msg: [u8; 200] = core::array::from_fn(|i| i as u8);
+
+        // Chain the tags so that the result depends on all of them.
+        let mut tag = [0u8; TAG_LEN];
+        for len in 0..msg.len() {
+            let mut ctxt = [0u8; 200];
+            let mut ptxt = [0u8; 200];
+            let aad = tag;
+            siv.encrypt(&nonce, &aad, &msg[..len], &mut ctxt, &mut tag)
+                .unwrap();
+            siv.decrypt(&nonce, &aad, &ctxt[..len], &tag, &mut ptxt)
+                .unwrap();
+            assert_eq!(ptxt[..len], msg[..len]);
+        }
+
+        tag
+    }
+
+    #[test]
+    fn portable() {
+        assert_eq!(
+            polyval::<crate::polyval::Polyval>().to_vec(),
+            hex::decode("f7a3b47b846119fae5b7866cf5e5b77e").unwrap()
+        );
+
+        // RFC 8452, Appendix C.1, with the portable implementation.
+        let mut key = [0u8; 16];
+        key[0] = 1;
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[0] = 3;
+        let mut tag = [0u8; TAG_LEN];
+        Portable::new(&key)
+            .encrypt(&nonce, &[], &[], &mut [], &mut tag)
+            .unwrap();
+        assert_eq!(
+            tag.to_vec(),
+            hex::decode("dc20e2d83f25705bb49e439eca56de25").unwrap()
+        );
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn aes_ni() {
+        if !libcrux_platform::aes_ni_support() {
+            return;
+        }
+
+        assert_eq!(
+            polyval::<crate::aesni::Polyval>(),
+            polyval::<crate::polyval::Polyval>()
+        );
+
+        for key in [&[0x42u8; 16][..], &[0x42u8; 32][..]] {
+            assert_eq!(
+                round_trips::<crate::aesni::Aes, crate::aesni::Polyval>(key),
+                round_trips::<crate::aes::Aes, crate::polyval::Polyval>(key)
+            );
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Constant-time GHASH.
+//!
+//! Multiplication in GF(2^128) is done with integer multiplications in which
+//! every fourth bit is masked out, so that carries can not spill into the
+//! bits that are kept. This follows the `ghash_ctmul64` implementation of
+//! [BearSSL](https://bearssl.org/constanttime.html).
+
+use crate::aes::BLOCK_LEN;
+
+/// Carry-less multiplication of the low 64 bits, truncated to 64 bits.
+#[inline(always)]
+fn bmul64(x: u64, y: u64) -> u64 {
+    let x0 = x & 0x1111111111111111;
+    let x1 = x & 0x2222222222222222;
+    let x2 = x & 0x4444444444444444;
+    let x3 = x & 0x8888888888888888;
+    let y0 = y & 0x1111111111111111;
+    let y1 = y & 0x2222222222222222;
+    let y2 = y & 0x4444444444444444;
+    let y3 = y & 0x8888888888888888;
+
+    let z0 = x0.wrapping_mul(y0) ^ x1.wrapping_mul(y3) ^ x2.wrapping_mul(y2) ^ x3.wrapping_mul(y1);
+    let z1 = x0.wrapping_mul(y1) ^ x1.wrapping_mul(y0) ^ x2.wrapping_mul(y3) ^ x3.wrapping_mul(y2);
+    let z2 = x0.wrapping_mul(y2) ^ x1.wrapping_mul(y1) ^ x2.wrapping_mul(y0) ^ x3.wrapping_mul(y3);
+    let z3 = x0.wrapping_mul(y3) ^ x1.wrapping_mul(y2) ^ x2.wrapping_mul(y1) ^ x3.wrapping_mul(y0);
+
+    (z0 & 0x1111111111111111)
+        | (z1 & 0x2222222222222222)
+        | (z2 & 0x4444444444444444)
+        | (z3 & 0x8888888888888888)
+}
+
+pub(crate) struct GHash {
+    h0: u64,
+    h1: u64,
+    h2: u64,
+    h0r: u64,
+    h1r: u64,
+    h2r: u64,
+    y0: u64,
+    y1: u64,
+}
+
+impl GHash {
+    pub(crate) fn new(h: &[u8; BLOCK_LEN]) -> Self {
+        // The first half of a block is the high word.
+        let h1 = u64::from_be_bytes(h[..8].try_into().unwrap());
+        let h0 = u64::from_be_bytes(h[8..].try_into().unwrap());
+        let h0r = h0.reverse_bits();
+        let h1r = h1.reverse_bits();
+
+        Self {
+            h0,
+            h1,
+            h2: h0 ^ h1,
+            h0r,
+            h1r,
+            h2r: h0r ^ h1r,
+            y0: 0,
+            y1: 0,
+        }
+    }
+
+    /// Process a single block: `y = (y ^ block) * h`.
+    pub(crate) fn update_block(&mut self, block: &[u8; BLOCK_LEN]) {
+        let y1 = self.y1 ^ u64::from_be_bytes(block[..8].try_into().unwrap());
+        let y0 = self.y0 ^ u64::from_be_bytes(block[8..].try_into().unwrap());
+        let y0r = y0.reverse_bits();
+        let y1r = y1.reverse_bits();
+        let y2 = y0 ^ y1;
+        let y2r = y0r ^ y1r;
+
+        // Karatsuba for the low and, on reversed inputs, the high halves.
+        let z0 = bmul64(y0, self.h0);
+        let z1 = bmul64(y1, self.h1);
+        let mut z2 = bmul64(y2, self.h2);
+        let mut z0h = bmul64(y0r, self.h0r);
+        let mut z1h = bmul64(y1r, self.h1r);
+        let mut z2h = bmul64(y2r, self.h2r);
+        z2 ^= z0 ^ z1;
+        z2h ^= z0h ^ z1h;
+        z0h = z0h.reverse_bits() >> 1;
+        z1h = z1h.reverse_bits() >> 1;
+        z2h = z2h.reverse_bits() >> 1;
+
+        let mut v0 = z0;
+        let mut v1 = z0h ^ z2;
+        let mut v2 = z1 ^ z2h;
+        let mut v3 = z1h;
+
+        // The bits are reversed in GHASH, so shift by one.
+        v3 = (v3 << 1) | (v2 >> 63);
+        v2 = (v2 << 1) | (v1 >> 63);
+        v1 = (v1 << 1) | (v0 >> 63);
+        v0 <<= 1;
+
+        // Reduce modulo x^128 + x^7 + x^2 + x + 1.
+        v2 ^= v0 ^ (v0 >> 1) ^ (v0 >> 2) ^ (v0 >> 7);
+        v1 ^= (v0 << 63) ^ (v0 << 62) ^ (v0 << 57);
+        v3 ^= v1 ^ (v1 >> 1) ^ (v1 >> 2) ^ (v1 >> 7);
+        v2 ^= (v1 << 63) ^ (v1 << 62) ^ (v1 << 57);
+
+        self.y0 = v2;
+        self.y1 = v3;
+    }
+
+    /// Process `data`, padding the last block with zeros.
+    pub(crate) fn update_padded(&mut self, data: &[u8]) {
+        let mut chunks = data.chunks_exact(BLOCK_LEN);
+        for block in chunks.by_ref() {
+            self.update_block(block.try_into().unwrap());
+        }
+
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut block = [0u8; BLOCK_LEN];
+            block[..rest.len()].copy_from_slice(rest);
+            self.update_block(&block);
+        }
+    }
+
+    /// Process the length block and return the result.
+    pub(crate) fn finish(mut self, aad_len: usize, msg_len: usize) -> [u8; BLOCK_LEN] {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..8].copy_from_slice(&((aad_len as u64) * 8).to_be_bytes());
+        block[8..].copy_from_slice(&((msg_len as u64) * 8).to_be_bytes());
+        self.update_block(&block);
+        self.state()
+    }
+
+    /// The current value of the accumulator `y`.
+    pub(crate) fn state(&self) -> [u8; BLOCK_LEN] {
+        let mut out = [0u8; BLOCK_LEN];
+        out[..8].copy_from_slice(&self.y1.to_be_bytes());
+        out[8..].copy_from_slice(&self.y0.to_be_bytes());
+        out
+    }
+}
@@ -0,0 +1,194 @@
+//! AES key wrap (RFC 3394) and AES key wrap with padding (RFC 5649).
+
+use crate::aes::BLOCK_LEN;
+use crate::block_cipher::BlockCipher;
+use crate::KeyWrapError;
+
+/// The length of a semiblock.
+pub(crate) const SEMIBLOCK_LEN: usize = 8;
+
+/// The default initial value of RFC 3394, Section 2.2.3.1.
+const IV: [u8; SEMIBLOCK_LEN] = [0xa6; SEMIBLOCK_LEN];
+
+/// The constant part of the alternative initial value of RFC 5649, Section 3.
+const AIV_PREFIX: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+/// The wrapping function `W` (RFC 3394, Section 2.2.1) on the initial value
+/// `a` and the semiblocks in `r`.
+fn wrap_semiblocks(cipher: &impl BlockCipher, a: &mut [u8; SEMIBLOCK_LEN], r: &mut [u8]) {
+    let n = r.len() / SEMIBLOCK_LEN;
+    let mut block = [0u8; BLOCK_LEN];
+    for j in 0..6 {
+        for (i, r) in r.chunks_exact_mut(SEMIBLOCK_LEN).enumerate() {
+            block[..SEMIBLOCK_LEN].copy_from_slice(a);
+            block[SEMIBLOCK_LEN..].copy_from_slice(r);
+            cipher.encrypt_block(&mut block);
+
+            let t = ((n * j + i + 1) as u64).to_be_bytes();
+            for ((a, b), t) in a.iter_mut().zip(block.iter()).zip(t.iter()) {
+                *a = b ^ t;
+            }
+            r.copy_from_slice(&block[SEMIBLOCK_LEN..]);
+        }
+    }
+}
+
+/// The unwrapping function `W^-1` (RFC 3394, Section 2.2.2). On return `a`
+/// holds the initial value that has to be checked by the caller.
+fn unwrap_semiblocks(cipher: &impl BlockCipher, a: &mut [u8; SEMIBLOCK_LEN], r: &mut [u8]) {
+    let n = r.len() / SEMIBLOCK_LEN;
+    let mut block = [0u8; BLOCK_LEN];
+    for j in (0..6).rev() {
+        for (i, r) in r.chunks_exact_mut(SEMIBLOCK_LEN).enumerate().rev() {
+            let t = ((n * j + i + 1) as u64).to_be_bytes();
+            for ((b, a), t) in block.iter_mut().zip(a.iter()).zip(t.iter()) {
+                *b = a ^ t;
+            }
+            block[SEMIBLOCK_LEN..].copy_from_slice(r);
+            cipher.decrypt_block(&mut block);
+
+            a.copy_from_slice(&block[..SEMIBLOCK_LEN]);
+            r.copy_from_slice(&block[SEMIBLOCK_LEN..]);
+        }
+    }
+}
+
+/// Returns `0xff` if `a == b` and `0` otherwise, in constant time.
+fn eq_mask(a: &[u8], b: &[u8]) -> u8 {
+    let mut diff = 0u8;
+    for (a, b) in a.iter().zip(b.iter()) {
+        diff |= a ^ b;
+    }
+    let diff = core::hint::black_box(diff);
+    ((diff as u16).wrapping_sub(1) >> 8) as u8
+}
+
+pub(crate) fn wrap<'a>(
+    cipher: &impl BlockCipher,
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a [u8], KeyWrapError> {
+    if !input.len().is_multiple_of(SEMIBLOCK_LEN) || input.len() < 2 * SEMIBLOCK_LEN {
+        return Err(KeyWrapError::InvalidInputLength);
+    }
+    if output.len() < input.len() + SEMIBLOCK_LEN {
+        return Err(KeyWrapError::OutputTooShort);
+    }
+    let output = &mut output[..input.len() + SEMIBLOCK_LEN];
+    let (a, r) = output.split_at_mut(SEMIBLOCK_LEN);
+
+    let mut iv = IV;
+    r.copy_from_slice(input);
+    wrap_semiblocks(cipher, &mut iv, r);
+    a.copy_from_slice(&iv);
+
+    Ok(output)
+}
+
+pub(crate) fn unwrap<'a>(
+    cipher: &impl BlockCipher,
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a [u8], KeyWrapError> {
+    if !input.len().is_multiple_of(SEMIBLOCK_LEN) || input.len() < 3 * SEMIBLOCK_LEN {
+        return Err(KeyWrapError::InvalidInputLength);
+    }
+    if output.len() < input.len() - SEMIBLOCK_LEN {
+        return Err(KeyWrapError::OutputTooShort);
+    }
+    let output = &mut output[..input.len() - SEMIBLOCK_LEN];
+
+    let mut a: [u8; SEMIBLOCK_LEN] = input[..SEMIBLOCK_LEN].try_into().unwrap();
+    output.copy_from_slice(&input[SEMIBLOCK_LEN..]);
+    unwrap_semiblocks(cipher, &mut a, output);
+
+    if eq_mask(&a, &IV) == 0 {
+        output.fill(0);
+        return Err(KeyWrapError::InvalidCiphertext);
+    }
+
+    Ok(output)
+}
+
+pub(crate) fn wrap_with_padding<'a>(
+    cipher: &impl BlockCipher,
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a [u8], KeyWrapError> {
+    if input.is_empty() || input.len() as u64 > u32::MAX as u64 {
+        return Err(KeyWrapError::InvalidInputLength);
+    }
+    let padded_len = input.len().next_multiple_of(SEMIBLOCK_LEN);
+    if output.len() < padded_len + SEMIBLOCK_LEN {
+        return Err(KeyWrapError::OutputTooShort);
+    }
+    let output = &mut output[..padded_len + SEMIBLOCK_LEN];
+
+    let mut aiv = [0u8; SEMIBLOCK_LEN];
+    aiv[..4].copy_from_slice(&AIV_PREFIX);
+    aiv[4..].copy_from_slice(&(input.len() as u32).to_be_bytes());
+
+    let (a, r) = output.split_at_mut(SEMIBLOCK_LEN);
+    r.fill(0);
+    r[..input.len()].copy_from_slice(input);
+
+    if padded_len == SEMIBLOCK_LEN {
+        // A single semiblock is encrypted together with the initial value.
+        let mut block = [0u8; BLOCK_LEN];
+        block[..SEMIBLOCK_LEN].copy_from_slice(&aiv);
+        block[SEMIBLOCK_LEN..].copy_from_slice(r);
+        cipher.encrypt_block(&mut block);
+        output.copy_from_slice(&block);
+    } else {
+        wrap_semiblocks(cipher, &mut aiv, r);
+        a.copy_from_slice(&aiv);
+    }
+
+    Ok(output)
+}
+
+pub(crate) fn unwrap_with_padding<'a>(
+    cipher: &impl BlockCipher,
+    input: &[u8],
+    output: &'a mut [u8],
+) -> Result<&'a [u8], KeyWrapError> {
+    if !input.len().is_multiple_of(SEMIBLOCK_LEN) || input.len() < 2 * SEMIBLOCK_LEN {
+        return Err(KeyWrapError::InvalidInputLength);
+    }
+    let padded_len = input.len() - SEMIBLOCK_LEN;
+    if output.len() < padded_len {
+        return Err(KeyWrapError::OutputTooShort);
+    }
+    let output = &mut output[..padded_len];
+
+    let mut a = [0u8; SEMIBLOCK_LEN];
+    if padded_len == SEMIBLOCK_LEN {
+        let mut block: [u8; BLOCK_LEN] = input.try_into().unwrap();
+        cipher.decrypt_block(&mut block);
+        a.copy_from_slice(&block[..SEMIBLOCK_LEN]);
+        output.copy_from_slice(&block[SEMIBLOCK_LEN..]);
+    } else {
+        a.copy_from_slice(&input[..SEMIBLOCK_LEN]);
+        output.copy_from_slice(&input[SEMIBLOCK_LEN..]);
+        unwrap_semiblocks(cipher, &mut a, output);
+    }
+
+    // Check the prefix, that the length lies in the last semiblock, and that
+    // the padding is zero, without branching on any of them.
+    let mli = u32::from_be_bytes(a[4..].try_into().unwrap()) as usize;
+    let mut valid = eq_mask(&a[..4], &AIV_PREFIX);
+    valid &= 0u8.wrapping_sub((mli > padded_len - SEMIBLOCK_LEN) as u8);
+    valid &= 0u8.wrapping_sub((mli <= padded_len) as u8);
+    let mut padding = 0u8;
+    for (i, b) in output.iter().enumerate().skip(padded_len - SEMIBLOCK_LEN) {
+        padding |= b & 0u8.wrapping_sub((i >= mli) as u8);
+    }
+    valid &= eq_mask(&[padding], &[0]);
+
+    if valid == 0 {
+        output.fill(0);
+        return Err(KeyWrapError::InvalidCiphertext);
+    }
+
+    Ok(&output[..mli])
+}
@@ -0,0 +1,424 @@
+//! # AES-GCM and AES-GCM-SIV
+//!
+//! A constant-time implementation of AES-128-GCM and AES-256-GCM that does not
+//! rely on AES-NI, PCLMULQDQ or any other CPU extension.
+//!
+//! AES is bitsliced and GHASH uses masked integer multiplications, so neither
+//! has secret dependent memory accesses or branches. This makes the
+//! implementation suitable for platforms where the hardware instructions are
+//! not available, e.g. virtual machines that mask the CPU flags. It is
+//! considerably slower than a hardware backed implementation.
+//!
+//! The nonce misuse-resistant AES-128-GCM-SIV and AES-256-GCM-SIV from
+//! [RFC 8452](https://www.rfc-editor.org/rfc/rfc8452) are available as well.
+//! They use AES-NI and PCLMULQDQ on x86-64 CPUs that support them and the
+//! same constant-time building blocks everywhere else.
+//!
+//! Only 12 byte nonces are supported.
+//!
+//! The AES core is further used for the key wrapping modes AES-KW
+//! ([RFC 3394](https://www.rfc-editor.org/rfc/rfc3394)) and AES-KWP
+//! ([RFC 5649](https://www.rfc-editor.org/rfc/rfc5649)), and for AES-CMAC
+//! ([RFC 4493](https://www.rfc-editor.org/rfc/rfc4493)).
+//!
+//! **NOTE:** This code has not been formally verified yet.
+
+#![no_std]
+#![deny(unsafe_code)]
+
+mod aes;
+#[cfg(target_arch = "x86_64")]
+mod aesni;
+mod block_cipher;
+mod cmac;
+mod gcm;
+mod gcm_siv;
+mod ghash;
+mod kw;
+mod polyval;
+
+/// The length of AES-GCM tags.
+pub const TAG_LEN: usize = 16;
+
+/// The length of AES-GCM nonces.
+pub const NONCE_LEN: usize = 12;
+
+/// Describes the error conditions of the AES-GCM AEAD.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AeadError {
+    /// Indicates that the plaintext argument is too large for AES-GCM.
+    PlaintextTooLarge,
+    /// Indicates that the ciphertext argument is too large for AES-GCM.
+    CiphertextTooLarge,
+    /// Indicates that the associated data argument is too large for AES-GCM.
+    AadTooLarge,
+    /// This indicates that the provided destination ciphertext does not fit the ciphertext and tag.
+    CiphertextTooShort,
+    /// This indicates that the provided destination plaintext is shorter than `ctxt.len() - TAG_LEN`
+    /// and thus will not fit the decrypted plaintext
+    PlaintextTooShort,
+    /// Indicates that the ciphertext is not a valid encryption under the given key and nonce.
+    InvalidCiphertext,
+}
+
+impl core::fmt::Display for AeadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            AeadError::PlaintextTooLarge => "The plaintext argument is too large for AES-GCM",
+            AeadError::CiphertextTooLarge => "The ciphertext argument is too large for AES-GCM",
+            AeadError::AadTooLarge => "The associated data argument is too large for AES-GCM",
+            AeadError::CiphertextTooShort => {
+                "The provided destination ciphertext does not fit the ciphertext and tag"
+            }
+            AeadError::PlaintextTooShort => {
+                "The provided destination plaintext is too short to fit the decrypted plaintext"
+            }
+            AeadError::InvalidCiphertext => {
+                "The ciphertext is not a valid encryption under the given key and nonce."
+            }
+        };
+
+        f.write_str(msg)
+    }
+}
+
+impl From<AeadError> for libcrux_traits::aead::AeadError {
+    fn from(e: AeadError) -> Self {
+        match e {
+            AeadError::PlaintextTooLarge => Self::PlaintextTooLarge,
+            AeadError::CiphertextTooLarge => Self::CiphertextTooLarge,
+            AeadError::AadTooLarge => Self::AadTooLarge,
+            AeadError::CiphertextTooShort => Self::CiphertextTooShort,
+            AeadError::PlaintextTooShort => Self::PlaintextTooShort,
+            AeadError::InvalidCiphertext => Self::InvalidCiphertext,
+        }
+    }
+}
+
+/// Describes the error conditions of AES-KW and AES-KWP.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum KeyWrapError {
+    /// Indicates that the length of the input is not valid for the mode.
+    InvalidInputLength,
+    /// Indicates that the provided output buffer is too short for the result.
+    OutputTooShort,
+    /// Indicates that the wrapped key failed the integrity check.
+    InvalidCiphertext,
+}
+
+impl core::fmt::Display for KeyWrapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            KeyWrapError::InvalidInputLength => "The length of the input is not valid",
+            KeyWrapError::OutputTooShort => "The provided output buffer is too short",
+            KeyWrapError::InvalidCiphertext => "The wrapped key failed the integrity check",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+/// Describes the error conditions of AES-CMAC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CmacError {
+    /// Indicates that the tag does not match the message.
+    InvalidTag,
+}
+
+impl core::fmt::Display for CmacError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CmacError::InvalidTag => f.write_str("The tag does not match the message"),
+        }
+    }
+}
+
+macro_rules! impl_aes_gcm {
+    ($name:ident, $aead:ident, $mode:ident, $key_len:literal, $doc:literal) => {
+        #[doc = $doc]
+        pub mod $name {
+            use crate::{AeadError, NONCE_LEN, TAG_LEN};
+
+            /// The length of the key.
+            pub const KEY_LEN: usize = $key_len;
+
+            #[doc = $doc]
+            ///
+            /// This implements the [`Aead`](libcrux_traits::aead::Aead) trait.
+            pub struct $aead;
+
+            impl libcrux_traits::aead::Aead for $aead {
+                const KEY_LEN: usize = KEY_LEN;
+                const NONCE_LEN: usize = NONCE_LEN;
+                const TAG_LEN: usize = TAG_LEN;
+
+                type Key = [u8; KEY_LEN];
+                type Nonce = [u8; NONCE_LEN];
+                type Tag = [u8; TAG_LEN];
+
+                fn encrypt_detached(
+                    key: &Self::Key,
+                    ptxt: &[u8],
+                    ctxt: &mut [u8],
+                    tag: &mut Self::Tag,
+                    aad: &[u8],
+                    nonce: &Self::Nonce,
+                ) -> Result<(), libcrux_traits::aead::AeadError> {
+                    encrypt_detached(key, ptxt, ctxt, tag, aad, nonce)?;
+                    Ok(())
+                }
+
+                fn decrypt_detached(
+                    key: &Self::Key,
+                    ptxt: &mut [u8],
+                    ctxt: &[u8],
+                    tag: &Self::Tag,
+                    aad: &[u8],
+                    nonce: &Self::Nonce,
+                ) -> Result<(), libcrux_traits::aead::AeadError> {
+                    decrypt_detached(key, ptxt, ctxt, tag, aad, nonce)?;
+                    Ok(())
+                }
+
+                fn encrypt_in_place(
+                    key: &Self::Key,
+                    buffer: &mut [u8],
+                    tag: &mut Self::Tag,
+                    aad: &[u8],
+                    nonce: &Self::Nonce,
+                ) -> Result<(), libcrux_traits::aead::AeadError> {
+                    encrypt_in_place(key, buffer, tag, aad, nonce)?;
+                    Ok(())
+                }
+
+                fn decrypt_in_place(
+                    key: &Self::Key,
+                    buffer: &mut [u8],
+                    tag: &Self::Tag,
+                    aad: &[u8],
+                    nonce: &Self::Nonce,
+                ) -> Result<(), libcrux_traits::aead::AeadError> {
+                    decrypt_in_place(key, buffer, tag, aad, nonce)?;
+                    Ok(())
+                }
+            }
+
+            /// Encrypt `buffer` in place and write the tag to `tag`.
+            pub fn encrypt_in_place(
+                key: &[u8; KEY_LEN],
+                buffer: &mut [u8],
+                tag: &mut [u8; TAG_LEN],
+                aad: &[u8],
+                nonce: &[u8; NONCE_LEN],
+            ) -> Result<(), AeadError> {
+                crate::$mode::encrypt_in_place(key, nonce, aad, buffer, tag)
+            }
+
+            /// Decrypt `buffer` in place with the detached `tag`.
+            ///
+            /// If the tag is invalid, no plaintext is left in `buffer`.
+            pub fn decrypt_in_place(
+                key: &[u8; KEY_LEN],
+                buffer: &mut [u8],
+                tag: &[u8; TAG_LEN],
+                aad: &[u8],
+                nonce: &[u8; NONCE_LEN],
+            ) -> Result<(), AeadError> {
+                crate::$mode::decrypt_in_place(key, nonce, aad, buffer, tag)
+            }
+
+            /// Encrypt `ptxt` and write the ciphertext to the first `ptxt.len()`
+            /// bytes of `ctxt` and the tag to `tag`.
+            pub fn encrypt_detached<'a>(
+                key: &[u8; KEY_LEN],
+                ptxt: &[u8],
+                ctxt: &'a mut [u8],
+                tag: &'a mut [u8; TAG_LEN],
+                aad: &[u8],
+                nonce: &[u8; NONCE_LEN],
+            ) -> Result<(&'a [u8], &'a [u8; TAG_LEN]), AeadError> {
+                crate::$mode::encrypt(key, nonce, aad, ptxt, ctxt, tag)?;
+                Ok((&ctxt[..ptxt.len()], tag))
+            }
+
+            /// Writes the concatenation of the ciphertext and the tag into
+            /// `ctxt` and returns the two pieces separately.
+            pub fn encrypt<'a>(
+                key: &[u8; KEY_LEN],
+                ptxt: &[u8],
+                ctxt: &'a mut [u8],
+                aad: &[u8],
+                nonce: &[u8; NONCE_LEN],
+            ) -> Result<(&'a [u8], &'a [u8; TAG_LEN]), AeadError> {
+                if (ctxt.len() as u64) < (ptxt.len() as u64) + (TAG_LEN as u64) {
+                    return Err(AeadError::CiphertextTooShort);
+                }
+
+                let (ctxt, tag) = ctxt[..ptxt.len() + TAG_LEN].split_at_mut(ptxt.len());
+                let tag: &mut [u8; TAG_LEN] = tag.try_into().unwrap();
+                encrypt_detached(key, ptxt, ctxt, tag, aad, nonce)
+            }
+
+            /// Decrypt `ctxt` with the detached `tag` into the first
+            /// `ctxt.len()` bytes of `ptxt` and return them.
+            ///
+            /// If the tag is invalid, no plaintext is left in `ptxt`.
+            pub fn decrypt_detached<'a>(
+                key: &[u8; KEY_LEN],
+                ptxt: &'a mut [u8],
+                ctxt: &[u8],
+                tag: &[u8; TAG_LEN],
+                aad: &[u8],
+                nonce: &[u8; NONCE_LEN],
+            ) -> Result<&'a [u8], AeadError> {
+                crate::$mode::decrypt(key, nonce, aad, ctxt, tag, ptxt)?;
+                Ok(&ptxt[..ctxt.len()])
+            }
+
+            /// Decrypt the concatenation of ciphertext and tag in `ctxt` into
+            /// the first `ctxt.len() - TAG_LEN` bytes of `ptxt` and return them.
+            ///
+            /// If the tag is invalid, no plaintext is left in `ptxt`.
+            pub fn decrypt<'a>(
+                key: &[u8; KEY_LEN],
+                ptxt: &'a mut [u8],
+                ctxt: &[u8],
+                aad: &[u8],
+                nonce: &[u8; NONCE_LEN],
+            ) -> Result<&'a [u8], AeadError> {
+                if ctxt.len() < TAG_LEN {
+                    return Err(AeadError::InvalidCiphertext);
+                }
+
+                let (ctxt, tag) = ctxt.split_at(ctxt.len() - TAG_LEN);
+                decrypt_detached(key, ptxt, ctxt, tag.try_into().unwrap(), aad, nonce)
+            }
+        }
+    };
+}
+
+impl_aes_gcm!(aes128, Aes128Gcm, gcm, 16, "AES-128-GCM");
+impl_aes_gcm!(aes256, Aes256Gcm, gcm, 32, "AES-256-GCM");
+impl_aes_gcm!(
+    aes128_gcm_siv,
+    Aes128GcmSiv,
+    gcm_siv,
+    16,
+    "AES-128-GCM-SIV (RFC 8452)"
+);
+impl_aes_gcm!(
+    aes256_gcm_siv,
+    Aes256GcmSiv,
+    gcm_siv,
+    32,
+    "AES-256-GCM-SIV (RFC 8452)"
+);
+
+macro_rules! impl_aes_kw {
+    ($name:ident, $key_len:literal, $doc:literal) => {
+        #[doc = $doc]
+        pub mod $name {
+            use crate::{block_cipher::BlockCipher, KeyWrapError};
+
+            /// The length of the key-encryption key.
+            pub const KEY_LEN: usize = $key_len;
+
+            /// Wrap `input` with AES-KW (RFC 3394) into the first
+            /// `input.len() + 8` bytes of `output` and return them.
+            ///
+            /// The length of `input` must be a multiple of 8 and at least 16.
+            pub fn wrap<'a>(
+                key: &[u8; KEY_LEN],
+                input: &[u8],
+                output: &'a mut [u8],
+            ) -> Result<&'a [u8], KeyWrapError> {
+                crate::kw::wrap(&crate::block_cipher::Cipher::new(key), input, output)
+            }
+
+            /// Unwrap the AES-KW (RFC 3394) ciphertext `input` into the first
+            /// `input.len() - 8` bytes of `output` and return them.
+            ///
+            /// If the integrity check fails, no key material is left in `output`.
+            pub fn unwrap<'a>(
+                key: &[u8; KEY_LEN],
+                input: &[u8],
+                output: &'a mut [u8],
+            ) -> Result<&'a [u8], KeyWrapError> {
+                crate::kw::unwrap(&crate::block_cipher::Cipher::new(key), input, output)
+            }
+
+            /// Wrap `input` of any non-zero length with AES-KWP (RFC 5649) and
+            /// return the wrapped key from the beginning of `output`.
+            ///
+            /// `output` must hold `input.len()` rounded up to a multiple of 8,
+            /// plus 8 bytes.
+            pub fn wrap_with_padding<'a>(
+                key: &[u8; KEY_LEN],
+                input: &[u8],
+                output: &'a mut [u8],
+            ) -> Result<&'a [u8], KeyWrapError> {
+                crate::kw::wrap_with_padding(&crate::block_cipher::Cipher::new(key), input, output)
+            }
+
+            /// Unwrap the AES-KWP (RFC 5649) ciphertext `input` and return the
+            /// key from the beginning of `output`.
+            ///
+            /// `output` must hold `input.len() - 8` bytes. If the integrity
+            /// check fails, no key material is left in `output`.
+            pub fn unwrap_with_padding<'a>(
+                key: &[u8; KEY_LEN],
+                input: &[u8],
+                output: &'a mut [u8],
+            ) -> Result<&'a [u8], KeyWrapError> {
+                crate::kw::unwrap_with_padding(
+                    &crate::block_cipher::Cipher::new(key),
+                    input,
+                    output,
+                )
+            }
+        }
+    };
+}
+
+impl_aes_kw!(
+    aes128_kw,
+    16,
+    "AES-KW and AES-KWP with a 128-bit key-encryption key"
+);
+impl_aes_kw!(
+    aes256_kw,
+    32,
+    "AES-KW and AES-KWP with a 256-bit key-encryption key"
+);
+
+/// The length of AES-CMAC tags.
+pub const CMAC_TAG_LEN: usize = 16;
+
+macro_rules! impl_aes_cmac {
+    ($name:ident, $key_len:literal, $doc:literal) => {
+        #[doc = $doc]
+        pub mod $name {
+            use crate::{block_cipher::BlockCipher, CmacError, CMAC_TAG_LEN};
+
+            /// The length of the key.
+            pub const KEY_LEN: usize = $key_len;
+
+            /// Compute the tag of `msg`.
+            pub fn mac(key: &[u8; KEY_LEN], msg: &[u8]) -> [u8; CMAC_TAG_LEN] {
+                crate::cmac::mac(&crate::block_cipher::Cipher::new(key), msg)
+            }
+
+            /// Check the `tag` of `msg` in constant time.
+            pub fn verify(
+                key: &[u8; KEY_LEN],
+                msg: &[u8],
+                tag: &[u8; CMAC_TAG_LEN],
+            ) -> Result<(), CmacError> {
+                crate::cmac::verify(&crate::block_cipher::Cipher::new(key), msg, tag)
+            }
+        }
+    };
+}
+
+impl_aes_cmac!(aes128_cmac, 16, "AES-128-CMAC");
+impl_aes_cmac!(aes256_cmac, 32, "AES-256-CMAC");
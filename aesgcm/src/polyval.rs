@@ -0,0 +1,44 @@
+//! Constant-time POLYVAL (RFC 8452, Section 3).
+//!
+//! POLYVAL is GHASH with the bytes of every block reversed, so it is computed
+//! with [`GHash`] and the mapping given in RFC 8452, Appendix A:
+//!
+//! ```text
+//! POLYVAL(H, X_1, ..., X_n) =
+//!     ByteReverse(GHASH(mulX_GHASH(ByteReverse(H)), ByteReverse(X_1), ..., ByteReverse(X_n)))
+//! ```
+
+use crate::aes::BLOCK_LEN;
+use crate::gcm_siv::UniversalHash;
+use crate::ghash::GHash;
+
+/// Multiply by `x` in the bit-reflected GHASH field.
+fn mul_x_ghash(block: &[u8; BLOCK_LEN]) -> [u8; BLOCK_LEN] {
+    let v = u128::from_be_bytes(*block);
+    let mask = 0u128.wrapping_sub(v & 1);
+    ((v >> 1) ^ (mask & (0xe1 << 120))).to_be_bytes()
+}
+
+fn byte_reverse(block: &[u8]) -> [u8; BLOCK_LEN] {
+    let mut out: [u8; BLOCK_LEN] = block.try_into().unwrap();
+    out.reverse();
+    out
+}
+
+pub(crate) struct Polyval(GHash);
+
+impl UniversalHash for Polyval {
+    fn new(h: &[u8; BLOCK_LEN]) -> Self {
+        Self(GHash::new(&mul_x_ghash(&byte_reverse(h))))
+    }
+
+    fn update_blocks(&mut self, blocks: &[u8]) {
+        for block in blocks.chunks_exact(BLOCK_LEN) {
+            self.0.update_block(&byte_reverse(block));
+        }
+    }
+
+    fn finish(self) -> [u8; BLOCK_LEN] {
+        byte_reverse(&self.0.state())
+    }
+}
@@ -0,0 +1,238 @@
+use std::{fs::File, io::BufReader};
+
+use libcrux_aesgcm::{aes128, aes256, AeadError, TAG_LEN};
+use rand_core::{OsRng, RngCore, TryRngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+pub(crate) trait ReadFromFile {
+    fn from_file<T: DeserializeOwned>(file_str: &'static str) -> T {
+        let file = match File::open(file_str) {
+            Ok(f) => f,
+            Err(_) => panic!("Couldn't open file {file_str}."),
+        };
+        let reader = BufReader::new(file);
+        match serde_json::from_reader(reader) {
+            Ok(r) => r,
+            Err(e) => {
+                println!("{:?}", e);
+                panic!("Error reading file {file_str}.")
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct AeadTestVector {
+    algorithm: String,
+    numberOfTests: usize,
+    notes: Option<Value>, // text notes (might not be present), keys correspond to flags
+    header: Vec<Value>,   // not used
+    testGroups: Vec<TestGroup>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct TestGroup {
+    ivSize: usize,
+    keySize: usize,
+    tagSize: usize,
+    r#type: String,
+    tests: Vec<Test>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct Test {
+    tcId: usize,
+    comment: String,
+    #[serde(with = "hex::serde")]
+    key: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    iv: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    aad: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    msg: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    ct: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    tag: Vec<u8>,
+    result: String,
+    flags: Vec<String>,
+}
+
+impl ReadFromFile for AeadTestVector {}
+
+/// Encrypt and decrypt with the key size given by the length of `key`.
+fn encrypt(key: &[u8], ptxt: &[u8], aad: &[u8], nonce: &[u8; 12]) -> (Vec<u8>, [u8; TAG_LEN]) {
+    let mut ctxt = vec![0u8; ptxt.len()];
+    let mut tag = [0u8; TAG_LEN];
+    match key.len() {
+        16 => aes128::encrypt_detached(
+            key.try_into().unwrap(),
+            ptxt,
+            &mut ctxt,
+            &mut tag,
+            aad,
+            nonce,
+        ),
+        32 => aes256::encrypt_detached(
+            key.try_into().unwrap(),
+            ptxt,
+            &mut ctxt,
+            &mut tag,
+            aad,
+            nonce,
+        ),
+        _ => unreachable!(),
+    }
+    .unwrap();
+
+    (ctxt, tag)
+}
+
+fn decrypt(
+    key: &[u8],
+    ctxt: &[u8],
+    tag: &[u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; 12],
+) -> Result<Vec<u8>, AeadError> {
+    let mut ptxt = vec![0u8; ctxt.len()];
+    match key.len() {
+        16 => aes128::decrypt_detached(key.try_into().unwrap(), &mut ptxt, ctxt, tag, aad, nonce),
+        32 => aes256::decrypt_detached(key.try_into().unwrap(), &mut ptxt, ctxt, tag, aad, nonce),
+        _ => unreachable!(),
+    }?;
+
+    Ok(ptxt)
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn wycheproof() {
+    let aes_gcm_tests: AeadTestVector =
+        AeadTestVector::from_file("../tests/wycheproof/aes_gcm_test.json");
+
+    let num_tests = aes_gcm_tests.numberOfTests;
+    let mut skipped_tests = 0;
+    let mut tests_run = 0;
+    assert_eq!(aes_gcm_tests.algorithm, "AES-GCM");
+
+    for testGroup in aes_gcm_tests.testGroups.iter() {
+        assert_eq!(testGroup.r#type, "AeadTest");
+        assert_eq!(testGroup.tagSize, 128);
+
+        if testGroup.keySize == 192 || testGroup.ivSize != 96 {
+            // Only AES-128 and AES-256 with 12-byte nonces are implemented.
+            skipped_tests += testGroup.tests.len();
+            continue;
+        }
+
+        for test in testGroup.tests.iter() {
+            println!("Test {:?}: {:?}", test.tcId, test.comment);
+            let valid = test.result.eq("valid");
+            let nonce = <&[u8; 12]>::try_from(&test.iv[..]).unwrap();
+            let exp_tag = <&[u8; TAG_LEN]>::try_from(&test.tag[..]).unwrap();
+
+            let (ctxt, tag) = encrypt(&test.key, &test.msg, &test.aad, nonce);
+            if valid {
+                assert_eq!(&tag, exp_tag);
+                assert_eq!(ctxt, test.ct);
+            } else {
+                assert!(tag != *exp_tag || ctxt != test.ct);
+            }
+
+            match decrypt(&test.key, &test.ct, exp_tag, &test.aad, nonce) {
+                Ok(ptxt) => {
+                    assert!(valid);
+                    assert_eq!(ptxt, test.msg);
+                }
+                Err(e) => {
+                    assert!(!valid);
+                    assert_eq!(e, AeadError::InvalidCiphertext);
+                }
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    // Check that we ran all tests.
+    println!(
+        "Ran {} out of {} tests and skipped {}.",
+        tests_run, num_tests, skipped_tests
+    );
+    assert_eq!(num_tests - skipped_tests, tests_run);
+}
+
+#[test]
+fn self_test_rand() {
+    let mut rng = OsRng.unwrap_err();
+
+    for len in [0, 1, 15, 16, 17, 63, 64, 65, 1000] {
+        let mut key = [0u8; 32];
+        let mut nonce = [0u8; 12];
+        let mut ptxt = vec![0u8; len];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut nonce);
+        rng.fill_bytes(&mut ptxt);
+
+        let mut ctxt = vec![0u8; len + TAG_LEN];
+        aes256::encrypt(&key, &ptxt, &mut ctxt, b"aad", &nonce).unwrap();
+        let mut decrypted = vec![0u8; len];
+        aes256::decrypt(&key, &mut decrypted, &ctxt, b"aad", &nonce).unwrap();
+        assert_eq!(decrypted, ptxt);
+
+        let key = <&[u8; 16]>::try_from(&key[..16]).unwrap();
+        aes128::encrypt(key, &ptxt, &mut ctxt, b"aad", &nonce).unwrap();
+        aes128::decrypt(key, &mut decrypted, &ctxt, b"aad", &nonce).unwrap();
+        assert_eq!(decrypted, ptxt);
+
+        // A modified tag is rejected and nothing is written.
+        ctxt[len] ^= 1;
+        let mut decrypted = vec![0u8; len];
+        assert_eq!(
+            aes128::decrypt(key, &mut decrypted, &ctxt, b"aad", &nonce),
+            Err(AeadError::InvalidCiphertext)
+        );
+        assert!(decrypted.iter().all(|b| *b == 0));
+    }
+}
+
+#[test]
+fn aead_trait() {
+    use libcrux_traits::aead::{Aead, AeadError};
+
+    fn round_trip<A: Aead>(key: &A::Key, nonce: &A::Nonce) {
+        let ptxt: Vec<u8> = (0..100).collect();
+
+        let mut ctxt = vec![0u8; ptxt.len() + A::TAG_LEN];
+        A::encrypt(key, &ptxt, &mut ctxt, b"aad", nonce).unwrap();
+
+        let mut buffer = ptxt.clone();
+        let mut tag = A::Tag::default();
+        A::encrypt_in_place(key, &mut buffer, &mut tag, b"aad", nonce).unwrap();
+        assert_eq!(buffer, ctxt[..ptxt.len()]);
+        assert_eq!(tag.as_ref(), &ctxt[ptxt.len()..]);
+
+        A::decrypt_in_place(key, &mut buffer, &tag, b"aad", nonce).unwrap();
+        assert_eq!(buffer, ptxt);
+
+        let mut decrypted = vec![0u8; ptxt.len()];
+        A::decrypt(key, &mut decrypted, &ctxt, b"aad", nonce).unwrap();
+        assert_eq!(decrypted, ptxt);
+
+        assert_eq!(
+            A::decrypt(key, &mut decrypted, &ctxt, b"other aad", nonce),
+            Err(AeadError::InvalidCiphertext)
+        );
+    }
+
+    round_trip::<aes128::Aes128Gcm>(&[1; 16], &[2; 12]);
+    round_trip::<aes256::Aes256Gcm>(&[1; 32], &[2; 12]);
+    round_trip::<libcrux_aesgcm::aes128_gcm_siv::Aes128GcmSiv>(&[1; 16], &[2; 12]);
+    round_trip::<libcrux_aesgcm::aes256_gcm_siv::Aes256GcmSiv>(&[1; 32], &[2; 12]);
+}
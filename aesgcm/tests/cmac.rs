@@ -0,0 +1,113 @@
+use std::{fs::File, io::BufReader};
+
+use libcrux_aesgcm::{aes128_cmac, aes256_cmac, CmacError, CMAC_TAG_LEN};
+use serde::{de::DeserializeOwned, Deserialize};
+
+fn from_file<T: DeserializeOwned>(file_str: &'static str) -> T {
+    let file = File::open(file_str).unwrap_or_else(|_| panic!("Couldn't open file {file_str}."));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("Error reading file {file_str}: {e:?}"))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct MacTestVector {
+    algorithm: String,
+    numberOfTests: usize,
+    testGroups: Vec<TestGroup>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct TestGroup {
+    keySize: usize,
+    tagSize: usize,
+    tests: Vec<Test>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct Test {
+    tcId: usize,
+    comment: String,
+    #[serde(with = "hex::serde")]
+    key: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    msg: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    tag: Vec<u8>,
+    result: String,
+}
+
+#[test]
+fn wycheproof() {
+    let tests: MacTestVector = from_file("../tests/wycheproof/aes_cmac_test.json");
+    assert_eq!(tests.algorithm, "AES-CMAC");
+
+    let mut skipped_tests = 0;
+    let mut tests_run = 0;
+    for group in tests.testGroups.iter() {
+        assert_eq!(group.tagSize, 128);
+        if group.keySize != 128 && group.keySize != 256 {
+            // Only AES-128 and AES-256 are implemented.
+            skipped_tests += group.tests.len();
+            continue;
+        }
+
+        for test in group.tests.iter() {
+            println!("Test {:?}: {:?}", test.tcId, test.comment);
+            let valid = test.result == "valid";
+            let tag = <&[u8; CMAC_TAG_LEN]>::try_from(&test.tag[..]).unwrap();
+
+            let (computed, verified) = match group.keySize {
+                128 => {
+                    let key = test.key.as_slice().try_into().unwrap();
+                    (
+                        aes128_cmac::mac(key, &test.msg),
+                        aes128_cmac::verify(key, &test.msg, tag),
+                    )
+                }
+                _ => {
+                    let key = test.key.as_slice().try_into().unwrap();
+                    (
+                        aes256_cmac::mac(key, &test.msg),
+                        aes256_cmac::verify(key, &test.msg, tag),
+                    )
+                }
+            };
+
+            if valid {
+                assert_eq!(&computed, tag);
+                verified.unwrap();
+            } else {
+                assert_ne!(&computed, tag);
+                assert_eq!(verified, Err(CmacError::InvalidTag));
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    assert_eq!(tests.numberOfTests - skipped_tests, tests_run);
+}
+
+#[test]
+fn rfc4493() {
+    // RFC 4493, Section 4
+    let key = hex::decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let key = key.as_slice().try_into().unwrap();
+    let msg = hex::decode(
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+         30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    )
+    .unwrap();
+
+    for (len, tag) in [
+        (0, "bb1d6929e95937287fa37d129b756746"),
+        (16, "070a16b46b4d4144f79bdd9dd04a287c"),
+        (40, "dfa66747de9ae63030ca32611497c827"),
+        (64, "51f0bebf7e3b9d92fc49741779363cfe"),
+    ] {
+        assert_eq!(hex::encode(aes128_cmac::mac(key, &msg[..len])), tag);
+    }
+}
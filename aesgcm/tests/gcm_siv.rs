@@ -0,0 +1,220 @@
+use std::{fs::File, io::BufReader};
+
+use libcrux_aesgcm::{aes128_gcm_siv, aes256_gcm_siv, AeadError, TAG_LEN};
+use rand_core::{OsRng, RngCore, TryRngCore};
+use serde::{de::DeserializeOwned, Deserialize};
+
+fn from_file<T: DeserializeOwned>(file_str: &'static str) -> T {
+    let file = File::open(file_str).unwrap_or_else(|_| panic!("Couldn't open file {file_str}."));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("Error reading file {file_str}: {e:?}"))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct AeadTestVector {
+    algorithm: String,
+    numberOfTests: usize,
+    testGroups: Vec<TestGroup>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct TestGroup {
+    ivSize: usize,
+    tagSize: usize,
+    tests: Vec<Test>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct Test {
+    tcId: usize,
+    comment: String,
+    #[serde(with = "hex::serde")]
+    key: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    iv: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    aad: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    msg: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    ct: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    tag: Vec<u8>,
+    result: String,
+}
+
+/// A known answer test from RFC 8452, Appendix C.
+#[derive(Deserialize, Debug, Clone)]
+struct Rfc8452Test {
+    section: String,
+    #[serde(with = "hex::serde")]
+    key: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    aad: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    msg: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    ct: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    tag: Vec<u8>,
+}
+
+/// Encrypt with the key size given by the length of `key`.
+fn encrypt(key: &[u8], ptxt: &[u8], aad: &[u8], nonce: &[u8; 12]) -> (Vec<u8>, [u8; TAG_LEN]) {
+    let mut ctxt = vec![0u8; ptxt.len()];
+    let mut tag = [0u8; TAG_LEN];
+    match key.len() {
+        16 => aes128_gcm_siv::encrypt_detached(
+            key.try_into().unwrap(),
+            ptxt,
+            &mut ctxt,
+            &mut tag,
+            aad,
+            nonce,
+        ),
+        32 => aes256_gcm_siv::encrypt_detached(
+            key.try_into().unwrap(),
+            ptxt,
+            &mut ctxt,
+            &mut tag,
+            aad,
+            nonce,
+        ),
+        _ => unreachable!(),
+    }
+    .unwrap();
+
+    (ctxt, tag)
+}
+
+/// Decrypt with the key size given by the length of `key`.
+fn decrypt(
+    key: &[u8],
+    ctxt: &[u8],
+    tag: &[u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; 12],
+) -> Result<Vec<u8>, AeadError> {
+    let mut ptxt = vec![0u8; ctxt.len()];
+    match key.len() {
+        16 => aes128_gcm_siv::decrypt_detached(
+            key.try_into().unwrap(),
+            &mut ptxt,
+            ctxt,
+            tag,
+            aad,
+            nonce,
+        ),
+        32 => aes256_gcm_siv::decrypt_detached(
+            key.try_into().unwrap(),
+            &mut ptxt,
+            ctxt,
+            tag,
+            aad,
+            nonce,
+        ),
+        _ => unreachable!(),
+    }?;
+
+    Ok(ptxt)
+}
+
+#[test]
+fn rfc8452() {
+    let tests: Vec<Rfc8452Test> = from_file("tests/rfc8452.json");
+    assert_eq!(tests.len(), 50);
+
+    for test in tests.iter() {
+        println!("RFC 8452 {}: {}", test.section, hex::encode(&test.msg));
+        let nonce = <&[u8; 12]>::try_from(&test.nonce[..]).unwrap();
+        let exp_tag = <&[u8; TAG_LEN]>::try_from(&test.tag[..]).unwrap();
+
+        let (ctxt, tag) = encrypt(&test.key, &test.msg, &test.aad, nonce);
+        assert_eq!(&tag, exp_tag);
+        assert_eq!(ctxt, test.ct);
+
+        let ptxt = decrypt(&test.key, &test.ct, exp_tag, &test.aad, nonce).unwrap();
+        assert_eq!(ptxt, test.msg);
+    }
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn wycheproof() {
+    let aes_gcm_siv_tests: AeadTestVector = from_file("../tests/wycheproof/aes_gcm_siv_test.json");
+    assert_eq!(aes_gcm_siv_tests.algorithm, "AES-GCM-SIV");
+
+    let mut tests_run = 0;
+    for testGroup in aes_gcm_siv_tests.testGroups.iter() {
+        assert_eq!(testGroup.ivSize, 96);
+        assert_eq!(testGroup.tagSize, 128);
+
+        for test in testGroup.tests.iter() {
+            println!("Test {:?}: {:?}", test.tcId, test.comment);
+            let valid = test.result.eq("valid");
+            let nonce = <&[u8; 12]>::try_from(&test.iv[..]).unwrap();
+            let exp_tag = <&[u8; TAG_LEN]>::try_from(&test.tag[..]).unwrap();
+
+            let (ctxt, tag) = encrypt(&test.key, &test.msg, &test.aad, nonce);
+            if valid {
+                assert_eq!(&tag, exp_tag);
+                assert_eq!(ctxt, test.ct);
+            } else {
+                assert!(tag != *exp_tag || ctxt != test.ct);
+            }
+
+            match decrypt(&test.key, &test.ct, exp_tag, &test.aad, nonce) {
+                Ok(ptxt) => {
+                    assert!(valid);
+                    assert_eq!(ptxt, test.msg);
+                }
+                Err(e) => {
+                    assert!(!valid);
+                    assert_eq!(e, AeadError::InvalidCiphertext);
+                }
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    assert_eq!(aes_gcm_siv_tests.numberOfTests, tests_run);
+}
+
+#[test]
+fn self_test_rand() {
+    let mut rng = OsRng.unwrap_err();
+
+    for len in [0, 1, 15, 16, 17, 63, 64, 65, 1000] {
+        let mut key = [0u8; 32];
+        let mut nonce = [0u8; 12];
+        let mut ptxt = vec![0u8; len];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut nonce);
+        rng.fill_bytes(&mut ptxt);
+
+        let mut ctxt = vec![0u8; len + TAG_LEN];
+        aes256_gcm_siv::encrypt(&key, &ptxt, &mut ctxt, b"aad", &nonce).unwrap();
+        let mut decrypted = vec![0u8; len];
+        aes256_gcm_siv::decrypt(&key, &mut decrypted, &ctxt, b"aad", &nonce).unwrap();
+        assert_eq!(decrypted, ptxt);
+
+        let key = <&[u8; 16]>::try_from(&key[..16]).unwrap();
+        aes128_gcm_siv::encrypt(key, &ptxt, &mut ctxt, b"aad", &nonce).unwrap();
+        aes128_gcm_siv::decrypt(key, &mut decrypted, &ctxt, b"aad", &nonce).unwrap();
+        assert_eq!(decrypted, ptxt);
+
+        // A modified tag is rejected and the decrypted plaintext is wiped.
+        ctxt[len] ^= 1;
+        assert_eq!(
+            aes128_gcm_siv::decrypt(key, &mut decrypted, &ctxt, b"aad", &nonce),
+            Err(AeadError::InvalidCiphertext)
+        );
+        assert!(decrypted.iter().all(|b| *b == 0));
+    }
+}
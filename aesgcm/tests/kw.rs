@@ -0,0 +1,170 @@
+use std::{fs::File, io::BufReader};
+
+use libcrux_aesgcm::{aes128_kw, aes256_kw, KeyWrapError};
+use serde::{de::DeserializeOwned, Deserialize};
+
+fn from_file<T: DeserializeOwned>(file_str: &'static str) -> T {
+    let file = File::open(file_str).unwrap_or_else(|_| panic!("Couldn't open file {file_str}."));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("Error reading file {file_str}: {e:?}"))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct KeyWrapTestVector {
+    algorithm: String,
+    numberOfTests: usize,
+    testGroups: Vec<TestGroup>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct TestGroup {
+    keySize: usize,
+    tests: Vec<Test>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[allow(non_snake_case)]
+struct Test {
+    tcId: usize,
+    comment: String,
+    #[serde(with = "hex::serde")]
+    key: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    msg: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    ct: Vec<u8>,
+    result: String,
+}
+
+type WrapFn = for<'a> fn(&[u8], &[u8], &'a mut [u8]) -> Result<&'a [u8], KeyWrapError>;
+
+/// Run the Wycheproof tests in `file` with the AES-128 and AES-256 functions.
+fn run_wycheproof(file: &'static str, algorithm: &str, wrap: [WrapFn; 2], unwrap: [WrapFn; 2]) {
+    let tests: KeyWrapTestVector = from_file(file);
+    assert_eq!(tests.algorithm, algorithm);
+
+    let mut skipped_tests = 0;
+    let mut tests_run = 0;
+    for group in tests.testGroups.iter() {
+        let (wrap, unwrap) = match group.keySize {
+            128 => (wrap[0], unwrap[0]),
+            256 => (wrap[1], unwrap[1]),
+            _ => {
+                // Only AES-128 and AES-256 are implemented.
+                skipped_tests += group.tests.len();
+                continue;
+            }
+        };
+
+        for test in group.tests.iter() {
+            println!("Test {:?}: {:?}", test.tcId, test.comment);
+            // Short keys are acceptable but not supported.
+            let valid = test.result == "valid";
+
+            let mut ct = vec![0u8; test.msg.len() + 16];
+            match wrap(&test.key, &test.msg, &mut ct) {
+                Ok(ct) => {
+                    if valid {
+                        assert_eq!(ct, test.ct);
+                    } else {
+                        assert_ne!(ct, test.ct);
+                    }
+                }
+                Err(e) => {
+                    assert!(!valid);
+                    assert_eq!(e, KeyWrapError::InvalidInputLength);
+                }
+            }
+
+            let mut msg = vec![0xffu8; test.ct.len()];
+            match unwrap(&test.key, &test.ct, &mut msg) {
+                Ok(msg) => {
+                    assert!(valid);
+                    assert_eq!(msg, test.msg);
+                }
+                Err(e) => {
+                    assert!(!valid);
+                    assert!(matches!(
+                        e,
+                        KeyWrapError::InvalidCiphertext | KeyWrapError::InvalidInputLength
+                    ));
+                    if e == KeyWrapError::InvalidCiphertext {
+                        assert!(msg.iter().take(test.ct.len() - 8).all(|b| *b == 0));
+                    }
+                }
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    assert_eq!(tests.numberOfTests - skipped_tests, tests_run);
+}
+
+#[test]
+fn wycheproof_kw() {
+    run_wycheproof(
+        "../tests/wycheproof/aes_wrap_test.json",
+        "AES-WRAP",
+        [
+            |k, i, o| aes128_kw::wrap(k.try_into().unwrap(), i, o),
+            |k, i, o| aes256_kw::wrap(k.try_into().unwrap(), i, o),
+        ],
+        [
+            |k, i, o| aes128_kw::unwrap(k.try_into().unwrap(), i, o),
+            |k, i, o| aes256_kw::unwrap(k.try_into().unwrap(), i, o),
+        ],
+    );
+}
+
+#[test]
+fn wycheproof_kwp() {
+    run_wycheproof(
+        "../tests/wycheproof/aes_kwp_test.json",
+        "AES-KWP",
+        [
+            |k, i, o| aes128_kw::wrap_with_padding(k.try_into().unwrap(), i, o),
+            |k, i, o| aes256_kw::wrap_with_padding(k.try_into().unwrap(), i, o),
+        ],
+        [
+            |k, i, o| aes128_kw::unwrap_with_padding(k.try_into().unwrap(), i, o),
+            |k, i, o| aes256_kw::unwrap_with_padding(k.try_into().unwrap(), i, o),
+        ],
+    );
+}
+
+#[test]
+fn rfc3394() {
+    // RFC 3394, Section 4.1 and 4.6
+    let kek = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let key = hex::decode("00112233445566778899aabbccddeeff").unwrap();
+    let mut wrapped = [0u8; 24];
+    aes128_kw::wrap(kek.as_slice().try_into().unwrap(), &key, &mut wrapped).unwrap();
+    assert_eq!(
+        hex::encode(wrapped),
+        "1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5"
+    );
+
+    let kek =
+        hex::decode("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+    let key =
+        hex::decode("00112233445566778899aabbccddeeff000102030405060708090a0b0c0d0e0f").unwrap();
+    let mut wrapped = [0u8; 40];
+    aes256_kw::wrap(kek.as_slice().try_into().unwrap(), &key, &mut wrapped).unwrap();
+    assert_eq!(
+        hex::encode(wrapped),
+        "28c9f404c4b810f4cbccb35cfb87f8263f5786e2d80ed326cbc7f0e71a99f43bfb988b9b7a02dd21"
+    );
+
+    let mut unwrapped = [0u8; 32];
+    aes256_kw::unwrap(kek.as_slice().try_into().unwrap(), &wrapped, &mut unwrapped).unwrap();
+    assert_eq!(unwrapped.to_vec(), key);
+
+    // Output buffers that are too short are rejected.
+    assert_eq!(
+        aes256_kw::unwrap(kek.as_slice().try_into().unwrap(), &wrapped, &mut [0u8; 31]),
+        Err(KeyWrapError::OutputTooShort)
+    );
+}
@@ -417,7 +417,7 @@ fn update_block(
     })
 }
 
-pub(crate) fn init(hash: &mut [u64], kk: u32, nn: u32) {
+pub fn init(hash: &mut [u64], kk: u32, nn: u32) {
     let salt: [u8; 16] = [0u8; 16usize];
     let personal: [u8; 16] = [0u8; 16usize];
     let p: crate::hacl::hash_blake2b::blake2_params = crate::hacl::hash_blake2b::blake2_params {
@@ -519,7 +519,7 @@ fn update_key(wv: &mut [u64], hash: &mut [u64], kk: u32, k: &[u8], ll: u32) {
     libcrux_hacl_rs::util::memzero0::memzero::<u8>(&mut b, 128u32)
 }
 
-pub(crate) fn update_multi(
+pub fn update_multi(
     len: u32,
     wv: &mut [u64],
     hash: &mut [u64],
@@ -538,7 +538,7 @@ pub(crate) fn update_multi(
     }
 }
 
-pub(crate) fn update_last(
+pub fn update_last(
     len: u32,
     wv: &mut [u64],
     hash: &mut [u64],
@@ -598,7 +598,7 @@ fn update(wv: &mut [u64], hash: &mut [u64], kk: u32, k: &[u8], ll: u32, d: &[u8]
     }
 }
 
-pub(crate) fn finish(nn: u32, output: &mut [u8], hash: &[u64]) {
+pub fn finish(nn: u32, output: &mut [u8], hash: &[u64]) {
     let mut b: [u8; 64] = [0u8; 64usize];
     let first: (&mut [u8], &mut [u8]) = b.split_at_mut(0usize);
     let second: (&mut [u8], &mut [u8]) = first.1.split_at_mut(32usize);
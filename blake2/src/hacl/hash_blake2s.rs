@@ -386,7 +386,7 @@ fn update_block(
     })
 }
 
-pub(crate) fn init(hash: &mut [u32], kk: u32, nn: u32) {
+pub fn init(hash: &mut [u32], kk: u32, nn: u32) {
     let salt: [u8; 8] = [0u8; 8usize];
     let personal: [u8; 8] = [0u8; 8usize];
     let p: crate::hacl::hash_blake2b::blake2_params = crate::hacl::hash_blake2b::blake2_params {
@@ -485,7 +485,7 @@ fn update_key(wv: &mut [u32], hash: &mut [u32], kk: u32, k: &[u8], ll: u32) {
     lib::memzero0::memzero::<u8>(&mut b, 64u32)
 }
 
-pub(crate) fn update_multi(
+pub fn update_multi(
     len: u32,
     wv: &mut [u32],
     hash: &mut [u32],
@@ -501,7 +501,7 @@ pub(crate) fn update_multi(
     }
 }
 
-pub(crate) fn update_last(
+pub fn update_last(
     len: u32,
     wv: &mut [u32],
     hash: &mut [u32],
@@ -544,7 +544,7 @@ fn update(wv: &mut [u32], hash: &mut [u32], kk: u32, k: &[u8], ll: u32, d: &[u8]
     }
 }
 
-pub(crate) fn finish(nn: u32, output: &mut [u8], hash: &[u32]) {
+pub fn finish(nn: u32, output: &mut [u8], hash: &[u32]) {
     let mut b: [u8; 32] = [0u8; 32usize];
     let first: (&mut [u8], &mut [u8]) = b.split_at_mut(0usize);
     let second: (&mut [u8], &mut [u8]) = first.1.split_at_mut(16usize);
@@ -6,10 +6,12 @@ use core::marker::PhantomData;
 use libcrux_hacl_rs::streaming_types::error_code;
 
 use crate::hacl::hash_blake2b::{
-    blake2_params, digest, index, malloc_raw, params_and_key, reset, reset_with_key, state_t,
+    blake2_params, copy, digest, index, malloc_raw, params_and_key, reset, reset_with_key, state_t,
     update0,
 };
 
+use super::blake2bp::Blake2bp;
+use super::blake2xb::{Blake2Xb, MAX_XOF_LEN};
 use super::{ConstDigestLen, ConstKeyLen, ConstKeyLenConstDigestLen, Dynamic, Error};
 
 const PARAM_LEN: usize = 16;
@@ -110,6 +112,16 @@ impl<'a> Blake2bBuilder<'a, &'a ()> {
             _phantom: PhantomData,
         })
     }
+
+    /// Constructs the [`Blake2bp`] tree mode hasher with dynamic digest length.
+    pub fn build_parallel(self, digest_length: u8) -> Result<Blake2bp, Error> {
+        self.parallel_with_key(&[], digest_length)
+    }
+
+    /// Constructs the [`Blake2Xb`] hasher with the given output length.
+    pub fn build_xof(self, output_length: u32) -> Result<Blake2Xb, Error> {
+        self.xof_with_key(&[], output_length)
+    }
 }
 
 impl<'a, const KEY_LEN: usize> Blake2bBuilder<'a, &'a [u8; KEY_LEN]> {
@@ -211,6 +223,16 @@ impl<'a, const KEY_LEN: usize> Blake2bBuilder<'a, &'a [u8; KEY_LEN]> {
             _phantom: PhantomData,
         })
     }
+
+    /// Constructs the [`Blake2bp`] tree mode hasher with dynamic digest length.
+    pub fn build_parallel(self, digest_length: u8) -> Result<Blake2bp, Error> {
+        self.parallel_with_key(self.key, digest_length)
+    }
+
+    /// Constructs the [`Blake2Xb`] hasher with the given output length.
+    pub fn build_xof(self, output_length: u32) -> Result<Blake2Xb, Error> {
+        self.xof_with_key(self.key, output_length)
+    }
 }
 
 impl<'a> Blake2bBuilder<'a, &'a [u8]> {
@@ -308,6 +330,16 @@ impl<'a> Blake2bBuilder<'a, &'a [u8]> {
             _phantom: PhantomData,
         })
     }
+
+    /// Constructs the [`Blake2bp`] tree mode hasher with dynamic digest length.
+    pub fn build_parallel(self, digest_length: u8) -> Result<Blake2bp, Error> {
+        self.parallel_with_key(self.key, digest_length)
+    }
+
+    /// Constructs the [`Blake2Xb`] hasher with the given output length.
+    pub fn build_xof(self, output_length: u32) -> Result<Blake2Xb, Error> {
+        self.xof_with_key(self.key, output_length)
+    }
 }
 
 impl<'a, T> Blake2bBuilder<'a, T> {
@@ -320,6 +352,22 @@ impl<'a, T> Blake2bBuilder<'a, T> {
     pub fn with_salt(self, salt: &'a [u8; PARAM_LEN]) -> Self {
         Self { salt, ..self }
     }
+
+    fn parallel_with_key(&self, key: &[u8], digest_length: u8) -> Result<Blake2bp, Error> {
+        if digest_length < 1 || digest_length as usize > MAX_LEN {
+            return Err(Error::InvalidDigestLength);
+        }
+
+        Ok(Blake2bp::new(key, digest_length, self.salt, self.personal))
+    }
+
+    fn xof_with_key(&self, key: &[u8], output_length: u32) -> Result<Blake2Xb, Error> {
+        if !(1..=MAX_XOF_LEN).contains(&output_length) {
+            return Err(Error::InvalidDigestLength);
+        }
+
+        Ok(Blake2Xb::new(key, output_length, self.salt, self.personal))
+    }
 }
 
 /// A hasher struct for the Blake2b (optionally keyed) hash function.
@@ -343,6 +391,15 @@ impl<T> Blake2b<T> {
     }
 }
 
+impl<T> Clone for Blake2b<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: copy(&self.state),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<const KEY_LEN: usize> Blake2b<ConstKeyLen<KEY_LEN>> {
     /// Compute the hash for the current hash state and write it to `dst`.
     ///
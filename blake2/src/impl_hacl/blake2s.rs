@@ -6,8 +6,12 @@ use core::marker::PhantomData;
 use libcrux_hacl_rs::streaming_types::error_code;
 
 use crate::hacl::hash_blake2b::{blake2_params, index, params_and_key};
-use crate::hacl::hash_blake2s::{digest, malloc_raw, reset, reset_with_key, state_t, update0};
+use crate::hacl::hash_blake2s::{
+    copy, digest, malloc_raw, reset, reset_with_key, state_t, update0,
+};
 
+use super::blake2sp::Blake2sp;
+use super::blake2xs::{Blake2Xs, MAX_XOF_LEN};
 use super::{ConstDigestLen, ConstKeyLen, ConstKeyLenConstDigestLen, Dynamic, Error};
 
 const PARAM_LEN: usize = 8;
@@ -108,6 +112,16 @@ impl<'a> Blake2sBuilder<'a, &'a ()> {
             _phantom: PhantomData,
         })
     }
+
+    /// Constructs the [`Blake2sp`] tree mode hasher with dynamic digest length.
+    pub fn build_parallel(self, digest_length: u8) -> Result<Blake2sp, Error> {
+        self.parallel_with_key(&[], digest_length)
+    }
+
+    /// Constructs the [`Blake2Xs`] hasher with the given output length.
+    pub fn build_xof(self, output_length: u16) -> Result<Blake2Xs, Error> {
+        self.xof_with_key(&[], output_length)
+    }
 }
 
 impl<'a, const KEY_LEN: usize> Blake2sBuilder<'a, &'a [u8; KEY_LEN]> {
@@ -209,6 +223,16 @@ impl<'a, const KEY_LEN: usize> Blake2sBuilder<'a, &'a [u8; KEY_LEN]> {
             _phantom: PhantomData,
         })
     }
+
+    /// Constructs the [`Blake2sp`] tree mode hasher with dynamic digest length.
+    pub fn build_parallel(self, digest_length: u8) -> Result<Blake2sp, Error> {
+        self.parallel_with_key(self.key, digest_length)
+    }
+
+    /// Constructs the [`Blake2Xs`] hasher with the given output length.
+    pub fn build_xof(self, output_length: u16) -> Result<Blake2Xs, Error> {
+        self.xof_with_key(self.key, output_length)
+    }
 }
 
 impl<'a> Blake2sBuilder<'a, &'a [u8]> {
@@ -306,6 +330,16 @@ impl<'a> Blake2sBuilder<'a, &'a [u8]> {
             _phantom: PhantomData,
         })
     }
+
+    /// Constructs the [`Blake2sp`] tree mode hasher with dynamic digest length.
+    pub fn build_parallel(self, digest_length: u8) -> Result<Blake2sp, Error> {
+        self.parallel_with_key(self.key, digest_length)
+    }
+
+    /// Constructs the [`Blake2Xs`] hasher with the given output length.
+    pub fn build_xof(self, output_length: u16) -> Result<Blake2Xs, Error> {
+        self.xof_with_key(self.key, output_length)
+    }
 }
 
 impl<'a, T> Blake2sBuilder<'a, T> {
@@ -318,6 +352,22 @@ impl<'a, T> Blake2sBuilder<'a, T> {
     pub fn with_salt(self, salt: &'a [u8; PARAM_LEN]) -> Self {
         Self { salt, ..self }
     }
+
+    fn parallel_with_key(&self, key: &[u8], digest_length: u8) -> Result<Blake2sp, Error> {
+        if digest_length < 1 || digest_length as usize > MAX_LEN {
+            return Err(Error::InvalidDigestLength);
+        }
+
+        Ok(Blake2sp::new(key, digest_length, self.salt, self.personal))
+    }
+
+    fn xof_with_key(&self, key: &[u8], output_length: u16) -> Result<Blake2Xs, Error> {
+        if !(1..=MAX_XOF_LEN).contains(&output_length) {
+            return Err(Error::InvalidDigestLength);
+        }
+
+        Ok(Blake2Xs::new(key, output_length, self.salt, self.personal))
+    }
 }
 
 /// A hasher struct for the Blake2s (optionally keyed) hash function.
@@ -341,6 +391,15 @@ impl<T> Blake2s<T> {
     }
 }
 
+impl<T> Clone for Blake2s<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: copy(&self.state),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<const KEY_LEN: usize> Blake2s<ConstKeyLen<KEY_LEN>> {
     /// Compute the hash for the current hash state and write it to `dst`.
     ///
@@ -0,0 +1,155 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+
+use libcrux_hacl_rs::streaming_types::error_code;
+
+use crate::hacl::hash_blake2b::{blake2_params, index, params_and_key};
+use crate::hacl::hash_blake2s::{digest, malloc_raw, state_t, update0};
+
+use super::Error;
+
+const PARAM_LEN: usize = 8;
+const MAX_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+
+/// The number of leaves of BLAKE2sp.
+const LEAVES: usize = 8;
+
+/// The parameters of a BLAKE2sp node.
+fn node_params<'a>(
+    digest_length: u8,
+    key_length: u8,
+    node_offset: u64,
+    node_depth: u8,
+    salt: &'a [u8; PARAM_LEN],
+    personal: &'a [u8; PARAM_LEN],
+) -> blake2_params<'a> {
+    blake2_params {
+        digest_length,
+        key_length,
+        fanout: LEAVES as u8,
+        depth: 2,
+        leaf_length: 0,
+        node_offset,
+        node_depth,
+        inner_length: MAX_LEN as u8,
+        salt,
+        personal,
+    }
+}
+
+/// A hasher struct for BLAKE2sp, the 8 leaf (optionally keyed) tree mode of
+/// Blake2s.
+///
+/// The input is split into blocks that are hashed by the leaves in turn.
+pub struct Blake2sp {
+    leaves: [Box<[state_t]>; LEAVES],
+    len: u64,
+    key_length: u8,
+    digest_length: u8,
+    salt: [u8; PARAM_LEN],
+    personal: [u8; PARAM_LEN],
+}
+
+impl Blake2sp {
+    /// The `key` must be at most 32 bytes and the `digest_length` in
+    /// `1..=32`. This is checked by the builder.
+    pub(super) fn new(
+        key: &[u8],
+        digest_length: u8,
+        salt: &[u8; PARAM_LEN],
+        personal: &[u8; PARAM_LEN],
+    ) -> Self {
+        let key_length = key.len() as u8;
+        let leaves = core::array::from_fn(|i| {
+            // The leaves are keyed and output `inner_length` bytes.
+            let kk = index {
+                key_length,
+                digest_length: MAX_LEN as u8,
+                last_node: i == LEAVES - 1,
+            };
+            let params = node_params(digest_length, key_length, i as u64, 0, salt, personal);
+            malloc_raw(
+                kk,
+                params_and_key {
+                    fst: &[params],
+                    snd: key,
+                },
+            )
+        });
+
+        Self {
+            leaves,
+            len: 0,
+            key_length,
+            digest_length,
+            salt: *salt,
+            personal: *personal,
+        }
+    }
+
+    /// Updates the hash state by adding the bytes from `chunk` to the hashed data.
+    pub fn update(&mut self, mut chunk: &[u8]) -> Result<(), Error> {
+        while !chunk.is_empty() {
+            let leaf = (self.len / BLOCK_LEN as u64) as usize % LEAVES;
+            let block_remaining = BLOCK_LEN - (self.len % BLOCK_LEN as u64) as usize;
+            let (block, rest) = chunk.split_at(core::cmp::min(block_remaining, chunk.len()));
+
+            match update0(self.leaves[leaf].as_mut(), block, block.len() as u32) {
+                error_code::Success => (),
+                error_code::MaximumLengthExceeded => return Err(Error::MaximumLengthExceeded),
+                _ => return Err(Error::Unexpected),
+            }
+
+            self.len += block.len() as u64;
+            chunk = rest;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the hash for the current hash state and write it to `dst`.
+    ///
+    /// Returns a `Result` that contains the length of the digest on success.
+    pub fn finalize(&self, dst: &mut [u8]) -> Result<usize, Error> {
+        if dst.len() < self.digest_length as usize {
+            return Err(Error::InvalidDigestLength);
+        }
+
+        // The root sets the key length in its parameters, but it doesn't
+        // absorb the key. Leaving the key length out of the index does that.
+        let kk = index {
+            key_length: 0,
+            digest_length: self.digest_length,
+            last_node: true,
+        };
+        let params = node_params(
+            self.digest_length,
+            self.key_length,
+            0,
+            1,
+            &self.salt,
+            &self.personal,
+        );
+        let zero_key = [0u8; MAX_LEN];
+        let mut root = malloc_raw(
+            kk,
+            params_and_key {
+                fst: &[params],
+                snd: &zero_key,
+            },
+        );
+
+        let mut leaf_digest = [0u8; MAX_LEN];
+        for leaf in self.leaves.iter() {
+            digest(leaf, &mut leaf_digest);
+            match update0(root.as_mut(), &leaf_digest, MAX_LEN as u32) {
+                error_code::Success => (),
+                _ => return Err(Error::Unexpected),
+            }
+        }
+
+        Ok(digest(&root, dst) as usize)
+    }
+}
@@ -0,0 +1,136 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+
+use libcrux_hacl_rs::streaming_types::error_code;
+
+use crate::hacl::hash_blake2b::{
+    blake2_params, digest, index, malloc_raw, params_and_key, state_t, update0,
+};
+
+use super::Error;
+
+const PARAM_LEN: usize = 16;
+const MAX_LEN: usize = 64;
+
+/// The largest output length of [`Blake2Xb`]. `u32::MAX` is reserved for
+/// outputs of unknown length.
+pub(super) const MAX_XOF_LEN: u32 = u32::MAX - 1;
+
+/// A hasher struct for BLAKE2Xb, the extendable-output (optionally keyed)
+/// variant of Blake2b.
+///
+/// The output length is part of the parameters and therefore fixed when the
+/// hasher is built.
+pub struct Blake2Xb {
+    root: Box<[state_t]>,
+    output_length: u32,
+    salt: [u8; PARAM_LEN],
+    personal: [u8; PARAM_LEN],
+}
+
+impl Blake2Xb {
+    /// The `key` must be at most 64 bytes and the `output_length` in
+    /// `1..=MAX_XOF_LEN`. This is checked by the builder.
+    pub(super) fn new(
+        key: &[u8],
+        output_length: u32,
+        salt: &[u8; PARAM_LEN],
+        personal: &[u8; PARAM_LEN],
+    ) -> Self {
+        let key_length = key.len() as u8;
+        let kk = index {
+            key_length,
+            digest_length: MAX_LEN as u8,
+            last_node: false,
+        };
+
+        // The node offset holds the XOF length in its upper half.
+        let params = blake2_params {
+            digest_length: MAX_LEN as u8,
+            key_length,
+            fanout: 1,
+            depth: 1,
+            leaf_length: 0,
+            node_offset: (output_length as u64) << 32,
+            node_depth: 0,
+            inner_length: 0,
+            salt,
+            personal,
+        };
+
+        Self {
+            root: malloc_raw(
+                kk,
+                params_and_key {
+                    fst: &[params],
+                    snd: key,
+                },
+            ),
+            output_length,
+            salt: *salt,
+            personal: *personal,
+        }
+    }
+
+    /// Updates the hash state by adding the bytes from `chunk` to the hashed data.
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), Error> {
+        if chunk.len() > (u32::MAX as usize) {
+            return Err(Error::InvalidChunkLength);
+        }
+
+        match update0(self.root.as_mut(), chunk, chunk.len() as u32) {
+            error_code::Success => Ok(()),
+            error_code::MaximumLengthExceeded => Err(Error::MaximumLengthExceeded),
+            _ => Err(Error::Unexpected),
+        }
+    }
+
+    /// Compute the output for the current hash state and write it to `dst`.
+    ///
+    /// `dst` must be exactly as long as the output length of the hasher.
+    pub fn finalize(&self, dst: &mut [u8]) -> Result<(), Error> {
+        if dst.len() != self.output_length as usize {
+            return Err(Error::InvalidDigestLength);
+        }
+
+        let mut h0 = [0u8; MAX_LEN];
+        digest(&self.root, &mut h0);
+
+        for (i, out) in dst.chunks_mut(MAX_LEN).enumerate() {
+            let digest_length = out.len() as u8;
+            let kk = index {
+                key_length: 0,
+                digest_length,
+                last_node: false,
+            };
+            let params = blake2_params {
+                digest_length,
+                key_length: 0,
+                fanout: 0,
+                depth: 0,
+                leaf_length: MAX_LEN as u32,
+                node_offset: i as u64 | (self.output_length as u64) << 32,
+                node_depth: 0,
+                inner_length: MAX_LEN as u8,
+                salt: &self.salt,
+                personal: &self.personal,
+            };
+
+            let mut node = malloc_raw(
+                kk,
+                params_and_key {
+                    fst: &[params],
+                    snd: &[],
+                },
+            );
+            match update0(node.as_mut(), &h0, MAX_LEN as u32) {
+                error_code::Success => (),
+                _ => return Err(Error::Unexpected),
+            }
+            digest(&node, out);
+        }
+
+        Ok(())
+    }
+}
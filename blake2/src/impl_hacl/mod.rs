@@ -1,11 +1,19 @@
 extern crate alloc;
 
 mod blake2b;
+mod blake2bp;
 mod blake2s;
+mod blake2sp;
+mod blake2xb;
+mod blake2xs;
 mod error;
 
 pub use blake2b::{Blake2b, Blake2bBuilder};
+pub use blake2bp::Blake2bp;
 pub use blake2s::{Blake2s, Blake2sBuilder};
+pub use blake2sp::Blake2sp;
+pub use blake2xb::Blake2Xb;
+pub use blake2xs::Blake2Xs;
 pub use error::Error;
 
 /// Type that holds the constants in case both key length and digest length are known at compile
@@ -1,5 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "expose-hacl"))]
 mod hacl {
     //! This module contains generated hacl code.
 
@@ -8,6 +9,21 @@ mod hacl {
     pub(crate) mod impl_blake2_constants;
 }
 
+#[cfg(feature = "expose-hacl")]
+pub mod hacl {
+    //! This module contains generated hacl code.
+
+    pub mod hash_blake2b;
+    pub mod hash_blake2s;
+    pub(crate) mod impl_blake2_constants;
+}
+
 mod impl_hacl;
 
-pub use impl_hacl::{Blake2b, Blake2bBuilder, Blake2s, Blake2sBuilder, Error};
+pub use impl_hacl::{
+    Blake2Xb, Blake2Xs, Blake2b, Blake2bBuilder, Blake2bp, Blake2s, Blake2sBuilder, Blake2sp, Error,
+};
+
+/// The types that determine which lengths of a [`Blake2b`] or [`Blake2s`]
+/// hasher are known at compile time.
+pub use impl_hacl::{ConstDigestLen, ConstKeyLen, ConstKeyLenConstDigestLen, Dynamic};
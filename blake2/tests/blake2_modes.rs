@@ -0,0 +1,309 @@
+//! Tests for the BLAKE2bp, BLAKE2sp, BLAKE2Xb and BLAKE2Xs modes.
+//!
+//! The BLAKE2bp and BLAKE2sp vectors are the unkeyed and keyed KATs from the
+//! official [BLAKE2 repository](https://github.com/BLAKE2/BLAKE2). The
+//! BLAKE2X vectors use the layout of its keyed `blake2x*-kat` files, i.e.
+//! the same input and key for all output lengths from 1 to 256 bytes.
+
+use libcrux_blake2::{Blake2bBuilder, Blake2sBuilder, Error};
+
+struct Kat {
+    input: Vec<u8>,
+    key: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+fn read_kats(file: &str) -> Vec<Kat> {
+    file.split("\n\n")
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let mut fields = entry.lines().map(|line| {
+                let (_, value) = line.split_once(':').unwrap();
+                hex::decode(value.trim()).unwrap()
+            });
+            Kat {
+                input: fields.next().unwrap(),
+                key: fields.next().unwrap(),
+                hash: fields.next().unwrap(),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn blake2bp_kat() {
+    let kats = read_kats(include_str!("data/blake2bp-kat.txt"));
+    assert_eq!(kats.len(), 512);
+
+    for kat in kats {
+        let mut hasher = Blake2bBuilder::new_keyed_dynamic(&kat.key)
+            .unwrap()
+            .build_parallel(64)
+            .unwrap();
+        hasher.update(&kat.input).unwrap();
+        let mut hash = [0; 64];
+        assert_eq!(hasher.finalize(&mut hash).unwrap(), 64);
+        assert_eq!(hash.as_slice(), kat.hash);
+    }
+}
+
+#[test]
+fn blake2sp_kat() {
+    let kats = read_kats(include_str!("data/blake2sp-kat.txt"));
+    assert_eq!(kats.len(), 512);
+
+    for kat in kats {
+        let mut hasher = Blake2sBuilder::new_keyed_dynamic(&kat.key)
+            .unwrap()
+            .build_parallel(32)
+            .unwrap();
+        hasher.update(&kat.input).unwrap();
+        let mut hash = [0; 32];
+        assert_eq!(hasher.finalize(&mut hash).unwrap(), 32);
+        assert_eq!(hash.as_slice(), kat.hash);
+    }
+}
+
+#[test]
+fn blake2xb_kat() {
+    let kats = read_kats(include_str!("data/blake2xb-kat.txt"));
+    assert_eq!(kats.len(), 256);
+
+    for kat in kats {
+        let mut hasher = Blake2bBuilder::new_keyed_dynamic(&kat.key)
+            .unwrap()
+            .build_xof(kat.hash.len() as u32)
+            .unwrap();
+        hasher.update(&kat.input).unwrap();
+        let mut hash = vec![0; kat.hash.len()];
+        hasher.finalize(&mut hash).unwrap();
+        assert_eq!(hash, kat.hash);
+    }
+}
+
+#[test]
+fn blake2xs_kat() {
+    let kats = read_kats(include_str!("data/blake2xs-kat.txt"));
+    assert_eq!(kats.len(), 256);
+
+    for kat in kats {
+        let mut hasher = Blake2sBuilder::new_keyed_dynamic(&kat.key)
+            .unwrap()
+            .build_xof(kat.hash.len() as u16)
+            .unwrap();
+        hasher.update(&kat.input).unwrap();
+        let mut hash = vec![0; kat.hash.len()];
+        hasher.finalize(&mut hash).unwrap();
+        assert_eq!(hash, kat.hash);
+    }
+}
+
+#[test]
+fn parallel_streaming() {
+    // Long enough for several rounds over all leaves.
+    let input: Vec<u8> = (0..5000u32).map(|i| (i * 7) as u8).collect();
+
+    let mut expected_b = [0; 64];
+    let mut hasher = Blake2bBuilder::new_unkeyed().build_parallel(64).unwrap();
+    hasher.update(&input).unwrap();
+    hasher.finalize(&mut expected_b).unwrap();
+
+    let mut expected_s = [0; 32];
+    let mut hasher = Blake2sBuilder::new_unkeyed().build_parallel(32).unwrap();
+    hasher.update(&input).unwrap();
+    hasher.finalize(&mut expected_s).unwrap();
+
+    for chunk_len in [1, 63, 64, 65, 127, 128, 129, 511, 512, 513, 1000] {
+        let mut hasher_b = Blake2bBuilder::new_unkeyed().build_parallel(64).unwrap();
+        let mut hasher_s = Blake2sBuilder::new_unkeyed().build_parallel(32).unwrap();
+        for chunk in input.chunks(chunk_len) {
+            hasher_b.update(chunk).unwrap();
+            hasher_s.update(chunk).unwrap();
+        }
+
+        let mut hash_b = [0; 64];
+        hasher_b.finalize(&mut hash_b).unwrap();
+        assert_eq!(hash_b, expected_b, "chunk length {chunk_len}");
+
+        let mut hash_s = [0; 32];
+        hasher_s.finalize(&mut hash_s).unwrap();
+        assert_eq!(hash_s, expected_s, "chunk length {chunk_len}");
+    }
+}
+
+#[test]
+fn long_input_with_parameters() {
+    // Computed with an independent implementation of the BLAKE2 parameter
+    // block, for inputs that go around all leaves several times.
+    let input: Vec<u8> = (0..5000u32).map(|i| (i * 7) as u8).collect();
+
+    let mut hasher = Blake2bBuilder::new_keyed_dynamic(b"key")
+        .unwrap()
+        .with_salt(&[1; 16])
+        .with_personalization(&[2; 16])
+        .build_parallel(40)
+        .unwrap();
+    hasher.update(&input).unwrap();
+    let mut hash = [0; 40];
+    hasher.finalize(&mut hash).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "b3c88b1255596a8f5bf42484a7b39b9e0752103a3dac89beed07b1057bd237c1\
+         e5acf42542134d31"
+    );
+
+    let mut hasher = Blake2sBuilder::new_keyed_dynamic(b"key")
+        .unwrap()
+        .with_salt(&[1; 8])
+        .with_personalization(&[2; 8])
+        .build_parallel(20)
+        .unwrap();
+    hasher.update(&input).unwrap();
+    let mut hash = [0; 20];
+    hasher.finalize(&mut hash).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "7432099aae1a07651e9c2f7db4fc82e75ec2ffb4"
+    );
+
+    let mut hasher = Blake2bBuilder::new_keyed_dynamic(b"key")
+        .unwrap()
+        .with_salt(&[1; 16])
+        .with_personalization(&[2; 16])
+        .build_xof(300)
+        .unwrap();
+    hasher.update(&input).unwrap();
+    let mut hash = [0; 300];
+    hasher.finalize(&mut hash).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "9d3b637fb607120bd66d04b48789c423011d4d7139f705dd9c48b3d224e40978\
+         eb6c1f95e8bdbbe37245dd11e446a5681a01067508385da89b320b9f6cdf508c\
+         92b88a0698b9f0489f6d71d678aca6233b89698386297776fc21c860a020a5f4\
+         a315c5d8683c9766f248fa184d6ab70650307fbd43b46fbc12c47f19e715ebcc\
+         0d8978d6fe4200a49ba994fcc664954827e83b900ec00e43b0070144f638b595\
+         59b49f2563c3f04ba6f2ff9c7b6071bf3f449cc129bc8f93cc33d2a63c5d9c1b\
+         e6d8a75e5c036bb4a5c367393762e0aac7cc3d11fb634eca5f377a4c93c858ce\
+         c051878241335235c40f2eb27113604fd310bc00b1405673d33687754f23418a\
+         85f084f9b1afb6b539e9dc253126dbc4feb64be42aeab1d16a2b72731625a4d1\
+         cae6a6b010ed9b891d367eb0"
+    );
+
+    let mut hasher = Blake2sBuilder::new_keyed_dynamic(b"key")
+        .unwrap()
+        .with_salt(&[1; 8])
+        .with_personalization(&[2; 8])
+        .build_xof(100)
+        .unwrap();
+    hasher.update(&input).unwrap();
+    let mut hash = [0; 100];
+    hasher.finalize(&mut hash).unwrap();
+    assert_eq!(
+        hex::encode(hash),
+        "c0d08605c181926ac29b60112b324cb2cb68e06d74d675f4ac7cb5e9813e382c\
+         568cbb19083a2b6ef344016727b7383344d407e35a7f94f8ab2d2871254646fc\
+         a35cd911eef57249ddb0833cd6014300f43d3622bcc3f6db6ce42df35b8e98a3\
+         ddfb3d17"
+    );
+}
+
+#[test]
+fn parallel_differs_from_sequential() {
+    let mut parallel = [0; 32];
+    let mut hasher = Blake2bBuilder::new_unkeyed().build_parallel(32).unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut parallel).unwrap();
+
+    let mut sequential = [0; 32];
+    let mut hasher = Blake2bBuilder::new_unkeyed()
+        .build_var_digest_len(32)
+        .unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut sequential).unwrap();
+
+    assert_ne!(parallel, sequential);
+}
+
+#[test]
+fn xof_prefixes_differ() {
+    // The output length is part of the parameters, so shorter outputs are not
+    // prefixes of longer ones.
+    let mut long = [0; 100];
+    let mut hasher = Blake2bBuilder::new_unkeyed().build_xof(100).unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut long).unwrap();
+
+    let mut short = [0; 99];
+    let mut hasher = Blake2bBuilder::new_unkeyed().build_xof(99).unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut short).unwrap();
+
+    assert_ne!(long[..99], short);
+}
+
+#[test]
+fn salt_and_personalization() {
+    let mut plain = [0; 64];
+    let mut hasher = Blake2bBuilder::new_unkeyed().build_xof(64).unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut plain).unwrap();
+
+    let mut salted = [0; 64];
+    let mut hasher = Blake2bBuilder::new_unkeyed()
+        .with_salt(&[1; 16])
+        .build_xof(64)
+        .unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut salted).unwrap();
+
+    let mut personal = [0; 32];
+    let mut hasher = Blake2sBuilder::new_unkeyed()
+        .with_personalization(&[1; 8])
+        .build_parallel(32)
+        .unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut personal).unwrap();
+
+    let mut unpersonal = [0; 32];
+    let mut hasher = Blake2sBuilder::new_unkeyed().build_parallel(32).unwrap();
+    hasher.update(b"this is a test").unwrap();
+    hasher.finalize(&mut unpersonal).unwrap();
+
+    assert_ne!(plain, salted);
+    assert_ne!(personal, unpersonal);
+}
+
+#[test]
+fn invalid_lengths() {
+    assert!(matches!(
+        Blake2bBuilder::new_unkeyed().build_parallel(0),
+        Err(Error::InvalidDigestLength)
+    ));
+    assert!(matches!(
+        Blake2sBuilder::new_unkeyed().build_parallel(33),
+        Err(Error::InvalidDigestLength)
+    ));
+    assert!(matches!(
+        Blake2bBuilder::new_unkeyed().build_xof(0),
+        Err(Error::InvalidDigestLength)
+    ));
+    assert!(matches!(
+        Blake2bBuilder::new_unkeyed().build_xof(u32::MAX),
+        Err(Error::InvalidDigestLength)
+    ));
+    assert!(matches!(
+        Blake2sBuilder::new_unkeyed().build_xof(u16::MAX),
+        Err(Error::InvalidDigestLength)
+    ));
+
+    let hasher = Blake2sBuilder::new_unkeyed().build_xof(10).unwrap();
+    assert!(matches!(
+        hasher.finalize(&mut [0; 11]),
+        Err(Error::InvalidDigestLength)
+    ));
+    let hasher = Blake2bBuilder::new_unkeyed().build_parallel(64).unwrap();
+    assert!(matches!(
+        hasher.finalize(&mut [0; 32]),
+        Err(Error::InvalidDigestLength)
+    ));
+}
@@ -0,0 +1,27 @@
+use std::env;
+
+fn main() {
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let disable_simd128 = read_env("LIBCRUX_DISABLE_SIMD128");
+    let disable_simd256 = read_env("LIBCRUX_DISABLE_SIMD256");
+
+    // The Neon backend is only available on aarch64.
+    let simd128_possible = target_arch == "aarch64";
+    if simd128_possible && !disable_simd128 {
+        println!("cargo:rustc-cfg=feature=\"simd128\"");
+    }
+
+    // The AVX2 backend is only available on x86_64. Enabling it doesn't mean
+    // that the CPU supports AVX2, this is checked at runtime.
+    let simd256_possible = target_arch == "x86_64";
+    if simd256_possible && !disable_simd256 {
+        println!("cargo:rustc-cfg=feature=\"simd256\"");
+    }
+}
+
+fn read_env(key: &str) -> bool {
+    match env::var(key) {
+        Ok(s) => s == "1" || s == "y" || s == "Y",
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,54 @@
+//! AVX2 backend, hashing 8 chunks in parallel.
+
+use libcrux_intrinsics::avx2::*;
+
+use crate::{portable::Cv, simd::Lanes};
+
+impl Lanes<8> for Vec256 {
+    #[inline(always)]
+    fn splat(x: u32) -> Self {
+        mm256_set1_epi32(x as i32)
+    }
+
+    #[inline(always)]
+    fn load(words: &[u32; 8]) -> Self {
+        mm256_loadu_si256_i32(&words.map(|w| w as i32))
+    }
+
+    #[inline(always)]
+    fn store(self, words: &mut [u32; 8]) {
+        let mut out = [0i32; 8];
+        mm256_storeu_si256_i32(&mut out, self);
+        *words = out.map(|w| w as u32);
+    }
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        mm256_add_epi32(self, other)
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        mm256_xor_si256(self, other)
+    }
+
+    #[inline(always)]
+    fn rotate_right<const R: i32, const L: i32>(self) -> Self {
+        debug_assert_eq!(R + L, 32);
+        mm256_or_si256(mm256_srli_epi32::<R>(self), mm256_slli_epi32::<L>(self))
+    }
+}
+
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_chunks_avx2(chunks: &[u8], key: &Cv, counter: u64, flags: u32) -> [Cv; 8] {
+    crate::simd::hash_chunks::<8, Vec256>(chunks, key, counter, flags)
+}
+
+/// The chaining values of the 8 full chunks in `chunks`.
+///
+/// The caller must check that AVX2 is available.
+#[allow(unsafe_code)]
+pub(crate) fn hash_chunks(chunks: &[u8], key: &Cv, counter: u64, flags: u32) -> [Cv; 8] {
+    unsafe { hash_chunks_avx2(chunks, key, counter, flags) }
+}
@@ -0,0 +1,468 @@
+//! # BLAKE3
+//!
+//! The [BLAKE3](https://github.com/BLAKE3-team/BLAKE3-specs/blob/master/blake3.pdf)
+//! hash function with its three modes
+//! - [`hash`]: the default hash function,
+//! - [`keyed_hash`]: a PRF and MAC with a 32 byte key,
+//! - [`derive_key`]: a KDF with a context string,
+//!
+//! and extendable output for all of them with [`Hasher::finalize_xof`].
+//!
+//! The input is split into chunks of 1024 bytes that are the leaves of a
+//! binary tree. Full chunks are hashed 8 at a time with AVX2, or 4 at a time
+//! with Neon, when the CPU supports it.
+//!
+//! **NOTE:** This code has not been formally verified yet.
+
+#![no_std]
+#![deny(unsafe_code)]
+
+mod portable;
+#[cfg(any(feature = "simd128", feature = "simd256"))]
+mod simd;
+
+#[cfg(feature = "simd256")]
+mod avx2;
+#[cfg(feature = "simd128")]
+mod neon;
+
+use portable::{block_words, compress, compress_cv, Cv};
+
+/// Re-export the `Digest` trait.
+pub use libcrux_traits::Digest;
+
+/// The length of a BLAKE3 hash in bytes.
+pub const BLAKE3_LENGTH: usize = 32;
+
+/// The length of a BLAKE3 key in bytes.
+pub const KEY_LENGTH: usize = 32;
+
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+/// The largest number of chunks that are hashed in parallel.
+const MAX_DEGREE: usize = 8;
+
+/// The depth of the tree for inputs of up to 2^64 bytes.
+const MAX_DEPTH: usize = 54;
+
+// Domain separation flags.
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+const IV: Cv = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The message word order of the 7 rounds, i.e. the message permutation
+/// applied 0 to 6 times.
+const MSG_SCHEDULE: [[usize; 16]; 7] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+#[inline(always)]
+fn key_words(key: &[u8; KEY_LENGTH]) -> Cv {
+    core::array::from_fn(|i| u32::from_le_bytes(key[4 * i..4 * i + 4].try_into().unwrap()))
+}
+
+/// Hash full chunks from the start of `chunks` with the widest available
+/// backend and write their chaining values into `cvs`.
+///
+/// Returns the number of chunks that were hashed.
+fn hash_chunks(
+    chunks: &[u8],
+    key: &Cv,
+    counter: u64,
+    flags: u32,
+    cvs: &mut [Cv; MAX_DEGREE],
+) -> usize {
+    debug_assert!(chunks.len() >= CHUNK_LEN);
+
+    #[cfg(feature = "simd256")]
+    if libcrux_platform::simd256_support() && chunks.len() >= 8 * CHUNK_LEN {
+        cvs.copy_from_slice(&avx2::hash_chunks(
+            &chunks[..8 * CHUNK_LEN],
+            key,
+            counter,
+            flags,
+        ));
+        return 8;
+    }
+
+    #[cfg(feature = "simd128")]
+    if libcrux_platform::simd128_support() && chunks.len() >= 4 * CHUNK_LEN {
+        cvs[..4].copy_from_slice(&neon::hash_chunks(
+            &chunks[..4 * CHUNK_LEN],
+            key,
+            counter,
+            flags,
+        ));
+        return 4;
+    }
+
+    cvs[0] = portable::hash_chunk(&chunks[..CHUNK_LEN], key, counter, flags);
+    1
+}
+
+/// The input of a compression that is either a chaining value or, for the
+/// root node, the output.
+#[derive(Clone)]
+struct Output {
+    input_cv: Cv,
+    block_words: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> Cv {
+        compress_cv(
+            &self.input_cv,
+            &self.block_words,
+            self.counter,
+            self.block_len,
+            self.flags,
+        )
+    }
+
+    /// The output block `block` of the root node.
+    fn root_output_block(&self, block: u64) -> [u8; BLOCK_LEN] {
+        let words = compress(
+            &self.input_cv,
+            &self.block_words,
+            block,
+            self.block_len,
+            self.flags | ROOT,
+        );
+        let mut out = [0u8; BLOCK_LEN];
+        for (out, word) in out.chunks_exact_mut(4).zip(words.iter()) {
+            out.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+fn parent_output(left: &Cv, right: &Cv, key: &Cv, flags: u32) -> Output {
+    let mut block_words = [0u32; 16];
+    block_words[..8].copy_from_slice(left);
+    block_words[8..].copy_from_slice(right);
+    Output {
+        input_cv: *key,
+        block_words,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT | flags,
+    }
+}
+
+/// The state of the chunk that is currently hashed.
+#[derive(Clone)]
+struct ChunkState {
+    cv: Cv,
+    counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &Cv, counter: u64, flags: u32) -> Self {
+        Self {
+            cv: *key,
+            counter,
+            block: [0u8; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // The last block is compressed in `output`, so a full block is
+            // only compressed when more input follows.
+            if self.block_len == BLOCK_LEN {
+                self.cv = compress_cv(
+                    &self.cv,
+                    &block_words(&self.block),
+                    self.counter,
+                    BLOCK_LEN as u32,
+                    self.flags | self.start_flag(),
+                );
+                self.blocks_compressed += 1;
+                self.block = [0u8; BLOCK_LEN];
+                self.block_len = 0;
+            }
+
+            let take = core::cmp::min(BLOCK_LEN - self.block_len, input.len());
+            self.block[self.block_len..self.block_len + take].copy_from_slice(&input[..take]);
+            self.block_len += take;
+            input = &input[take..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            input_cv: self.cv,
+            block_words: block_words(&self.block),
+            counter: self.counter,
+            block_len: self.block_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// BLAKE3
+///
+/// Returns the BLAKE3 hash of `payload`.
+#[inline(always)]
+pub fn hash(payload: &[u8]) -> [u8; BLAKE3_LENGTH] {
+    let mut hasher = Hasher::new();
+    hasher.update(payload);
+    hasher.finalize()
+}
+
+/// BLAKE3 in keyed mode.
+///
+/// Returns the keyed hash of `payload`, which can be used as a MAC or PRF.
+#[inline(always)]
+pub fn keyed_hash(key: &[u8; KEY_LENGTH], payload: &[u8]) -> [u8; BLAKE3_LENGTH] {
+    let mut hasher = Hasher::new_keyed(key);
+    hasher.update(payload);
+    hasher.finalize()
+}
+
+/// BLAKE3 in key derivation mode.
+///
+/// Returns a key derived from `key_material` for the `context`. The context
+/// should be hard-coded, globally unique and application-specific.
+#[inline(always)]
+pub fn derive_key(context: &str, key_material: &[u8]) -> [u8; BLAKE3_LENGTH] {
+    let mut hasher = Hasher::new_derive_key(context);
+    hasher.update(key_material);
+    hasher.finalize()
+}
+
+/// The streaming BLAKE3 state.
+#[derive(Clone)]
+pub struct Hasher {
+    key: Cv,
+    flags: u32,
+    chunk_state: ChunkState,
+    // The chaining values of the complete subtrees, with at most one subtree
+    // of each size.
+    cv_stack: [Cv; MAX_DEPTH],
+    cv_stack_len: usize,
+}
+
+impl Hasher {
+    fn new_internal(key: Cv, flags: u32) -> Self {
+        Self {
+            key,
+            flags,
+            chunk_state: ChunkState::new(&key, 0, flags),
+            cv_stack: [[0u32; 8]; MAX_DEPTH],
+            cv_stack_len: 0,
+        }
+    }
+
+    /// Initialize a new digest state for streaming use.
+    pub fn new() -> Self {
+        Self::new_internal(IV, 0)
+    }
+
+    /// Initialize a new keyed digest state for streaming use.
+    pub fn new_keyed(key: &[u8; KEY_LENGTH]) -> Self {
+        Self::new_internal(key_words(key), KEYED_HASH)
+    }
+
+    /// Initialize a new key derivation state for the `context`.
+    ///
+    /// The key material is added with [`Digest::update`].
+    pub fn new_derive_key(context: &str) -> Self {
+        let mut context_hasher = Self::new_internal(IV, DERIVE_KEY_CONTEXT);
+        context_hasher.update(context.as_bytes());
+        let context_key = context_hasher.finalize();
+        Self::new_internal(key_words(&context_key), DERIVE_KEY_MATERIAL)
+    }
+
+    /// Add the chaining value of a complete chunk, and merge all subtrees
+    /// that are complete with it.
+    fn push_chunk_cv(&mut self, mut cv: Cv, total_chunks: u64) {
+        // Every trailing zero bit of the chunk count is a complete subtree.
+        let mut total_chunks = total_chunks;
+        while total_chunks & 1 == 0 {
+            self.cv_stack_len -= 1;
+            cv = parent_output(
+                &self.cv_stack[self.cv_stack_len],
+                &cv,
+                &self.key,
+                self.flags,
+            )
+            .chaining_value();
+            total_chunks >>= 1;
+        }
+        self.cv_stack[self.cv_stack_len] = cv;
+        self.cv_stack_len += 1;
+    }
+
+    fn root_output(&self) -> Output {
+        let mut output = self.chunk_state.output();
+        for cv in self.cv_stack[..self.cv_stack_len].iter().rev() {
+            output = parent_output(cv, &output.chaining_value(), &self.key, self.flags);
+        }
+        output
+    }
+
+    /// Get the 32 byte hash.
+    ///
+    /// Note that the digest state can be continued to be used, to extend the
+    /// digest.
+    pub fn finalize(&self) -> [u8; BLAKE3_LENGTH] {
+        let block = self.root_output().root_output_block(0);
+        block[..BLAKE3_LENGTH].try_into().unwrap()
+    }
+
+    /// Get a reader for output of any length.
+    ///
+    /// The first 32 bytes are the same as the ones returned by
+    /// [`Hasher::finalize`].
+    pub fn finalize_xof(&self) -> OutputReader {
+        OutputReader {
+            output: self.root_output(),
+            position: 0,
+        }
+    }
+}
+
+impl Digest<BLAKE3_LENGTH> for Hasher {
+    /// Write the digest for the given input byte slice into `digest`, in
+    /// immediate mode.
+    ///
+    /// Panics if `digest` is not [`BLAKE3_LENGTH`] bytes long.
+    fn hash(digest: &mut [u8], payload: &[u8]) {
+        let mut state = Self::new();
+        state.update(payload);
+        state.finish(digest.try_into().unwrap());
+    }
+
+    /// Add the `payload` to the digest.
+    fn update(&mut self, mut payload: &[u8]) {
+        while !payload.is_empty() {
+            // A chunk is only finished when more input follows, because the
+            // last chunk may be the root.
+            if self.chunk_state.len() == CHUNK_LEN {
+                let cv = self.chunk_state.output().chaining_value();
+                let total_chunks = self.chunk_state.counter + 1;
+                self.push_chunk_cv(cv, total_chunks);
+                self.chunk_state = ChunkState::new(&self.key, total_chunks, self.flags);
+            }
+
+            // Full chunks that are followed by more input are hashed in
+            // parallel.
+            if self.chunk_state.len() == 0 && payload.len() > CHUNK_LEN {
+                let full_chunks = (payload.len() - 1) / CHUNK_LEN;
+                let counter = self.chunk_state.counter;
+                let mut cvs = [[0u32; 8]; MAX_DEGREE];
+                let hashed = hash_chunks(
+                    &payload[..full_chunks * CHUNK_LEN],
+                    &self.key,
+                    counter,
+                    self.flags,
+                    &mut cvs,
+                );
+                for (i, cv) in cvs[..hashed].iter().enumerate() {
+                    self.push_chunk_cv(*cv, counter + i as u64 + 1);
+                }
+                self.chunk_state = ChunkState::new(&self.key, counter + hashed as u64, self.flags);
+                payload = &payload[hashed * CHUNK_LEN..];
+                continue;
+            }
+
+            let take = core::cmp::min(CHUNK_LEN - self.chunk_state.len(), payload.len());
+            self.chunk_state.update(&payload[..take]);
+            payload = &payload[take..];
+        }
+    }
+
+    /// Get the digest.
+    ///
+    /// Note that the digest state can be continued to be used, to extend the
+    /// digest.
+    fn finish(&self, digest: &mut [u8; BLAKE3_LENGTH]) {
+        *digest = self.finalize();
+    }
+
+    /// Reset the digest state.
+    ///
+    /// The key, or the derived key, is kept.
+    fn reset(&mut self) {
+        self.chunk_state = ChunkState::new(&self.key, 0, self.flags);
+        self.cv_stack_len = 0;
+    }
+}
+
+impl Default for Hasher {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A reader for the extendable output of BLAKE3.
+#[derive(Clone)]
+pub struct OutputReader {
+    output: Output,
+    position: u64,
+}
+
+impl OutputReader {
+    /// Write the next `out.len()` bytes of output into `out`.
+    pub fn fill(&mut self, mut out: &mut [u8]) {
+        while !out.is_empty() {
+            let block = self
+                .output
+                .root_output_block(self.position / BLOCK_LEN as u64);
+            let offset = (self.position % BLOCK_LEN as u64) as usize;
+            let take = core::cmp::min(BLOCK_LEN - offset, out.len());
+            out[..take].copy_from_slice(&block[offset..offset + take]);
+            out = &mut out[take..];
+            self.position += take as u64;
+        }
+    }
+
+    /// The position of the next output byte.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Seek to `position` in the output.
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+}
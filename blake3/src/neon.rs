@@ -0,0 +1,45 @@
+//! Neon backend, hashing 4 chunks in parallel.
+
+use libcrux_intrinsics::arm64::*;
+
+use crate::{portable::Cv, simd::Lanes};
+
+impl Lanes<4> for _uint32x4_t {
+    #[inline(always)]
+    fn splat(x: u32) -> Self {
+        _vdupq_n_u32(x)
+    }
+
+    #[inline(always)]
+    fn load(words: &[u32; 4]) -> Self {
+        _vld1q_u32(words)
+    }
+
+    #[inline(always)]
+    fn store(self, words: &mut [u32; 4]) {
+        _vst1q_u32(words, self)
+    }
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        _vaddq_u32(self, other)
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        _veorq_u32(self, other)
+    }
+
+    #[inline(always)]
+    fn rotate_right<const R: i32, const L: i32>(self) -> Self {
+        debug_assert_eq!(R + L, 32);
+        _vorrq_u32(_vshrq_n_u32::<R>(self), _vshlq_n_u32::<L>(self))
+    }
+}
+
+/// The chaining values of the 4 full chunks in `chunks`.
+///
+/// The caller must check that Neon is available.
+pub(crate) fn hash_chunks(chunks: &[u8], key: &Cv, counter: u64, flags: u32) -> [Cv; 4] {
+    crate::simd::hash_chunks::<4, _uint32x4_t>(chunks, key, counter, flags)
+}
@@ -0,0 +1,116 @@
+//! The portable BLAKE3 compression function.
+
+use crate::{BLOCK_LEN, CHUNK_END, CHUNK_LEN, CHUNK_START, IV, MSG_SCHEDULE};
+
+/// A chaining value or key, as little-endian words.
+pub(crate) type Cv = [u32; 8];
+
+#[inline(always)]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+#[inline(always)]
+fn round(state: &mut [u32; 16], m: &[u32; 16], schedule: &[usize; 16]) {
+    // Mix the columns.
+    g(state, 0, 4, 8, 12, m[schedule[0]], m[schedule[1]]);
+    g(state, 1, 5, 9, 13, m[schedule[2]], m[schedule[3]]);
+    g(state, 2, 6, 10, 14, m[schedule[4]], m[schedule[5]]);
+    g(state, 3, 7, 11, 15, m[schedule[6]], m[schedule[7]]);
+    // Mix the diagonals.
+    g(state, 0, 5, 10, 15, m[schedule[8]], m[schedule[9]]);
+    g(state, 1, 6, 11, 12, m[schedule[10]], m[schedule[11]]);
+    g(state, 2, 7, 8, 13, m[schedule[12]], m[schedule[13]]);
+    g(state, 3, 4, 9, 14, m[schedule[14]], m[schedule[15]]);
+}
+
+/// The block words of a (possibly partial and zero padded) block.
+#[inline(always)]
+pub(crate) fn block_words(block: &[u8; BLOCK_LEN]) -> [u32; 16] {
+    core::array::from_fn(|i| u32::from_le_bytes(block[4 * i..4 * i + 4].try_into().unwrap()))
+}
+
+/// The BLAKE3 compression function.
+///
+/// Returns the full 16 word state. The first 8 words are the new chaining
+/// value.
+#[inline(always)]
+pub(crate) fn compress(
+    cv: &Cv,
+    block_words: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    for schedule in MSG_SCHEDULE.iter() {
+        round(&mut state, block_words, schedule);
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+/// The chaining value after compressing a block.
+#[inline(always)]
+pub(crate) fn compress_cv(
+    cv: &Cv,
+    block_words: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> Cv {
+    let state = compress(cv, block_words, counter, block_len, flags);
+    core::array::from_fn(|i| state[i])
+}
+
+/// The chaining value of the full, non-root chunk `chunk` with the chunk
+/// `counter`.
+pub(crate) fn hash_chunk(chunk: &[u8], key: &Cv, counter: u64, flags: u32) -> Cv {
+    debug_assert_eq!(chunk.len(), CHUNK_LEN);
+
+    let mut cv = *key;
+    for (i, block) in chunk.chunks_exact(BLOCK_LEN).enumerate() {
+        let mut block_flags = flags;
+        if i == 0 {
+            block_flags |= CHUNK_START;
+        }
+        if i == CHUNK_LEN / BLOCK_LEN - 1 {
+            block_flags |= CHUNK_END;
+        }
+        cv = compress_cv(
+            &cv,
+            &block_words(block.try_into().unwrap()),
+            counter,
+            BLOCK_LEN as u32,
+            block_flags,
+        );
+    }
+    cv
+}
@@ -0,0 +1,124 @@
+//! BLAKE3 on vectors of 32 bit words.
+//!
+//! Every lane of the vectors hashes a different chunk. The chunks are hashed
+//! in lock-step, block by block, which is possible because all chunks except
+//! for the last one have the same length.
+
+use crate::{portable::Cv, BLOCK_LEN, CHUNK_END, CHUNK_LEN, CHUNK_START, IV, MSG_SCHEDULE};
+
+/// A vector of `N` 32 bit words.
+pub(crate) trait Lanes<const N: usize>: Copy {
+    fn splat(x: u32) -> Self;
+    fn load(words: &[u32; N]) -> Self;
+    fn store(self, words: &mut [u32; N]);
+    fn add(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    /// Rotate right by `R` bits. `L` must be `32 - R`.
+    fn rotate_right<const R: i32, const L: i32>(self) -> Self;
+}
+
+#[inline(always)]
+fn g<const N: usize, V: Lanes<N>>(
+    v: &mut [V; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    mx: V,
+    my: V,
+) {
+    v[a] = v[a].add(v[b]).add(mx);
+    v[d] = v[d].xor(v[a]).rotate_right::<16, 16>();
+    v[c] = v[c].add(v[d]);
+    v[b] = v[b].xor(v[c]).rotate_right::<12, 20>();
+    v[a] = v[a].add(v[b]).add(my);
+    v[d] = v[d].xor(v[a]).rotate_right::<8, 24>();
+    v[c] = v[c].add(v[d]);
+    v[b] = v[b].xor(v[c]).rotate_right::<7, 25>();
+}
+
+#[inline(always)]
+fn round<const N: usize, V: Lanes<N>>(v: &mut [V; 16], m: &[V; 16], schedule: &[usize; 16]) {
+    // Mix the columns.
+    g(v, 0, 4, 8, 12, m[schedule[0]], m[schedule[1]]);
+    g(v, 1, 5, 9, 13, m[schedule[2]], m[schedule[3]]);
+    g(v, 2, 6, 10, 14, m[schedule[4]], m[schedule[5]]);
+    g(v, 3, 7, 11, 15, m[schedule[6]], m[schedule[7]]);
+    // Mix the diagonals.
+    g(v, 0, 5, 10, 15, m[schedule[8]], m[schedule[9]]);
+    g(v, 1, 6, 11, 12, m[schedule[10]], m[schedule[11]]);
+    g(v, 2, 7, 8, 13, m[schedule[12]], m[schedule[13]]);
+    g(v, 3, 4, 9, 14, m[schedule[14]], m[schedule[15]]);
+}
+
+/// The chaining values of the `N` full, non-root chunks in `chunks`, starting
+/// with the chunk `counter`.
+#[inline(always)]
+pub(crate) fn hash_chunks<const N: usize, V: Lanes<N>>(
+    chunks: &[u8],
+    key: &Cv,
+    counter: u64,
+    flags: u32,
+) -> [Cv; N] {
+    debug_assert_eq!(chunks.len(), N * CHUNK_LEN);
+
+    let counter_low = V::load(&core::array::from_fn(|lane| (counter + lane as u64) as u32));
+    let counter_high = V::load(&core::array::from_fn(|lane| {
+        ((counter + lane as u64) >> 32) as u32
+    }));
+
+    let mut h: [V; 8] = core::array::from_fn(|i| V::splat(key[i]));
+    for block in 0..CHUNK_LEN / BLOCK_LEN {
+        let mut block_flags = flags;
+        if block == 0 {
+            block_flags |= CHUNK_START;
+        }
+        if block == CHUNK_LEN / BLOCK_LEN - 1 {
+            block_flags |= CHUNK_END;
+        }
+
+        // Word `i` of the message vectors holds word `i` of the block in
+        // every chunk.
+        let m: [V; 16] = core::array::from_fn(|i| {
+            V::load(&core::array::from_fn(|lane| {
+                let offset = lane * CHUNK_LEN + block * BLOCK_LEN + 4 * i;
+                u32::from_le_bytes(chunks[offset..offset + 4].try_into().unwrap())
+            }))
+        });
+
+        let mut v = [
+            h[0],
+            h[1],
+            h[2],
+            h[3],
+            h[4],
+            h[5],
+            h[6],
+            h[7],
+            V::splat(IV[0]),
+            V::splat(IV[1]),
+            V::splat(IV[2]),
+            V::splat(IV[3]),
+            counter_low,
+            counter_high,
+            V::splat(BLOCK_LEN as u32),
+            V::splat(block_flags),
+        ];
+        for schedule in MSG_SCHEDULE.iter() {
+            round(&mut v, &m, schedule);
+        }
+        for i in 0..8 {
+            h[i] = v[i].xor(v[i + 8]);
+        }
+    }
+
+    let mut cvs = [[0u32; 8]; N];
+    let mut words = [0u32; N];
+    for (i, h) in h.iter().enumerate() {
+        h.store(&mut words);
+        for (cv, word) in cvs.iter_mut().zip(words.iter()) {
+            cv[i] = *word;
+        }
+    }
+    cvs
+}
@@ -0,0 +1,133 @@
+use libcrux_blake3::{derive_key, hash, keyed_hash, Digest, Hasher, BLAKE3_LENGTH};
+use serde::Deserialize;
+
+/// The test vectors in the format of the official `test_vectors.json`.
+///
+/// The outputs were generated with the reference `blake3` crate.
+#[derive(Deserialize)]
+struct TestVectors {
+    key: String,
+    context_string: String,
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    input_len: usize,
+    hash: String,
+    keyed_hash: String,
+    derive_key: String,
+}
+
+fn test_vectors() -> TestVectors {
+    serde_json::from_str(include_str!("data/test_vectors.json")).unwrap()
+}
+
+fn input(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn xof(hasher: &Hasher, len: usize) -> String {
+    let mut out = vec![0u8; len];
+    hasher.finalize_xof().fill(&mut out);
+    hex::encode(out)
+}
+
+#[test]
+fn blake3_test_vectors() {
+    let tvs = test_vectors();
+    let key: [u8; 32] = tvs.key.as_bytes().try_into().unwrap();
+
+    for case in tvs.cases {
+        let input = input(case.input_len);
+        let out_len = case.hash.len() / 2;
+
+        assert_eq!(hex::encode(hash(&input)), case.hash[..64]);
+        assert_eq!(hex::encode(keyed_hash(&key, &input)), case.keyed_hash[..64]);
+        assert_eq!(
+            hex::encode(derive_key(&tvs.context_string, &input)),
+            case.derive_key[..64]
+        );
+
+        let mut hasher = Hasher::new();
+        hasher.update(&input);
+        assert_eq!(xof(&hasher, out_len), case.hash);
+
+        let mut hasher = Hasher::new_keyed(&key);
+        hasher.update(&input);
+        assert_eq!(xof(&hasher, out_len), case.keyed_hash);
+
+        let mut hasher = Hasher::new_derive_key(&tvs.context_string);
+        hasher.update(&input);
+        assert_eq!(xof(&hasher, out_len), case.derive_key);
+    }
+}
+
+#[test]
+fn blake3_streaming() {
+    let tvs = test_vectors();
+
+    for case in tvs.cases {
+        let input = input(case.input_len);
+
+        for chunk_len in [1, 63, 64, 65, 1000, 1024, 4097] {
+            let mut hasher = Hasher::new();
+            for chunk in input.chunks(chunk_len) {
+                hasher.update(chunk);
+            }
+            let mut digest = [0u8; BLAKE3_LENGTH];
+            hasher.finish(&mut digest);
+            assert_eq!(hex::encode(digest), case.hash[..64]);
+        }
+    }
+}
+
+#[test]
+fn blake3_digest_trait() {
+    let input = input(5000);
+
+    let mut digest = [0u8; BLAKE3_LENGTH];
+    Hasher::hash(&mut digest, &input);
+    assert_eq!(digest, hash(&input));
+
+    // The state can be continued after finishing and reset.
+    let mut hasher = Hasher::default();
+    hasher.update(&input[..3000]);
+    hasher.finish(&mut digest);
+    assert_eq!(digest, hash(&input[..3000]));
+    hasher.update(&input[3000..]);
+    hasher.finish(&mut digest);
+    assert_eq!(digest, hash(&input));
+
+    hasher.reset();
+    hasher.update(b"abc");
+    hasher.finish(&mut digest);
+    assert_eq!(digest, hash(b"abc"));
+}
+
+#[test]
+fn blake3_keyed_reset_keeps_key() {
+    let key = [0x42u8; 32];
+    let mut hasher = Hasher::new_keyed(&key);
+    hasher.update(b"some data");
+    hasher.reset();
+    hasher.update(b"other data");
+    assert_eq!(hasher.finalize(), keyed_hash(&key, b"other data"));
+}
+
+#[test]
+fn blake3_xof_seek() {
+    let mut hasher = Hasher::new();
+    hasher.update(&input(2049));
+
+    let mut expected = [0u8; 300];
+    hasher.finalize_xof().fill(&mut expected);
+
+    let mut reader = hasher.finalize_xof();
+    reader.set_position(100);
+    let mut out = [0u8; 150];
+    reader.fill(&mut out[..7]);
+    reader.fill(&mut out[7..]);
+    assert_eq!(reader.position(), 250);
+    assert_eq!(out, expected[100..250]);
+}
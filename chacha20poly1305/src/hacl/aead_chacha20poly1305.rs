@@ -384,7 +384,14 @@ fn poly1305_padded_32(ctx: &mut [u64], len: u32, text: &[u8]) {
 }
 
 #[inline]
-fn poly1305_do_32(k: &[u8], aadlen: u32, aad: &[u8], mlen: u32, m: &[u8], out: &mut [u8]) {
+pub(crate) fn poly1305_do_32(
+    k: &[u8],
+    aadlen: u32,
+    aad: &[u8],
+    mlen: u32,
+    m: &[u8],
+    out: &mut [u8],
+) {
     let mut ctx: [u64; 25] = [0u64; 25usize];
     let mut block: [u8; 16] = [0u8; 16usize];
     crate::hacl::mac_poly1305::poly1305_init(&mut ctx, k);
@@ -177,6 +177,28 @@ fn chacha20_update(ctx: &[u32], len: u32, out: &mut [u8], text: &[u8]) {
     }
 }
 
+/// Like [`chacha20_encrypt`], but XORs the key stream into `buf` in place.
+///
+/// This is not part of the generated code.
+pub(crate) fn chacha20_encrypt_in_place(len: u32, buf: &mut [u8], key: &[u8], n: &[u8], ctr: u32) {
+    let mut ctx: [u32; 16] = [0u32; 16usize];
+    crate::hacl::chacha20::chacha20_init(&mut ctx, key, n, ctr);
+    let nb: u32 = len.wrapping_div(64u32);
+    let rem: u32 = len.wrapping_rem(64u32);
+    for i in 0u32..nb {
+        let block: &mut [u8] = &mut buf[i.wrapping_mul(64u32) as usize..];
+        let mut text: [u8; 64] = [0u8; 64usize];
+        text.copy_from_slice(&block[0usize..64usize]);
+        crate::hacl::chacha20::chacha20_encrypt_block(&ctx, block, i, &text)
+    }
+    if rem > 0u32 {
+        let last: &mut [u8] = &mut buf[nb.wrapping_mul(64u32) as usize..];
+        let mut text: [u8; 64] = [0u8; 64usize];
+        ((&mut text)[0usize..rem as usize]).copy_from_slice(&last[0usize..rem as usize]);
+        crate::hacl::chacha20::chacha20_encrypt_last(&ctx, rem, last, nb, &text)
+    }
+}
+
 pub fn chacha20_encrypt(len: u32, out: &mut [u8], text: &[u8], key: &[u8], n: &[u8], ctr: u32) {
     let mut ctx: [u32; 16] = [0u32; 16usize];
     crate::hacl::chacha20::chacha20_init(&mut ctx, key, n, ctr);
@@ -1,3 +1,5 @@
+use libcrux_traits::aead::Aead;
+
 use crate::{AeadError, KEY_LEN, NONCE_LEN, TAG_LEN};
 
 const DETACHED: bool = true;
@@ -147,3 +149,160 @@ pub fn decrypt_detached<'a>(
         _ => Err(AeadError::InvalidCiphertext),
     }
 }
+
+/// XOR `buffer` in place with the ChaCha20 key stream, starting at block
+/// counter 1.
+///
+/// The length of `buffer` must fit into a `u32`.
+fn chacha20_in_place(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], buffer: &mut [u8]) {
+    crate::hacl::chacha20::chacha20_encrypt_in_place(buffer.len() as u32, buffer, key, nonce, 1);
+}
+
+/// Compute the Poly1305 tag over `aad` and `ctxt`.
+fn poly1305_tag(
+    key: &[u8; KEY_LEN],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    aad_len: u32,
+    ctxt: &[u8],
+    ctxt_len: u32,
+    tag: &mut [u8; TAG_LEN],
+) {
+    let mut poly_key = [0u8; 64];
+    crate::hacl::chacha20::chacha20_encrypt(64, &mut poly_key, &[0u8; 64], key, nonce, 0);
+
+    crate::hacl::aead_chacha20poly1305::poly1305_do_32(
+        &poly_key, aad_len, aad, ctxt_len, ctxt, tag,
+    );
+}
+
+/// The ChaCha20-Poly1305 AEAD encryption function, encrypting `buffer` in place and writing the
+/// tag to `tag`.
+///
+/// This implementation is backed by hacl-rs and can only handle inputs up to a length of `u32::MAX`.
+/// When provided longer values, this function will return an error.
+pub fn encrypt_in_place(
+    key: &[u8; KEY_LEN],
+    buffer: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; NONCE_LEN],
+) -> Result<(), AeadError> {
+    let ptxt_len: u32 = buffer
+        .len()
+        .try_into()
+        .map_err(|_| AeadError::PlaintextTooLarge)?;
+    let aad_len: u32 = aad.len().try_into().map_err(|_| AeadError::AadTooLarge)?;
+
+    chacha20_in_place(key, nonce, buffer);
+    poly1305_tag(key, nonce, aad, aad_len, buffer, ptxt_len, tag);
+
+    Ok(())
+}
+
+/// The ChaCha20-Poly1305 AEAD decryption function, decrypting `buffer` in place with the detached
+/// `tag`. If the tag is invalid, `buffer` is left unchanged.
+///
+/// This implementation is backed by hacl-rs and can only handle inputs up to a length of `u32::MAX`.
+/// When provided longer values, this function will return an error.
+pub fn decrypt_in_place(
+    key: &[u8; KEY_LEN],
+    buffer: &mut [u8],
+    tag: &[u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; NONCE_LEN],
+) -> Result<(), AeadError> {
+    let ctxt_len: u32 = buffer
+        .len()
+        .try_into()
+        .map_err(|_| AeadError::CiphertextTooLarge)?;
+    let aad_len: u32 = aad.len().try_into().map_err(|_| AeadError::AadTooLarge)?;
+
+    let mut computed_tag = [0u8; TAG_LEN];
+    poly1305_tag(
+        key,
+        nonce,
+        aad,
+        aad_len,
+        buffer,
+        ctxt_len,
+        &mut computed_tag,
+    );
+
+    let mut diff = 0u8;
+    for (a, b) in computed_tag.iter().zip(tag.iter()) {
+        diff |= a ^ b;
+    }
+    if core::hint::black_box(diff) != 0 {
+        return Err(AeadError::InvalidCiphertext);
+    }
+
+    chacha20_in_place(key, nonce, buffer);
+
+    Ok(())
+}
+
+/// The ChaCha20-Poly1305 AEAD.
+///
+/// This implements the [`Aead`] trait.
+pub struct ChaCha20Poly1305;
+
+impl Aead for ChaCha20Poly1305 {
+    const KEY_LEN: usize = KEY_LEN;
+    const NONCE_LEN: usize = NONCE_LEN;
+    const TAG_LEN: usize = TAG_LEN;
+
+    type Key = [u8; KEY_LEN];
+    type Nonce = [u8; NONCE_LEN];
+    type Tag = [u8; TAG_LEN];
+
+    fn encrypt_detached(
+        key: &Self::Key,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        tag: &mut Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        if ctxt.len() < ptxt.len() {
+            return Err(libcrux_traits::aead::AeadError::CiphertextTooShort);
+        }
+
+        encrypt_detached(key, ptxt, &mut ctxt[..ptxt.len()], tag, aad, nonce)?;
+        Ok(())
+    }
+
+    fn decrypt_detached(
+        key: &Self::Key,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        tag: &Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        decrypt_detached(key, ptxt, ctxt, tag, aad, nonce)?;
+        Ok(())
+    }
+
+    fn encrypt_in_place(
+        key: &Self::Key,
+        buffer: &mut [u8],
+        tag: &mut Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        encrypt_in_place(key, buffer, tag, aad, nonce)?;
+        Ok(())
+    }
+
+    fn decrypt_in_place(
+        key: &Self::Key,
+        buffer: &mut [u8],
+        tag: &Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        decrypt_in_place(key, buffer, tag, aad, nonce)?;
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod stream;
 pub mod xchacha20_poly1305;
 
 /// The length of ChaCha20-Poly1305 keys.
@@ -56,6 +60,19 @@ impl core::fmt::Display for AeadError {
     }
 }
 
+impl From<AeadError> for libcrux_traits::aead::AeadError {
+    fn from(e: AeadError) -> Self {
+        match e {
+            AeadError::PlaintextTooLarge => Self::PlaintextTooLarge,
+            AeadError::CiphertextTooLarge => Self::CiphertextTooLarge,
+            AeadError::AadTooLarge => Self::AadTooLarge,
+            AeadError::CiphertextTooShort => Self::CiphertextTooShort,
+            AeadError::PlaintextTooShort => Self::PlaintextTooShort,
+            AeadError::InvalidCiphertext => Self::InvalidCiphertext,
+        }
+    }
+}
+
 /// Describes the error conditions of the Poly1305 MAC.
 #[derive(Debug)]
 pub enum MacError {
@@ -0,0 +1,360 @@
+//! Online authenticated encryption of long messages with the STREAM
+//! construction from Hoang, Reyhanitabar, Rogaway and Vizár,
+//! ["Online Authenticated-Encryption and its Nonce-Reuse Misuse-Resistance"](https://eprint.iacr.org/2015/189).
+//!
+//! A message is split into segments that are encrypted one at a time. The
+//! nonce of the `i`-th segment is
+//!
+//! ```text
+//! nonce_prefix || I2OSP(i, 4) || last_segment
+//! ```
+//!
+//! where `last_segment` is `0x01` for the final segment and `0x00` otherwise.
+//! This binds every segment to its position, so reordered, duplicated or
+//! dropped segments fail to decrypt, and a stream that is cut off at a
+//! segment boundary is detected because its final segment is not marked as
+//! the last one.
+//!
+//! A key and nonce prefix pair must never be used for more than one stream.
+//! The 7 byte prefix of [`ChaCha20Poly1305`] is too short to be picked at
+//! random for many streams under the same key; use a fresh key per stream or
+//! [`XChaCha20Poly1305`] with a random 19 byte prefix.
+//!
+//! With the `std` feature, [`io`] provides [`std::io::Write`] and
+//! [`std::io::Read`] adapters on top of [`StreamEncryptor`] and
+//! [`StreamDecryptor`].
+//!
+//! ```
+//! use libcrux_chacha20poly1305::{stream::*, TAG_LEN};
+//!
+//! let key = [0x42; 32];
+//! let nonce_prefix = [0x23; XCHACHA20_POLY1305_NONCE_PREFIX_LEN];
+//!
+//! let mut encryptor = StreamEncryptor::<XChaCha20Poly1305>::new(&key, &nonce_prefix);
+//! let mut first = [0u8; 5 + TAG_LEN];
+//! let mut last = [0u8; 5 + TAG_LEN];
+//! encryptor.encrypt_next(b"hello", &mut first, b"").unwrap();
+//! encryptor.encrypt_last(b"world", &mut last, b"").unwrap();
+//!
+//! let mut decryptor = StreamDecryptor::<XChaCha20Poly1305>::new(&key, &nonce_prefix);
+//! let mut ptxt = [0u8; 5];
+//! assert_eq!(decryptor.decrypt_next(&mut ptxt, &first, b"").unwrap(), b"hello");
+//! assert_eq!(decryptor.decrypt_last(&mut ptxt, &last, b"").unwrap(), b"world");
+//! ```
+
+use crate::{AeadError, KEY_LEN, TAG_LEN};
+
+#[cfg(feature = "std")]
+pub mod io;
+
+/// The length of the nonce prefix for [`ChaCha20Poly1305`] streams.
+pub const CHACHA20_POLY1305_NONCE_PREFIX_LEN: usize = crate::NONCE_LEN - 5;
+
+/// The length of the nonce prefix for [`XChaCha20Poly1305`] streams.
+pub const XCHACHA20_POLY1305_NONCE_PREFIX_LEN: usize = crate::xchacha20_poly1305::NONCE_LEN - 5;
+
+/// Describes the error conditions of the STREAM construction.
+#[derive(Debug)]
+pub enum StreamError {
+    /// An error of the underlying AEAD. A segment that is out of order,
+    /// truncated or modified results in [`AeadError::InvalidCiphertext`].
+    Aead(AeadError),
+    /// Indicates that the stream already has the maximum number of segments.
+    SegmentLimitReached,
+}
+
+impl From<AeadError> for StreamError {
+    fn from(value: AeadError) -> Self {
+        StreamError::Aead(value)
+    }
+}
+
+impl core::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StreamError::Aead(e) => e.fmt(f),
+            StreamError::SegmentLimitReached => {
+                f.write_str("The stream has reached the maximum number of segments")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StreamError {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An AEAD that can be used with the STREAM construction.
+///
+/// This trait is sealed and implemented by [`ChaCha20Poly1305`] and
+/// [`XChaCha20Poly1305`].
+pub trait StreamAead: sealed::Sealed {
+    /// The nonce prefix that is shared by all segments of a stream.
+    type NoncePrefix: Copy;
+
+    #[doc(hidden)]
+    fn encrypt_segment(
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &Self::NoncePrefix,
+        counter: u32,
+        last: bool,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        aad: &[u8],
+    ) -> Result<(), AeadError>;
+
+    #[doc(hidden)]
+    fn decrypt_segment(
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &Self::NoncePrefix,
+        counter: u32,
+        last: bool,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+    ) -> Result<(), AeadError>;
+}
+
+/// STREAM over ChaCha20-Poly1305 with a 7 byte nonce prefix.
+pub struct ChaCha20Poly1305;
+
+/// STREAM over XChaCha20-Poly1305 with a 19 byte nonce prefix.
+pub struct XChaCha20Poly1305;
+
+impl sealed::Sealed for ChaCha20Poly1305 {}
+impl sealed::Sealed for XChaCha20Poly1305 {}
+
+/// Write `nonce_prefix || I2OSP(counter, 4) || last` into `nonce`.
+fn segment_nonce<const N: usize>(nonce_prefix: &[u8], counter: u32, last: bool) -> [u8; N] {
+    let mut nonce = [0u8; N];
+    nonce[..N - 5].copy_from_slice(nonce_prefix);
+    nonce[N - 5..N - 1].copy_from_slice(&counter.to_be_bytes());
+    nonce[N - 1] = last as u8;
+    nonce
+}
+
+impl StreamAead for ChaCha20Poly1305 {
+    type NoncePrefix = [u8; CHACHA20_POLY1305_NONCE_PREFIX_LEN];
+
+    fn encrypt_segment(
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &Self::NoncePrefix,
+        counter: u32,
+        last: bool,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        aad: &[u8],
+    ) -> Result<(), AeadError> {
+        let nonce = segment_nonce(nonce_prefix, counter, last);
+        crate::encrypt(key, ptxt, ctxt, aad, &nonce).map(|_| ())
+    }
+
+    fn decrypt_segment(
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &Self::NoncePrefix,
+        counter: u32,
+        last: bool,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+    ) -> Result<(), AeadError> {
+        let nonce = segment_nonce(nonce_prefix, counter, last);
+        crate::decrypt(key, ptxt, ctxt, aad, &nonce).map(|_| ())
+    }
+}
+
+impl StreamAead for XChaCha20Poly1305 {
+    type NoncePrefix = [u8; XCHACHA20_POLY1305_NONCE_PREFIX_LEN];
+
+    fn encrypt_segment(
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &Self::NoncePrefix,
+        counter: u32,
+        last: bool,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        aad: &[u8],
+    ) -> Result<(), AeadError> {
+        let nonce = segment_nonce(nonce_prefix, counter, last);
+        crate::xchacha20_poly1305::encrypt(key, ptxt, ctxt, aad, &nonce).map(|_| ())
+    }
+
+    fn decrypt_segment(
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &Self::NoncePrefix,
+        counter: u32,
+        last: bool,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+    ) -> Result<(), AeadError> {
+        let nonce = segment_nonce(nonce_prefix, counter, last);
+        crate::xchacha20_poly1305::decrypt(key, ptxt, ctxt, aad, &nonce).map(|_| ())
+    }
+}
+
+/// Encrypts a stream segment by segment.
+///
+/// Segments can have different lengths. The stream has to be terminated with
+/// [`StreamEncryptor::encrypt_last`], otherwise decryption fails.
+pub struct StreamEncryptor<A: StreamAead> {
+    key: [u8; KEY_LEN],
+    nonce_prefix: A::NoncePrefix,
+    counter: u32,
+}
+
+impl<A: StreamAead> StreamEncryptor<A> {
+    /// Start a new stream. The `key` and `nonce_prefix` pair must be unique.
+    pub fn new(key: &[u8; KEY_LEN], nonce_prefix: &A::NoncePrefix) -> Self {
+        Self {
+            key: *key,
+            nonce_prefix: *nonce_prefix,
+            counter: 0,
+        }
+    }
+
+    /// Encrypt the next segment that is not the last one.
+    ///
+    /// Writes the ciphertext and tag into the first `ptxt.len() + TAG_LEN`
+    /// bytes of `ctxt` and returns them.
+    pub fn encrypt_next<'a>(
+        &mut self,
+        ptxt: &[u8],
+        ctxt: &'a mut [u8],
+        aad: &[u8],
+    ) -> Result<&'a [u8], StreamError> {
+        // The last segment may use the largest counter value.
+        if self.counter == u32::MAX {
+            return Err(StreamError::SegmentLimitReached);
+        }
+
+        let ctxt = self.encrypt(false, ptxt, ctxt, aad)?;
+        self.counter += 1;
+
+        Ok(ctxt)
+    }
+
+    /// Encrypt the last segment, which ends the stream.
+    ///
+    /// Writes the ciphertext and tag into the first `ptxt.len() + TAG_LEN`
+    /// bytes of `ctxt` and returns them.
+    pub fn encrypt_last<'a>(
+        self,
+        ptxt: &[u8],
+        ctxt: &'a mut [u8],
+        aad: &[u8],
+    ) -> Result<&'a [u8], StreamError> {
+        self.encrypt(true, ptxt, ctxt, aad)
+    }
+
+    fn encrypt<'a>(
+        &self,
+        last: bool,
+        ptxt: &[u8],
+        ctxt: &'a mut [u8],
+        aad: &[u8],
+    ) -> Result<&'a [u8], StreamError> {
+        if (ctxt.len() as u64) < (ptxt.len() as u64) + (TAG_LEN as u64) {
+            return Err(AeadError::CiphertextTooShort.into());
+        }
+        let ctxt = &mut ctxt[..ptxt.len() + TAG_LEN];
+
+        A::encrypt_segment(
+            &self.key,
+            &self.nonce_prefix,
+            self.counter,
+            last,
+            ptxt,
+            ctxt,
+            aad,
+        )?;
+
+        Ok(ctxt)
+    }
+}
+
+/// Decrypts a stream segment by segment.
+///
+/// The segments have to be passed in the order they were encrypted in. The
+/// stream is only complete and authentic once
+/// [`StreamDecryptor::decrypt_last`] succeeded.
+pub struct StreamDecryptor<A: StreamAead> {
+    key: [u8; KEY_LEN],
+    nonce_prefix: A::NoncePrefix,
+    counter: u32,
+}
+
+impl<A: StreamAead> StreamDecryptor<A> {
+    /// Start decrypting the stream for the `key` and `nonce_prefix`.
+    pub fn new(key: &[u8; KEY_LEN], nonce_prefix: &A::NoncePrefix) -> Self {
+        Self {
+            key: *key,
+            nonce_prefix: *nonce_prefix,
+            counter: 0,
+        }
+    }
+
+    /// Decrypt the next segment that is not the last one.
+    ///
+    /// Writes the plaintext into the first `ctxt.len() - TAG_LEN` bytes of
+    /// `ptxt` and returns them.
+    pub fn decrypt_next<'a>(
+        &mut self,
+        ptxt: &'a mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+    ) -> Result<&'a [u8], StreamError> {
+        if self.counter == u32::MAX {
+            return Err(StreamError::SegmentLimitReached);
+        }
+
+        let ptxt = self.decrypt(false, ptxt, ctxt, aad)?;
+        self.counter += 1;
+
+        Ok(ptxt)
+    }
+
+    /// Decrypt the last segment, which ends the stream.
+    ///
+    /// Writes the plaintext into the first `ctxt.len() - TAG_LEN` bytes of
+    /// `ptxt` and returns them.
+    pub fn decrypt_last<'a>(
+        self,
+        ptxt: &'a mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+    ) -> Result<&'a [u8], StreamError> {
+        self.decrypt(true, ptxt, ctxt, aad)
+    }
+
+    fn decrypt<'a>(
+        &self,
+        last: bool,
+        ptxt: &'a mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+    ) -> Result<&'a [u8], StreamError> {
+        if ctxt.len() < TAG_LEN {
+            return Err(AeadError::InvalidCiphertext.into());
+        }
+        if ptxt.len() < ctxt.len() - TAG_LEN {
+            return Err(AeadError::PlaintextTooShort.into());
+        }
+        let ptxt = &mut ptxt[..ctxt.len() - TAG_LEN];
+
+        A::decrypt_segment(
+            &self.key,
+            &self.nonce_prefix,
+            self.counter,
+            last,
+            ptxt,
+            ctxt,
+            aad,
+        )?;
+
+        Ok(ptxt)
+    }
+}
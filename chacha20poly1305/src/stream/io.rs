@@ -0,0 +1,229 @@
+//! [`std::io`] adapters for the STREAM construction.
+//!
+//! Plaintext is cut into segments of a fixed length, which is part of the
+//! format: a stream has to be decrypted with the segment length it was
+//! encrypted with. The final segment is at most that long and may be empty.
+
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+
+use super::{StreamAead, StreamDecryptor, StreamEncryptor, StreamError};
+use crate::{AeadError, KEY_LEN, TAG_LEN};
+
+/// The plaintext segment length used by [`EncryptingWriter::new`] and
+/// [`DecryptingReader::new`].
+pub const DEFAULT_SEGMENT_LEN: usize = 64 * 1024;
+
+fn invalid_data(e: StreamError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// A [`Write`]r that encrypts everything written to it into the inner writer.
+///
+/// The stream must be terminated with [`EncryptingWriter::finish`]. Dropping
+/// the writer without finishing it leaves a truncated stream that fails to
+/// decrypt.
+pub struct EncryptingWriter<W: Write, A: StreamAead> {
+    inner: W,
+    encryptor: StreamEncryptor<A>,
+    segment_len: usize,
+    ptxt: Vec<u8>,
+    ctxt: Vec<u8>,
+}
+
+impl<W: Write, A: StreamAead> EncryptingWriter<W, A> {
+    /// Encrypt into `inner` with segments of [`DEFAULT_SEGMENT_LEN`] bytes.
+    pub fn new(inner: W, key: &[u8; KEY_LEN], nonce_prefix: &A::NoncePrefix) -> Self {
+        Self::with_segment_len(inner, key, nonce_prefix, DEFAULT_SEGMENT_LEN)
+    }
+
+    /// Encrypt into `inner` with segments of `segment_len` bytes.
+    ///
+    /// Panics if `segment_len` is 0.
+    pub fn with_segment_len(
+        inner: W,
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &A::NoncePrefix,
+        segment_len: usize,
+    ) -> Self {
+        assert!(segment_len > 0, "the segment length must not be 0");
+
+        Self {
+            inner,
+            encryptor: StreamEncryptor::new(key, nonce_prefix),
+            segment_len,
+            ptxt: Vec::with_capacity(segment_len),
+            ctxt: std::vec![0u8; segment_len + TAG_LEN],
+        }
+    }
+
+    /// Encrypt the buffered plaintext as the last segment and return the
+    /// inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let ctxt = self
+            .encryptor
+            .encrypt_last(&self.ptxt, &mut self.ctxt, b"")
+            .map_err(invalid_data)?;
+        self.inner.write_all(ctxt)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+    fn write_segment(&mut self) -> io::Result<()> {
+        let ctxt = self
+            .encryptor
+            .encrypt_next(&self.ptxt, &mut self.ctxt, b"")
+            .map_err(invalid_data)?;
+        self.inner.write_all(ctxt)?;
+        self.ptxt.clear();
+
+        Ok(())
+    }
+}
+
+impl<W: Write, A: StreamAead> Write for EncryptingWriter<W, A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            // A full segment is only written once more data follows, because
+            // it could still turn out to be the last one.
+            if self.ptxt.len() == self.segment_len {
+                self.write_segment()?;
+            }
+
+            let take = core::cmp::min(self.segment_len - self.ptxt.len(), buf.len() - written);
+            self.ptxt.extend_from_slice(&buf[written..written + take]);
+            written += take;
+        }
+
+        Ok(written)
+    }
+
+    /// Flushes the inner writer. Buffered plaintext is only encrypted once a
+    /// segment is complete.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A [`Read`]er that decrypts a stream read from the inner reader.
+///
+/// Reading returns an [`io::ErrorKind::InvalidData`] error if the stream was
+/// modified, reordered or truncated. Plaintext of a segment is only returned
+/// after the segment was authenticated, but earlier segments may already have
+/// been returned when a later one fails.
+pub struct DecryptingReader<R: Read, A: StreamAead> {
+    inner: R,
+    decryptor: Option<StreamDecryptor<A>>,
+    segment_len: usize,
+    ctxt: Vec<u8>,
+    ctxt_len: usize,
+    ptxt: Vec<u8>,
+    ptxt_pos: usize,
+    ptxt_len: usize,
+    failed: bool,
+}
+
+impl<R: Read, A: StreamAead> DecryptingReader<R, A> {
+    /// Decrypt from `inner` with segments of [`DEFAULT_SEGMENT_LEN`] bytes.
+    pub fn new(inner: R, key: &[u8; KEY_LEN], nonce_prefix: &A::NoncePrefix) -> Self {
+        Self::with_segment_len(inner, key, nonce_prefix, DEFAULT_SEGMENT_LEN)
+    }
+
+    /// Decrypt from `inner` with segments of `segment_len` bytes.
+    ///
+    /// Panics if `segment_len` is 0.
+    pub fn with_segment_len(
+        inner: R,
+        key: &[u8; KEY_LEN],
+        nonce_prefix: &A::NoncePrefix,
+        segment_len: usize,
+    ) -> Self {
+        assert!(segment_len > 0, "the segment length must not be 0");
+
+        Self {
+            inner,
+            decryptor: Some(StreamDecryptor::new(key, nonce_prefix)),
+            segment_len,
+            // One byte more than a segment to tell whether another one follows.
+            ctxt: std::vec![0u8; segment_len + TAG_LEN + 1],
+            ctxt_len: 0,
+            ptxt: std::vec![0u8; segment_len],
+            ptxt_pos: 0,
+            ptxt_len: 0,
+            failed: false,
+        }
+    }
+
+    /// Return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Read and decrypt the next segment into the plaintext buffer.
+    fn read_segment(&mut self) -> io::Result<()> {
+        let Some(decryptor) = self.decryptor.as_mut() else {
+            return Ok(());
+        };
+
+        while self.ctxt_len < self.ctxt.len() {
+            match self.inner.read(&mut self.ctxt[self.ctxt_len..]) {
+                Ok(0) => break,
+                Ok(n) => self.ctxt_len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let segment_len = self.segment_len + TAG_LEN;
+        self.ptxt_len = if self.ctxt_len > segment_len {
+            let ptxt_len = decryptor
+                .decrypt_next(&mut self.ptxt, &self.ctxt[..segment_len], b"")
+                .map_err(invalid_data)?
+                .len();
+
+            // Keep the byte of the following segment.
+            self.ctxt[0] = self.ctxt[segment_len];
+            self.ctxt_len = 1;
+            ptxt_len
+        } else {
+            // The inner reader is exhausted, so this has to be the last segment.
+            let decryptor = self.decryptor.take().unwrap();
+            let ptxt_len = decryptor
+                .decrypt_last(&mut self.ptxt, &self.ctxt[..self.ctxt_len], b"")
+                .map_err(invalid_data)?
+                .len();
+            self.ctxt_len = 0;
+            ptxt_len
+        };
+
+        self.ptxt_pos = 0;
+
+        Ok(())
+    }
+}
+
+impl<R: Read, A: StreamAead> Read for DecryptingReader<R, A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.ptxt_pos == self.ptxt_len {
+            if self.failed {
+                return Err(invalid_data(AeadError::InvalidCiphertext.into()));
+            }
+            if self.decryptor.is_none() {
+                return Ok(0);
+            }
+            if let Err(e) = self.read_segment() {
+                // Don't report a clean end of the stream after a failure.
+                self.failed = e.kind() == io::ErrorKind::InvalidData;
+                return Err(e);
+            }
+        }
+
+        let n = core::cmp::min(buf.len(), self.ptxt_len - self.ptxt_pos);
+        buf[..n].copy_from_slice(&self.ptxt[self.ptxt_pos..self.ptxt_pos + n]);
+        self.ptxt_pos += n;
+
+        Ok(n)
+    }
+}
@@ -3,6 +3,8 @@
 //!
 //! **NOTE:** This part of the code has not been formally verified yet.
 
+use libcrux_traits::aead::Aead;
+
 use crate::{
     hacl::chacha20::{chacha20_constants, rounds},
     AeadError, KEY_LEN, TAG_LEN,
@@ -21,10 +23,7 @@ pub fn encrypt<'a>(
     aad: &[u8],
     nonce: &[u8; NONCE_LEN],
 ) -> Result<(&'a [u8], &'a [u8; TAG_LEN]), AeadError> {
-    let subkey = hchacha20(key, &nonce[0..16].try_into().unwrap());
-
-    let mut new_nonce = [0u8; super::NONCE_LEN];
-    new_nonce[4..].copy_from_slice(&nonce[16..]);
+    let (subkey, new_nonce) = derive(key, nonce);
 
     super::encrypt(&subkey, ptxt, ctxt, aad, &new_nonce)
 }
@@ -38,12 +37,140 @@ pub fn decrypt<'a>(
     aad: &[u8],
     nonce: &[u8; NONCE_LEN],
 ) -> Result<&'a [u8], AeadError> {
+    let (subkey, new_nonce) = derive(key, nonce);
+
+    super::decrypt(&subkey, ptxt, ctxt, aad, &new_nonce)
+}
+
+/// XChacha20Poly1305 encrypt with detached tag: Writes the ciphertext to the
+/// first `ptxt.len()` bytes of `ctxt` and the tag to `tag`.
+pub fn encrypt_detached<'a>(
+    key: &[u8; KEY_LEN],
+    ptxt: &[u8],
+    ctxt: &'a mut [u8],
+    tag: &'a mut [u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; NONCE_LEN],
+) -> Result<(&'a [u8], &'a [u8; TAG_LEN]), AeadError> {
+    let (subkey, new_nonce) = derive(key, nonce);
+
+    super::encrypt_detached(&subkey, ptxt, ctxt, tag, aad, &new_nonce)
+}
+
+/// XChacha20Poly1305 decrypt with detached tag: Writes the result of the
+/// decryption to `ptxt`, and returns the slice of appropriate length.
+pub fn decrypt_detached<'a>(
+    key: &[u8; KEY_LEN],
+    ptxt: &'a mut [u8],
+    ctxt: &[u8],
+    tag: &[u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; NONCE_LEN],
+) -> Result<&'a [u8], AeadError> {
+    let (subkey, new_nonce) = derive(key, nonce);
+
+    super::decrypt_detached(&subkey, ptxt, ctxt, tag, aad, &new_nonce)
+}
+
+/// XChacha20Poly1305 encrypt in place: Encrypts `buffer` in place and writes
+/// the tag to `tag`.
+pub fn encrypt_in_place(
+    key: &[u8; KEY_LEN],
+    buffer: &mut [u8],
+    tag: &mut [u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; NONCE_LEN],
+) -> Result<(), AeadError> {
+    let (subkey, new_nonce) = derive(key, nonce);
+
+    super::encrypt_in_place(&subkey, buffer, tag, aad, &new_nonce)
+}
+
+/// XChacha20Poly1305 decrypt in place: Decrypts `buffer` in place with the
+/// detached `tag`. If the tag is invalid, `buffer` is left unchanged.
+pub fn decrypt_in_place(
+    key: &[u8; KEY_LEN],
+    buffer: &mut [u8],
+    tag: &[u8; TAG_LEN],
+    aad: &[u8],
+    nonce: &[u8; NONCE_LEN],
+) -> Result<(), AeadError> {
+    let (subkey, new_nonce) = derive(key, nonce);
+
+    super::decrypt_in_place(&subkey, buffer, tag, aad, &new_nonce)
+}
+
+/// The XChaCha20-Poly1305 AEAD.
+///
+/// This implements the [`Aead`] trait.
+pub struct XChaCha20Poly1305;
+
+impl Aead for XChaCha20Poly1305 {
+    const KEY_LEN: usize = KEY_LEN;
+    const NONCE_LEN: usize = NONCE_LEN;
+    const TAG_LEN: usize = TAG_LEN;
+
+    type Key = [u8; KEY_LEN];
+    type Nonce = [u8; NONCE_LEN];
+    type Tag = [u8; TAG_LEN];
+
+    fn encrypt_detached(
+        key: &Self::Key,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        tag: &mut Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        let (subkey, new_nonce) = derive(key, nonce);
+
+        crate::ChaCha20Poly1305::encrypt_detached(&subkey, ptxt, ctxt, tag, aad, &new_nonce)
+    }
+
+    fn decrypt_detached(
+        key: &Self::Key,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        tag: &Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        decrypt_detached(key, ptxt, ctxt, tag, aad, nonce)?;
+        Ok(())
+    }
+
+    fn encrypt_in_place(
+        key: &Self::Key,
+        buffer: &mut [u8],
+        tag: &mut Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        encrypt_in_place(key, buffer, tag, aad, nonce)?;
+        Ok(())
+    }
+
+    fn decrypt_in_place(
+        key: &Self::Key,
+        buffer: &mut [u8],
+        tag: &Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), libcrux_traits::aead::AeadError> {
+        decrypt_in_place(key, buffer, tag, aad, nonce)?;
+        Ok(())
+    }
+}
+
+/// Derive the ChaCha20-Poly1305 key and nonce from the XChaCha20-Poly1305
+/// `key` and `nonce`.
+fn derive(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN]) -> ([u8; KEY_LEN], [u8; super::NONCE_LEN]) {
     let subkey = hchacha20(key, &nonce[0..16].try_into().unwrap());
 
     let mut new_nonce = [0u8; super::NONCE_LEN];
     new_nonce[4..].copy_from_slice(&nonce[16..]);
 
-    super::decrypt(&subkey, ptxt, ctxt, aad, &new_nonce)
+    (subkey, new_nonce)
 }
 
 /// Convert the `key` and `nonce` into the subkey.
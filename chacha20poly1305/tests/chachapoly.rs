@@ -275,3 +275,39 @@ fn chachapoly_self_test_rand() {
 
     assert_eq!(msg, &ptxt);
 }
+
+#[test]
+fn aead_trait() {
+    use libcrux_chacha20poly1305::{xchacha20_poly1305::XChaCha20Poly1305, ChaCha20Poly1305};
+    use libcrux_traits::aead::{Aead, AeadError};
+
+    fn round_trip<A: Aead>(key: &A::Key, nonce: &A::Nonce) {
+        for len in [0, 1, 63, 64, 65, 200] {
+            let ptxt: Vec<u8> = (0..len as u8).collect();
+
+            let mut ctxt = vec![0u8; ptxt.len() + A::TAG_LEN];
+            A::encrypt(key, &ptxt, &mut ctxt, b"aad", nonce).unwrap();
+
+            let mut buffer = ptxt.clone();
+            let mut tag = A::Tag::default();
+            A::encrypt_in_place(key, &mut buffer, &mut tag, b"aad", nonce).unwrap();
+            assert_eq!(buffer, ctxt[..ptxt.len()]);
+            assert_eq!(tag.as_ref(), &ctxt[ptxt.len()..]);
+
+            A::decrypt_in_place(key, &mut buffer, &tag, b"aad", nonce).unwrap();
+            assert_eq!(buffer, ptxt);
+
+            let mut decrypted = vec![0u8; ptxt.len()];
+            A::decrypt(key, &mut decrypted, &ctxt, b"aad", nonce).unwrap();
+            assert_eq!(decrypted, ptxt);
+
+            assert_eq!(
+                A::decrypt(key, &mut decrypted, &ctxt, b"other aad", nonce),
+                Err(AeadError::InvalidCiphertext)
+            );
+        }
+    }
+
+    round_trip::<ChaCha20Poly1305>(&[1; 32], &[2; 12]);
+    round_trip::<XChaCha20Poly1305>(&[1; 32], &[2; 24]);
+}
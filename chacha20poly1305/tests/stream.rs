@@ -0,0 +1,207 @@
+use std::io::{Read, Write};
+
+use libcrux_chacha20poly1305::stream::{io::*, *};
+use libcrux_chacha20poly1305::{AeadError, TAG_LEN};
+
+const KEY: [u8; 32] = [0x42; 32];
+
+/// Encrypt `segments` and return the ciphertext segments.
+fn encrypt_segments<A: StreamAead>(
+    nonce_prefix: &A::NoncePrefix,
+    segments: &[&[u8]],
+) -> Vec<Vec<u8>> {
+    let mut encryptor = StreamEncryptor::<A>::new(&KEY, nonce_prefix);
+    let (last, segments) = segments.split_last().unwrap();
+
+    let mut ctxts = vec![];
+    for (i, segment) in segments.iter().enumerate() {
+        let mut ctxt = vec![0u8; segment.len() + TAG_LEN];
+        encryptor
+            .encrypt_next(segment, &mut ctxt, &[i as u8])
+            .unwrap();
+        ctxts.push(ctxt);
+    }
+    let mut ctxt = vec![0u8; last.len() + TAG_LEN];
+    encryptor
+        .encrypt_last(last, &mut ctxt, &[segments.len() as u8])
+        .unwrap();
+    ctxts.push(ctxt);
+
+    ctxts
+}
+
+/// Decrypt `ctxts` and return the plaintext segments.
+fn decrypt_segments<A: StreamAead>(
+    nonce_prefix: &A::NoncePrefix,
+    ctxts: &[Vec<u8>],
+) -> Result<Vec<Vec<u8>>, StreamError> {
+    let mut decryptor = StreamDecryptor::<A>::new(&KEY, nonce_prefix);
+    let (last, ctxts) = ctxts.split_last().unwrap();
+
+    let mut ptxts = vec![];
+    for (i, ctxt) in ctxts.iter().enumerate() {
+        let mut ptxt = vec![0u8; ctxt.len() - TAG_LEN];
+        decryptor.decrypt_next(&mut ptxt, ctxt, &[i as u8])?;
+        ptxts.push(ptxt);
+    }
+    let mut ptxt = vec![0u8; last.len() - TAG_LEN];
+    decryptor.decrypt_last(&mut ptxt, last, &[ctxts.len() as u8])?;
+    ptxts.push(ptxt);
+
+    Ok(ptxts)
+}
+
+fn segments_roundtrip<A: StreamAead>(nonce_prefix: &A::NoncePrefix) {
+    let segments: [&[u8]; 4] = [b"first segment", b"", b"third", b"the last one"];
+    let ctxts = encrypt_segments::<A>(nonce_prefix, &segments);
+    let ptxts = decrypt_segments::<A>(nonce_prefix, &ctxts).unwrap();
+    assert_eq!(ptxts, segments);
+
+    // Reordered segments.
+    let mut reordered = ctxts.clone();
+    reordered.swap(0, 2);
+    assert!(matches!(
+        decrypt_segments::<A>(nonce_prefix, &reordered),
+        Err(StreamError::Aead(AeadError::InvalidCiphertext))
+    ));
+
+    // Truncated at a segment boundary.
+    assert!(matches!(
+        decrypt_segments::<A>(nonce_prefix, &ctxts[..3]),
+        Err(StreamError::Aead(AeadError::InvalidCiphertext))
+    ));
+
+    // Modified segment.
+    let mut modified = ctxts.clone();
+    modified[1][0] ^= 1;
+    assert!(matches!(
+        decrypt_segments::<A>(nonce_prefix, &modified),
+        Err(StreamError::Aead(AeadError::InvalidCiphertext))
+    ));
+}
+
+#[test]
+fn chacha20poly1305_segments() {
+    segments_roundtrip::<ChaCha20Poly1305>(&[7; CHACHA20_POLY1305_NONCE_PREFIX_LEN]);
+}
+
+#[test]
+fn xchacha20poly1305_segments() {
+    segments_roundtrip::<XChaCha20Poly1305>(&[7; XCHACHA20_POLY1305_NONCE_PREFIX_LEN]);
+}
+
+#[test]
+fn segment_nonce() {
+    // The first segment uses the nonce prefix || 0 || 0.
+    let nonce_prefix = [0x11; CHACHA20_POLY1305_NONCE_PREFIX_LEN];
+    let mut encryptor = StreamEncryptor::<ChaCha20Poly1305>::new(&KEY, &nonce_prefix);
+    let mut ctxt = [0u8; 5 + TAG_LEN];
+    encryptor.encrypt_next(b"hello", &mut ctxt, b"").unwrap();
+
+    let mut nonce = [0u8; 12];
+    nonce[..7].copy_from_slice(&nonce_prefix);
+    let mut expected = [0u8; 5 + TAG_LEN];
+    libcrux_chacha20poly1305::encrypt(&KEY, b"hello", &mut expected, b"", &nonce).unwrap();
+    assert_eq!(ctxt, expected);
+
+    // The second one, if it is the last, prefix || 1 || 1.
+    encryptor.encrypt_last(b"world", &mut ctxt, b"").unwrap();
+    nonce[10] = 1;
+    nonce[11] = 1;
+    libcrux_chacha20poly1305::encrypt(&KEY, b"world", &mut expected, b"", &nonce).unwrap();
+    assert_eq!(ctxt, expected);
+}
+
+fn io_encrypt<A: StreamAead>(
+    nonce_prefix: &A::NoncePrefix,
+    segment_len: usize,
+    ptxt: &[u8],
+    chunk_len: usize,
+) -> Vec<u8> {
+    let mut writer =
+        EncryptingWriter::<_, A>::with_segment_len(vec![], &KEY, nonce_prefix, segment_len);
+    for chunk in ptxt.chunks(chunk_len) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.finish().unwrap()
+}
+
+fn io_decrypt<A: StreamAead>(
+    nonce_prefix: &A::NoncePrefix,
+    segment_len: usize,
+    ctxt: &[u8],
+) -> std::io::Result<Vec<u8>> {
+    let mut reader =
+        DecryptingReader::<_, A>::with_segment_len(ctxt, &KEY, nonce_prefix, segment_len);
+    let mut ptxt = vec![];
+    reader.read_to_end(&mut ptxt)?;
+    Ok(ptxt)
+}
+
+fn io_roundtrip<A: StreamAead>(nonce_prefix: &A::NoncePrefix) {
+    let segment_len = 64;
+    let ptxt: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+
+    for len in [0, 1, 63, 64, 65, 128, 129, 1000] {
+        for chunk_len in [1, 7, 64, 1000] {
+            let ctxt = io_encrypt::<A>(nonce_prefix, segment_len, &ptxt[..len], chunk_len);
+
+            // Every segment, including an empty last one, carries a tag.
+            let segments = if len == 0 {
+                1
+            } else {
+                len.div_ceil(segment_len)
+            };
+            assert_eq!(ctxt.len(), len + segments * TAG_LEN);
+
+            let decrypted = io_decrypt::<A>(nonce_prefix, segment_len, &ctxt).unwrap();
+            assert_eq!(decrypted, &ptxt[..len]);
+
+            // Truncating the stream anywhere must be detected.
+            for truncated_len in [0, ctxt.len() / 2, ctxt.len() - 1] {
+                let err =
+                    io_decrypt::<A>(nonce_prefix, segment_len, &ctxt[..truncated_len]).unwrap_err();
+                assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            }
+        }
+    }
+
+    // Dropping a full segment at the end.
+    let ctxt = io_encrypt::<A>(nonce_prefix, segment_len, &ptxt[..128], 1000);
+    let err = io_decrypt::<A>(nonce_prefix, segment_len, &ctxt[..64 + TAG_LEN]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // Decrypting with the wrong segment length.
+    assert!(io_decrypt::<A>(nonce_prefix, 32, &ctxt).is_err());
+}
+
+#[test]
+fn chacha20poly1305_io() {
+    io_roundtrip::<ChaCha20Poly1305>(&[9; CHACHA20_POLY1305_NONCE_PREFIX_LEN]);
+}
+
+#[test]
+fn xchacha20poly1305_io() {
+    io_roundtrip::<XChaCha20Poly1305>(&[9; XCHACHA20_POLY1305_NONCE_PREFIX_LEN]);
+}
+
+#[test]
+fn io_error_is_sticky() {
+    let nonce_prefix = [3; XCHACHA20_POLY1305_NONCE_PREFIX_LEN];
+    let mut ctxt = io_encrypt::<XChaCha20Poly1305>(&nonce_prefix, 16, &[0x55; 20], 20);
+    let last = ctxt.len() - 1;
+    ctxt[last] ^= 1;
+
+    let mut reader = DecryptingReader::<_, XChaCha20Poly1305>::with_segment_len(
+        &ctxt[..],
+        &KEY,
+        &nonce_prefix,
+        16,
+    );
+    let mut buf = [0u8; 64];
+
+    // The first segment is fine.
+    assert_eq!(reader.read(&mut buf).unwrap(), 16);
+    assert!(reader.read(&mut buf).is_err());
+    assert!(reader.read(&mut buf).is_err());
+}
@@ -6,9 +6,10 @@ use libcrux_p256::{
     validate_private_key, validate_public_key,
 };
 
-use crate::DigestAlgorithm;
+use crate::{DigestAlgorithm, RAND_LIMIT};
 
 use ::rand::TryRngCore;
+use libcrux_traits::signature::{SignatureError, Signer, Verifier};
 
 use super::Error;
 
@@ -197,8 +198,6 @@ fn validate_private_key_slice(scalar: &[u8]) -> Result<PrivateKey, Error> {
 /// Prepare the nonce for EcDSA and validate the key
 #[cfg(feature = "rand")]
 pub mod rand {
-    use crate::RAND_LIMIT;
-
     use super::*;
     use ::rand::{CryptoRng, RngCore};
 
@@ -359,3 +358,182 @@ pub fn verify(
         Err(Error::InvalidSignature)
     }
 }
+
+/// The order of the P-256 group.
+const N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// Reduce a 256 bit big endian integer modulo [`N`].
+///
+/// The integer is smaller than `2N`, so a single conditional subtraction is
+/// enough.
+fn reduce_once(value: &[u8; 32]) -> [u8; 32] {
+    let mut difference = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let d = value[i] as i16 - N[i] as i16 - borrow;
+        difference[i] = d as u8;
+        borrow = (d >> 15) & 1;
+    }
+
+    // Keep `value` if the subtraction borrowed, i.e. if `value < N`.
+    let mask = 0u8.wrapping_sub(borrow as u8);
+    let mut reduced = [0u8; 32];
+    for i in 0..32 {
+        reduced[i] = (value[i] & mask) | (difference[i] & !mask);
+    }
+    reduced
+}
+
+/// Write the HMAC with `hash` over the concatenation of `parts` into `out`.
+fn hmac(hash: DigestAlgorithm, key: &[u8], parts: &[&[u8]], out: &mut [u8]) {
+    macro_rules! mac {
+        ($hmac:ty) => {{
+            let mut state = <$hmac>::new(key);
+            for part in parts {
+                state.update(part);
+            }
+            out.copy_from_slice(&state.finalize());
+        }};
+    }
+
+    match hash {
+        DigestAlgorithm::Sha256 => mac!(libcrux_hmac::HmacSha256),
+        DigestAlgorithm::Sha384 => mac!(libcrux_hmac::HmacSha384),
+        DigestAlgorithm::Sha512 => mac!(libcrux_hmac::HmacSha512),
+        DigestAlgorithm::Sha224 => unreachable!("SHA-224 is not used for P-256 signatures"),
+    }
+}
+
+/// Derive the nonce for signing `msg` with `private_key` as in RFC 6979,
+/// Section 3.2, hedged with the `additional_data` of Section 3.6.
+///
+/// With empty `additional_data` this is deterministic RFC 6979.
+fn derive_nonce(
+    hash: DigestAlgorithm,
+    msg: &[u8],
+    private_key: &PrivateKey,
+    additional_data: &[u8],
+) -> Result<Nonce, Error> {
+    let hash_len = hash.hash_len();
+
+    // bits2octets(H(m)): all hashes are at least 256 bits long, so only the
+    // leftmost 256 bits are used.
+    let mut digest = [0u8; 64];
+    hash.hash(msg, &mut digest[..hash_len]);
+    let h1 = reduce_once(digest[..32].try_into().map_err(|_| Error::SigningError)?);
+
+    let mut k = [0u8; 64];
+    let mut v = [1u8; 64];
+    let mut t = [0u8; 64];
+    let (k, v, t) = (&mut k[..hash_len], &mut v[..hash_len], &mut t[..hash_len]);
+
+    for separator in [0u8, 1] {
+        hmac(
+            hash,
+            k,
+            &[v, &[separator], private_key.as_ref(), &h1, additional_data],
+            t,
+        );
+        k.copy_from_slice(t);
+        hmac(hash, k, &[v], t);
+        v.copy_from_slice(t);
+    }
+
+    for _ in 0..RAND_LIMIT {
+        hmac(hash, k, &[v], t);
+        v.copy_from_slice(t);
+
+        // Every HMAC output holds at least the 256 bits of a candidate.
+        if let Ok(nonce) = validate_scalar_slice(&v[..32]) {
+            return Ok(Nonce(nonce));
+        }
+
+        hmac(hash, k, &[v, &[0]], t);
+        k.copy_from_slice(t);
+        hmac(hash, k, &[v], t);
+        v.copy_from_slice(t);
+    }
+
+    Err(Error::SigningError)
+}
+
+/// Sign with a nonce derived with hedged RFC 6979 from the `randomness`.
+fn sign_hedged(
+    hash: DigestAlgorithm,
+    msg: &[u8],
+    ctx: &[u8],
+    private_key: &PrivateKey,
+    randomness: &[u8],
+) -> Result<Signature, SignatureError> {
+    if !ctx.is_empty() {
+        return Err(SignatureError::InvalidContext);
+    }
+    if !randomness.is_empty() && randomness.len() != 32 {
+        return Err(SignatureError::InvalidRandomness);
+    }
+    u32_len(msg).map_err(|_| SignatureError::MessageTooLarge)?;
+
+    let nonce =
+        derive_nonce(hash, msg, private_key, randomness).map_err(|_| SignatureError::Signing)?;
+
+    _sign(hash, msg, private_key, &nonce).map_err(|e| match e {
+        Error::InvalidInput => SignatureError::MessageTooLarge,
+        _ => SignatureError::Signing,
+    })
+}
+
+macro_rules! impl_signature_traits {
+    ($name:ident, $hash:ident, $doc:literal) => {
+        #[doc = concat!("ECDSA on P-256 with ", $doc, " as a [`Signer`] and [`Verifier`].")]
+        ///
+        /// The nonce is derived with hedged RFC 6979 from the private key, the
+        /// message and the 32 bytes of signing randomness, so fixed randomness
+        /// does not leak the key. Empty randomness gives deterministic RFC 6979
+        /// signatures. Contexts are not supported.
+        pub struct $name;
+
+        impl Signer for $name {
+            const RANDOMNESS_LEN: usize = 32;
+
+            type SigningKey = PrivateKey;
+            type Signature = Signature;
+
+            fn sign_with_randomness(
+                key: &PrivateKey,
+                msg: &[u8],
+                ctx: &[u8],
+                randomness: &[u8],
+            ) -> Result<Signature, SignatureError> {
+                sign_hedged(DigestAlgorithm::$hash, msg, ctx, key, randomness)
+            }
+        }
+
+        impl Verifier for $name {
+            type VerificationKey = PublicKey;
+            type Signature = Signature;
+
+            fn verify(
+                key: &PublicKey,
+                msg: &[u8],
+                ctx: &[u8],
+                signature: &Signature,
+            ) -> Result<(), SignatureError> {
+                if !ctx.is_empty() {
+                    return Err(SignatureError::InvalidContext);
+                }
+
+                verify(DigestAlgorithm::$hash, msg, signature, key).map_err(|e| match e {
+                    Error::InvalidInput => SignatureError::MessageTooLarge,
+                    _ => SignatureError::InvalidSignature,
+                })
+            }
+        }
+    };
+}
+
+impl_signature_traits!(EcdsaP256Sha256, Sha256, "SHA-256");
+impl_signature_traits!(EcdsaP256Sha384, Sha384, "SHA-384");
+impl_signature_traits!(EcdsaP256Sha512, Sha512, "SHA-512");
@@ -33,3 +33,88 @@ fn test_self() {
         .expect_err("The message is wrong for the signature");
     assert!(matches!(error, Error::InvalidSignature));
 }
+
+#[test]
+fn signature_traits() {
+    use libcrux_traits::signature::{SignatureError, Signer, Verifier};
+    use p256::{EcdsaP256Sha256, EcdsaP256Sha384};
+
+    let mut rng = rand::rng();
+
+    let sk = PrivateKey::random(&mut rng).unwrap();
+    let mut pk = [0u8; 64];
+    assert!(libcrux_p256::dh_initiator(&mut pk, sk.as_ref()));
+    let pk = PublicKey::try_from(&pk).unwrap();
+    let msg = b"the message to be signed";
+
+    let sig = EcdsaP256Sha256::sign(&sk, msg, b"", &mut rng).unwrap();
+    EcdsaP256Sha256::verify(&pk, msg, b"", &sig).unwrap();
+    assert_eq!(
+        EcdsaP256Sha384::verify(&pk, msg, b"", &sig).unwrap_err(),
+        SignatureError::InvalidSignature
+    );
+
+    // Signing is deterministic for fixed randomness, but different
+    // randomness gives a different nonce.
+    let randomness = [0x2a; 32];
+    let sig = EcdsaP256Sha384::sign_with_randomness(&sk, msg, b"", &randomness).unwrap();
+    let again = EcdsaP256Sha384::sign_with_randomness(&sk, msg, b"", &randomness).unwrap();
+    assert_eq!(sig.as_bytes(), again.as_bytes());
+    EcdsaP256Sha384::verify(&pk, msg, b"", &sig).unwrap();
+    let other = EcdsaP256Sha384::sign_with_randomness(&sk, msg, b"", &[0; 32]).unwrap();
+    assert_ne!(sig.as_bytes().0, other.as_bytes().0);
+    EcdsaP256Sha384::verify(&pk, msg, b"", &other).unwrap();
+
+    assert!(matches!(
+        EcdsaP256Sha256::sign_with_randomness(&sk, msg, b"", &[0; 16]),
+        Err(SignatureError::InvalidRandomness)
+    ));
+    assert!(matches!(
+        EcdsaP256Sha256::sign(&sk, msg, b"context", &mut rng),
+        Err(SignatureError::InvalidContext)
+    ));
+}
+
+#[test]
+fn signature_traits_rfc6979() {
+    use libcrux_traits::signature::{Signer, Verifier};
+    use p256::{EcdsaP256Sha256, EcdsaP256Sha384, EcdsaP256Sha512};
+
+    // From https://tools.ietf.org/html/rfc6979#appendix-A.2.5
+    const PK_HEX: &str = "0460FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB67903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299";
+    const SK_HEX: &str = "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721";
+
+    let pk = PublicKey::try_from(hex_str_to_bytes(PK_HEX).as_slice()).unwrap();
+    let sk = PrivateKey::try_from(&hex_str_to_array::<[u8; 32]>(SK_HEX)).unwrap();
+    let msg = b"sample";
+
+    // Empty randomness gives deterministic RFC 6979 signatures.
+    let check = |(r, s): (&[u8; 32], &[u8; 32]), expected_r: &str, expected_s: &str| {
+        assert_eq!(r, &hex_str_to_array::<[u8; 32]>(expected_r));
+        assert_eq!(s, &hex_str_to_array::<[u8; 32]>(expected_s));
+    };
+
+    let sig = EcdsaP256Sha256::sign_with_randomness(&sk, msg, b"", b"").unwrap();
+    check(
+        sig.as_bytes(),
+        "EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716",
+        "F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8",
+    );
+    EcdsaP256Sha256::verify(&pk, msg, b"", &sig).unwrap();
+
+    let sig = EcdsaP256Sha384::sign_with_randomness(&sk, msg, b"", b"").unwrap();
+    check(
+        sig.as_bytes(),
+        "0EAFEA039B20E9B42309FB1D89E213057CBF973DC0CFC8F129EDDDC800EF7719",
+        "4861F0491E6998B9455193E34E7B0D284DDD7149A74B95B9261F13ABDE940954",
+    );
+    EcdsaP256Sha384::verify(&pk, msg, b"", &sig).unwrap();
+
+    let sig = EcdsaP256Sha512::sign_with_randomness(&sk, msg, b"", b"").unwrap();
+    check(
+        sig.as_bytes(),
+        "8496A60B5E9B47C825488827E0495B0E3FA109EC4568FD3F8D1097678EB97F00",
+        "2362AB1ADBE2B8ADF9CB9EDAB740EA6049C028114F2460F96554F61FAE3302FE",
+    );
+    EcdsaP256Sha512::verify(&pk, msg, b"", &sig).unwrap();
+}
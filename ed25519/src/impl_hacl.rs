@@ -136,3 +136,54 @@ pub fn generate_key_pair(
 
     Ok((SigningKey { value: sk }, VerificationKey { value: pk }))
 }
+
+/// Ed25519 as a [`Signer`](libcrux_traits::signature::Signer) and
+/// [`Verifier`](libcrux_traits::signature::Verifier).
+///
+/// Ed25519 is deterministic and doesn't support contexts.
+pub struct Ed25519;
+
+impl libcrux_traits::signature::Signer for Ed25519 {
+    const RANDOMNESS_LEN: usize = 0;
+
+    type SigningKey = SigningKey;
+    type Signature = [u8; 64];
+
+    fn sign_with_randomness(
+        key: &SigningKey,
+        msg: &[u8],
+        ctx: &[u8],
+        randomness: &[u8],
+    ) -> Result<[u8; 64], libcrux_traits::signature::SignatureError> {
+        use libcrux_traits::signature::SignatureError;
+
+        if !ctx.is_empty() {
+            return Err(SignatureError::InvalidContext);
+        }
+        if !randomness.is_empty() {
+            return Err(SignatureError::InvalidRandomness);
+        }
+
+        sign(msg, key.as_ref()).map_err(|_| SignatureError::MessageTooLarge)
+    }
+}
+
+impl libcrux_traits::signature::Verifier for Ed25519 {
+    type VerificationKey = VerificationKey;
+    type Signature = [u8; 64];
+
+    fn verify(
+        key: &VerificationKey,
+        msg: &[u8],
+        ctx: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<(), libcrux_traits::signature::SignatureError> {
+        use libcrux_traits::signature::SignatureError;
+
+        if !ctx.is_empty() {
+            return Err(SignatureError::InvalidContext);
+        }
+
+        verify(msg, key.as_ref(), signature).map_err(|_| SignatureError::InvalidSignature)
+    }
+}
@@ -16,3 +16,27 @@ fn self_test() {
     let verified = verify(msg, vk.as_ref(), &signature);
     assert!(verified.is_ok());
 }
+
+#[test]
+fn signature_traits() {
+    use libcrux_ed25519::Ed25519;
+    use libcrux_traits::signature::{SignatureError, Signer, Verifier};
+
+    let mut rng = rand::rng();
+
+    let (sk, vk) = generate_key_pair(&mut rng).unwrap();
+    let msg = b"the message to be signed";
+
+    let signature = Ed25519::sign(&sk, msg, b"", &mut rng).unwrap();
+    assert_eq!(signature, sign(msg, sk.as_ref()).unwrap());
+    assert!(Ed25519::verify(&vk, msg, b"", &signature).is_ok());
+
+    assert_eq!(
+        Ed25519::verify(&vk, b"another message", b"", &signature),
+        Err(SignatureError::InvalidSignature)
+    );
+    assert_eq!(
+        Ed25519::sign(&sk, msg, b"context", &mut rng).unwrap_err(),
+        SignatureError::InvalidContext
+    );
+}
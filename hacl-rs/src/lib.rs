@@ -5,7 +5,11 @@
 
 #![no_std]
 
+// The streaming states and the bignum code allocate. Without the `alloc`
+// feature, only the allocation-free parts are available.
+
 // Utility modules. In the generated hacl-rs, these are individual crates.
+#[cfg(feature = "alloc")]
 pub mod bignum;
 pub mod fstar;
 pub mod lowstar;
@@ -17,10 +21,15 @@ pub mod curve25519_51;
 pub mod streaming_types;
 
 pub mod prelude {
+    #[cfg(feature = "alloc")]
     extern crate alloc;
 
+    #[cfg(feature = "alloc")]
     pub use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
     pub use alloc::{vec, vec::Vec};
 
-    pub use crate::{bignum, fstar, lowstar, streaming_types, util as lib};
+    #[cfg(feature = "alloc")]
+    pub use crate::bignum;
+    pub use crate::{fstar, lowstar, streaming_types, util as lib};
 }
@@ -4,6 +4,7 @@
 #![allow(unused_assignments)]
 #![allow(unreachable_patterns)]
 
+#[cfg(feature = "alloc")]
 use crate::prelude::*;
 
 #[derive(PartialEq, Clone, Copy)]
@@ -32,6 +33,7 @@ pub enum error_code {
     MaximumLengthExceeded,
 }
 
+#[cfg(feature = "alloc")]
 #[derive(PartialEq, Clone)]
 pub struct state_32 {
     pub block_state: Box<[u32]>,
@@ -39,6 +41,7 @@ pub struct state_32 {
     pub total_len: u64,
 }
 
+#[cfg(feature = "alloc")]
 #[derive(PartialEq, Clone)]
 pub struct state_64 {
     pub block_state: Box<[u64]>,
@@ -299,7 +299,6 @@ pub fn extract_sha2_512(prk: &mut [u8], salt: &[u8], saltlen: u32, ikm: &[u8], i
     libcrux_hmac::hacl::hmac::compute_sha2_512(prk, salt, saltlen, ikm, ikmlen)
 }
 
-/*
 /**
 Expand pseudorandom key to desired length.
 
@@ -493,4 +492,3 @@ Extract a fixed-length pseudorandom key from input keying material.
 pub fn extract_blake2b_32(prk: &mut [u8], salt: &[u8], saltlen: u32, ikm: &[u8], ikmlen: u32) {
     libcrux_hmac::hacl::hmac::compute_blake2b_32(prk, salt, saltlen, ikm, ikmlen)
 }
-*/
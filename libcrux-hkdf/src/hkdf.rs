@@ -1,6 +1,6 @@
 //! HKDF
 //!
-//! This crate implements HKDF on SHA 1 and SHA 2 (except for SHA 224).
+//! This crate implements HKDF on SHA 2 (except for SHA 224), SHA 3 and BLAKE2.
 #![no_std]
 
 extern crate alloc;
@@ -10,8 +10,10 @@ use alloc::vec::Vec;
 pub mod hacl;
 
 mod impl_hacl;
+mod impl_hmac;
 
-pub use impl_hacl::{HkdfSha2_256, HkdfSha2_384, HkdfSha2_512};
+pub use impl_hacl::{HkdfBlake2b, HkdfBlake2s, HkdfSha2_256, HkdfSha2_384, HkdfSha2_512};
+pub use impl_hmac::{HkdfSha3_224, HkdfSha3_256, HkdfSha3_384, HkdfSha3_512};
 
 pub trait HkdfMode<const HASH_LEN: usize> {
     /// The hash algorithm used in this HKDF mode.
@@ -87,6 +89,14 @@ pub enum Algorithm {
     Sha256,
     Sha384,
     Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    /// HKDF with HMAC-BLAKE2s.
+    Blake2s,
+    /// HKDF with HMAC-BLAKE2b.
+    Blake2b,
 }
 
 impl Algorithm {
@@ -96,6 +106,12 @@ impl Algorithm {
             Algorithm::Sha256 => 32,
             Algorithm::Sha384 => 48,
             Algorithm::Sha512 => 64,
+            Algorithm::Sha3_224 => 28,
+            Algorithm::Sha3_256 => 32,
+            Algorithm::Sha3_384 => 48,
+            Algorithm::Sha3_512 => 64,
+            Algorithm::Blake2s => 32,
+            Algorithm::Blake2b => 64,
         }
     }
 }
@@ -110,6 +126,15 @@ pub enum Error {
     ArgumentsTooLarge,
 }
 
+impl From<Error> for libcrux_traits::kdf::KdfError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::OkmTooLarge => Self::OkmTooLarge,
+            Error::ArgumentsTooLarge => Self::ArgumentsTooLarge,
+        }
+    }
+}
+
 /// HKDF extract using hash function `mode`, `salt`, and the input key material `ikm`.
 /// Returns the pre-key material in a vector of tag length.
 #[inline(always)]
@@ -124,6 +149,12 @@ pub fn extract(
         Algorithm::Sha256 => allocbuf(|prk| HkdfSha2_256::extract(prk, salt, ikm)),
         Algorithm::Sha384 => allocbuf(|prk| HkdfSha2_384::extract(prk, salt, ikm)),
         Algorithm::Sha512 => allocbuf(|prk| HkdfSha2_512::extract(prk, salt, ikm)),
+        Algorithm::Sha3_224 => allocbuf(|prk| HkdfSha3_224::extract(prk, salt, ikm)),
+        Algorithm::Sha3_256 => allocbuf(|prk| HkdfSha3_256::extract(prk, salt, ikm)),
+        Algorithm::Sha3_384 => allocbuf(|prk| HkdfSha3_384::extract(prk, salt, ikm)),
+        Algorithm::Sha3_512 => allocbuf(|prk| HkdfSha3_512::extract(prk, salt, ikm)),
+        Algorithm::Blake2s => allocbuf(|prk| HkdfBlake2s::extract(prk, salt, ikm)),
+        Algorithm::Blake2b => allocbuf(|prk| HkdfBlake2b::extract(prk, salt, ikm)),
     }
 }
 
@@ -143,6 +174,12 @@ pub fn expand(
         Algorithm::Sha256 => HkdfSha2_256::expand_vec(prk, info, okm_len),
         Algorithm::Sha384 => HkdfSha2_384::expand_vec(prk, info, okm_len),
         Algorithm::Sha512 => HkdfSha2_512::expand_vec(prk, info, okm_len),
+        Algorithm::Sha3_224 => HkdfSha3_224::expand_vec(prk, info, okm_len),
+        Algorithm::Sha3_256 => HkdfSha3_256::expand_vec(prk, info, okm_len),
+        Algorithm::Sha3_384 => HkdfSha3_384::expand_vec(prk, info, okm_len),
+        Algorithm::Sha3_512 => HkdfSha3_512::expand_vec(prk, info, okm_len),
+        Algorithm::Blake2s => HkdfBlake2s::expand_vec(prk, info, okm_len),
+        Algorithm::Blake2b => HkdfBlake2b::expand_vec(prk, info, okm_len),
     }
 }
 
@@ -166,6 +203,12 @@ pub fn hkdf(
         Algorithm::Sha256 => HkdfSha2_256::hkdf_vec(salt, ikm, info, okm_len),
         Algorithm::Sha384 => HkdfSha2_384::hkdf_vec(salt, ikm, info, okm_len),
         Algorithm::Sha512 => HkdfSha2_512::hkdf_vec(salt, ikm, info, okm_len),
+        Algorithm::Sha3_224 => HkdfSha3_224::hkdf_vec(salt, ikm, info, okm_len),
+        Algorithm::Sha3_256 => HkdfSha3_256::hkdf_vec(salt, ikm, info, okm_len),
+        Algorithm::Sha3_384 => HkdfSha3_384::hkdf_vec(salt, ikm, info, okm_len),
+        Algorithm::Sha3_512 => HkdfSha3_512::hkdf_vec(salt, ikm, info, okm_len),
+        Algorithm::Blake2s => HkdfBlake2s::hkdf_vec(salt, ikm, info, okm_len),
+        Algorithm::Blake2b => HkdfBlake2b::hkdf_vec(salt, ikm, info, okm_len),
     }
 }
 
@@ -11,6 +11,7 @@ macro_rules! impl_hkdf {
         pub mod $name {
             use super::{checked_u32, $struct_name, Algorithm, Error, HkdfMode};
             use libcrux_hacl_rs::prelude::*;
+            use libcrux_traits::kdf::{Kdf, KdfError};
 
             impl HkdfMode<$hash_len> for $struct_name {
                 const MODE: Algorithm = $mode;
@@ -39,6 +40,22 @@ macro_rules! impl_hkdf {
                 }
             }
 
+            impl Kdf for $struct_name {
+                const PRK_LEN: usize = $hash_len;
+
+                type Prk = [u8; $hash_len];
+
+                fn extract(salt: &[u8], ikm: &[u8]) -> Result<[u8; $hash_len], KdfError> {
+                    let mut prk = [0u8; $hash_len];
+                    extract(&mut prk, salt, ikm)?;
+                    Ok(prk)
+                }
+
+                fn expand(okm: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), KdfError> {
+                    Ok(expand_into(okm, prk, info)?)
+                }
+            }
+
             /// HKDF extract using the `salt` and the input key material `ikm`.
             /// The result is written to `prk`.
             ///
@@ -96,21 +113,28 @@ macro_rules! impl_hkdf {
             /// Returns [`Error::ArgumentsTooLarge`] if `prk` or `info` is longer than [`u32::MAX`] bytes.
             #[inline(always)]
             pub fn expand_vec(prk: &[u8], info: &[u8], okm_len: usize) -> Result<Vec<u8>, Error> {
-                if okm_len > 255 * $hash_len {
+                let mut okm = vec![0u8; okm_len];
+                expand_into(&mut okm, prk, info)?;
+                Ok(okm)
+            }
+
+            /// HKDF expand into a slice `okm` of any length.
+            #[inline(always)]
+            fn expand_into(okm: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), Error> {
+                if okm.len() > 255 * $hash_len {
                     // Output size is too large. HACL doesn't catch this.
                     return Err(Error::OkmTooLarge);
                 }
 
-                let mut okm = vec![0u8; okm_len];
                 crate::hacl::$expand(
-                    &mut okm,
+                    okm,
                     prk,
                     checked_u32(prk.len())?,
                     info,
                     checked_u32(info.len())?,
-                    checked_u32(okm_len)?,
+                    checked_u32(okm.len())?,
                 );
-                Ok(okm)
+                Ok(())
             }
 
             /// HKDF using the `salt`, input key material `ikm`, `info`.
@@ -186,6 +210,26 @@ impl_hkdf!(
     64
 );
 
+impl_hkdf!(
+    HkdfBlake2s,
+    blake2s,
+    "BLAKE2s",
+    Algorithm::Blake2s,
+    extract_blake2s_32,
+    expand_blake2s_32,
+    32
+);
+
+impl_hkdf!(
+    HkdfBlake2b,
+    blake2b,
+    "BLAKE2b",
+    Algorithm::Blake2b,
+    extract_blake2b_32,
+    expand_blake2b_32,
+    64
+);
+
 fn checked_u32(num: usize) -> Result<u32, Error> {
     num.try_into().map_err(|_| Error::ArgumentsTooLarge)
 }
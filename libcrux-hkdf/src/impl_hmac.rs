@@ -0,0 +1,112 @@
+//! HKDF on top of the streaming HMAC of `libcrux-hmac`, for the SHA-3 hash
+//! functions that HACL doesn't provide HKDF for.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use libcrux_traits::kdf::{Kdf, KdfError};
+
+use crate::{Algorithm, Error, HkdfMode};
+
+macro_rules! impl_hkdf {
+    ($struct_name:ident, $string_name:literal, $mode:path, $hmac:ty, $hash_len:literal) => {
+        #[doc = "Implementation of HKDF backed by"]
+        #[doc = $string_name]
+        pub struct $struct_name;
+
+        impl $struct_name {
+            fn expand_into(okm: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), Error> {
+                if okm.len() > 255 * $hash_len {
+                    return Err(Error::OkmTooLarge);
+                }
+
+                let mac = <$hmac>::new(prk);
+                let mut t = [0u8; $hash_len];
+                for (i, okm) in okm.chunks_mut($hash_len).enumerate() {
+                    let mut mac = mac.clone();
+                    if i > 0 {
+                        mac.update(&t);
+                    }
+                    t = mac.update(info).update(&[i as u8 + 1]).finalize();
+                    okm.copy_from_slice(&t[..okm.len()]);
+                }
+                Ok(())
+            }
+        }
+
+        impl HkdfMode<$hash_len> for $struct_name {
+            const MODE: Algorithm = $mode;
+
+            #[inline(always)]
+            fn extract(prk: &mut [u8; $hash_len], salt: &[u8], ikm: &[u8]) -> Result<(), Error> {
+                // An empty salt is the same as a salt of zeroes, because
+                // HMAC pads the key with zeroes.
+                *prk = <$hmac>::new(salt).update(ikm).finalize();
+                Ok(())
+            }
+
+            #[inline(always)]
+            fn expand<const OKM_LEN: usize>(
+                okm: &mut [u8; OKM_LEN],
+                prk: &[u8],
+                info: &[u8],
+            ) -> Result<(), Error> {
+                Self::expand_into(okm, prk, info)
+            }
+
+            #[inline(always)]
+            fn expand_vec(prk: &[u8], info: &[u8], okm_len: usize) -> Result<Vec<u8>, Error> {
+                if okm_len > 255 * $hash_len {
+                    return Err(Error::OkmTooLarge);
+                }
+
+                let mut okm = vec![0u8; okm_len];
+                Self::expand_into(&mut okm, prk, info)?;
+                Ok(okm)
+            }
+        }
+
+        impl Kdf for $struct_name {
+            const PRK_LEN: usize = $hash_len;
+
+            type Prk = [u8; $hash_len];
+
+            fn extract(salt: &[u8], ikm: &[u8]) -> Result<[u8; $hash_len], KdfError> {
+                Ok(<$hmac>::new(salt).update(ikm).finalize())
+            }
+
+            fn expand(okm: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), KdfError> {
+                Ok(Self::expand_into(okm, prk, info)?)
+            }
+        }
+    };
+}
+
+impl_hkdf!(
+    HkdfSha3_224,
+    "SHA3-224",
+    Algorithm::Sha3_224,
+    libcrux_hmac::HmacSha3_224,
+    28
+);
+impl_hkdf!(
+    HkdfSha3_256,
+    "SHA3-256",
+    Algorithm::Sha3_256,
+    libcrux_hmac::HmacSha3_256,
+    32
+);
+impl_hkdf!(
+    HkdfSha3_384,
+    "SHA3-384",
+    Algorithm::Sha3_384,
+    libcrux_hmac::HmacSha3_384,
+    48
+);
+impl_hkdf!(
+    HkdfSha3_512,
+    "SHA3-512",
+    Algorithm::Sha3_512,
+    libcrux_hmac::HmacSha3_512,
+    64
+);
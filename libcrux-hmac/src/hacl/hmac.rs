@@ -6,6 +6,7 @@
 
 //! This module contains generated hacl code.
 
+use libcrux_blake2::hacl::{hash_blake2b, hash_blake2s};
 use libcrux_hacl_rs::prelude::*;
 use libcrux_macros as krml;
 use libcrux_sha2::hacl as hash_sha2;
@@ -480,8 +481,6 @@ pub fn compute_sha2_512(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8], d
     hash_sha2::sha512_finish(s, dst)
 }
 
-/* no blake2 for now
-
 /**
 Write the HMAC-BLAKE2s MAC of a message (`data`) by using a key (`key`) into `dst`.
 
@@ -498,7 +497,7 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
     if key_len <= 64u32 {
         (zeroes.0[0usize..key_len as usize]).copy_from_slice(&key[0usize..key_len as usize])
     } else {
-        crate::hash_blake2s::hash_with_key(zeroes.0, 32u32, key, key_len, &[], 0u32)
+        hash_blake2s::hash_with_key(zeroes.0, 32u32, key, key_len, &[], 0u32)
     };
     let mut ipad: Box<[u8]> = vec![0x36u8; l as usize].into_boxed_slice();
     for i in 0u32..l {
@@ -513,11 +512,11 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         (&mut opad)[i as usize] = xi ^ yi
     }
     let mut s: [u32; 16] = [0u32; 16usize];
-    crate::hash_blake2s::init(&mut s, 0u32, 32u32);
+    hash_blake2s::init(&mut s, 0u32, 32u32);
     let s0: &mut [u32] = &mut s;
     if data_len == 0u32 {
         let mut wv: [u32; 16] = [0u32; 16usize];
-        crate::hash_blake2s::update_last(64u32, &mut wv, s0, false, 0u64, 64u32, &ipad)
+        hash_blake2s::update_last(64u32, &mut wv, s0, false, 0u64, 64u32, &ipad)
     } else {
         let block_len: u32 = 64u32;
         let n_blocks: u32 = data_len.wrapping_div(block_len);
@@ -540,9 +539,9 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         let full_blocks: (&[u8], &[u8]) = data.split_at(0usize);
         let rem0: (&[u8], &[u8]) = full_blocks.1.split_at(full_blocks_len as usize);
         let mut wv: [u32; 16] = [0u32; 16usize];
-        crate::hash_blake2s::update_multi(64u32, &mut wv, s0, 0u64, &ipad, 1u32);
+        hash_blake2s::update_multi(64u32, &mut wv, s0, 0u64, &ipad, 1u32);
         let mut wv0: [u32; 16] = [0u32; 16usize];
-        crate::hash_blake2s::update_multi(
+        hash_blake2s::update_multi(
             n_blocks0.wrapping_mul(64u32),
             &mut wv0,
             s0,
@@ -551,7 +550,7 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
             n_blocks0,
         );
         let mut wv1: [u32; 16] = [0u32; 16usize];
-        crate::hash_blake2s::update_last(
+        hash_blake2s::update_last(
             rem_len,
             &mut wv1,
             s0,
@@ -562,9 +561,9 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         )
     };
     let dst1: (&mut [u8], &mut [u8]) = ipad.split_at_mut(0usize);
-    crate::hash_blake2s::finish(32u32, dst1.1, s0);
+    hash_blake2s::finish(32u32, dst1.1, s0);
     let hash1: (&[u8], &[u8]) = dst1.1.split_at(0usize);
-    crate::hash_blake2s::init(s0, 0u32, 32u32);
+    hash_blake2s::init(s0, 0u32, 32u32);
     let block_len: u32 = 64u32;
     let n_blocks: u32 = 32u32.wrapping_div(block_len);
     let rem: u32 = 32u32.wrapping_rem(block_len);
@@ -586,9 +585,9 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
     let full_blocks: (&[u8], &[u8]) = hash1.1.split_at(0usize);
     let rem0: (&[u8], &[u8]) = full_blocks.1.split_at(full_blocks_len as usize);
     let mut wv: [u32; 16] = [0u32; 16usize];
-    crate::hash_blake2s::update_multi(64u32, &mut wv, s0, 0u64, &opad, 1u32);
+    hash_blake2s::update_multi(64u32, &mut wv, s0, 0u64, &opad, 1u32);
     let mut wv0: [u32; 16] = [0u32; 16usize];
-    crate::hash_blake2s::update_multi(
+    hash_blake2s::update_multi(
         n_blocks0.wrapping_mul(64u32),
         &mut wv0,
         s0,
@@ -597,7 +596,7 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         n_blocks0,
     );
     let mut wv1: [u32; 16] = [0u32; 16usize];
-    crate::hash_blake2s::update_last(
+    hash_blake2s::update_last(
         rem_len,
         &mut wv1,
         s0,
@@ -606,7 +605,7 @@ pub fn compute_blake2s_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         rem_len,
         rem0.1,
     );
-    crate::hash_blake2s::finish(32u32, dst, s0)
+    hash_blake2s::finish(32u32, dst, s0)
 }
 
 /**
@@ -625,7 +624,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
     if key_len <= 128u32 {
         (zeroes.0[0usize..key_len as usize]).copy_from_slice(&key[0usize..key_len as usize])
     } else {
-        crate::hash_blake2b::hash_with_key(zeroes.0, 64u32, key, key_len, &[], 0u32)
+        hash_blake2b::hash_with_key(zeroes.0, 64u32, key, key_len, &[], 0u32)
     };
     let mut ipad: Box<[u8]> = vec![0x36u8; l as usize].into_boxed_slice();
     for i in 0u32..l {
@@ -640,11 +639,11 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         (&mut opad)[i as usize] = xi ^ yi
     }
     let mut s: [u64; 16] = [0u64; 16usize];
-    crate::hash_blake2b::init(&mut s, 0u32, 64u32);
+    hash_blake2b::init(&mut s, 0u32, 64u32);
     let s0: &mut [u64] = &mut s;
     if data_len == 0u32 {
         let mut wv: [u64; 16] = [0u64; 16usize];
-        crate::hash_blake2b::update_last(
+        hash_blake2b::update_last(
             128u32,
             &mut wv,
             s0,
@@ -675,7 +674,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         let full_blocks: (&[u8], &[u8]) = data.split_at(0usize);
         let rem0: (&[u8], &[u8]) = full_blocks.1.split_at(full_blocks_len as usize);
         let mut wv: [u64; 16] = [0u64; 16usize];
-        crate::hash_blake2b::update_multi(
+        hash_blake2b::update_multi(
             128u32,
             &mut wv,
             s0,
@@ -684,7 +683,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
             1u32,
         );
         let mut wv0: [u64; 16] = [0u64; 16usize];
-        crate::hash_blake2b::update_multi(
+        hash_blake2b::update_multi(
             n_blocks0.wrapping_mul(128u32),
             &mut wv0,
             s0,
@@ -693,7 +692,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
             n_blocks0,
         );
         let mut wv1: [u64; 16] = [0u64; 16usize];
-        crate::hash_blake2b::update_last(
+        hash_blake2b::update_last(
             rem_len,
             &mut wv1,
             s0,
@@ -707,9 +706,9 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         )
     };
     let dst1: (&mut [u8], &mut [u8]) = ipad.split_at_mut(0usize);
-    crate::hash_blake2b::finish(64u32, dst1.1, s0);
+    hash_blake2b::finish(64u32, dst1.1, s0);
     let hash1: (&[u8], &[u8]) = dst1.1.split_at(0usize);
-    crate::hash_blake2b::init(s0, 0u32, 64u32);
+    hash_blake2b::init(s0, 0u32, 64u32);
     let block_len: u32 = 128u32;
     let n_blocks: u32 = 64u32.wrapping_div(block_len);
     let rem: u32 = 64u32.wrapping_rem(block_len);
@@ -731,7 +730,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
     let full_blocks: (&[u8], &[u8]) = hash1.1.split_at(0usize);
     let rem0: (&[u8], &[u8]) = full_blocks.1.split_at(full_blocks_len as usize);
     let mut wv: [u64; 16] = [0u64; 16usize];
-    crate::hash_blake2b::update_multi(
+    hash_blake2b::update_multi(
         128u32,
         &mut wv,
         s0,
@@ -740,7 +739,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         1u32,
     );
     let mut wv0: [u64; 16] = [0u64; 16usize];
-    crate::hash_blake2b::update_multi(
+    hash_blake2b::update_multi(
         n_blocks0.wrapping_mul(128u32),
         &mut wv0,
         s0,
@@ -749,7 +748,7 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         n_blocks0,
     );
     let mut wv1: [u64; 16] = [0u64; 16usize];
-    crate::hash_blake2b::update_last(
+    hash_blake2b::update_last(
         rem_len,
         &mut wv1,
         s0,
@@ -761,6 +760,5 @@ pub fn compute_blake2b_32(dst: &mut [u8], key: &[u8], key_len: u32, data: &[u8],
         rem_len,
         rem0.1,
     );
-    crate::hash_blake2b::finish(64u32, dst, s0)
+    hash_blake2b::finish(64u32, dst, s0)
 }
-*/
@@ -0,0 +1,110 @@
+//! Hash states implementing [`Digest`] for the streaming HMAC.
+//!
+//! The SHA-1 state is the streaming state of the hacl SHA-1, which allocates.
+//! The SHA-2 states are the hacl streaming types of `libcrux-sha2`, or its
+//! `no_alloc` types without the `alloc` feature. The BLAKE2 states wrap the
+//! hashers of `libcrux-blake2`, which allocate.
+
+#[cfg(not(feature = "alloc"))]
+pub(crate) use libcrux_sha2::no_alloc::{Sha256, Sha384, Sha512};
+#[cfg(feature = "alloc")]
+pub(crate) use libcrux_sha2::{Sha256, Sha384, Sha512};
+
+#[cfg(feature = "alloc")]
+use libcrux_blake2::{Blake2bBuilder, Blake2sBuilder, ConstKeyLenConstDigestLen};
+#[cfg(feature = "alloc")]
+use libcrux_traits::Digest;
+
+#[cfg(feature = "alloc")]
+use libcrux_hacl_rs::{prelude::*, streaming_types::state_32};
+
+/// The streaming state of the hacl SHA-1.
+///
+/// This is not the collision detecting SHA-1 of `libcrux-sha1`, which changes
+/// the digest of attack blocks, so that HMAC-SHA1 stays interoperable.
+#[cfg(feature = "alloc")]
+pub(crate) struct Sha1(Box<[state_32]>);
+
+#[cfg(feature = "alloc")]
+impl Digest<20> for Sha1 {
+    fn hash(digest: &mut [u8], payload: &[u8]) {
+        let mut state = Self::default();
+        state.update(payload);
+        state.finish(digest.try_into().unwrap());
+    }
+
+    fn update(&mut self, payload: &[u8]) {
+        for chunk in payload.chunks(u32::MAX as usize) {
+            // The only error is exceeding the maximum input length of
+            // 2^61 - 1 bytes.
+            let result = crate::hacl::hash_sha1::update0(&mut self.0, chunk, chunk.len() as u32);
+            assert!(result == libcrux_hacl_rs::streaming_types::error_code::Success);
+        }
+    }
+
+    fn finish(&self, digest: &mut [u8; 20]) {
+        crate::hacl::hash_sha1::digest(&self.0, digest);
+    }
+
+    fn reset(&mut self) {
+        crate::hacl::hash_sha1::reset(&mut self.0);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self(crate::hacl::hash_sha1::malloc())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Clone for Sha1 {
+    fn clone(&self) -> Self {
+        Self(crate::hacl::hash_sha1::copy(&self.0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_blake2_state {
+    ($name:ident, $hasher:ident, $builder:ident, $digest_len:literal) => {
+        #[derive(Clone)]
+        pub(crate) struct $name(libcrux_blake2::$hasher<ConstKeyLenConstDigestLen<0, $digest_len>>);
+
+        impl Digest<$digest_len> for $name {
+            fn hash(digest: &mut [u8], payload: &[u8]) {
+                let mut state = Self::default();
+                state.update(payload);
+                state.finish(digest.try_into().unwrap());
+            }
+
+            fn update(&mut self, payload: &[u8]) {
+                for chunk in payload.chunks(u32::MAX as usize) {
+                    // The only other error is exceeding the maximum input
+                    // length of at least 2^64 bytes.
+                    self.0.update(chunk).unwrap();
+                }
+            }
+
+            fn finish(&self, digest: &mut [u8; $digest_len]) {
+                self.0.finalize(digest);
+            }
+
+            fn reset(&mut self) {
+                self.0.reset();
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                // The digest length is valid.
+                Self($builder::new_unkeyed().build_const_digest_len().unwrap())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_blake2_state!(Blake2s, Blake2s, Blake2sBuilder, 32);
+#[cfg(feature = "alloc")]
+impl_blake2_state!(Blake2b, Blake2b, Blake2bBuilder, 64);
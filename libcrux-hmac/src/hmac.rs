@@ -1,13 +1,20 @@
 //! HMAC
 //!
-//! This crate implements HMAC on SHA 1 and SHA 2 (except for SHA 224).
+//! This crate implements HMAC on SHA 1, SHA 2 (except for SHA 224), SHA 3 and
+//! BLAKE2.
+//!
+//! The streaming types, e.g. [`HmacSha256`], check tags in constant time.
+//! The ones on SHA 2 and SHA 3 don't allocate, and are the only ones available
+//! without the default `alloc` feature.
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-#[cfg(not(feature = "expose-hacl"))]
+#[cfg(all(feature = "alloc", not(feature = "expose-hacl")))]
 mod hacl {
     pub(crate) mod hash_sha1;
     pub(crate) mod hmac;
@@ -19,9 +26,14 @@ pub mod hacl {
     pub mod hmac;
 }
 
+mod hash_state;
+#[cfg(feature = "alloc")]
 mod impl_hacl;
+mod streaming;
 
+#[cfg(feature = "alloc")]
 pub use impl_hacl::*;
+pub use streaming::*;
 
 /// The HMAC algorithm defining the used hash function.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -32,6 +44,23 @@ pub enum Algorithm {
     Sha256,
     Sha384,
     Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    /// HMAC-BLAKE2s with 32 byte tags.
+    Blake2s,
+    /// HMAC-BLAKE2b with 64 byte tags.
+    Blake2b,
+}
+
+/// HMAC errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The tag doesn't match.
+    InvalidTag,
+    /// The tag is too short or too long.
+    InvalidTagLength,
 }
 
 /// Get the tag size for a given algorithm.
@@ -41,13 +70,21 @@ pub const fn tag_size(alg: Algorithm) -> usize {
         Algorithm::Sha256 => 32,
         Algorithm::Sha384 => 48,
         Algorithm::Sha512 => 64,
+        Algorithm::Sha3_224 => 28,
+        Algorithm::Sha3_256 => 32,
+        Algorithm::Sha3_384 => 48,
+        Algorithm::Sha3_512 => 64,
+        Algorithm::Blake2s => 32,
+        Algorithm::Blake2b => 64,
     }
 }
 
 /// Compute the HMAC value with the given `alg` and `key` on `data` with an
 /// output tag length of `tag_length`.
 /// Returns a vector of length `tag_length`.
-/// Panics if either `key` or `data` are longer than `u32::MAX`.
+/// Panics if either `key` or `data` are longer than `u32::MAX` for SHA 1, SHA 2
+/// and BLAKE2.
+#[cfg(feature = "alloc")]
 pub fn hmac(alg: Algorithm, key: &[u8], data: &[u8], tag_length: Option<usize>) -> Vec<u8> {
     let native_tag_length = tag_size(alg);
     let tag_length = match tag_length {
@@ -59,11 +96,18 @@ pub fn hmac(alg: Algorithm, key: &[u8], data: &[u8], tag_length: Option<usize>)
         Algorithm::Sha256 => wrap_bufalloc(|buf| hmac_sha2_256(buf, key, data)),
         Algorithm::Sha384 => wrap_bufalloc(|buf| hmac_sha2_384(buf, key, data)),
         Algorithm::Sha512 => wrap_bufalloc(|buf| hmac_sha2_512(buf, key, data)),
+        Algorithm::Sha3_224 => HmacSha3_224::new(key).update(data).finalize().to_vec(),
+        Algorithm::Sha3_256 => HmacSha3_256::new(key).update(data).finalize().to_vec(),
+        Algorithm::Sha3_384 => HmacSha3_384::new(key).update(data).finalize().to_vec(),
+        Algorithm::Sha3_512 => HmacSha3_512::new(key).update(data).finalize().to_vec(),
+        Algorithm::Blake2s => wrap_bufalloc(|buf| hmac_blake2s(buf, key, data)),
+        Algorithm::Blake2b => wrap_bufalloc(|buf| hmac_blake2b(buf, key, data)),
     };
     dst.truncate(tag_length);
     dst
 }
 
+#[cfg(feature = "alloc")]
 #[inline(always)]
 fn wrap_bufalloc<const N: usize, F: Fn(&mut [u8; N])>(f: F) -> Vec<u8> {
     let mut buf = [0u8; N];
@@ -21,3 +21,5 @@ impl_hmac!(hmac_sha1, crate::hacl::hmac::compute_sha1, 20);
 impl_hmac!(hmac_sha2_256, crate::hacl::hmac::compute_sha2_256, 32);
 impl_hmac!(hmac_sha2_384, crate::hacl::hmac::compute_sha2_384, 48);
 impl_hmac!(hmac_sha2_512, crate::hacl::hmac::compute_sha2_512, 64);
+impl_hmac!(hmac_blake2s, crate::hacl::hmac::compute_blake2s_32, 32);
+impl_hmac!(hmac_blake2b, crate::hacl::hmac::compute_blake2b_32, 64);
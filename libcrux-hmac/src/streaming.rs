@@ -0,0 +1,172 @@
+//! Streaming HMAC.
+//!
+//! The streaming types don't limit the length of the data. Except for the
+//! SHA-1 and BLAKE2 based ones, which require the `alloc` feature, they don't
+//! allocate either. They also implement the one-shot [`Mac`] trait.
+
+use libcrux_traits::{
+    mac::{Mac, MacError},
+    Digest,
+};
+
+use crate::{hash_state, Error};
+
+// These are crude attempts to prevent LLVM from optimizing the tag comparison
+// into a comparison that exits early. This is not guaranteed to work.
+
+/// Return 1 if `value` is not zero and 0 otherwise.
+#[inline(never)]
+fn is_non_zero(value: u8) -> u8 {
+    let value = value as u16;
+    core::hint::black_box(((!value).wrapping_add(1) >> 8) as u8 & 1)
+}
+
+/// Return 1 if the bytes of `lhs` and `rhs` do not exactly match and 0
+/// otherwise, without branching on the bytes.
+#[inline(never)]
+fn compare_in_constant_time(lhs: &[u8], rhs: &[u8]) -> u8 {
+    debug_assert_eq!(lhs.len(), rhs.len());
+
+    let mut r = 0u8;
+    for (l, r_) in lhs.iter().zip(rhs.iter()) {
+        r |= l ^ r_;
+    }
+    core::hint::black_box(is_non_zero(r))
+}
+
+macro_rules! impl_streaming_hmac {
+    ($name:ident, $alg:literal, $state:ty, $block_len:literal, $tag_len:literal) => {
+        #[doc = concat!("Streaming HMAC-", $alg, ".")]
+        ///
+        /// The state is initialized with the key, and the data is added with
+        /// `update`.
+        #[derive(Clone)]
+        pub struct $name {
+            inner: $state,
+            outer: $state,
+        }
+
+        impl $name {
+            /// The length of the tag in bytes.
+            pub const TAG_LENGTH: usize = $tag_len;
+
+            /// Initialize a new HMAC state with the `key`.
+            pub fn new(key: &[u8]) -> Self {
+                let mut key_block = [0u8; $block_len];
+                if key.len() > $block_len {
+                    let mut digest = <$state>::default();
+                    digest.update(key);
+                    digest.finish((&mut key_block[..$tag_len]).try_into().unwrap());
+                } else {
+                    key_block[..key.len()].copy_from_slice(key);
+                }
+
+                let mut inner = <$state>::default();
+                inner.update(&key_block.map(|k| k ^ 0x36));
+                let mut outer = <$state>::default();
+                outer.update(&key_block.map(|k| k ^ 0x5c));
+
+                Self { inner, outer }
+            }
+
+            /// Add the `data` to the MAC.
+            pub fn update(&mut self, data: &[u8]) -> &mut Self {
+                self.inner.update(data);
+                self
+            }
+
+            /// Get the tag.
+            ///
+            /// Note that the state can be continued to be used, to extend the
+            /// data.
+            pub fn finalize(&self) -> [u8; $tag_len] {
+                let mut inner_digest = [0u8; $tag_len];
+                self.inner.finish(&mut inner_digest);
+
+                let mut outer = self.outer.clone();
+                outer.update(&inner_digest);
+                let mut tag = [0u8; $tag_len];
+                outer.finish(&mut tag);
+                tag
+            }
+
+            /// Check the `tag` in constant time.
+            pub fn verify(&self, tag: &[u8; $tag_len]) -> Result<(), Error> {
+                self.verify_truncated(tag)
+            }
+
+            /// Check the truncated `tag` in constant time.
+            ///
+            /// The tag is compared to the leftmost bytes of the tag. Following
+            /// RFC 2104, it must be at least half as long as the tag and
+            /// no shorter than 10 bytes.
+            pub fn verify_truncated(&self, tag: &[u8]) -> Result<(), Error> {
+                if tag.len() > $tag_len || tag.len() < core::cmp::max($tag_len / 2, 10) {
+                    return Err(Error::InvalidTagLength);
+                }
+
+                let expected = self.finalize();
+                if compare_in_constant_time(&expected[..tag.len()], tag) == 0 {
+                    Ok(())
+                } else {
+                    Err(Error::InvalidTag)
+                }
+            }
+        }
+
+        impl Mac for $name {
+            const TAG_LEN: usize = $tag_len;
+
+            type Tag = [u8; $tag_len];
+
+            fn mac(key: &[u8], data: &[u8]) -> [u8; $tag_len] {
+                Self::new(key).update(data).finalize()
+            }
+
+            fn verify(key: &[u8], data: &[u8], tag: &[u8; $tag_len]) -> Result<(), MacError> {
+                Self::new(key)
+                    .update(data)
+                    .verify(tag)
+                    .map_err(|_| MacError::InvalidTag)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_streaming_hmac!(HmacSha1, "SHA1", hash_state::Sha1, 64, 20);
+impl_streaming_hmac!(HmacSha256, "SHA256", hash_state::Sha256, 64, 32);
+impl_streaming_hmac!(HmacSha384, "SHA384", hash_state::Sha384, 128, 48);
+impl_streaming_hmac!(HmacSha512, "SHA512", hash_state::Sha512, 128, 64);
+impl_streaming_hmac!(
+    HmacSha3_224,
+    "SHA3-224",
+    libcrux_sha3::portable::incremental::Sha3_224,
+    144,
+    28
+);
+impl_streaming_hmac!(
+    HmacSha3_256,
+    "SHA3-256",
+    libcrux_sha3::portable::incremental::Sha3_256,
+    136,
+    32
+);
+impl_streaming_hmac!(
+    HmacSha3_384,
+    "SHA3-384",
+    libcrux_sha3::portable::incremental::Sha3_384,
+    104,
+    48
+);
+impl_streaming_hmac!(
+    HmacSha3_512,
+    "SHA3-512",
+    libcrux_sha3::portable::incremental::Sha3_512,
+    72,
+    64
+);
+#[cfg(feature = "alloc")]
+impl_streaming_hmac!(HmacBlake2s, "BLAKE2s", hash_state::Blake2s, 64, 32);
+#[cfg(feature = "alloc")]
+impl_streaming_hmac!(HmacBlake2b, "BLAKE2b", hash_state::Blake2b, 128, 64);
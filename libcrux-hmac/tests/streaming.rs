@@ -0,0 +1,243 @@
+use libcrux_hmac::{
+    hacl, hmac, Algorithm, Error, HmacBlake2b, HmacBlake2s, HmacSha1, HmacSha256, HmacSha384,
+    HmacSha512,
+};
+
+/// Test cases 1, 2 and 6 from RFC 4231 and the SHA-1 tags from RFC 2202.
+///
+/// The key of test case 6 of RFC 2202 is 80 bytes long instead of 131.
+#[allow(clippy::type_complexity)]
+const KATS: [(&[u8], usize, &[u8], [&str; 4]); 3] = [
+    (
+        &[0x0b],
+        20,
+        b"Hi There",
+        [
+            "b617318655057264e28bc0b6fb378c8ef146be00",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        ],
+    ),
+    (
+        b"Jefe",
+        1,
+        b"what do ya want for nothing?",
+        [
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        ],
+    ),
+    (
+        &[0xaa],
+        131,
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        [
+            "",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+        ],
+    ),
+];
+
+#[test]
+fn streaming_kats() {
+    for (key, repeat, data, tags) in KATS {
+        let key = key.repeat(repeat);
+
+        if !tags[0].is_empty() {
+            assert_eq!(
+                hex::encode(HmacSha1::new(&key).update(data).finalize()),
+                tags[0]
+            );
+        }
+        assert_eq!(
+            hex::encode(HmacSha256::new(&key).update(data).finalize()),
+            tags[1]
+        );
+        assert_eq!(
+            hex::encode(HmacSha384::new(&key).update(data).finalize()),
+            tags[2]
+        );
+        assert_eq!(
+            hex::encode(HmacSha512::new(&key).update(data).finalize()),
+            tags[3]
+        );
+    }
+
+    let key = [0xaa; 80];
+    let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+    assert_eq!(
+        hex::encode(HmacSha1::new(&key).update(data).finalize()),
+        "aa4ae5e15272d00e95705637ce8a3b55ed402112"
+    );
+}
+
+/// HMAC-SHA1 with the first SHAttered PDF prefix as key.
+///
+/// The key is longer than a block and hashed first. The collision detecting
+/// SHA-1 of `libcrux-sha1` would change its digest, so this checks that the
+/// plain SHA-1 is used. The tag is from Python's `hmac` module.
+#[test]
+fn sha1_attack_block_key() {
+    let key = include_bytes!("../../sha1/tests/data/shattered-1-prefix.bin");
+    let data = b"Hi There";
+    let tag = "ca5cab1be8149c3b00e847363345bebdfb91dea8";
+
+    assert_eq!(hex::encode(hmac(Algorithm::Sha1, key, data, None)), tag);
+    assert_eq!(hex::encode(HmacSha1::new(key).update(data).finalize()), tag);
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    let key: Vec<u8> = (0..100u8).collect();
+    let data: Vec<u8> = (0..3000u32).map(|i| (i * 13) as u8).collect();
+
+    for split in [0, 1, 63, 64, 65, 127, 128, 129, 1000, 3000] {
+        let mut mac = HmacSha256::new(&key);
+        mac.update(&data[..split]).update(&data[split..]);
+        assert_eq!(
+            mac.finalize().as_slice(),
+            hmac(Algorithm::Sha256, &key, &data, None)
+        );
+
+        let mut mac = HmacSha512::new(&key);
+        for chunk in data.chunks(split.max(1)) {
+            mac.update(chunk);
+        }
+        assert_eq!(
+            mac.finalize().as_slice(),
+            hmac(Algorithm::Sha512, &key, &data, None)
+        );
+    }
+
+    // The state can be continued after finalizing.
+    let mut mac = HmacSha384::new(&key);
+    mac.update(&data[..10]);
+    assert_eq!(
+        mac.finalize().as_slice(),
+        hmac(Algorithm::Sha384, &key, &data[..10], None)
+    );
+    mac.update(&data[10..]);
+    assert_eq!(
+        mac.finalize().as_slice(),
+        hmac(Algorithm::Sha384, &key, &data, None)
+    );
+}
+
+#[test]
+fn verify() {
+    let mut mac = HmacSha256::new(b"key");
+    mac.update(b"data");
+    let mut tag = mac.finalize();
+
+    assert_eq!(mac.verify(&tag), Ok(()));
+    assert_eq!(mac.verify_truncated(&tag[..16]), Ok(()));
+    assert_eq!(
+        mac.verify_truncated(&tag[..15]),
+        Err(Error::InvalidTagLength)
+    );
+    assert_eq!(mac.verify_truncated(&[]), Err(Error::InvalidTagLength));
+
+    tag[31] ^= 1;
+    assert_eq!(mac.verify(&tag), Err(Error::InvalidTag));
+    assert_eq!(mac.verify_truncated(&tag[..16]), Ok(()));
+    tag[0] ^= 1;
+    assert_eq!(mac.verify_truncated(&tag[..16]), Err(Error::InvalidTag));
+
+    let mac = HmacSha1::new(b"key");
+    assert_eq!(mac.verify_truncated(&mac.finalize()[..10]), Ok(()));
+    assert_eq!(
+        mac.verify_truncated(&mac.finalize()[..9]),
+        Err(Error::InvalidTagLength)
+    );
+}
+
+/// HMAC-BLAKE2 on the inputs of test cases 1, 2 and 6 of RFC 4231.
+///
+/// There are no official test vectors for HMAC-BLAKE2. These tags were
+/// computed with Python's `hmac` and `hashlib` modules, and are also checked
+/// against the hacl HMAC-BLAKE2.
+#[test]
+fn blake2_kats() {
+    const TAGS: [[&str; 2]; 3] = [
+        [
+            "65a8b7c5cc9136d424e82c37e2707e74e913c0655b99c75f40edf387453a3260",
+            "358a6a184924894fc34bee5680eedf57d84a37bb38832f288e3b27dc63a98cc8c91e76da476b508bc6b2d408a248857452906e4a20b48c6b4b55d2df0fe1dd24",
+        ],
+        [
+            "90b6281e2f3038c9056af0b4a7e763cae6fe5d9eb4386a0ec95237890c104ff0",
+            "6ff884f8ddc2a6586b3c98a4cd6ebdf14ec10204b6710073eb5865ade37a2643b8807c1335d107ecdb9ffeaeb6828c4625ba172c66379efcd222c2de11727ab4",
+        ],
+        [
+            "d23d79394f53d536a096e6514447eeaabb05ded01be32c1937da6a8f7103bc4e",
+            "a54b2943b2a20227d41ca46c0945af09bc1faefb2f49894c23aebc557fb79c4889dca74408dc865086667aedee4a3185c53a49c80b814c4c5813ea0c8b38a8f8",
+        ],
+    ];
+
+    for ((key, repeat, data, _), tags) in KATS.into_iter().zip(TAGS) {
+        let key = key.repeat(repeat);
+
+        assert_eq!(
+            hex::encode(HmacBlake2s::new(&key).update(data).finalize()),
+            tags[0]
+        );
+        assert_eq!(
+            hex::encode(hmac(Algorithm::Blake2s, &key, data, None)),
+            tags[0]
+        );
+        assert_eq!(
+            hex::encode(HmacBlake2b::new(&key).update(data).finalize()),
+            tags[1]
+        );
+        assert_eq!(
+            hex::encode(hmac(Algorithm::Blake2b, &key, data, None)),
+            tags[1]
+        );
+
+        let (key_len, data_len) = (key.len() as u32, data.len() as u32);
+        let mut tag = [0u8; 32];
+        hacl::hmac::compute_blake2s_32(&mut tag, &key, key_len, data, data_len);
+        assert_eq!(hex::encode(tag), tags[0]);
+        let mut tag = [0u8; 64];
+        hacl::hmac::compute_blake2b_32(&mut tag, &key, key_len, data, data_len);
+        assert_eq!(hex::encode(tag), tags[1]);
+    }
+
+    // Keys longer than a block are hashed first.
+    for key_len in [64, 65, 128, 129, 200] {
+        let key: Vec<u8> = (0..key_len).map(|i| i as u8).collect();
+        let data = b"key longer than a block";
+
+        let mut tag = [0u8; 32];
+        hacl::hmac::compute_blake2s_32(&mut tag, &key, key_len, data, data.len() as u32);
+        assert_eq!(HmacBlake2s::new(&key).update(data).finalize(), tag);
+        let mut tag = [0u8; 64];
+        hacl::hmac::compute_blake2b_32(&mut tag, &key, key_len, data, data.len() as u32);
+        assert_eq!(HmacBlake2b::new(&key).update(data).finalize(), tag);
+    }
+}
+
+#[test]
+fn mac_trait() {
+    use libcrux_traits::mac::{Mac, MacError};
+
+    let key = b"Jefe";
+    let data = b"what do ya want for nothing?";
+
+    let tag = HmacSha256::mac(key, data);
+    assert_eq!(tag.as_slice(), hmac(Algorithm::Sha256, key, data, None));
+    // The inherent `verify` of the streaming types shadows the trait's.
+    assert!(<HmacSha256 as Mac>::verify(key, data, &tag).is_ok());
+    assert_eq!(
+        <HmacSha256 as Mac>::verify(b"Jeff", data, &tag),
+        Err(MacError::InvalidTag)
+    );
+
+    let tag = HmacBlake2b::mac(key, data);
+    assert_eq!(tag.len(), HmacBlake2b::TAG_LEN);
+    assert!(<HmacBlake2b as Mac>::verify(key, data, &tag).is_ok());
+}
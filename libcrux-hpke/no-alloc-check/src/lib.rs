@@ -0,0 +1,37 @@
+#![no_std]
+
+use libcrux_hpke::*;
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+/// Seal a message to `pk_r`, open it again with `sk_r` and export a secret.
+///
+/// Returns 0 on success.
+#[no_mangle]
+pub extern "C" fn hpke_roundtrip(
+    sk_r: &[u8; NSK],
+    pk_r: &[u8; NPK],
+    randomness: &[u8; RANDOMNESS_LEN],
+    msg: &[u8; 32],
+) -> i32 {
+    let mut ctxt = [0u8; 32 + NT];
+    let mut enc = [0u8; NENC];
+    if seal(&mut ctxt, &mut enc, pk_r, b"info", b"aad", msg, None, None, randomness).is_err() {
+        return 1;
+    }
+
+    let mut ptxt = [0u8; 32];
+    if open(&mut ptxt, &enc, sk_r, b"info", b"aad", &ctxt, None, None).is_err() {
+        return 1;
+    }
+
+    let mut secret = [0u8; 32];
+    if receive_export(&mut secret, &enc, sk_r, b"info", b"context", None, None).is_err() {
+        return 1;
+    }
+
+    (ptxt != *msg) as i32
+}
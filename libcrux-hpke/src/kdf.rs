@@ -0,0 +1,77 @@
+//! Labeled HKDF-SHA256 as used by HPKE.
+//!
+//! The labeled inputs are absorbed piece by piece into HMAC instead of being
+//! concatenated into a buffer first.
+
+use libcrux_hmac::HmacSha256;
+
+use crate::Error;
+
+/// `Nh` of HKDF-SHA256.
+pub(crate) const NH: usize = HmacSha256::TAG_LENGTH;
+
+const HPKE_VERSION: &[u8] = b"HPKE-v1";
+
+/// ```text
+/// def LabeledExtract(salt, label, ikm):
+///   labeled_ikm = concat("HPKE-v1", suite_id, label, ikm)
+///   return Extract(salt, labeled_ikm)
+/// ```
+///
+/// An empty `salt` is the same as `Nh` zero bytes, because HMAC pads the key
+/// with zeros.
+pub(crate) fn labeled_extract(
+    prk: &mut [u8; NH],
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) {
+    let mut hmac = HmacSha256::new(salt);
+    hmac.update(HPKE_VERSION);
+    hmac.update(suite_id);
+    hmac.update(label);
+    hmac.update(ikm);
+    *prk = hmac.finalize();
+}
+
+/// ```text
+/// def LabeledExpand(prk, label, info, L):
+///   labeled_info = concat(I2OSP(L, 2), "HPKE-v1", suite_id,
+///                         label, info)
+///   return Expand(prk, labeled_info, L)
+/// ```
+///
+/// `L` is the length of `okm`, which must not be larger than `255 * Nh`.
+pub(crate) fn labeled_expand(
+    okm: &mut [u8],
+    suite_id: &[u8],
+    prk: &[u8; NH],
+    label: &[u8],
+    info: &[u8],
+) -> Result<(), Error> {
+    if okm.len() > 255 * NH {
+        return Err(Error::InvalidParameters);
+    }
+    let length = (okm.len() as u16).to_be_bytes();
+
+    // T(i) = HMAC(prk, T(i - 1) | labeled_info | i)
+    let mut t = [0u8; NH];
+    for (i, chunk) in okm.chunks_mut(NH).enumerate() {
+        let mut hmac = HmacSha256::new(prk);
+        if i > 0 {
+            hmac.update(&t);
+        }
+        hmac.update(&length);
+        hmac.update(HPKE_VERSION);
+        hmac.update(suite_id);
+        hmac.update(label);
+        hmac.update(info);
+        hmac.update(&[i as u8 + 1]);
+        t = hmac.finalize();
+
+        chunk.copy_from_slice(&t[..chunk.len()]);
+    }
+
+    Ok(())
+}
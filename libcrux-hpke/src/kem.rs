@@ -0,0 +1,157 @@
+//! DHKEM(X25519, HKDF-SHA256)
+
+use libcrux_curve25519::{ecdh, secret_to_public};
+
+use crate::kdf::{labeled_expand, labeled_extract, NH};
+use crate::Error;
+
+/// The length of an encapsulated key.
+pub const NENC: usize = 32;
+
+/// The length of a public key.
+pub const NPK: usize = 32;
+
+/// The length of a private key.
+pub const NSK: usize = 32;
+
+/// The length of the KEM shared secret.
+pub(crate) const NSECRET: usize = 32;
+
+/// `suite_id = concat("KEM", I2OSP(kem_id, 2))`
+const SUITE_ID: [u8; 5] = [b'K', b'E', b'M', 0x00, 0x20];
+
+/// Deterministically derive a key pair from the input keying material `ikm`.
+///
+/// ```text
+/// def DeriveKeyPair(ikm):
+///   dkp_prk = LabeledExtract("", "dkp_prk", ikm)
+///   sk = LabeledExpand(dkp_prk, "sk", "", Nsk)
+///   return (sk, pk(sk))
+/// ```
+pub fn derive_key_pair(sk: &mut [u8; NSK], pk: &mut [u8; NPK], ikm: &[u8]) {
+    let mut dkp_prk = [0u8; NH];
+    labeled_extract(&mut dkp_prk, &SUITE_ID, b"", b"dkp_prk", ikm);
+
+    // Nsk is always smaller than 255 * Nh.
+    labeled_expand(sk, &SUITE_ID, &dkp_prk, b"sk", b"").unwrap();
+    secret_to_public(pk, sk);
+}
+
+fn dh(out: &mut [u8; 32], sk: &[u8; NSK], pk: &[u8; NPK]) -> Result<(), Error> {
+    ecdh(out, pk, sk).map_err(|_| Error::ValidationError)
+}
+
+/// ```text
+/// def ExtractAndExpand(dh, kem_context):
+///   eae_prk = LabeledExtract("", "eae_prk", dh)
+///   shared_secret = LabeledExpand(eae_prk, "shared_secret",
+///                                 kem_context, Nsecret)
+///   return shared_secret
+/// ```
+fn extract_and_expand(shared_secret: &mut [u8; NSECRET], dh: &[u8], kem_context: &[u8]) {
+    let mut eae_prk = [0u8; NH];
+    labeled_extract(&mut eae_prk, &SUITE_ID, b"", b"eae_prk", dh);
+
+    // Nsecret is always smaller than 255 * Nh.
+    labeled_expand(
+        shared_secret,
+        &SUITE_ID,
+        &eae_prk,
+        b"shared_secret",
+        kem_context,
+    )
+    .unwrap();
+}
+
+/// `Encap(pkR)` with the ephemeral key pair derived from `randomness`.
+pub(crate) fn encap(
+    shared_secret: &mut [u8; NSECRET],
+    enc: &mut [u8; NENC],
+    pk_r: &[u8; NPK],
+    randomness: &[u8],
+) -> Result<(), Error> {
+    let mut sk_e = [0u8; NSK];
+    derive_key_pair(&mut sk_e, enc, randomness);
+
+    let mut dh_out = [0u8; 32];
+    dh(&mut dh_out, &sk_e, pk_r)?;
+
+    let mut kem_context = [0u8; NENC + NPK];
+    kem_context[..NENC].copy_from_slice(enc);
+    kem_context[NENC..].copy_from_slice(pk_r);
+
+    extract_and_expand(shared_secret, &dh_out, &kem_context);
+    Ok(())
+}
+
+/// `Decap(enc, skR)`
+pub(crate) fn decap(
+    shared_secret: &mut [u8; NSECRET],
+    enc: &[u8; NENC],
+    sk_r: &[u8; NSK],
+) -> Result<(), Error> {
+    let mut dh_out = [0u8; 32];
+    dh(&mut dh_out, sk_r, enc)?;
+
+    let mut pk_r = [0u8; NPK];
+    secret_to_public(&mut pk_r, sk_r);
+
+    let mut kem_context = [0u8; NENC + NPK];
+    kem_context[..NENC].copy_from_slice(enc);
+    kem_context[NENC..].copy_from_slice(&pk_r);
+
+    extract_and_expand(shared_secret, &dh_out, &kem_context);
+    Ok(())
+}
+
+/// `AuthEncap(pkR, skS)` with the ephemeral key pair derived from `randomness`.
+pub(crate) fn auth_encap(
+    shared_secret: &mut [u8; NSECRET],
+    enc: &mut [u8; NENC],
+    pk_r: &[u8; NPK],
+    sk_s: &[u8; NSK],
+    randomness: &[u8],
+) -> Result<(), Error> {
+    let mut sk_e = [0u8; NSK];
+    derive_key_pair(&mut sk_e, enc, randomness);
+
+    let mut dh_out = [0u8; 64];
+    let (dh_e, dh_s) = dh_out.split_at_mut(32);
+    dh(dh_e.try_into().unwrap(), &sk_e, pk_r)?;
+    dh(dh_s.try_into().unwrap(), sk_s, pk_r)?;
+
+    let mut pk_s = [0u8; NPK];
+    secret_to_public(&mut pk_s, sk_s);
+
+    let mut kem_context = [0u8; NENC + 2 * NPK];
+    kem_context[..NENC].copy_from_slice(enc);
+    kem_context[NENC..NENC + NPK].copy_from_slice(pk_r);
+    kem_context[NENC + NPK..].copy_from_slice(&pk_s);
+
+    extract_and_expand(shared_secret, &dh_out, &kem_context);
+    Ok(())
+}
+
+/// `AuthDecap(enc, skR, pkS)`
+pub(crate) fn auth_decap(
+    shared_secret: &mut [u8; NSECRET],
+    enc: &[u8; NENC],
+    sk_r: &[u8; NSK],
+    pk_s: &[u8; NPK],
+) -> Result<(), Error> {
+    let mut dh_out = [0u8; 64];
+    let (dh_e, dh_s) = dh_out.split_at_mut(32);
+    dh(dh_e.try_into().unwrap(), sk_r, enc)?;
+    dh(dh_s.try_into().unwrap(), sk_r, pk_s)?;
+
+    let mut pk_r = [0u8; NPK];
+    secret_to_public(&mut pk_r, sk_r);
+
+    let mut kem_context = [0u8; NENC + 2 * NPK];
+    kem_context[..NENC].copy_from_slice(enc);
+    kem_context[NENC..NENC + NPK].copy_from_slice(&pk_r);
+    kem_context[NENC + NPK..].copy_from_slice(pk_s);
+
+    extract_and_expand(shared_secret, &dh_out, &kem_context);
+    Ok(())
+}
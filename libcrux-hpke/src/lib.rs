@@ -0,0 +1,375 @@
+//! # Single-shot HPKE without allocations
+//!
+//! This crate implements the single-shot APIs of [HPKE] (RFC 9180) for the
+//! ciphersuite
+//!
+//! | KEM                        | KDF         | AEAD             |
+//! | -------------------------- | ----------- | ---------------- |
+//! | DHKEM(X25519, HKDF-SHA256) | HKDF-SHA256 | ChaCha20Poly1305 |
+//!
+//! in all four modes. The mode is determined by the optional arguments:
+//!
+//! | `psk`     | sender key | mode            |
+//! | --------- | ---------- | --------------- |
+//! | `None`    | `None`     | `mode_base`     |
+//! | `Some(_)` | `None`     | `mode_psk`      |
+//! | `None`    | `Some(_)`  | `mode_auth`     |
+//! | `Some(_)` | `Some(_)`  | `mode_auth_psk` |
+//!
+//! All outputs are written into caller-provided buffers and only fixed-size
+//! arrays are used internally, so this crate never allocates. It doesn't link
+//! against `alloc` either and can be used on targets without a global
+//! allocator.
+//!
+//! ```
+//! use libcrux_hpke::*;
+//!
+//! let mut sk_r = [0u8; NSK];
+//! let mut pk_r = [0u8; NPK];
+//! derive_key_pair(&mut sk_r, &mut pk_r, b"receiver key material");
+//!
+//! let randomness = [0x42u8; RANDOMNESS_LEN]; // Use real randomness here.
+//! let ptxt = b"a secret message";
+//! let mut ctxt = [0u8; 16 + NT];
+//! let mut enc = [0u8; NENC];
+//! seal(&mut ctxt, &mut enc, &pk_r, b"info", b"aad", ptxt, None, None, &randomness).unwrap();
+//!
+//! let mut decrypted = [0u8; 16];
+//! open(&mut decrypted, &enc, &sk_r, b"info", b"aad", &ctxt, None, None).unwrap();
+//! assert_eq!(&decrypted, ptxt);
+//! ```
+//!
+//! [HPKE]: https://www.rfc-editor.org/rfc/rfc9180.html
+
+#![no_std]
+
+mod kdf;
+mod kem;
+
+pub use kem::{derive_key_pair, NENC, NPK, NSK};
+
+use kdf::{labeled_expand, labeled_extract, NH};
+use kem::NSECRET;
+use libcrux_chacha20poly1305::{KEY_LEN, NONCE_LEN, TAG_LEN};
+
+/// The length of the AEAD authentication tag.
+pub const NT: usize = TAG_LEN;
+
+/// The number of random bytes needed to generate the ephemeral key pair.
+pub const RANDOMNESS_LEN: usize = NSK;
+
+/// `suite_id = concat("HPKE", I2OSP(kem_id, 2), I2OSP(kdf_id, 2), I2OSP(aead_id, 2))`
+const SUITE_ID: [u8; 10] = [b'H', b'P', b'K', b'E', 0x00, 0x20, 0x00, 0x01, 0x00, 0x03];
+
+/// Errors of the single-shot HPKE APIs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// KEM input or output validation failure.
+    ValidationError,
+    /// AEAD `Open()` failure.
+    OpenError,
+    /// PSK inputs are inconsistent, i.e. exactly one of them is empty.
+    InconsistentPskInputs,
+    /// An input is too large or an output buffer has the wrong size.
+    InvalidParameters,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            Error::ValidationError => "KEM input or output validation failed",
+            Error::OpenError => "decryption failed",
+            Error::InconsistentPskInputs => "the PSK inputs are inconsistent",
+            Error::InvalidParameters => "an argument has an invalid length",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+/// A pre-shared key and its identifier.
+///
+/// Passing a [`Psk`] selects `mode_psk` or `mode_auth_psk`.
+#[derive(Debug, Clone, Copy)]
+pub struct Psk<'a> {
+    /// The pre-shared key.
+    pub psk: &'a [u8],
+    /// The identifier of the pre-shared key.
+    pub psk_id: &'a [u8],
+}
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+enum Mode {
+    Base = 0x00,
+    Psk = 0x01,
+    Auth = 0x02,
+    AuthPsk = 0x03,
+}
+
+impl Mode {
+    fn new(psk: bool, auth: bool) -> Self {
+        match (psk, auth) {
+            (false, false) => Mode::Base,
+            (true, false) => Mode::Psk,
+            (false, true) => Mode::Auth,
+            (true, true) => Mode::AuthPsk,
+        }
+    }
+}
+
+/// The secrets derived by the key schedule.
+struct Context {
+    key: [u8; KEY_LEN],
+    base_nonce: [u8; NONCE_LEN],
+    exporter_secret: [u8; NH],
+}
+
+/// ```text
+/// def KeySchedule<ROLE>(mode, shared_secret, info, psk, psk_id):
+///   VerifyPSKInputs(mode, psk, psk_id)
+///
+///   psk_id_hash = LabeledExtract("", "psk_id_hash", psk_id)
+///   info_hash = LabeledExtract("", "info_hash", info)
+///   key_schedule_context = concat(mode, psk_id_hash, info_hash)
+///
+///   secret = LabeledExtract(shared_secret, "secret", psk)
+///
+///   key = LabeledExpand(secret, "key", key_schedule_context, Nk)
+///   base_nonce = LabeledExpand(secret, "base_nonce",
+///                              key_schedule_context, Nn)
+///   exporter_secret = LabeledExpand(secret, "exp",
+///                                   key_schedule_context, Nh)
+/// ```
+fn key_schedule(
+    mode: Mode,
+    shared_secret: &[u8; NSECRET],
+    info: &[u8],
+    psk: Option<Psk>,
+) -> Result<Context, Error> {
+    let (psk, psk_id) = match psk {
+        Some(Psk { psk, psk_id }) => {
+            if psk.is_empty() || psk_id.is_empty() {
+                return Err(Error::InconsistentPskInputs);
+            }
+            (psk, psk_id)
+        }
+        None => (&[][..], &[][..]),
+    };
+
+    let mut key_schedule_context = [0u8; 1 + 2 * NH];
+    key_schedule_context[0] = mode as u8;
+    let (psk_id_hash, info_hash) = key_schedule_context[1..].split_at_mut(NH);
+    labeled_extract(
+        psk_id_hash.try_into().unwrap(),
+        &SUITE_ID,
+        b"",
+        b"psk_id_hash",
+        psk_id,
+    );
+    labeled_extract(
+        info_hash.try_into().unwrap(),
+        &SUITE_ID,
+        b"",
+        b"info_hash",
+        info,
+    );
+
+    let mut secret = [0u8; NH];
+    labeled_extract(&mut secret, &SUITE_ID, shared_secret, b"secret", psk);
+
+    let mut context = Context {
+        key: [0u8; KEY_LEN],
+        base_nonce: [0u8; NONCE_LEN],
+        exporter_secret: [0u8; NH],
+    };
+    labeled_expand(
+        &mut context.key,
+        &SUITE_ID,
+        &secret,
+        b"key",
+        &key_schedule_context,
+    )?;
+    labeled_expand(
+        &mut context.base_nonce,
+        &SUITE_ID,
+        &secret,
+        b"base_nonce",
+        &key_schedule_context,
+    )?;
+    labeled_expand(
+        &mut context.exporter_secret,
+        &SUITE_ID,
+        &secret,
+        b"exp",
+        &key_schedule_context,
+    )?;
+
+    Ok(context)
+}
+
+/// Encapsulate to `pk_r` and run the key schedule for the sender.
+fn setup_sender(
+    enc: &mut [u8; NENC],
+    pk_r: &[u8; NPK],
+    info: &[u8],
+    psk: Option<Psk>,
+    sk_s: Option<&[u8; NSK]>,
+    randomness: &[u8; RANDOMNESS_LEN],
+) -> Result<Context, Error> {
+    let mut shared_secret = [0u8; NSECRET];
+    match sk_s {
+        Some(sk_s) => kem::auth_encap(&mut shared_secret, enc, pk_r, sk_s, randomness)?,
+        None => kem::encap(&mut shared_secret, enc, pk_r, randomness)?,
+    }
+
+    key_schedule(
+        Mode::new(psk.is_some(), sk_s.is_some()),
+        &shared_secret,
+        info,
+        psk,
+    )
+}
+
+/// Decapsulate `enc` and run the key schedule for the recipient.
+fn setup_receiver(
+    enc: &[u8; NENC],
+    sk_r: &[u8; NSK],
+    info: &[u8],
+    psk: Option<Psk>,
+    pk_s: Option<&[u8; NPK]>,
+) -> Result<Context, Error> {
+    let mut shared_secret = [0u8; NSECRET];
+    match pk_s {
+        Some(pk_s) => kem::auth_decap(&mut shared_secret, enc, sk_r, pk_s)?,
+        None => kem::decap(&mut shared_secret, enc, sk_r)?,
+    }
+
+    key_schedule(
+        Mode::new(psk.is_some(), pk_s.is_some()),
+        &shared_secret,
+        info,
+        psk,
+    )
+}
+
+/// Single-shot encryption of `ptxt` to the recipient public key `pk_r`.
+///
+/// The encapsulated key is written to `enc` and the ciphertext including the
+/// tag to `ctxt`, which must be exactly `ptxt.len() + NT` bytes long.
+/// The ephemeral key pair is derived from `randomness`.
+///
+/// Passing a `psk` and/or a sender private key `sk_s` selects the mode.
+#[allow(clippy::too_many_arguments)]
+pub fn seal(
+    ctxt: &mut [u8],
+    enc: &mut [u8; NENC],
+    pk_r: &[u8; NPK],
+    info: &[u8],
+    aad: &[u8],
+    ptxt: &[u8],
+    psk: Option<Psk>,
+    sk_s: Option<&[u8; NSK]>,
+    randomness: &[u8; RANDOMNESS_LEN],
+) -> Result<(), Error> {
+    if ctxt.len() != ptxt.len() + NT {
+        return Err(Error::InvalidParameters);
+    }
+
+    let context = setup_sender(enc, pk_r, info, psk, sk_s, randomness)?;
+
+    // The sequence number of a single-shot context is 0, so the nonce is the
+    // base nonce.
+    libcrux_chacha20poly1305::encrypt(&context.key, ptxt, ctxt, aad, &context.base_nonce)
+        .map_err(|_| Error::InvalidParameters)?;
+
+    Ok(())
+}
+
+/// Single-shot decryption of `ctxt` with the recipient private key `sk_r`.
+///
+/// The plaintext is written to `ptxt`, which must be exactly
+/// `ctxt.len() - NT` bytes long.
+///
+/// Passing a `psk` and/or a sender public key `pk_s` selects the mode.
+#[allow(clippy::too_many_arguments)]
+pub fn open(
+    ptxt: &mut [u8],
+    enc: &[u8; NENC],
+    sk_r: &[u8; NSK],
+    info: &[u8],
+    aad: &[u8],
+    ctxt: &[u8],
+    psk: Option<Psk>,
+    pk_s: Option<&[u8; NPK]>,
+) -> Result<(), Error> {
+    if ctxt.len() < NT || ptxt.len() != ctxt.len() - NT {
+        return Err(Error::InvalidParameters);
+    }
+
+    let context = setup_receiver(enc, sk_r, info, psk, pk_s)?;
+
+    libcrux_chacha20poly1305::decrypt(&context.key, ptxt, ctxt, aad, &context.base_nonce).map_err(
+        |e| match e {
+            libcrux_chacha20poly1305::AeadError::InvalidCiphertext => Error::OpenError,
+            _ => Error::InvalidParameters,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Derive a secret for the `exporter_context` on the sender side.
+///
+/// The encapsulated key is written to `enc` and the exported secret fills
+/// `exported`, which can be at most `255 * 32` bytes long.
+///
+/// Passing a `psk` and/or a sender private key `sk_s` selects the mode.
+#[allow(clippy::too_many_arguments)]
+pub fn send_export(
+    exported: &mut [u8],
+    enc: &mut [u8; NENC],
+    pk_r: &[u8; NPK],
+    info: &[u8],
+    exporter_context: &[u8],
+    psk: Option<Psk>,
+    sk_s: Option<&[u8; NSK]>,
+    randomness: &[u8; RANDOMNESS_LEN],
+) -> Result<(), Error> {
+    let context = setup_sender(enc, pk_r, info, psk, sk_s, randomness)?;
+    export(exported, &context, exporter_context)
+}
+
+/// Derive a secret for the `exporter_context` on the recipient side.
+///
+/// The exported secret fills `exported`, which can be at most `255 * 32`
+/// bytes long.
+///
+/// Passing a `psk` and/or a sender public key `pk_s` selects the mode.
+pub fn receive_export(
+    exported: &mut [u8],
+    enc: &[u8; NENC],
+    sk_r: &[u8; NSK],
+    info: &[u8],
+    exporter_context: &[u8],
+    psk: Option<Psk>,
+    pk_s: Option<&[u8; NPK]>,
+) -> Result<(), Error> {
+    let context = setup_receiver(enc, sk_r, info, psk, pk_s)?;
+    export(exported, &context, exporter_context)
+}
+
+/// ```text
+/// def Context.Export(exporter_context, L):
+///   return LabeledExpand(self.exporter_secret, "sec",
+///                        exporter_context, L)
+/// ```
+fn export(exported: &mut [u8], context: &Context, exporter_context: &[u8]) -> Result<(), Error> {
+    labeled_expand(
+        exported,
+        &SUITE_ID,
+        &context.exporter_secret,
+        b"sec",
+        exporter_context,
+    )
+}
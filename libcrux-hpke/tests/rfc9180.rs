@@ -0,0 +1,340 @@
+use libcrux_hpke::*;
+
+fn hex<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s).unwrap().try_into().unwrap()
+}
+
+fn hex_vec(s: &str) -> Vec<u8> {
+    hex::decode(s).unwrap()
+}
+
+const INFO: &str = "4f6465206f6e2061204772656369616e2055726e";
+const AAD: &str = "436f756e742d30";
+const PTXT: &str = "4265617574792069732074727574682c20747275746820626561757479";
+const PSK: &str = "0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82";
+const PSK_ID: &str = "456e6e796e20447572696e206172616e204d6f726961";
+
+/// The single-shot vectors from RFC 9180 Appendix A.2.
+struct Vector {
+    randomness: &'static str,
+    pk_r: &'static str,
+    sk_r: &'static str,
+    sender: Option<(&'static str, &'static str)>,
+    psk: bool,
+    ctxt: &'static str,
+}
+
+const VECTORS: [Vector; 4] = [
+    // A.2.1.1
+    Vector {
+        randomness: "909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b",
+        pk_r: "4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a",
+        sk_r: "8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb",
+        sender: None,
+        psk: false,
+        ctxt: "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db21993c62ce81883d2dd1b51a28",
+    },
+    // A.2.2.1
+    Vector {
+        randomness: "35706a0b09fb26fb45c39c2f5079c709c7cf98e43afa973f14d88ece7e29c2e3",
+        pk_r: "13640af826b722fc04feaa4de2f28fbd5ecc03623b317834e7ff4120dbe73062",
+        sk_r: "77d114e0212be51cb1d76fa99dd41cfd4d0166b08caa09074430a6c59ef17879",
+        sender: None,
+        psk: true,
+        ctxt: "4a177f9c0d6f15cfdf533fb65bf84aecdc6ab16b8b85b4cf65a370e07fc1d78d28fb073214525276f4a89608ff",
+    },
+    // A.2.3.1
+    Vector {
+        randomness: "938d3daa5a8904540bc24f48ae90eed3f4f7f11839560597b55e7c9598c996c0",
+        pk_r: "1a478716d63cb2e16786ee93004486dc151e988b34b475043d3e0175bdb01c44",
+        sk_r: "3ca22a6d1cda1bb9480949ec5329d3bf0b080ca4c45879c95eddb55c70b80b82",
+        sender: Some((
+            "2def0cb58ffcf83d1062dd085c8aceca7f4c0c3fd05912d847b61f3e54121f05",
+            "f0f4f9e96c54aeed3f323de8534fffd7e0577e4ce269896716bcb95643c8712b",
+        )),
+        psk: false,
+        ctxt: "ab1a13c9d4f01a87ec3440dbd756e2677bd2ecf9df0ce7ed73869b98e00c09be111cb9fdf077347aeb88e61bdf",
+    },
+    // A.2.4.1
+    Vector {
+        randomness: "49d6eac8c6c558c953a0a252929a818745bb08cd3d29e15f9f5db5eb2e7d4b84",
+        pk_r: "a5099431c35c491ec62ca91df1525d6349cb8aa170c51f9581f8627be6334851",
+        sk_r: "7b36a42822e75bf3362dfabbe474b3016236408becb83b859a6909e22803cb0c",
+        sender: Some((
+            "90761c5b0a7ef0985ed66687ad708b921d9803d51637c8d1cb72d03ed0f64418",
+            "3ac5bd4dd66ff9f2740bef0d6ccb66daa77bff7849d7895182b07fb74d087c45",
+        )),
+        psk: true,
+        ctxt: "9aa52e29274fc6172e38a4461361d2342585d3aeec67fb3b721ecd63f059577c7fe886be0ede01456ebc67d597",
+    },
+];
+
+#[test]
+fn derive_key_pair_x25519() {
+    // A.1
+    let mut sk = [0u8; NSK];
+    let mut pk = [0u8; NPK];
+
+    derive_key_pair(
+        &mut sk,
+        &mut pk,
+        &hex_vec("7268600d403fce431561aef583ee1613527cff655c1343f29812e66706df3234"),
+    );
+    assert_eq!(
+        sk,
+        hex("52c4a758a802cd8b936eceea314432798d5baf2d7e9235dc084ab1b9cfa2f736")
+    );
+    assert_eq!(
+        pk,
+        hex("37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431")
+    );
+
+    derive_key_pair(
+        &mut sk,
+        &mut pk,
+        &hex_vec("6db9df30aa07dd42ee5e8181afdb977e538f5e1fec8a06223f33f7013e525037"),
+    );
+    assert_eq!(
+        sk,
+        hex("4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8")
+    );
+    assert_eq!(
+        pk,
+        hex("3948cfe0ad1ddb695d780e59077195da6c56506b027329794ab02bca80815c4d")
+    );
+}
+
+#[test]
+fn single_shot_kat() {
+    let info = hex_vec(INFO);
+    let aad = hex_vec(AAD);
+    let ptxt = hex_vec(PTXT);
+    let psk_bytes = hex_vec(PSK);
+    let psk_id = hex_vec(PSK_ID);
+
+    for vector in VECTORS {
+        let psk = vector.psk.then_some(Psk {
+            psk: &psk_bytes,
+            psk_id: &psk_id,
+        });
+        let sender = vector.sender.map(|(sk, pk)| (hex(sk), hex(pk)));
+
+        let mut ctxt = vec![0u8; ptxt.len() + NT];
+        let mut enc = [0u8; NENC];
+        seal(
+            &mut ctxt,
+            &mut enc,
+            &hex(vector.pk_r),
+            &info,
+            &aad,
+            &ptxt,
+            psk,
+            sender.as_ref().map(|(sk_s, _)| sk_s),
+            &hex(vector.randomness),
+        )
+        .unwrap();
+        assert_eq!(ctxt, hex_vec(vector.ctxt));
+
+        let mut decrypted = vec![0u8; ptxt.len()];
+        open(
+            &mut decrypted,
+            &enc,
+            &hex(vector.sk_r),
+            &info,
+            &aad,
+            &ctxt,
+            psk,
+            sender.as_ref().map(|(_, pk_s)| pk_s),
+        )
+        .unwrap();
+        assert_eq!(decrypted, ptxt);
+    }
+}
+
+#[test]
+fn export_matches_libcrux() {
+    use libcrux::hpke::{aead::AEAD, kdf::KDF, kem::KEM, HPKEConfig, Mode, ReceiveExport};
+
+    let info = hex_vec(INFO);
+    let psk_bytes = hex_vec(PSK);
+    let psk_id = hex_vec(PSK_ID);
+
+    for vector in VECTORS {
+        let psk = vector.psk.then_some(Psk {
+            psk: &psk_bytes,
+            psk_id: &psk_id,
+        });
+        let sender = vector.sender.map(|(sk, pk)| (hex(sk), hex(pk)));
+
+        for (exporter_context, len) in [(&b""[..], 32), (b"\x00", 32), (b"TestContext", 100)] {
+            let mut exported = vec![0u8; len];
+            let mut enc = [0u8; NENC];
+            send_export(
+                &mut exported,
+                &mut enc,
+                &hex(vector.pk_r),
+                &info,
+                exporter_context,
+                psk,
+                sender.as_ref().map(|(sk_s, _)| sk_s),
+                &hex(vector.randomness),
+            )
+            .unwrap();
+
+            let mut received = vec![0u8; len];
+            receive_export(
+                &mut received,
+                &enc,
+                &hex(vector.sk_r),
+                &info,
+                exporter_context,
+                psk,
+                sender.as_ref().map(|(_, pk_s)| pk_s),
+            )
+            .unwrap();
+            assert_eq!(exported, received);
+
+            let mode = match (vector.psk, vector.sender.is_some()) {
+                (false, false) => Mode::mode_base,
+                (true, false) => Mode::mode_psk,
+                (false, true) => Mode::mode_auth,
+                (true, true) => Mode::mode_auth_psk,
+            };
+            let config = HPKEConfig(
+                mode,
+                KEM::DHKEM_X25519_HKDF_SHA256,
+                KDF::HKDF_SHA256,
+                AEAD::ChaCha20Poly1305,
+            );
+            let expected = ReceiveExport(
+                config,
+                &enc,
+                &hex_vec(vector.sk_r),
+                &info,
+                exporter_context.to_vec(),
+                len,
+                vector.psk.then_some(&psk_bytes[..]),
+                vector.psk.then_some(&psk_id[..]),
+                sender.as_ref().map(|(_, pk_s)| &pk_s[..]),
+            )
+            .unwrap();
+            assert_eq!(exported, expected);
+        }
+    }
+}
+
+#[test]
+fn open_error() {
+    let info = hex_vec(INFO);
+    let aad = hex_vec(AAD);
+    let ptxt = hex_vec(PTXT);
+    let vector = &VECTORS[0];
+
+    let mut ctxt = vec![0u8; ptxt.len() + NT];
+    let mut enc = [0u8; NENC];
+    seal(
+        &mut ctxt,
+        &mut enc,
+        &hex(vector.pk_r),
+        &info,
+        &aad,
+        &ptxt,
+        None,
+        None,
+        &hex(vector.randomness),
+    )
+    .unwrap();
+
+    let last = ctxt.len() - 1;
+    ctxt[last] ^= 1;
+    let mut decrypted = vec![0u8; ptxt.len()];
+    let result = open(
+        &mut decrypted,
+        &enc,
+        &hex(vector.sk_r),
+        &info,
+        &aad,
+        &ctxt,
+        None,
+        None,
+    );
+    assert_eq!(result, Err(Error::OpenError));
+}
+
+#[test]
+fn invalid_inputs() {
+    let vector = &VECTORS[0];
+    let pk_r = hex(vector.pk_r);
+    let randomness = hex(vector.randomness);
+    let mut enc = [0u8; NENC];
+
+    // The ciphertext buffer has to fit exactly.
+    let mut ctxt = [0u8; 4 + NT + 1];
+    assert_eq!(
+        seal(
+            &mut ctxt,
+            &mut enc,
+            &pk_r,
+            b"",
+            b"",
+            b"ptxt",
+            None,
+            None,
+            &randomness
+        ),
+        Err(Error::InvalidParameters)
+    );
+
+    // An empty PSK ID is inconsistent with a PSK.
+    let mut ctxt = [0u8; 4 + NT];
+    let psk = Psk {
+        psk: &[0x42; 32],
+        psk_id: b"",
+    };
+    assert_eq!(
+        seal(
+            &mut ctxt,
+            &mut enc,
+            &pk_r,
+            b"",
+            b"",
+            b"ptxt",
+            Some(psk),
+            None,
+            &randomness
+        ),
+        Err(Error::InconsistentPskInputs)
+    );
+
+    // A low order point as public key results in an all zero shared secret.
+    assert_eq!(
+        seal(
+            &mut ctxt,
+            &mut enc,
+            &[0u8; NPK],
+            b"",
+            b"",
+            b"ptxt",
+            None,
+            None,
+            &randomness
+        ),
+        Err(Error::ValidationError)
+    );
+
+    // At most 255 * Nh bytes can be exported.
+    let mut exported = vec![0u8; 255 * 32 + 1];
+    assert_eq!(
+        send_export(
+            &mut exported,
+            &mut enc,
+            &pk_r,
+            b"",
+            b"",
+            None,
+            None,
+            &randomness
+        ),
+        Err(Error::InvalidParameters)
+    );
+}
@@ -149,6 +149,26 @@ pub fn _veorq_u64(mask: uint64x2_t, shifted: uint64x2_t) -> uint64x2_t {
     unsafe { veorq_u64(mask, shifted) }
 }
 
+#[inline(always)]
+pub fn _veorq_u32(a: uint32x4_t, b: uint32x4_t) -> uint32x4_t {
+    unsafe { veorq_u32(a, b) }
+}
+
+#[inline(always)]
+pub fn _vorrq_u32(a: uint32x4_t, b: uint32x4_t) -> uint32x4_t {
+    unsafe { vorrq_u32(a, b) }
+}
+
+#[inline(always)]
+pub fn _vld1q_u32(ptr: &[u32]) -> uint32x4_t {
+    unsafe { vld1q_u32(ptr.as_ptr()) }
+}
+
+#[inline(always)]
+pub fn _vst1q_u32(out: &mut [u32], v: uint32x4_t) {
+    unsafe { vst1q_u32(out.as_mut_ptr(), v) }
+}
+
 #[inline(always)]
 pub fn _vdupq_n_u32(value: u32) -> uint32x4_t {
     unsafe { vdupq_n_u32(value) }
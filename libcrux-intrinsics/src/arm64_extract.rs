@@ -189,6 +189,26 @@ pub fn _veor3q_u64(a: _uint64x2_t, b: _uint64x2_t, c: _uint64x2_t) -> _uint64x2_
     unimplemented!()
 }
 
+#[inline(always)]
+pub fn _veorq_u32(a: _uint32x4_t, b: _uint32x4_t) -> _uint32x4_t {
+    unimplemented!()
+}
+
+#[inline(always)]
+pub fn _vorrq_u32(a: _uint32x4_t, b: _uint32x4_t) -> _uint32x4_t {
+    unimplemented!()
+}
+
+#[inline(always)]
+pub fn _vld1q_u32(ptr: &[u32]) -> _uint32x4_t {
+    unimplemented!()
+}
+
+#[inline(always)]
+pub fn _vst1q_u32(out: &mut [u32], v: _uint32x4_t) {
+    unimplemented!()
+}
+
 #[inline(always)]
 pub fn _vdupq_n_u32(value: u32) -> _uint32x4_t {
     unimplemented!()
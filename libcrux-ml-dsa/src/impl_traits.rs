@@ -0,0 +1,88 @@
+//! Implementations of the signature traits of `libcrux-traits`.
+
+use libcrux_traits::signature::SignatureError;
+
+use crate::{SigningError, VerificationError};
+
+macro_rules! impl_signature_traits {
+    ($name:ident, $module:ident, $sk:ident, $vk:ident, $sig:ident, $doc:literal) => {
+        #[doc = concat!($doc, " as a [`Signer`](libcrux_traits::signature::Signer) and [`Verifier`](libcrux_traits::signature::Verifier).")]
+        ///
+        /// The signing randomness has [`SIGNING_RANDOMNESS_SIZE`](crate::SIGNING_RANDOMNESS_SIZE)
+        /// bytes. Signing with zero randomness is the deterministic variant.
+        /// Contexts are at most 255 bytes long.
+        pub struct $name;
+
+        impl libcrux_traits::signature::Signer for $name {
+            const RANDOMNESS_LEN: usize = crate::SIGNING_RANDOMNESS_SIZE;
+
+            type SigningKey = crate::$module::$sk;
+            type Signature = crate::$module::$sig;
+
+            fn sign_with_randomness(
+                key: &Self::SigningKey,
+                msg: &[u8],
+                ctx: &[u8],
+                randomness: &[u8],
+            ) -> Result<Self::Signature, SignatureError> {
+                let randomness = randomness
+                    .try_into()
+                    .map_err(|_| SignatureError::InvalidRandomness)?;
+
+                crate::$module::sign(key, msg, ctx, randomness).map_err(|e| match e {
+                    SigningError::ContextTooLongError => SignatureError::InvalidContext,
+                    SigningError::RejectionSamplingError => SignatureError::Signing,
+                })
+            }
+        }
+
+        impl libcrux_traits::signature::Verifier for $name {
+            type VerificationKey = crate::$module::$vk;
+            type Signature = crate::$module::$sig;
+
+            fn verify(
+                key: &Self::VerificationKey,
+                msg: &[u8],
+                ctx: &[u8],
+                signature: &Self::Signature,
+            ) -> Result<(), SignatureError> {
+                crate::$module::verify(key, msg, ctx, signature).map_err(|e| match e {
+                    VerificationError::VerificationContextTooLongError => {
+                        SignatureError::InvalidContext
+                    }
+                    _ => SignatureError::InvalidSignature,
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "mldsa44")]
+impl_signature_traits!(
+    MlDsa44,
+    ml_dsa_44,
+    MLDSA44SigningKey,
+    MLDSA44VerificationKey,
+    MLDSA44Signature,
+    "ML-DSA 44"
+);
+
+#[cfg(feature = "mldsa65")]
+impl_signature_traits!(
+    MlDsa65,
+    ml_dsa_65,
+    MLDSA65SigningKey,
+    MLDSA65VerificationKey,
+    MLDSA65Signature,
+    "ML-DSA 65"
+);
+
+#[cfg(feature = "mldsa87")]
+impl_signature_traits!(
+    MlDsa87,
+    ml_dsa_87,
+    MLDSA87SigningKey,
+    MLDSA87VerificationKey,
+    MLDSA87Signature,
+    "ML-DSA 87"
+);
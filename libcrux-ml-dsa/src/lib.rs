@@ -24,10 +24,16 @@ mod specs;
 
 mod types;
 
+#[cfg(not(any(hax, eurydice)))]
+mod impl_traits;
+
 // Public interface
 
 pub use types::*;
 
+#[cfg(not(any(hax, eurydice)))]
+pub use impl_traits::*;
+
 pub use crate::constants::KEY_GENERATION_RANDOMNESS_SIZE;
 pub use crate::constants::SIGNING_RANDOMNESS_SIZE;
 
@@ -188,3 +188,62 @@ impl_modified_signing_key_test!(
     ml_dsa_87::sign,
     ml_dsa_87::verify
 );
+
+macro_rules! impl_signature_traits_test {
+    ($name:ident, $scheme:ty, $key_gen:expr, $sign:expr) => {
+        #[test]
+        fn $name() {
+            use libcrux_traits::signature::{SignatureError, Signer, Verifier};
+
+            let key_pair = $key_gen(random_array());
+            let message = random_message();
+            let context = b"context";
+
+            let signature =
+                <$scheme>::sign(&key_pair.signing_key, &message, context, &mut rand::rng())
+                    .expect("Rejection sampling failure probability is < 2⁻¹²⁸");
+            <$scheme>::verify(&key_pair.verification_key, &message, context, &signature)
+                .expect("Verification should pass since the signature was honestly generated");
+            assert!(matches!(
+                <$scheme>::verify(&key_pair.verification_key, &message, b"", &signature),
+                Err(SignatureError::InvalidSignature)
+            ));
+
+            // The randomness is passed through.
+            let randomness = random_array();
+            let signature =
+                <$scheme>::sign_with_randomness(&key_pair.signing_key, &message, b"", &randomness)
+                    .unwrap();
+            let expected = $sign(&key_pair.signing_key, &message, b"", randomness).unwrap();
+            assert_eq!(signature.as_slice(), expected.as_slice());
+
+            assert!(matches!(
+                <$scheme>::sign_with_randomness(&key_pair.signing_key, &message, b"", &[0; 16]),
+                Err(SignatureError::InvalidRandomness)
+            ));
+            assert!(matches!(
+                <$scheme>::sign(&key_pair.signing_key, &message, &[0; 256], &mut rand::rng()),
+                Err(SignatureError::InvalidContext)
+            ));
+        }
+    };
+}
+
+impl_signature_traits_test!(
+    signature_traits_44,
+    libcrux_ml_dsa::MlDsa44,
+    ml_dsa_44::generate_key_pair,
+    ml_dsa_44::sign
+);
+impl_signature_traits_test!(
+    signature_traits_65,
+    libcrux_ml_dsa::MlDsa65,
+    ml_dsa_65::generate_key_pair,
+    ml_dsa_65::sign
+);
+impl_signature_traits_test!(
+    signature_traits_87,
+    libcrux_ml_dsa::MlDsa87,
+    ml_dsa_87::generate_key_pair,
+    ml_dsa_87::sign
+);
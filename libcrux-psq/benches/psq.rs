@@ -1,4 +1,4 @@
-use classic_mceliece_rust::{decapsulate_boxed, encapsulate_boxed};
+use libcrux_psq::classic_mceliece::{ClassicMcEliece, McEliece348864f, McEliece460896f};
 use libcrux_psq::traits::PSQ;
 use libcrux_traits::kem::KEM;
 use std::time::Duration;
@@ -26,16 +26,15 @@ pub fn kem_key_generation(c: &mut Criterion) {
             let _ = libcrux_kem::key_gen(libcrux_kem::Algorithm::XWingKemDraft06, &mut rng);
         })
     });
-    group.bench_function("classic_mceliece_rust (mceliece460896f)", |b| {
+    group.bench_function("libcrux Classic McEliece (mceliece460896f)", |b| {
         b.iter(|| {
-            let _ = classic_mceliece_rust::keypair_boxed(&mut rand_old::thread_rng());
+            let _ = ClassicMcEliece::<McEliece460896f>::generate_key_pair(&mut rng);
         })
     });
 }
 
 pub fn kem_encaps(c: &mut Criterion) {
     let mut rng = rand::rng();
-    let mut old_rng = rand_old::thread_rng();
     let mut group = c.benchmark_group("Raw KEM Encapsulation");
     group.measurement_time(Duration::from_secs(15));
 
@@ -69,11 +68,11 @@ pub fn kem_encaps(c: &mut Criterion) {
         )
     });
 
-    group.bench_function("classic_mceliece_rust (mceliece460896f)", |b| {
+    group.bench_function("libcrux Classic McEliece (mceliece460896f)", |b| {
         b.iter_batched(
-            || classic_mceliece_rust::keypair_boxed(&mut old_rng),
-            |(pk, _sk)| {
-                let _ = encapsulate_boxed(&pk, &mut rand_old::thread_rng());
+            || ClassicMcEliece::<McEliece460896f>::generate_key_pair(&mut rng).unwrap(),
+            |(_sk, pk)| {
+                let _ = ClassicMcEliece::<McEliece460896f>::encapsulate(&pk, &mut rand::rng());
             },
             BatchSize::SmallInput,
         )
@@ -82,7 +81,6 @@ pub fn kem_encaps(c: &mut Criterion) {
 
 pub fn kem_decaps(c: &mut Criterion) {
     let mut rng = rand::rng();
-    let mut old_rng = rand_old::thread_rng();
     let mut group = c.benchmark_group("Raw KEM Decapsulation");
     group.measurement_time(Duration::from_secs(15));
 
@@ -126,14 +124,16 @@ pub fn kem_decaps(c: &mut Criterion) {
         )
     });
 
-    group.bench_function("classic_mceliece_rust (mceliece460896f)", |b| {
+    group.bench_function("libcrux Classic McEliece (mceliece460896f)", |b| {
         b.iter_batched(
             || {
-                let (pk, sk) = classic_mceliece_rust::keypair_boxed(&mut old_rng);
-                let (enc, _ss) = encapsulate_boxed(&pk, &mut old_rng);
+                let (sk, pk) =
+                    ClassicMcEliece::<McEliece460896f>::generate_key_pair(&mut rng).unwrap();
+                let (_ss, enc) =
+                    ClassicMcEliece::<McEliece460896f>::encapsulate(&pk, &mut rng).unwrap();
                 (sk, enc)
             },
-            |(sk, enc)| decapsulate_boxed(&enc, &sk),
+            |(sk, enc)| ClassicMcEliece::<McEliece460896f>::decapsulate(&sk, &enc),
             BatchSize::SmallInput,
         )
     });
@@ -145,7 +145,7 @@ pub fn psq_encaps(c: &mut Criterion) {
     group.measurement_time(Duration::from_secs(15));
 
     macro_rules! encaps_bench {
-        ($name:literal, $implementation:path) => {
+        ($name:literal, $implementation:ty) => {
             group.bench_function($name, |b| {
                 b.iter_batched(
                     || {
@@ -173,8 +173,12 @@ pub fn psq_encaps(c: &mut Criterion) {
         libcrux_psq::impls::XWingKemDraft06
     );
     encaps_bench!(
-        "classic_mceliece_rust (mceliece460896f)",
-        libcrux_psq::classic_mceliece::ClassicMcEliece
+        "libcrux Classic McEliece (mceliece348864f)",
+        ClassicMcEliece<McEliece348864f>
+    );
+    encaps_bench!(
+        "libcrux Classic McEliece (mceliece460896f)",
+        ClassicMcEliece<McEliece460896f>
     );
 }
 
@@ -184,7 +188,7 @@ pub fn psq_decaps(c: &mut Criterion) {
     group.measurement_time(Duration::from_secs(15));
 
     macro_rules! decaps_bench {
-        ($name:literal, $implementation:path) => {
+        ($name:literal, $implementation:ty) => {
             group.bench_function($name, |b| {
                 b.iter_batched(
                     || {
@@ -219,8 +223,12 @@ pub fn psq_decaps(c: &mut Criterion) {
         libcrux_psq::impls::XWingKemDraft06
     );
     decaps_bench!(
-        "classic_mceliece_rust (mceliece460896f)",
-        libcrux_psq::classic_mceliece::ClassicMcEliece
+        "libcrux Classic McEliece (mceliece348864f)",
+        ClassicMcEliece<McEliece348864f>
+    );
+    decaps_bench!(
+        "libcrux Classic McEliece (mceliece460896f)",
+        ClassicMcEliece<McEliece460896f>
     );
 }
 
@@ -7,14 +7,19 @@ use std::{
 
 use clap::Parser;
 use libcrux_psq::{
+    clock::SystemClock,
     cred::{Authenticator, Ed25519},
     impls::MlKem768,
-    psk_registration::{Initiator, InitiatorMsg, Responder, ResponderMsg},
+    psk_derivation::wireguard_psk,
+    psk_registration::{
+        ChaCha20Poly1305, Initiator, InitiatorMsg, Responder, ResponderConfig, ResponderMsg,
+    },
+    psk_store::InMemoryPskStore,
     traits::{Decode, Encode},
 };
 use libcrux_traits::kem::KEM;
 
-/// This is hardcoded for ML-KEM 768, for ClassicMcEliece it would be `524160`.
+/// This is hardcoded for ML-KEM 768, for ClassicMcEliece (`mceliece460896f`) it would be `524160`.
 const RESPONDER_PK_LEN: usize = 1184;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -25,6 +30,15 @@ enum Error {
     Kem,
 }
 
+/// A short fingerprint of `key` for the logs, so that both sides can be
+/// compared without logging the key itself.
+fn fingerprint(key: &[u8]) -> String {
+    libcrux_sha2::sha256(key)[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
 fn print_bt() {
     let bt = backtrace::Backtrace::capture();
     log::error!("{bt}");
@@ -138,12 +152,13 @@ fn initiator(host: String, port: u16, ctx: String) -> Result<(), Error> {
 
     // Generate the first PSQ message
     let mut rng = rand::rng();
-    let (state, msg) = Initiator::send_initial_message::<Ed25519, MlKem768>(
+    let (state, msg) = Initiator::<ChaCha20Poly1305>::send_initial_message::<Ed25519, MlKem768>(
         ctx.as_bytes(),
         Duration::from_secs(3600),
         &responder_pk,
-        sk.as_ref(),
+        &sk,
         credential.as_ref(),
+        &SystemClock,
         &mut rng,
     )
     .unwrap();
@@ -169,7 +184,11 @@ fn initiator(host: String, port: u16, ctx: String) -> Result<(), Error> {
         "Registered psk for: {}",
         String::from_utf8(psk.psk_handle.clone()).unwrap()
     );
-    log::debug!("  with psk: {:x?}", psk.psk);
+    log::debug!("  with psk: {}", fingerprint(&psk.psk));
+    log::debug!(
+        "  WireGuard PresharedKey: {}",
+        fingerprint(&wireguard_psk(&psk.psk)?)
+    );
 
     Ok(())
 }
@@ -180,6 +199,8 @@ fn responder(host: String, port: u16, ctx: String, handle: String) -> Result<(),
     log::info!("Listening for incoming connection ...");
     log::debug!("  {host}:{port}");
 
+    let mut store = InMemoryPskStore::new();
+
     for stream in listener.incoming() {
         let Ok(mut stream) = stream else {
             return Err(Error::Io);
@@ -214,14 +235,18 @@ fn responder(host: String, port: u16, ctx: String, handle: String) -> Result<(),
         stream.read_exact(&mut msg)?;
         let (msg, _) = InitiatorMsg::<MlKem768>::decode(&msg)?;
 
-        let (psk, msg) = Responder::send::<Ed25519, MlKem768>(
+        let (psk, msg) = Responder::send(
+            &ResponderConfig::<Ed25519, MlKem768> {
+                sctx: ctx.as_bytes(),
+                psk_ttl: Duration::from_secs(3600),
+                pqpk: &pk,
+                pqsk: &sk,
+                client_certificate: &initiator_credential,
+            },
             handle.as_bytes(),
-            Duration::from_secs(3600),
-            ctx.as_bytes(),
-            &pk,
-            &sk,
-            &initiator_credential,
             &msg,
+            &mut store,
+            &SystemClock,
         )?;
 
         // Send the message back.
@@ -234,7 +259,11 @@ fn responder(host: String, port: u16, ctx: String, handle: String) -> Result<(),
             "Registered psk for: {}",
             String::from_utf8(psk.psk_handle.clone()).unwrap()
         );
-        log::debug!("  with psk: {:x?}", psk.psk);
+        log::debug!("  with psk: {}", fingerprint(&psk.psk));
+        log::debug!(
+            "  WireGuard PresharedKey: {}",
+            fingerprint(&wireguard_psk(&psk.psk)?)
+        );
     }
 
     Ok(())
@@ -0,0 +1,717 @@
+//! # Certificate Chains
+//!
+//! This module provides a minimal certificate format and an
+//! [`Authenticator`] that validates chains of such certificates up to
+//! a trust anchor, so that a responder only needs to know the keys of
+//! a few certificate authorities instead of every initiator's key.
+//!
+//! A [`Certificate`] binds a subject key to an identity for a validity
+//! period and is signed by its issuer. An initiator presents its leaf
+//! certificate together with any intermediate certificates as its
+//! credential, and signs with the key certified by the leaf.
+//!
+//! All certificates in a chain use the same signature scheme.
+
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use core::{marker::PhantomData, time::Duration};
+
+use rand::CryptoRng;
+
+use crate::{
+    cred::{Authenticator, OutOfBand, SignatureScheme, ValidatedCredential},
+    traits::{Decode, Encode},
+    Error,
+};
+
+/// The maximum number of certificates in a chain, excluding the trust anchor.
+pub const MAX_CHAIN_LEN: usize = 4;
+
+/// The maximum length of an identity in bytes.
+pub const MAX_IDENTITY_LEN: usize = u8::MAX as usize;
+
+const CERTIFICATE_CONTEXT: &[u8] = b"PSQ-Certificate";
+
+/// A signature scheme that can be used to sign certificates.
+pub trait CertSignatureScheme: Authenticator {
+    /// Parse a raw verification key.
+    fn verification_key(bytes: &[u8]) -> Result<Self::VerificationKey, Error>;
+}
+
+impl<S: SignatureScheme> CertSignatureScheme for OutOfBand<S> {
+    fn verification_key(bytes: &[u8]) -> Result<Self::VerificationKey, Error> {
+        let key = bytes.try_into().map_err(|_| Error::CredError)?;
+        S::decode_key(&key)
+    }
+}
+
+/// The signed content of a [`Certificate`].
+///
+/// Times are given in seconds since the UNIX epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TbsCertificate {
+    /// The serial number, unique per issuer
+    pub serial: u64,
+    /// The certificate is not valid before this time
+    pub not_before: u64,
+    /// The certificate is not valid from this time on
+    pub not_after: u64,
+    /// Whether the subject may issue certificates
+    pub ca: bool,
+    /// The subject's identity
+    pub identity: Vec<u8>,
+    /// The identity of the issuer
+    pub issuer: Vec<u8>,
+    /// The subject's serialized verification key
+    pub subject_key: Vec<u8>,
+}
+
+impl TbsCertificate {
+    /// Sign the certificate with the issuer's `signing_key`.
+    pub fn sign<S: CertSignatureScheme>(
+        self,
+        signing_key: &S::SigningKey,
+        rng: &mut impl CryptoRng,
+    ) -> Result<Certificate, Error> {
+        if self.identity.len() > MAX_IDENTITY_LEN
+            || self.issuer.len() > MAX_IDENTITY_LEN
+            || self.subject_key.len() > u16::MAX as usize
+        {
+            return Err(Error::CredError);
+        }
+        let signature = S::sign(signing_key, &self.signature_input(), rng)?;
+
+        Ok(Certificate {
+            tbs: self,
+            signature: signature.as_ref().to_vec(),
+        })
+    }
+
+    fn signature_input(&self) -> Vec<u8> {
+        let mut out = CERTIFICATE_CONTEXT.to_vec();
+        out.extend_from_slice(&self.encode());
+        out
+    }
+
+    fn is_valid_at(&self, now: u64) -> bool {
+        self.not_before <= now && now < self.not_after
+    }
+}
+
+impl Encode for TbsCertificate {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        out.extend_from_slice(&self.serial.to_be_bytes());
+        out.extend_from_slice(&self.not_before.to_be_bytes());
+        out.extend_from_slice(&self.not_after.to_be_bytes());
+        out.push(self.ca as u8);
+        out.push(self.identity.len() as u8);
+        out.extend_from_slice(&self.identity);
+        out.push(self.issuer.len() as u8);
+        out.extend_from_slice(&self.issuer);
+        out.extend_from_slice(&(self.subject_key.len() as u16).to_be_bytes());
+        out.extend_from_slice(&self.subject_key);
+
+        out
+    }
+}
+
+impl Decode for TbsCertificate {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let mut reader = Reader::new(bytes);
+
+        let serial = u64::from_be_bytes(reader.array()?);
+        let not_before = u64::from_be_bytes(reader.array()?);
+        let not_after = u64::from_be_bytes(reader.array()?);
+        let ca = match reader.array::<1>()? {
+            [0] => false,
+            [1] => true,
+            _ => return Err(Error::Decoding),
+        };
+        let identity_len = reader.array::<1>()?[0].into();
+        let identity = reader.take(identity_len)?.to_vec();
+        let issuer_len = reader.array::<1>()?[0].into();
+        let issuer = reader.take(issuer_len)?.to_vec();
+        let key_len = u16::from_be_bytes(reader.array()?).into();
+        let subject_key = reader.take(key_len)?.to_vec();
+
+        let out = Self {
+            serial,
+            not_before,
+            not_after,
+            ca,
+            identity,
+            issuer,
+            subject_key,
+        };
+
+        Ok((out, reader.read))
+    }
+}
+
+/// A signed certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Certificate {
+    /// The signed content
+    pub tbs: TbsCertificate,
+    /// The issuer's signature over the content
+    pub signature: Vec<u8>,
+}
+
+impl Certificate {
+    fn verify<S: CertSignatureScheme>(&self, issuer_key: &S::VerificationKey) -> Result<(), Error> {
+        let signature = S::deserialize_signature(&self.signature)?;
+        S::verify(issuer_key, &signature, &self.tbs.signature_input())
+    }
+}
+
+impl Encode for Certificate {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = self.tbs.encode();
+
+        out.extend_from_slice(&(self.signature.len() as u16).to_be_bytes());
+        out.extend_from_slice(&self.signature);
+
+        out
+    }
+}
+
+impl Decode for Certificate {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (tbs, tbs_len) = TbsCertificate::decode(bytes)?;
+        let mut reader = Reader::new(&bytes[tbs_len..]);
+        let signature_len = u16::from_be_bytes(reader.array()?).into();
+        let signature = reader.take(signature_len)?.to_vec();
+
+        Ok((Self { tbs, signature }, tbs_len + reader.read))
+    }
+}
+
+/// A certificate chain, starting with the leaf certificate.
+///
+/// The chain does not include the trust anchor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateChain(pub Vec<Certificate>);
+
+impl Encode for CertificateChain {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = vec![self.0.len() as u8];
+
+        for certificate in &self.0 {
+            out.extend_from_slice(&certificate.encode());
+        }
+
+        out
+    }
+}
+
+impl Decode for CertificateChain {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let (&count, _) = bytes.split_first().ok_or(Error::Decoding)?;
+        let count = usize::from(count);
+        if count == 0 || count > MAX_CHAIN_LEN {
+            return Err(Error::Decoding);
+        }
+
+        let mut read = 1;
+        let mut certificates = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (certificate, len) = Certificate::decode(&bytes[read..])?;
+            certificates.push(certificate);
+            read += len;
+        }
+
+        Ok((Self(certificates), read))
+    }
+}
+
+/// The trust anchors and revoked certificates known to a responder.
+pub struct TrustStore<S: CertSignatureScheme> {
+    anchors: Vec<(Vec<u8>, S::VerificationKey)>,
+    revoked: BTreeSet<(Vec<u8>, u64)>,
+}
+
+impl<S: CertSignatureScheme> Default for TrustStore<S> {
+    fn default() -> Self {
+        Self {
+            anchors: Vec::new(),
+            revoked: BTreeSet::new(),
+        }
+    }
+}
+
+impl<S: CertSignatureScheme> TrustStore<S> {
+    /// Create an empty trust store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust certificates issued by `identity` under `verification_key`.
+    ///
+    /// Several keys can be added for the same `identity`, e.g. to roll
+    /// over to a new key while certificates issued under the old one are
+    /// still in use.
+    pub fn add_anchor(&mut self, identity: &[u8], verification_key: &[u8]) -> Result<(), Error> {
+        let verification_key = S::verification_key(verification_key)?;
+        self.anchors.push((identity.to_vec(), verification_key));
+
+        Ok(())
+    }
+
+    /// Revoke the certificate with `serial` issued by `issuer`.
+    pub fn revoke(&mut self, issuer: &[u8], serial: u64) {
+        self.revoked.insert((issuer.to_vec(), serial));
+    }
+
+    /// Returns `true` if `certificate` has been revoked.
+    pub fn is_revoked(&self, certificate: &Certificate) -> bool {
+        self.revoked
+            .contains(&(certificate.tbs.issuer.clone(), certificate.tbs.serial))
+    }
+
+    /// Validate `chain` at time `now` and return the leaf's verification key
+    /// and identity.
+    ///
+    /// `now` is the time since the UNIX epoch.
+    pub fn validate(
+        &self,
+        chain: &CertificateChain,
+        now: Duration,
+    ) -> Result<ValidatedCredential<S::VerificationKey>, Error> {
+        let now = now.as_secs();
+        let certificates = &chain.0;
+        if certificates.is_empty() || certificates.len() > MAX_CHAIN_LEN {
+            return Err(Error::CredError);
+        }
+
+        for (i, certificate) in certificates.iter().enumerate() {
+            if !certificate.tbs.is_valid_at(now) {
+                return Err(Error::CredentialExpired);
+            }
+            if self.is_revoked(certificate) {
+                return Err(Error::CredentialRevoked);
+            }
+
+            match certificates.get(i + 1) {
+                Some(issuer) => {
+                    if !issuer.tbs.ca || issuer.tbs.identity != certificate.tbs.issuer {
+                        return Err(Error::CredError);
+                    }
+                    certificate.verify::<S>(&S::verification_key(&issuer.tbs.subject_key)?)?;
+                }
+                None => {
+                    let mut anchor_keys = self
+                        .anchors
+                        .iter()
+                        .filter(|(identity, _)| *identity == certificate.tbs.issuer)
+                        .map(|(_, key)| key);
+                    if !anchor_keys.any(|key| certificate.verify::<S>(key).is_ok()) {
+                        return Err(Error::CredError);
+                    }
+                }
+            }
+        }
+
+        let leaf = &certificates[0].tbs;
+        Ok(ValidatedCredential {
+            verification_key: S::verification_key(&leaf.subject_key)?,
+            identity: leaf.identity.clone(),
+        })
+    }
+}
+
+/// An authenticator where the initiator's credential is a
+/// [`CertificateChain`] leading up to a trust anchor in the responder's
+/// [`TrustStore`].
+///
+/// The identity of a validated credential is the identity of the leaf
+/// certificate.
+///
+/// Credentials have variable length, so this authenticator can not be
+/// used as a component of a [`Hybrid`](crate::cred::Hybrid) authenticator,
+/// which requires credentials of fixed length.
+pub struct CertChain<S> {
+    _scheme: PhantomData<S>,
+}
+
+/// Evaluation fails at compile time if `S` is itself composite.
+const fn cert_chain_id(scheme: u16) -> u16 {
+    assert!(
+        scheme < 0x40,
+        "certificates must use a basic signature scheme"
+    );
+    0x8000 | scheme
+}
+
+const fn max_certificate_len(key_len: usize, sig_len: usize) -> usize {
+    // serial, validity, ca flag, identity, issuer, key, signature
+    8 + 16 + 1 + 2 * (1 + MAX_IDENTITY_LEN) + 2 + key_len + 2 + sig_len
+}
+
+impl<S: CertSignatureScheme> Authenticator for CertChain<S> {
+    type Signature = S::Signature;
+
+    type SigningKey = S::SigningKey;
+
+    type VerificationKey = S::VerificationKey;
+
+    type Credential = Vec<u8>;
+
+    type Certificate = TrustStore<S>;
+
+    const CRED_LEN: usize = 1 + MAX_CHAIN_LEN * max_certificate_len(S::CRED_LEN, S::SIG_LEN);
+
+    const SIG_LEN: usize = S::SIG_LEN;
+
+    const ID: u16 = cert_chain_id(S::ID);
+
+    fn sign(
+        signing_key: &Self::SigningKey,
+        message: &[u8],
+        rng: &mut impl CryptoRng,
+    ) -> Result<Self::Signature, Error> {
+        S::sign(signing_key, message, rng)
+    }
+
+    fn verify(
+        verification_key: &Self::VerificationKey,
+        signature: &Self::Signature,
+        message: &[u8],
+    ) -> Result<(), Error> {
+        S::verify(verification_key, signature, message)
+    }
+
+    fn deserialize_credential(bytes: &[u8]) -> Result<Self::Credential, Error> {
+        let (_, len) = CertificateChain::decode(bytes)?;
+        if len != bytes.len() {
+            return Err(Error::Decoding);
+        }
+
+        Ok(bytes.to_vec())
+    }
+
+    fn deserialize_signature(bytes: &[u8]) -> Result<Self::Signature, Error> {
+        S::deserialize_signature(bytes)
+    }
+
+    fn validate_credential(
+        credential: Self::Credential,
+        trust_store: &Self::Certificate,
+        now: Duration,
+    ) -> Result<ValidatedCredential<Self::VerificationKey>, Error> {
+        let (chain, _) = CertificateChain::decode(&credential)?;
+        trust_store.validate(&chain, now)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    read: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, read: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let out = self
+            .bytes
+            .get(self.read..self.read + len)
+            .ok_or(Error::Decoding)?;
+        self.read += len;
+        Ok(out)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        self.take(N)?.try_into().map_err(|_| Error::Decoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libcrux_traits::kem::KEM;
+
+    use super::*;
+    use crate::{
+        clock::{Clock, SystemClock},
+        cred::{Ed25519, MlDsa65},
+        impls::MlKem768,
+        psk_registration::{ChaCha20Poly1305, Initiator, Responder, ResponderConfig},
+        psk_store::{InMemoryPskStore, PskStore},
+    };
+
+    fn now() -> u64 {
+        SystemClock.now().unwrap().as_secs()
+    }
+
+    fn tbs(serial: u64, identity: &[u8], issuer: &[u8], key: &[u8], ca: bool) -> TbsCertificate {
+        TbsCertificate {
+            serial,
+            not_before: now() - 60,
+            not_after: now() + 3600,
+            ca,
+            identity: identity.to_vec(),
+            issuer: issuer.to_vec(),
+            subject_key: key.to_vec(),
+        }
+    }
+
+    struct Pki {
+        root_pk: [u8; 32],
+        intermediate_sk: libcrux_ed25519::SigningKey,
+        intermediate: Certificate,
+        leaf_sk: libcrux_ed25519::SigningKey,
+        leaf_pk: [u8; 32],
+    }
+
+    fn ed25519_pki() -> Pki {
+        let mut rng = rand::rng();
+        let (root_sk, root_pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let (intermediate_sk, intermediate_pk) =
+            libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let (leaf_sk, leaf_pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+
+        let intermediate = tbs(1, b"intermediate", b"root", intermediate_pk.as_ref(), true)
+            .sign::<Ed25519>(&root_sk, &mut rng)
+            .unwrap();
+
+        Pki {
+            root_pk: *root_pk.as_ref(),
+            intermediate_sk,
+            intermediate,
+            leaf_sk,
+            leaf_pk: *leaf_pk.as_ref(),
+        }
+    }
+
+    fn register<S: CertSignatureScheme>(
+        signing_key: &S::SigningKey,
+        chain: &CertificateChain,
+        trust_store: &TrustStore<S>,
+    ) -> Result<Vec<u8>, Error> {
+        let mut rng = rand::rng();
+        let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+        let sctx = b"test context";
+        let mut store = InMemoryPskStore::new();
+
+        let (initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<CertChain<S>, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                signing_key,
+                &chain.encode(),
+                &SystemClock,
+                &mut rng,
+            )?;
+
+        let (registered, responder_msg) = Responder::send(
+            &ResponderConfig::<CertChain<S>, MlKem768> {
+                sctx,
+                psk_ttl: Duration::from_secs(3600),
+                pqpk: &receiver_pqpk,
+                pqsk: &receiver_pqsk,
+                client_certificate: trust_store,
+            },
+            b"test handle",
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        )?;
+        assert_eq!(
+            initiator.complete_handshake(&responder_msg)?.psk,
+            registered.psk
+        );
+
+        let identity = registered.identity.unwrap();
+        assert_eq!(store.get(b"test handle").unwrap().identity, identity);
+        Ok(identity)
+    }
+
+    #[test]
+    fn encoding() {
+        let pki = ed25519_pki();
+        let chain = CertificateChain(vec![pki.intermediate.clone(), pki.intermediate]);
+        let encoded = chain.encode();
+
+        let (decoded, len) = CertificateChain::decode(&encoded).unwrap();
+        assert_eq!(len, encoded.len());
+        assert_eq!(decoded, chain);
+
+        for len in 0..encoded.len() {
+            assert!(CertificateChain::decode(&encoded[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn registration_ed25519_chain() {
+        let mut rng = rand::rng();
+        let pki = ed25519_pki();
+        let leaf = tbs(7, b"initiator", b"intermediate", &pki.leaf_pk, false)
+            .sign::<Ed25519>(&pki.intermediate_sk, &mut rng)
+            .unwrap();
+        let chain = CertificateChain(vec![leaf, pki.intermediate.clone()]);
+
+        let mut trust_store = TrustStore::<Ed25519>::new();
+        register(&pki.leaf_sk, &chain, &trust_store).unwrap_err();
+
+        trust_store.add_anchor(b"root", &pki.root_pk).unwrap();
+        assert_eq!(
+            register(&pki.leaf_sk, &chain, &trust_store).unwrap(),
+            b"initiator"
+        );
+
+        // The initiator must sign with the certified key.
+        let (other_sk, _) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        register(&other_sk, &chain, &trust_store).unwrap_err();
+
+        // The intermediate certificate is required.
+        let chain = CertificateChain(vec![chain.0[0].clone()]);
+        register(&pki.leaf_sk, &chain, &trust_store).unwrap_err();
+    }
+
+    #[test]
+    fn expired_and_revoked() {
+        let mut rng = rand::rng();
+        let pki = ed25519_pki();
+        let mut trust_store = TrustStore::<Ed25519>::new();
+        trust_store.add_anchor(b"root", &pki.root_pk).unwrap();
+
+        let mut expired = tbs(7, b"initiator", b"intermediate", &pki.leaf_pk, false);
+        expired.not_after = now() - 1;
+        let expired = expired
+            .sign::<Ed25519>(&pki.intermediate_sk, &mut rng)
+            .unwrap();
+        let chain = CertificateChain(vec![expired, pki.intermediate.clone()]);
+        assert!(matches!(
+            trust_store.validate(&chain, SystemClock.now().unwrap()),
+            Err(Error::CredentialExpired)
+        ));
+
+        let leaf = tbs(8, b"initiator", b"intermediate", &pki.leaf_pk, false)
+            .sign::<Ed25519>(&pki.intermediate_sk, &mut rng)
+            .unwrap();
+        let chain = CertificateChain(vec![leaf, pki.intermediate.clone()]);
+        trust_store
+            .validate(&chain, SystemClock.now().unwrap())
+            .unwrap();
+
+        trust_store.revoke(b"intermediate", 8);
+        assert!(matches!(
+            trust_store.validate(&chain, SystemClock.now().unwrap()),
+            Err(Error::CredentialRevoked)
+        ));
+        assert!(matches!(
+            register(&pki.leaf_sk, &chain, &trust_store),
+            Err(Error::CredentialRevoked)
+        ));
+
+        // Revoking the intermediate invalidates everything it issued.
+        let leaf = tbs(9, b"initiator", b"intermediate", &pki.leaf_pk, false)
+            .sign::<Ed25519>(&pki.intermediate_sk, &mut rng)
+            .unwrap();
+        let chain = CertificateChain(vec![leaf, pki.intermediate.clone()]);
+        trust_store.revoke(b"root", 1);
+        assert!(matches!(
+            trust_store.validate(&chain, SystemClock.now().unwrap()),
+            Err(Error::CredentialRevoked)
+        ));
+    }
+
+    #[test]
+    fn anchor_rollover() {
+        let mut rng = rand::rng();
+        let pki = ed25519_pki();
+        let (new_root_sk, new_root_pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+
+        let mut trust_store = TrustStore::<Ed25519>::new();
+        trust_store.add_anchor(b"root", &pki.root_pk).unwrap();
+        trust_store
+            .add_anchor(b"root", new_root_pk.as_ref())
+            .unwrap();
+
+        let leaf = tbs(7, b"initiator", b"intermediate", &pki.leaf_pk, false)
+            .sign::<Ed25519>(&pki.intermediate_sk, &mut rng)
+            .unwrap();
+        let old_chain = CertificateChain(vec![leaf.clone(), pki.intermediate.clone()]);
+        let validated = trust_store
+            .validate(&old_chain, SystemClock.now().unwrap())
+            .unwrap();
+        assert_eq!(validated.identity, b"initiator");
+
+        // The intermediate is reissued under the new root key.
+        let new_intermediate = pki
+            .intermediate
+            .tbs
+            .clone()
+            .sign::<Ed25519>(&new_root_sk, &mut rng)
+            .unwrap();
+        let new_chain = CertificateChain(vec![leaf.clone(), new_intermediate]);
+        assert_eq!(
+            register(&pki.leaf_sk, &new_chain, &trust_store).unwrap(),
+            b"initiator"
+        );
+
+        // Neither root key signed this one.
+        let (other_sk, _) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let forged = pki
+            .intermediate
+            .tbs
+            .sign::<Ed25519>(&other_sk, &mut rng)
+            .unwrap();
+        let forged_chain = CertificateChain(vec![leaf, forged]);
+        assert!(trust_store
+            .validate(&forged_chain, SystemClock.now().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn leaf_can_not_issue() {
+        let mut rng = rand::rng();
+        let pki = ed25519_pki();
+        let mut trust_store = TrustStore::<Ed25519>::new();
+        trust_store.add_anchor(b"root", &pki.root_pk).unwrap();
+
+        let leaf = tbs(7, b"initiator", b"intermediate", &pki.leaf_pk, false)
+            .sign::<Ed25519>(&pki.intermediate_sk, &mut rng)
+            .unwrap();
+        let (other_sk, other_pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let other = tbs(1, b"other", b"initiator", other_pk.as_ref(), false)
+            .sign::<Ed25519>(&pki.leaf_sk, &mut rng)
+            .unwrap();
+        let chain = CertificateChain(vec![other, leaf, pki.intermediate]);
+
+        register(&other_sk, &chain, &trust_store).unwrap_err();
+    }
+
+    #[test]
+    fn registration_mldsa65_chain() {
+        let mut rng = rand::rng();
+        let root = libcrux_ml_dsa::ml_dsa_65::generate_key_pair([1; 32]);
+        let leaf_keys = libcrux_ml_dsa::ml_dsa_65::generate_key_pair([2; 32]);
+
+        let leaf = tbs(
+            1,
+            b"initiator",
+            b"root",
+            leaf_keys.verification_key.as_slice(),
+            false,
+        )
+        .sign::<MlDsa65>(&root.signing_key, &mut rng)
+        .unwrap();
+        let chain = CertificateChain(vec![leaf]);
+
+        let mut trust_store = TrustStore::<MlDsa65>::new();
+        trust_store
+            .add_anchor(b"root", root.verification_key.as_slice())
+            .unwrap();
+        register(&leaf_keys.signing_key, &chain, &trust_store).unwrap();
+
+        trust_store.revoke(b"root", 1);
+        assert!(matches!(
+            register(&leaf_keys.signing_key, &chain, &trust_store),
+            Err(Error::CredentialRevoked)
+        ));
+    }
+}
@@ -1,87 +1,284 @@
-//! PSQ implementation backed by `classic-mceliece-rust`
+//! PSQ implementation using Classic McEliece
 //!
-//! This module implements PSQ using ClassicMcEliece (parameter set
-//! `mceliece460896f`) as the underlying KEM.
+//! This module implements PSQ using Classic McEliece as the underlying
+//! KEM. The parameter set is chosen with the type parameter of
+//! [`ClassicMcEliece`], which defaults to [`McEliece460896f`]:
+//!
+//! | Parameter set        | Public key    | Ciphertext |
+//! |----------------------|---------------|------------|
+//! | [`McEliece348864f`]  | 261120 bytes  | 96 bytes   |
+//! | [`McEliece460896f`]  | 524160 bytes  | 156 bytes  |
+//! | [`McEliece6688128f`] | 1044992 bytes | 208 bytes  |
+//! | [`McEliece6960119f`] | 1047319 bytes | 194 bytes  |
+//! | [`McEliece8192128f`] | 1357824 bytes | 208 bytes  |
+//!
+//! Only the semi-systematic `f` parameter sets are supported, since their
+//! key generation succeeds far more often. Keys, ciphertexts and shared
+//! secrets are compatible with the Round 4 submission to NIST.
 
-use classic_mceliece_rust::{
-    decapsulate_boxed, encapsulate_boxed, keypair_boxed, Ciphertext, PublicKey, SecretKey,
-    SharedSecret,
-};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
+use libcrux_sha3::shake256;
 use libcrux_traits::kem::{KEMError, KeyPair, KEM};
 
 use crate::traits::*;
 
-/// A code-based KEM based on the McEliece cryptosystem.
-pub struct ClassicMcEliece;
+mod benes;
+mod decrypt;
+mod encrypt;
+mod gf;
+mod keygen;
+mod sort;
 
-impl Encode for PublicKey<'_> {
-    fn encode(&self) -> Vec<u8> {
-        self.as_ref().to_vec()
+/// A Classic McEliece parameter set.
+///
+/// This trait is sealed, the parameter sets are [`McEliece348864f`],
+/// [`McEliece460896f`], [`McEliece6688128f`], [`McEliece6960119f`] and
+/// [`McEliece8192128f`].
+pub trait ParameterSet: private::Seal {
+    /// The degree `m` of the field `GF(2^m)`.
+    const GFBITS: usize;
+    /// The code length `n`.
+    const SYS_N: usize;
+    /// The number of errors `t` the code corrects.
+    const SYS_T: usize;
+    /// The polynomial defining `GF(2^m)`, including the term `x^m`.
+    const GF_POLY: u32;
+    /// The polynomial defining `GF((2^m)^t)`, without the term `y^t`, as
+    /// pairs of degree and coefficient.
+    const EXT_POLY: &'static [(usize, u16)];
+    /// The identifier of the parameter set in PSQ messages.
+    const KEM_ID: u16;
+
+    /// The mask of the bits of an element of `GF(2^m)`.
+    const GFMASK: u16 = (1 << Self::GFBITS) - 1;
+    /// The length of the encoded Goppa polynomial.
+    const IRR_BYTES: usize = Self::SYS_T * 2;
+    /// The length of the control bits of the Beneš network.
+    const COND_BYTES: usize = (1 << (Self::GFBITS - 4)) * (2 * Self::GFBITS - 1);
+    /// The number of rows of the public key.
+    const PK_NROWS: usize = Self::SYS_T * Self::GFBITS;
+    /// The number of columns of the public key.
+    const PK_NCOLS: usize = Self::SYS_N - Self::PK_NROWS;
+    /// The length of a row of the public key.
+    const PK_ROW_BYTES: usize = Self::PK_NCOLS.div_ceil(8);
+    /// The length of the syndrome, i.e. of a ciphertext.
+    const SYND_BYTES: usize = Self::PK_NROWS.div_ceil(8);
+
+    /// The length of a public key.
+    const PUBLIC_KEY_LEN: usize = Self::PK_NROWS * Self::PK_ROW_BYTES;
+    /// The length of a secret key.
+    const SECRET_KEY_LEN: usize = 40 + Self::IRR_BYTES + Self::COND_BYTES + Self::SYS_N / 8;
+}
+
+/// The parameter set `mceliece348864f`, for NIST security category 1.
+pub struct McEliece348864f;
+
+impl private::Seal for McEliece348864f {}
+
+impl ParameterSet for McEliece348864f {
+    const GFBITS: usize = 12;
+    const SYS_N: usize = 3488;
+    const SYS_T: usize = 64;
+    const GF_POLY: u32 = 0x1009;
+    const EXT_POLY: &'static [(usize, u16)] = &[(3, 1), (1, 1), (0, 2)];
+    const KEM_ID: u16 = 0xff00;
+}
+
+/// The parameter set `mceliece460896f`, for NIST security category 3.
+pub struct McEliece460896f;
+
+impl private::Seal for McEliece460896f {}
+
+impl ParameterSet for McEliece460896f {
+    const GFBITS: usize = 13;
+    const SYS_N: usize = 4608;
+    const SYS_T: usize = 96;
+    const GF_POLY: u32 = 0x201b;
+    const EXT_POLY: &'static [(usize, u16)] = &[(10, 1), (9, 1), (6, 1), (0, 1)];
+    const KEM_ID: u16 = 0xff01;
+}
+
+/// The parameter set `mceliece6688128f`, for NIST security category 5.
+pub struct McEliece6688128f;
+
+impl private::Seal for McEliece6688128f {}
+
+impl ParameterSet for McEliece6688128f {
+    const GFBITS: usize = 13;
+    const SYS_N: usize = 6688;
+    const SYS_T: usize = 128;
+    const GF_POLY: u32 = 0x201b;
+    const EXT_POLY: &'static [(usize, u16)] = &[(7, 1), (2, 1), (1, 1), (0, 1)];
+    const KEM_ID: u16 = 0xff02;
+}
+
+/// The parameter set `mceliece6960119f`, for NIST security category 5.
+pub struct McEliece6960119f;
+
+impl private::Seal for McEliece6960119f {}
+
+impl ParameterSet for McEliece6960119f {
+    const GFBITS: usize = 13;
+    const SYS_N: usize = 6960;
+    const SYS_T: usize = 119;
+    const GF_POLY: u32 = 0x201b;
+    const EXT_POLY: &'static [(usize, u16)] = &[(8, 1), (0, 1)];
+    const KEM_ID: u16 = 0xff03;
+}
+
+/// The parameter set `mceliece8192128f`, for NIST security category 5.
+pub struct McEliece8192128f;
+
+impl private::Seal for McEliece8192128f {}
+
+impl ParameterSet for McEliece8192128f {
+    const GFBITS: usize = 13;
+    const SYS_N: usize = 8192;
+    const SYS_T: usize = 128;
+    const GF_POLY: u32 = 0x201b;
+    const EXT_POLY: &'static [(usize, u16)] = &[(7, 1), (2, 1), (1, 1), (0, 1)];
+    const KEM_ID: u16 = 0xff04;
+}
+
+/// A code-based KEM based on the McEliece cryptosystem, with parameter
+/// set `P`.
+pub struct ClassicMcEliece<P: ParameterSet = McEliece460896f>(PhantomData<P>);
+
+/// A Classic McEliece public key.
+pub struct PublicKey<P: ParameterSet>(Vec<u8>, PhantomData<P>);
+
+/// A Classic McEliece secret key.
+pub struct SecretKey<P: ParameterSet>(Vec<u8>, PhantomData<P>);
+
+/// A Classic McEliece ciphertext.
+pub struct Ciphertext<P: ParameterSet>(Vec<u8>, PhantomData<P>);
+
+/// A Classic McEliece shared secret.
+pub struct SharedSecret([u8; 32]);
+
+impl<P: ParameterSet> AsRef<[u8]> for PublicKey<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
     }
 }
 
-impl Encode for SharedSecret<'_> {
-    fn encode(&self) -> Vec<u8> {
-        self.as_ref().to_vec()
+impl<P: ParameterSet> AsRef<[u8]> for SecretKey<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
     }
 }
 
-impl Encode for Ciphertext {
-    fn encode(&self) -> Vec<u8> {
-        self.as_ref().to_vec()
+impl<P: ParameterSet> AsRef<[u8]> for Ciphertext<P> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for SharedSecret {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
     }
 }
 
-impl private::Seal for ClassicMcEliece {}
+impl<P: ParameterSet> TryFrom<&[u8]> for PublicKey<P> {
+    type Error = crate::Error;
 
-// This is only here because `classic-mceliece-rust` still depends on
-// `rand` version `0.8.0`.
-struct McElieceRng<'a, T: rand::CryptoRng> {
-    inner_rng: &'a mut T,
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != P::PUBLIC_KEY_LEN {
+            return Err(crate::Error::Decoding);
+        }
+
+        Ok(Self(bytes.to_vec(), PhantomData))
+    }
 }
 
-impl<'a, T: rand::CryptoRng> McElieceRng<'a, T> {
-    fn new(inner_rng: &'a mut T) -> Self {
-        Self { inner_rng }
+impl<P: ParameterSet> TryFrom<&[u8]> for SecretKey<P> {
+    type Error = crate::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() != P::SECRET_KEY_LEN {
+            return Err(crate::Error::Decoding);
+        }
+
+        Ok(Self(bytes.to_vec(), PhantomData))
     }
 }
 
-impl<T: rand::CryptoRng> rand_old::RngCore for McElieceRng<'_, T> {
-    fn next_u32(&mut self) -> u32 {
-        self.inner_rng.next_u32()
+impl<P: ParameterSet> Drop for SecretKey<P> {
+    fn drop(&mut self) {
+        self.0.fill(0);
     }
-    fn next_u64(&mut self) -> u64 {
-        self.inner_rng.next_u64()
+}
+
+impl<P: ParameterSet> Encode for PublicKey<P> {
+    fn encode(&self) -> Vec<u8> {
+        self.0.clone()
     }
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        self.inner_rng.fill_bytes(dest)
+}
+
+impl Encode for SharedSecret {
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_vec()
     }
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_old::Error> {
-        self.inner_rng.fill_bytes(dest);
-        Ok(())
+}
+
+impl<P: ParameterSet> Encode for Ciphertext<P> {
+    fn encode(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+impl<P: ParameterSet> private::Seal for ClassicMcEliece<P> {}
+
+/// Zero if the padding bits of the rows of `pk` are zero, `0xFF`
+/// otherwise.
+fn check_pk_padding<P: ParameterSet>(pk: &[u8]) -> u8 {
+    if P::PK_NCOLS % 8 == 0 {
+        return 0;
     }
+
+    let b = pk
+        .chunks_exact(P::PK_ROW_BYTES)
+        .fold(0, |b, row| b | row[P::PK_ROW_BYTES - 1])
+        >> (P::PK_NCOLS % 8);
+
+    ((b as u16).wrapping_sub(1) >> 8) as u8 ^ 0xff
 }
 
-impl<T: rand::CryptoRng> rand_old::CryptoRng for McElieceRng<'_, T> {}
+/// Zero if the padding bits of `c` are zero, `0xFF` otherwise.
+fn check_c_padding<P: ParameterSet>(c: &[u8]) -> u8 {
+    if P::PK_NROWS % 8 == 0 {
+        return 0;
+    }
+
+    let b = c[P::SYND_BYTES - 1] >> (P::PK_NROWS % 8);
 
-impl KEM for ClassicMcEliece {
+    ((b as u16).wrapping_sub(1) >> 8) as u8 ^ 0xff
+}
+
+impl<P: ParameterSet> KEM for ClassicMcEliece<P> {
     /// The KEM's ciphertext.
-    type Ciphertext = Ciphertext;
+    type Ciphertext = Ciphertext<P>;
     /// The KEM's shared secret.
-    type SharedSecret = SharedSecret<'static>;
+    type SharedSecret = SharedSecret;
     /// The KEM's encapsulation key.
-    type EncapsulationKey = PublicKey<'static>;
+    type EncapsulationKey = PublicKey<P>;
     /// The KEM's decapsulation key.
-    type DecapsulationKey = SecretKey<'static>;
+    type DecapsulationKey = SecretKey<P>;
 
     /// Generate a pair of encapsulation and decapsulation keys.
     fn generate_key_pair(
         rng: &mut impl rand::CryptoRng,
-    ) -> Result<KeyPair<SecretKey<'static>, PublicKey<'static>>, KEMError> {
-        let mut rng = McElieceRng::new(rng);
-        let (pk, sk) = keypair_boxed(&mut rng);
-        Ok((sk, pk))
+    ) -> Result<KeyPair<SecretKey<P>, PublicKey<P>>, KEMError> {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+
+        let (pk, sk) = keygen::keypair::<P>(&seed);
+        seed.fill(0);
+
+        Ok((SecretKey(sk, PhantomData), PublicKey(pk, PhantomData)))
     }
 
     /// Encapsulate a shared secret towards a given encapsulation key.
@@ -89,38 +286,235 @@ impl KEM for ClassicMcEliece {
         ek: &Self::EncapsulationKey,
         rng: &mut impl rand::CryptoRng,
     ) -> Result<(Self::SharedSecret, Self::Ciphertext), KEMError> {
-        let mut rng = McElieceRng::new(rng);
-        let (enc, ss) = encapsulate_boxed(ek, &mut rng);
-        Ok((ss, enc))
+        if check_pk_padding::<P>(&ek.0) != 0 {
+            return Err(KEMError::Encapsulation);
+        }
+
+        let (e, c) = encrypt::encrypt::<P>(&ek.0, rng);
+
+        let mut one_ec = Vec::with_capacity(1 + e.len() + c.len());
+        one_ec.push(1);
+        one_ec.extend_from_slice(&e);
+        one_ec.extend_from_slice(&c);
+        let key = shake256::<32>(&one_ec);
+        one_ec.fill(0);
+
+        Ok((SharedSecret(key), Ciphertext(c, PhantomData)))
     }
 
     /// Decapsulate a shared secret.
+    ///
+    /// An invalid ciphertext yields a pseudorandom shared secret
+    /// (implicit rejection) rather than an error.
     fn decapsulate(
         dk: &Self::DecapsulationKey,
         ctxt: &Self::Ciphertext,
     ) -> Result<Self::SharedSecret, KEMError> {
-        let ss = decapsulate_boxed(ctxt, dk);
-        Ok(ss)
+        let sk = &dk.0[40..];
+        let s = &sk[P::IRR_BYTES + P::COND_BYTES..];
+        let c = &ctxt.0;
+        let padding = check_c_padding::<P>(c);
+
+        let (e, mask) = decrypt::decrypt::<P>(sk, c);
+
+        let mut preimage = Vec::with_capacity(1 + e.len() + c.len());
+        preimage.push(mask & 1);
+        preimage.extend(e.iter().zip(s).map(|(e, s)| (!mask & s) | (mask & e)));
+        preimage.extend_from_slice(c);
+        let mut key = shake256::<32>(&preimage);
+        preimage.fill(0);
+
+        for k in key.iter_mut() {
+            *k |= padding;
+        }
+
+        Ok(SharedSecret(key))
     }
 }
 
-impl PSQ for ClassicMcEliece {
+impl<P: ParameterSet> DecodeCiphertext for ClassicMcEliece<P> {
+    const CIPHERTEXT_LEN: usize = P::SYND_BYTES;
+
+    fn decode_ciphertext(bytes: &[u8]) -> Result<Self::Ciphertext, crate::Error> {
+        if bytes.len() != Self::CIPHERTEXT_LEN {
+            return Err(crate::Error::Decoding);
+        }
+
+        Ok(Ciphertext(bytes.to_vec(), PhantomData))
+    }
+}
+
+impl<P: ParameterSet> PSQ for ClassicMcEliece<P> {
     type InnerKEM = Self;
+
+    // There are no registered identifiers, so we use ones from the
+    // private use range.
+    const KEM_ID: u16 = P::KEM_ID;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use aes::cipher::{BlockEncrypt, KeyInit};
+    use libcrux_sha2::sha256;
+
+    /// The AES-256 CTR DRBG of the NIST KAT generator, without a
+    /// personalization string.
+    struct NistDrbg {
+        key: [u8; 32],
+        v: [u8; 16],
+    }
+
+    impl NistDrbg {
+        fn new(entropy_input: &[u8; 48]) -> Self {
+            let mut drbg = Self {
+                key: [0u8; 32],
+                v: [0u8; 16],
+            };
+            drbg.update(Some(entropy_input));
+            drbg
+        }
+
+        fn next_block(&mut self) -> [u8; 16] {
+            self.v = (u128::from_be_bytes(self.v).wrapping_add(1)).to_be_bytes();
+            let mut block = self.v.into();
+            aes::Aes256::new(&self.key.into()).encrypt_block(&mut block);
+            block.into()
+        }
+
+        fn update(&mut self, provided_data: Option<&[u8; 48]>) {
+            let mut temp = [0u8; 48];
+            for chunk in temp.chunks_exact_mut(16) {
+                chunk.copy_from_slice(&self.next_block());
+            }
+            if let Some(provided_data) = provided_data {
+                for (t, p) in temp.iter_mut().zip(provided_data) {
+                    *t ^= p;
+                }
+            }
+            self.key.copy_from_slice(&temp[..32]);
+            self.v.copy_from_slice(&temp[32..]);
+        }
+    }
+
+    impl rand::RngCore for NistDrbg {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0u8; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        /// `randombytes` of the KAT generator, so the output depends on how
+        /// it is split into calls.
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            for chunk in dst.chunks_mut(16) {
+                let block = self.next_block();
+                chunk.copy_from_slice(&block[..chunk.len()]);
+            }
+            self.update(None);
+        }
+    }
+
+    impl rand::CryptoRng for NistDrbg {}
+
+    fn hex(bytes: &[u8]) -> alloc::string::String {
+        bytes.iter().map(|b| alloc::format!("{b:02X}")).collect()
+    }
+
+    /// Check the first entry of the Round 4 `kat_kem.rsp`, by the SHA-256 of
+    /// the entry that PQClean lists as `nistkat-sha256` in its `META.yml`.
+    ///
+    /// Also check that a modified ciphertext is rejected with the key
+    /// `SHAKE256(0 || s || C)`.
+    fn nist_kat<P: ParameterSet>(kat_sha256: &str) {
+        let entropy_input = core::array::from_fn(|i| i as u8);
+        let mut seed = [0u8; 48];
+        rand::RngCore::fill_bytes(&mut NistDrbg::new(&entropy_input), &mut seed);
+        let mut rng = NistDrbg::new(&seed);
+
+        let (dk, ek) = ClassicMcEliece::<P>::generate_key_pair(&mut rng).unwrap();
+        assert_eq!(ek.as_ref().len(), P::PUBLIC_KEY_LEN);
+        assert_eq!(dk.as_ref().len(), P::SECRET_KEY_LEN);
+
+        let (shared, ctxt) = ClassicMcEliece::<P>::encapsulate(&ek, &mut rng).unwrap();
+        let decapsulated = ClassicMcEliece::<P>::decapsulate(&dk, &ctxt).unwrap();
+        assert_eq!(decapsulated.as_ref(), shared.as_ref());
+
+        let kat = alloc::format!(
+            "count = 0\nseed = {}\npk = {}\nsk = {}\nct = {}\nss = {}\n",
+            hex(&seed),
+            hex(ek.as_ref()),
+            hex(dk.as_ref()),
+            hex(ctxt.as_ref()),
+            hex(shared.as_ref()),
+        );
+        assert_eq!(hex(&sha256(kat.as_bytes())).to_lowercase(), kat_sha256);
+
+        let mut tampered = ctxt.encode();
+        tampered[0] ^= 1;
+        let rejected = ClassicMcEliece::<P>::decapsulate(
+            &dk,
+            &ClassicMcEliece::<P>::decode_ciphertext(&tampered).unwrap(),
+        )
+        .unwrap();
+        let s = &dk.as_ref()[P::SECRET_KEY_LEN - P::SYS_N / 8..];
+        let mut preimage = alloc::vec![0u8];
+        preimage.extend_from_slice(s);
+        preimage.extend_from_slice(&tampered);
+        assert_eq!(rejected.as_ref(), shake256::<32>(&preimage));
+    }
+
+    #[test]
+    fn nist_kat_348864f() {
+        nist_kat::<McEliece348864f>(
+            "9b17b21becc1d3acf9df0a6d87875790259c075abeb50f97ea254c8d29395a41",
+        );
+    }
+
+    #[test]
+    fn nist_kat_460896f() {
+        nist_kat::<McEliece460896f>(
+            "a027478ab01849de3d492176ea95c071110bcb8f7e4e6afa136a30cd1a1f6074",
+        );
+    }
+
+    #[test]
+    fn nist_kat_6688128f() {
+        nist_kat::<McEliece6688128f>(
+            "1fa84d1abd8ef104cdcf75277ca4399475945e97087dde3183a09415e1d61987",
+        );
+    }
+
+    #[test]
+    fn nist_kat_6960119f() {
+        nist_kat::<McEliece6960119f>(
+            "9a586a40d1af4819efb3f7343a05c260bd27d7e5d450945fee0ace5593761c3b",
+        );
+    }
+
+    #[test]
+    fn nist_kat_8192128f() {
+        nist_kat::<McEliece8192128f>(
+            "f497b217022465568f0ed6c7987c462b74ba2d3e39f963ac357436c727ed9bdb",
+        );
+    }
+
     #[test]
     fn simple_classic_mceliece() {
         let mut rng = rand::rng();
-        let (sk, pk) = ClassicMcEliece::generate_key_pair(&mut rng).unwrap();
+        let (sk, pk) = <ClassicMcEliece>::generate_key_pair(&mut rng).unwrap();
         let sctx = b"test context";
         let (psk_initiator, message) =
-            ClassicMcEliece::encapsulate_psq(&pk, sctx, &mut rng).unwrap();
+            <ClassicMcEliece>::encapsulate_psq(&pk, sctx, &mut rng).unwrap();
 
-        let psk_responder = ClassicMcEliece::decapsulate_psq(&sk, &pk, &message, sctx).unwrap();
+        let psk_responder = <ClassicMcEliece>::decapsulate_psq(&sk, &pk, &message, sctx).unwrap();
         assert_eq!(psk_initiator, psk_responder);
     }
 }
@@ -0,0 +1,246 @@
+//! Beneš networks: computing the control bits for a permutation and
+//! applying them.
+//!
+//! The control bits are computed with the algorithm from D. J. Bernstein,
+//! ["Verified fast formulas for control bits for permutation
+//! networks"](https://cr.yp.to/papers/controlbits-20200923.pdf).
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{
+    gf::{bitrev, Gf},
+    sort::int32_sort,
+    ParameterSet,
+};
+
+/// Apply one layer of the network with stride `2^s` to `p`, using the
+/// control bits in `cb`.
+fn layer(p: &mut [i16], cb: &[u8], s: usize) {
+    let stride = 1 << s;
+    let mut index = 0;
+
+    for i in (0..p.len()).step_by(2 * stride) {
+        for j in i..i + stride {
+            let m = -(((cb[index >> 3] >> (index & 7)) & 1) as i16);
+            let d = (p[j] ^ p[j + stride]) & m;
+            p[j] ^= d;
+            p[j + stride] ^= d;
+            index += 1;
+        }
+    }
+}
+
+/// Apply the network configured by `cb` for `2^w` elements to `p`.
+fn apply(p: &mut [i16], cb: &[u8], w: usize) {
+    let layer_bytes = p.len() >> 4;
+    let mut layers = cb.chunks(layer_bytes);
+
+    for s in (0..w).chain((0..w - 1).rev()) {
+        layer(p, layers.next().unwrap(), s);
+    }
+}
+
+/// One recursion step of the control bit computation, writing the
+/// control bits for the permutation `pi` of `n = 2^w` elements to
+/// positions `pos`, `pos + step`, ... of `out`.
+///
+/// `temp` needs room for `2n` elements.
+fn cbrecursion(
+    out: &mut [u8],
+    mut pos: usize,
+    step: usize,
+    pi: &[i16],
+    w: usize,
+    n: usize,
+    temp: &mut [i32],
+) {
+    if w == 1 {
+        out[pos >> 3] ^= (pi[0] << (pos & 7)) as u8;
+        return;
+    }
+
+    let (a, b) = temp[..2 * n].split_at_mut(n);
+
+    for x in 0..n {
+        a[x] = (((pi[x] ^ 1) as i32) << 16) | pi[x ^ 1] as i32;
+    }
+    int32_sort(a); // A = (id<<16)+pibar
+
+    for x in 0..n {
+        let px = a[x] & 0xffff;
+        let cx = px.min(x as i32);
+        b[x] = (px << 16) | cx;
+    }
+    // B = (p<<16)+c
+
+    for (x, ax) in a.iter_mut().enumerate() {
+        *ax = (*ax << 16) | x as i32; // A = (pibar<<16)+id
+    }
+    int32_sort(a); // A = (id<<16)+pibar^-1
+
+    for x in 0..n {
+        a[x] = (a[x] << 16) + (b[x] >> 16); // A = (pibar^(-1)<<16)+pibar
+    }
+    int32_sort(a); // A = (id<<16)+pibar^2
+
+    if w <= 10 {
+        for x in 0..n {
+            b[x] = ((a[x] & 0xffff) << 10) | (b[x] & 0x3ff);
+        }
+
+        for _ in 1..w - 1 {
+            // B = (p<<10)+c
+            for x in 0..n {
+                a[x] = ((b[x] & !0x3ff) << 6) | x as i32; // A = (p<<16)+id
+            }
+            int32_sort(a); // A = (id<<16)+p^{-1}
+
+            for x in 0..n {
+                a[x] = (a[x] << 20) | b[x]; // A = (p^{-1}<<20)+(p<<10)+c
+            }
+            int32_sort(a); // A = (id<<20)+(pp<<10)+cp
+
+            for x in 0..n {
+                let ppcpx = a[x] & 0xfffff;
+                let ppcx = (a[x] & 0xffc00) | (b[x] & 0x3ff);
+                b[x] = ppcx.min(ppcpx);
+            }
+        }
+
+        for bx in b.iter_mut() {
+            *bx &= 0x3ff;
+        }
+    } else {
+        for x in 0..n {
+            b[x] = (a[x] << 16) | (b[x] & 0xffff);
+        }
+
+        for i in 1..w - 1 {
+            // B = (p<<16)+c
+            for x in 0..n {
+                a[x] = (b[x] & !0xffff) | x as i32;
+            }
+            int32_sort(a); // A = (id<<16)+p^(-1)
+
+            for x in 0..n {
+                a[x] = (a[x] << 16) | (b[x] & 0xffff);
+            }
+            // A = p^(-1)<<16+c
+
+            if i < w - 2 {
+                for x in 0..n {
+                    b[x] = (a[x] & !0xffff) | (b[x] >> 16);
+                }
+                // B = (p^(-1)<<16)+p
+                int32_sort(b); // B = (id<<16)+p^(-2)
+
+                for x in 0..n {
+                    b[x] = (b[x] << 16) | (a[x] & 0xffff);
+                }
+                // B = (p^(-2)<<16)+c
+            }
+
+            int32_sort(a); // A = id<<16+cp
+
+            for x in 0..n {
+                let cpx = (b[x] & !0xffff) | (a[x] & 0xffff);
+                b[x] = b[x].min(cpx);
+            }
+        }
+
+        for bx in b.iter_mut() {
+            *bx &= 0xffff;
+        }
+    }
+
+    for x in 0..n {
+        a[x] = ((pi[x] as i32) << 16) + x as i32;
+    }
+    int32_sort(a); // A = (id<<16)+pi^(-1)
+
+    for j in 0..n / 2 {
+        let x = 2 * j;
+        let fj = b[x] & 1; // f[j]
+        let fx = x as i32 + fj; // F[x]
+        let fx1 = fx ^ 1; // F[x+1]
+
+        out[pos >> 3] ^= (fj << (pos & 7)) as u8;
+        pos += step;
+
+        b[x] = (a[x] << 16) | fx;
+        b[x + 1] = (a[x + 1] << 16) | fx1;
+    }
+    // B = (pi^(-1)<<16)+F
+
+    int32_sort(b); // B = (id<<16)+F(pi)
+
+    pos += (2 * w - 3) * step * (n / 2);
+
+    for k in 0..n / 2 {
+        let y = 2 * k;
+        let lk = b[y] & 1; // l[k]
+        let ly = y as i32 + lk; // L[y]
+        let ly1 = ly ^ 1; // L[y+1]
+
+        out[pos >> 3] ^= (lk << (pos & 7)) as u8;
+        pos += step;
+
+        a[y] = (ly << 16) | (b[y] & 0xffff);
+        a[y + 1] = (ly1 << 16) | (b[y + 1] & 0xffff);
+    }
+    // A = (L<<16)+F(pi)
+
+    int32_sort(a); // A = (id<<16)+F(pi(L)) = (id<<16)+M
+
+    pos -= (2 * w - 2) * step * (n / 2);
+
+    let mut q = vec![0i16; n];
+    for j in 0..n / 2 {
+        q[j] = ((a[2 * j] & 0xffff) >> 1) as i16;
+        q[j + n / 2] = ((a[2 * j + 1] & 0xffff) >> 1) as i16;
+    }
+
+    let (q0, q1) = q.split_at(n / 2);
+    cbrecursion(out, pos, step * 2, q0, w - 1, n / 2, temp);
+    cbrecursion(out, pos + step, step * 2, q1, w - 1, n / 2, temp);
+}
+
+/// Compute the control bits of the network implementing the
+/// permutation `pi` of `2^m` elements.
+///
+/// Returns `None` if the network does not implement `pi`, which only
+/// happens on faults.
+pub(super) fn controlbits<P: ParameterSet>(pi: &[i16]) -> Option<Vec<u8>> {
+    let w = P::GFBITS;
+    let n = 1 << w;
+
+    let mut out = vec![0u8; P::COND_BYTES];
+    let mut temp = vec![0i32; 2 * n];
+    cbrecursion(&mut out, 0, 1, pi, w, n, &mut temp);
+
+    let mut pi_test: Vec<i16> = (0..n as i16).collect();
+    apply(&mut pi_test, &out, w);
+
+    let diff = pi
+        .iter()
+        .zip(pi_test)
+        .fold(0, |diff, (a, b)| diff | (a ^ b));
+    if diff != 0 {
+        return None;
+    }
+
+    Some(out)
+}
+
+/// Compute the support `L` of the Goppa code, i.e. `bitrev(pi[i])` for
+/// the `n` first elements of the permutation `pi`, from the control
+/// bits `cond` of `pi`.
+pub(super) fn support_gen<P: ParameterSet>(cond: &[u8]) -> Vec<Gf> {
+    let mut l: Vec<i16> = (0..1 << P::GFBITS)
+        .map(|i| bitrev::<P>(i as Gf) as i16)
+        .collect();
+    apply(&mut l, cond, P::GFBITS);
+
+    l[..P::SYS_N].iter().map(|&a| a as Gf).collect()
+}
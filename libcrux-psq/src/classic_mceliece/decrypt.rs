@@ -0,0 +1,115 @@
+//! Decapsulation: decoding the error vector from a syndrome.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{
+    benes::support_gen,
+    gf::{eval, gf_frac, gf_inv, gf_iszero, gf_mul, load_gf, Gf},
+    ParameterSet,
+};
+
+/// Evaluate the polynomial `f` at all points of the support `l`.
+pub(super) fn root<P: ParameterSet>(f: &[Gf], l: &[Gf]) -> Vec<Gf> {
+    l.iter().map(|&a| eval::<P>(f, a)).collect()
+}
+
+/// Compute the `2t` syndrome values of the received word `r` for the
+/// Goppa polynomial `f` and support `l`.
+fn synd<P: ParameterSet>(f: &[Gf], l: &[Gf], r: &[u8]) -> Vec<Gf> {
+    let mut out = vec![0 as Gf; 2 * P::SYS_T];
+
+    for (i, &a) in l.iter().enumerate() {
+        let c = ((r[i / 8] >> (i % 8)) & 1) as Gf;
+        let e = eval::<P>(f, a);
+        let mut e_inv = gf_inv::<P>(gf_mul::<P>(e, e));
+
+        for o in out.iter_mut() {
+            *o ^= gf_mul::<P>(e_inv, c);
+            e_inv = gf_mul::<P>(e_inv, a);
+        }
+    }
+
+    out
+}
+
+/// The Berlekamp-Massey algorithm, computing the error locator
+/// polynomial from the syndrome `s`.
+fn bm<P: ParameterSet>(s: &[Gf]) -> Vec<Gf> {
+    let t = P::SYS_T;
+
+    let mut l: u16 = 0;
+    let mut b: Gf = 1;
+    let mut c = vec![0 as Gf; t + 1];
+    let mut bb = vec![0 as Gf; t + 1];
+    c[0] = 1;
+    bb[1] = 1;
+
+    for n in 0..2 * t {
+        let mut d: Gf = 0;
+        for i in 0..=n.min(t) {
+            d ^= gf_mul::<P>(c[i], s[n - i]);
+        }
+
+        let mne = (d.wrapping_sub(1) >> 15).wrapping_sub(1);
+        let mle = ((n as u16).wrapping_sub(2 * l) >> 15).wrapping_sub(1) & mne;
+
+        let previous = c.clone();
+        let f = gf_frac::<P>(b, d);
+        for (ci, bi) in c.iter_mut().zip(&bb) {
+            *ci ^= gf_mul::<P>(f, *bi) & mne;
+        }
+
+        l = (l & !mle) | ((n as u16 + 1).wrapping_sub(l) & mle);
+        for (bi, ti) in bb.iter_mut().zip(&previous) {
+            *bi = (*bi & !mle) | (ti & mle);
+        }
+        b = (b & !mle) | (d & mle);
+
+        bb.rotate_right(1);
+        bb[0] = 0;
+    }
+
+    c.reverse();
+    c
+}
+
+/// Decode the error vector from the ciphertext `c` with the secret key
+/// `sk`, starting at the Goppa polynomial.
+///
+/// Returns the error vector, and a mask that is `0xFF` if decoding
+/// succeeded and zero otherwise.
+pub(super) fn decrypt<P: ParameterSet>(sk: &[u8], c: &[u8]) -> (Vec<u8>, u8) {
+    let mut r = vec![0u8; P::SYS_N / 8];
+    r[..P::SYND_BYTES].copy_from_slice(c);
+    if P::PK_NROWS % 8 != 0 {
+        r[P::SYND_BYTES - 1] &= (1 << (P::PK_NROWS % 8)) - 1;
+    }
+
+    let (irr, cond) = sk.split_at(P::IRR_BYTES);
+    let mut g: Vec<Gf> = irr.chunks_exact(2).map(load_gf::<P>).collect();
+    g.push(1);
+
+    let l = support_gen::<P>(&cond[..P::COND_BYTES]);
+    let s = synd::<P>(&g, &l, &r);
+    let locator = bm::<P>(&s);
+    let images = root::<P>(&locator, &l);
+
+    let mut e = vec![0u8; P::SYS_N / 8];
+    let mut w: u16 = 0;
+    for (i, &image) in images.iter().enumerate() {
+        let t = gf_iszero(image) & 1;
+        e[i / 8] |= (t as u8) << (i % 8);
+        w += t;
+    }
+
+    let s_cmp = synd::<P>(&g, &l, &e);
+    let mut check = w ^ P::SYS_T as u16;
+    for (a, b) in s.iter().zip(&s_cmp) {
+        check |= a ^ b;
+    }
+
+    let success = (check.wrapping_sub(1) >> 15).wrapping_neg() as u8;
+
+    (e, success)
+}
@@ -0,0 +1,98 @@
+//! Encapsulation: encoding a random error vector of weight `t`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use rand::CryptoRng;
+
+use super::{gf::load_gf, ParameterSet};
+
+/// `0xFF` if `x == y`, zero otherwise.
+fn same_mask(x: u16, y: u16) -> u8 {
+    (((x ^ y) as u32).wrapping_sub(1) >> 31).wrapping_neg() as u8
+}
+
+/// Sample an error vector of weight `t`.
+fn gen_e<P: ParameterSet>(rng: &mut impl CryptoRng) -> Vec<u8> {
+    let t = P::SYS_T;
+    let mut ind = Vec::with_capacity(t);
+
+    loop {
+        ind.clear();
+
+        if P::SYS_N == 1 << P::GFBITS {
+            // Every field element is a valid index.
+            let mut buf = vec![0u8; 2 * t];
+            rng.fill_bytes(&mut buf);
+            ind.extend(buf.chunks_exact(2).map(load_gf::<P>));
+        } else {
+            let mut buf = vec![0u8; 4 * t];
+            rng.fill_bytes(&mut buf);
+            ind.extend(
+                buf.chunks_exact(2)
+                    .map(load_gf::<P>)
+                    .filter(|&i| (i as usize) < P::SYS_N)
+                    .take(t),
+            );
+            if ind.len() < t {
+                continue;
+            }
+        }
+
+        let repeated = (1..t).any(|i| ind[..i].contains(&ind[i]));
+        if !repeated {
+            break;
+        }
+    }
+
+    let mut e = vec![0u8; P::SYS_N / 8];
+    for (i, byte) in e.iter_mut().enumerate() {
+        for &j in &ind {
+            *byte |= (1 << (j & 7)) & same_mask(i as u16, j >> 3);
+        }
+    }
+
+    e
+}
+
+/// Extract the bits `[PK_NROWS, SYS_N)` of `e`, which are multiplied with
+/// the rows of the public key.
+fn tail<P: ParameterSet>(e: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; P::PK_ROW_BYTES];
+    let (offset, shift) = (P::PK_NROWS / 8, P::PK_NROWS % 8);
+
+    for (j, byte) in out.iter_mut().enumerate() {
+        *byte = e[offset + j] >> shift;
+        if shift != 0 && offset + j + 1 < e.len() {
+            *byte |= e[offset + j + 1] << (8 - shift);
+        }
+    }
+
+    out
+}
+
+/// Compute the syndrome of the error vector `e` under the public key `pk`.
+pub(super) fn syndrome<P: ParameterSet>(pk: &[u8], e: &[u8]) -> Vec<u8> {
+    let mut s = vec![0u8; P::SYND_BYTES];
+    let e_tail = tail::<P>(e);
+
+    for (i, row) in pk.chunks_exact(P::PK_ROW_BYTES).enumerate() {
+        let mut b = (e[i / 8] >> (i % 8)) & 1;
+        for (r, e) in row.iter().zip(&e_tail) {
+            b ^= (r & e).count_ones() as u8;
+        }
+        s[i / 8] |= (b & 1) << (i % 8);
+    }
+
+    s
+}
+
+/// Encrypt a random error vector under the public key `pk`.
+///
+/// Returns the error vector and the ciphertext.
+pub(super) fn encrypt<P: ParameterSet>(pk: &[u8], rng: &mut impl CryptoRng) -> (Vec<u8>, Vec<u8>) {
+    let e = gen_e::<P>(rng);
+    let c = syndrome::<P>(pk, &e);
+
+    (e, c)
+}
@@ -0,0 +1,93 @@
+//! Arithmetic in the field `GF(2^m)` and in its extension `GF((2^m)^t)`.
+
+use super::ParameterSet;
+
+/// An element of `GF(2^m)`, with `m` at most 13.
+pub(super) type Gf = u16;
+
+/// Returns `0x1FFF` if `a` is zero and `0` otherwise.
+pub(super) fn gf_iszero(a: Gf) -> Gf {
+    ((a as u32).wrapping_sub(1) >> 19) as Gf
+}
+
+/// Reduce a product of degree below `2m - 1` modulo the field polynomial.
+fn reduce<P: ParameterSet>(mut x: u32) -> Gf {
+    for i in (P::GFBITS..2 * P::GFBITS - 1).rev() {
+        let bit = (x >> i) & 1;
+        x ^= 0u32.wrapping_sub(bit) & (P::GF_POLY << (i - P::GFBITS));
+    }
+
+    x as Gf
+}
+
+pub(super) fn gf_mul<P: ParameterSet>(a: Gf, b: Gf) -> Gf {
+    let a = a as u32;
+    let b = b as u32;
+
+    let mut x = a * (b & 1);
+    for i in 1..P::GFBITS {
+        x ^= a * (b & (1 << i));
+    }
+
+    reduce::<P>(x)
+}
+
+fn gf_sq<P: ParameterSet>(a: Gf) -> Gf {
+    gf_mul::<P>(a, a)
+}
+
+/// Computes `a^-1 = a^(2^m - 2)`, mapping zero to zero.
+pub(super) fn gf_inv<P: ParameterSet>(a: Gf) -> Gf {
+    let mut out = a;
+    for _ in 2..P::GFBITS {
+        out = gf_mul::<P>(gf_sq::<P>(out), a);
+    }
+
+    gf_sq::<P>(out)
+}
+
+/// Computes `num / den`.
+pub(super) fn gf_frac<P: ParameterSet>(den: Gf, num: Gf) -> Gf {
+    gf_mul::<P>(gf_inv::<P>(den), num)
+}
+
+/// Multiplication in `GF((2^m)^t)`, where `a`, `b` and `out` hold `t`
+/// coefficients each.
+pub(super) fn gf_mul_ext<P: ParameterSet>(out: &mut [Gf], a: &[Gf], b: &[Gf]) {
+    let t = P::SYS_T;
+    let mut prod = alloc::vec![0 as Gf; 2 * t - 1];
+
+    for i in 0..t {
+        for j in 0..t {
+            prod[i + j] ^= gf_mul::<P>(a[i], b[j]);
+        }
+    }
+
+    for i in (t..2 * t - 1).rev() {
+        for &(degree, coefficient) in P::EXT_POLY {
+            prod[i - t + degree] ^= gf_mul::<P>(prod[i], coefficient);
+        }
+    }
+
+    out[..t].copy_from_slice(&prod[..t]);
+}
+
+/// Reverse the lowest `m` bits of `a`.
+pub(super) fn bitrev<P: ParameterSet>(a: Gf) -> Gf {
+    a.reverse_bits() >> (16 - P::GFBITS)
+}
+
+/// Load a field element from 2 little-endian bytes.
+pub(super) fn load_gf<P: ParameterSet>(bytes: &[u8]) -> Gf {
+    u16::from_le_bytes([bytes[0], bytes[1]]) & P::GFMASK
+}
+
+/// Evaluate the polynomial `f` of degree `t` at `a`.
+pub(super) fn eval<P: ParameterSet>(f: &[Gf], a: Gf) -> Gf {
+    let mut r = f[P::SYS_T];
+    for &coefficient in f[..P::SYS_T].iter().rev() {
+        r = gf_mul::<P>(r, a) ^ coefficient;
+    }
+
+    r
+}
@@ -0,0 +1,345 @@
+//! Key generation for the semi-systematic (`f`) parameter sets.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use libcrux_sha3::shake256_ema;
+
+use super::{
+    benes::controlbits,
+    decrypt::root,
+    gf::{bitrev, gf_inv, gf_iszero, gf_mul, gf_mul_ext, load_gf, Gf},
+    sort::uint64_sort,
+    ParameterSet,
+};
+
+/// The number of rows of the matrix whose pivots may be moved.
+const MU: usize = 32;
+/// The number of columns searched for the moved pivots.
+const NU: usize = 64;
+
+/// Compute the minimal polynomial of `f` in `GF((2^m)^t)`.
+///
+/// Returns `None` if the minimal polynomial does not have degree `t`.
+fn genpoly_gen<P: ParameterSet>(f: &[Gf]) -> Option<Vec<Gf>> {
+    let t = P::SYS_T;
+
+    // Row `j` holds `f^j`.
+    let mut mat = vec![0 as Gf; (t + 1) * t];
+    mat[0] = 1;
+    mat[t..2 * t].copy_from_slice(f);
+    for j in 2..=t {
+        let (prev, next) = mat.split_at_mut(j * t);
+        gf_mul_ext::<P>(&mut next[..t], &prev[(j - 1) * t..], f);
+    }
+
+    for j in 0..t {
+        for k in j + 1..t {
+            let mask = gf_iszero(mat[j * t + j]);
+            for c in j..t + 1 {
+                mat[c * t + j] ^= mat[c * t + k] & mask;
+            }
+        }
+
+        if mat[j * t + j] == 0 {
+            return None;
+        }
+
+        let inv = gf_inv::<P>(mat[j * t + j]);
+        for c in j..t + 1 {
+            mat[c * t + j] = gf_mul::<P>(mat[c * t + j], inv);
+        }
+
+        for k in 0..t {
+            if k != j {
+                let factor = mat[j * t + k];
+                for c in j..t + 1 {
+                    mat[c * t + k] ^= gf_mul::<P>(mat[c * t + j], factor);
+                }
+            }
+        }
+    }
+
+    Some(mat[t * t..].to_vec())
+}
+
+/// A binary matrix, stored row by row in 64-bit words.
+struct Matrix {
+    words: Vec<u64>,
+    words_per_row: usize,
+}
+
+impl Matrix {
+    fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(64);
+
+        Self {
+            words: vec![0; rows * words_per_row],
+            words_per_row,
+        }
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.words[i * self.words_per_row..(i + 1) * self.words_per_row]
+    }
+
+    fn bit(&self, i: usize, j: usize) -> u64 {
+        (self.row(i)[j / 64] >> (j % 64)) & 1
+    }
+
+    fn set_bit(&mut self, i: usize, j: usize, bit: u64) {
+        self.words[i * self.words_per_row + j / 64] |= bit << (j % 64);
+    }
+
+    /// Load the 64 bits of row `i` starting at column `j`, padded with
+    /// zeros at the end of the row.
+    fn get64(&self, i: usize, j: usize) -> u64 {
+        let row = self.row(i);
+        let (w, s) = (j / 64, j % 64);
+
+        if s == 0 {
+            row[w]
+        } else {
+            let high = row.get(w + 1).copied().unwrap_or(0);
+            (row[w] >> s) | (high << (64 - s))
+        }
+    }
+
+    /// Store the 64 bits of row `i` starting at column `j`.
+    fn set64(&mut self, i: usize, j: usize, value: u64) {
+        let offset = i * self.words_per_row;
+        let (w, s) = (j / 64, j % 64);
+
+        if s == 0 {
+            self.words[offset + w] = value;
+        } else {
+            let low = u64::MAX << s;
+            self.words[offset + w] = (self.words[offset + w] & !low) | (value << s);
+            self.words[offset + w + 1] = (self.words[offset + w + 1] & low) | (value >> (64 - s));
+        }
+    }
+
+    /// Add row `k` to row `i` if `mask` is all ones.
+    fn add_row(&mut self, i: usize, k: usize, mask: u64) {
+        let n = self.words_per_row;
+        let (target, source) = if i < k {
+            let (low, high) = self.words.split_at_mut(k * n);
+            (&mut low[i * n..(i + 1) * n], &high[..n])
+        } else {
+            let (low, high) = self.words.split_at_mut(i * n);
+            (&mut high[..n], &low[k * n..(k + 1) * n])
+        };
+
+        for (t, s) in target.iter_mut().zip(source) {
+            *t ^= s & mask;
+        }
+    }
+}
+
+/// The number of trailing zeros of the non-zero `x`, in constant time.
+fn ctz(x: u64) -> usize {
+    let mut m = 0;
+    let mut r = 0;
+
+    for i in 0..64 {
+        let b = ((x >> i) & 1) as usize;
+        m |= b;
+        r += (m ^ 1) & (b ^ 1);
+    }
+
+    r
+}
+
+/// All ones if `x == y`, zero otherwise.
+fn same_mask(x: usize, y: usize) -> u64 {
+    (((x ^ y) as u64).wrapping_sub(1) >> 63).wrapping_neg()
+}
+
+/// Move pivots into the last `MU` rows of the systematic part, searching
+/// the following `NU` columns, and update the permutation `pi` to match.
+///
+/// Returns the positions of the pivots, or `None` if there are not
+/// enough of them.
+fn mov_columns<P: ParameterSet>(mat: &mut Matrix, pi: &mut [i16]) -> Option<u64> {
+    let row = P::PK_NROWS - MU;
+
+    let mut buf = [0u64; MU];
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = mat.get64(row + i, row);
+    }
+
+    // Compute the column indices of the pivots by Gaussian elimination.
+    let mut pivots = 0;
+    let mut ctz_list = [0usize; MU];
+    for i in 0..MU {
+        let t = buf[i..].iter().fold(0, |t, b| t | b);
+        if t == 0 {
+            return None;
+        }
+
+        let s = ctz(t);
+        ctz_list[i] = s;
+        pivots |= 1 << s;
+
+        for j in i + 1..MU {
+            let mask = ((buf[i] >> s) & 1).wrapping_sub(1);
+            buf[i] ^= buf[j] & mask;
+        }
+        for j in i + 1..MU {
+            let mask = ((buf[j] >> s) & 1).wrapping_neg();
+            buf[j] ^= buf[i] & mask;
+        }
+    }
+
+    for (j, &c) in ctz_list.iter().enumerate() {
+        for k in j + 1..NU {
+            let d = (pi[row + j] ^ pi[row + k]) & same_mask(k, c) as i16;
+            pi[row + j] ^= d;
+            pi[row + k] ^= d;
+        }
+    }
+
+    // Move the columns of the matrix to match.
+    for i in 0..P::PK_NROWS {
+        let mut t = mat.get64(i, row);
+        for (j, &c) in ctz_list.iter().enumerate() {
+            let d = ((t >> j) ^ (t >> c)) & 1;
+            t ^= d << c;
+            t ^= d << j;
+        }
+        mat.set64(i, row, t);
+    }
+
+    Some(pivots)
+}
+
+/// Compute the public key from the Goppa polynomial `g` and the random
+/// values `perm`.
+///
+/// On success, returns the public key and the pivots, and updates the
+/// permutation `pi` to the one induced by `perm` and the pivots.
+fn pk_gen<P: ParameterSet>(g: &[Gf], perm: &[u32], pi: &mut [i16]) -> Option<(Vec<u8>, u64)> {
+    let mut buf: Vec<u64> = perm
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| ((p as u64) << 31) | i as u64)
+        .collect();
+    uint64_sort(&mut buf);
+
+    if buf.windows(2).any(|w| w[0] >> 31 == w[1] >> 31) {
+        return None;
+    }
+
+    for (p, b) in pi.iter_mut().zip(buf) {
+        *p = (b & P::GFMASK as u64) as i16;
+    }
+    let l: Vec<Gf> = pi[..P::SYS_N]
+        .iter()
+        .map(|&p| bitrev::<P>(p as Gf))
+        .collect();
+
+    // Fill the matrix.
+    let mut inv = root::<P>(g, &l);
+    for x in inv.iter_mut() {
+        *x = gf_inv::<P>(*x);
+    }
+
+    let mut mat = Matrix::new(P::PK_NROWS, P::SYS_N);
+    for i in 0..P::SYS_T {
+        for (j, x) in inv.iter_mut().enumerate() {
+            for k in 0..P::GFBITS {
+                mat.set_bit(i * P::GFBITS + k, j, ((*x >> k) & 1) as u64);
+            }
+            *x = gf_mul::<P>(*x, l[j]);
+        }
+    }
+
+    // Gaussian elimination.
+    let mut pivots = 0;
+    for row in 0..P::PK_NROWS {
+        if row == P::PK_NROWS - MU {
+            pivots = mov_columns::<P>(&mut mat, pi)?;
+        }
+
+        for k in row + 1..P::PK_NROWS {
+            let mask = (mat.bit(row, row) ^ mat.bit(k, row)).wrapping_neg();
+            mat.add_row(row, k, mask);
+        }
+
+        if mat.bit(row, row) == 0 {
+            return None;
+        }
+
+        for k in 0..P::PK_NROWS {
+            if k != row {
+                let mask = mat.bit(k, row).wrapping_neg();
+                mat.add_row(k, row, mask);
+            }
+        }
+    }
+
+    let mut pk = vec![0u8; P::PK_NROWS * P::PK_ROW_BYTES];
+    for (i, pk_row) in pk.chunks_exact_mut(P::PK_ROW_BYTES).enumerate() {
+        for (j, bytes) in pk_row.chunks_mut(8).enumerate() {
+            let word = mat.get64(i, P::PK_NROWS + 64 * j).to_le_bytes();
+            bytes.copy_from_slice(&word[..bytes.len()]);
+        }
+    }
+
+    Some((pk, pivots))
+}
+
+/// Generate a key pair from the 32 byte `seed`.
+///
+/// Returns the public and the secret key.
+pub(super) fn keypair<P: ParameterSet>(seed: &[u8; 32]) -> (Vec<u8>, Vec<u8>) {
+    let n = 1 << P::GFBITS;
+    let s_len = P::SYS_N / 8;
+    let perm_len = 4 * n;
+    let f_len = 2 * P::SYS_T;
+
+    let mut delta = [0u8; 33];
+    delta[0] = 64;
+    delta[1..].copy_from_slice(seed);
+
+    let mut r = vec![0u8; s_len + perm_len + f_len + 32];
+    let mut pi = vec![0i16; n];
+
+    loop {
+        shake256_ema(&mut r, &delta);
+
+        let mut sk = Vec::with_capacity(P::SECRET_KEY_LEN);
+        sk.extend_from_slice(&delta[1..]);
+        delta[1..].copy_from_slice(&r[r.len() - 32..]);
+
+        let (s, rest) = r.split_at(s_len);
+        let (perm, f) = rest.split_at(perm_len);
+
+        let f: Vec<Gf> = f[..f_len].chunks_exact(2).map(load_gf::<P>).collect();
+        let Some(mut g) = genpoly_gen::<P>(&f) else {
+            continue;
+        };
+
+        let perm: Vec<u32> = perm
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+
+        g.push(1);
+        let Some((pk, pivots)) = pk_gen::<P>(&g, &perm, &mut pi) else {
+            continue;
+        };
+        let Some(cond) = controlbits::<P>(&pi) else {
+            continue;
+        };
+
+        sk.extend_from_slice(&pivots.to_le_bytes());
+        for c in &g[..P::SYS_T] {
+            sk.extend_from_slice(&c.to_le_bytes());
+        }
+        sk.extend_from_slice(&cond);
+        sk.extend_from_slice(s);
+
+        return (pk, sk);
+    }
+}
@@ -0,0 +1,71 @@
+//! Constant-time sorting networks, following
+//! [djbsort](https://sorting.cr.yp.to/).
+
+/// Swap `x[a]` and `x[b]` if `x[a] > x[b]`.
+fn int32_minmax(x: &mut [i32], a: usize, b: usize) {
+    let ab = x[b] ^ x[a];
+    let mut c = x[b].wrapping_sub(x[a]);
+    c ^= ab & (c ^ x[b]);
+    c >>= 31;
+    c &= ab;
+    x[a] ^= c;
+    x[b] ^= c;
+}
+
+/// Swap `x[a]` and `x[b]` if `x[a] > x[b]`.
+///
+/// Only correct for values below `2^63`.
+fn uint64_minmax(x: &mut [u64], a: usize, b: usize) {
+    let mut c = x[b].wrapping_sub(x[a]);
+    c >>= 63;
+    c = 0u64.wrapping_sub(c);
+    c &= x[a] ^ x[b];
+    x[a] ^= c;
+    x[b] ^= c;
+}
+
+fn sort<T>(x: &mut [T], minmax: impl Fn(&mut [T], usize, usize)) {
+    let n = x.len();
+    if n < 2 {
+        return;
+    }
+
+    let mut top = 1;
+    while top < n - top {
+        top += top;
+    }
+
+    let mut p = top;
+    while p > 0 {
+        for i in 0..n - p {
+            if i & p == 0 {
+                minmax(x, i, i + p);
+            }
+        }
+
+        let mut i = 0;
+        let mut q = top;
+        while q > p {
+            while i < n - q {
+                if i & p == 0 {
+                    let mut r = q;
+                    while r > p {
+                        minmax(x, i + p, i + r);
+                        r >>= 1;
+                    }
+                }
+                i += 1;
+            }
+            q >>= 1;
+        }
+        p >>= 1;
+    }
+}
+
+pub(super) fn int32_sort(x: &mut [i32]) {
+    sort(x, int32_minmax)
+}
+
+pub(super) fn uint64_sort(x: &mut [u64]) {
+    sort(x, uint64_minmax)
+}
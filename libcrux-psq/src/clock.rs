@@ -0,0 +1,31 @@
+//! # Clocks
+//!
+//! The protocol needs the current time to timestamp and check
+//! registration messages, and to expire PSKs and credentials. Since
+//! there is no common time source on `no_std` targets, the caller
+//! supplies it through a [`Clock`].
+//!
+//! All points in time are given as the [`Duration`] since the UNIX epoch.
+
+use core::time::Duration;
+
+use crate::Error;
+
+/// A source of the current time.
+pub trait Clock {
+    /// The time elapsed since the UNIX epoch.
+    fn now(&self) -> Result<Duration, Error>;
+}
+
+/// A [`Clock`] reading the system time.
+#[cfg(feature = "std")]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Result<Duration, Error> {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|_| Error::OsError)
+    }
+}
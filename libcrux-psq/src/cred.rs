@@ -1,4 +1,10 @@
 //! This module provides a trait for a generic authenticator.
+use alloc::vec::Vec;
+use core::{marker::PhantomData, time::Duration};
+
+use libcrux_traits::signature::{Signer, Verifier};
+use rand::CryptoRng;
+
 use crate::Error;
 
 /// A generic authentication primitive.
@@ -19,19 +25,30 @@ pub trait Authenticator {
     type Credential: AsRef<[u8]>;
     /// Information necessary to validate the credential.
     type Certificate;
-    /// Length (in bytes) of a serialized credential key.
+    /// Maximum length (in bytes) of a serialized credential.
+    ///
+    /// For most authenticators, all credentials have exactly this length.
     const CRED_LEN: usize;
     /// Length (in bytes) of a serialized signature.
     const SIG_LEN: usize;
+    /// Identifier of the authenticator in the wire format.
+    const ID: u16;
 
     /// Return a signature
-    fn sign(signing_key: &Self::SigningKey, message: &[u8]) -> Result<Self::Signature, Error>;
+    fn sign(
+        signing_key: &Self::SigningKey,
+        message: &[u8],
+        rng: &mut impl CryptoRng,
+    ) -> Result<Self::Signature, Error>;
 
-    /// Retrieve the client verification key from a valid credential.
+    /// Retrieve the client verification key and identity from a
+    /// credential that is valid at time `now`, given as the time since
+    /// the UNIX epoch.
     fn validate_credential(
         credential: Self::Credential,
         certificate: &Self::Certificate,
-    ) -> Result<Self::VerificationKey, Error>;
+        now: Duration,
+    ) -> Result<ValidatedCredential<Self::VerificationKey>, Error>;
 
     /// Verify a signature.
     fn verify(
@@ -47,6 +64,14 @@ pub trait Authenticator {
     fn deserialize_signature(bytes: &[u8]) -> Result<Self::Signature, Error>;
 }
 
+/// The result of validating a client's credential.
+pub struct ValidatedCredential<K> {
+    /// The key to verify the client's signatures with
+    pub verification_key: K,
+    /// The identity the credential is bound to
+    pub identity: Vec<u8>,
+}
+
 /// A no-op authenticator that does nothing.
 pub struct NoAuth {}
 
@@ -59,8 +84,13 @@ impl Authenticator for NoAuth {
 
     const CRED_LEN: usize = 0;
     const SIG_LEN: usize = 0;
+    const ID: u16 = 0x0000;
 
-    fn sign(_signing_key: &Self::SigningKey, _message: &[u8]) -> Result<Self::Signature, Error> {
+    fn sign(
+        _signing_key: &Self::SigningKey,
+        _message: &[u8],
+        _rng: &mut impl CryptoRng,
+    ) -> Result<Self::Signature, Error> {
         Ok([0; 0])
     }
 
@@ -83,34 +113,176 @@ impl Authenticator for NoAuth {
     fn validate_credential(
         _credential: Self::Credential,
         _certificate: &Self::Certificate,
-    ) -> Result<Self::VerificationKey, Error> {
-        Ok([0; 0])
+        _now: Duration,
+    ) -> Result<ValidatedCredential<Self::VerificationKey>, Error> {
+        Ok(ValidatedCredential {
+            verification_key: [0; 0],
+            identity: Vec::new(),
+        })
     }
 }
 
-/// A simple authenticator based on Ed25519 signatures, where it is
-/// assumed that the responder obtains the initiators verification key
-/// out of band and the verification key itself serves as the
-/// certificate.
-pub struct Ed25519 {}
+/// A signature scheme with fixed-length encodings that can be used
+/// in an [`OutOfBand`] authenticator.
+///
+/// Signing and verification go through the scheme's [`Signer`] and
+/// [`Verifier`] implementations, with an empty context string.
+pub trait SignatureScheme: Signer + Verifier<Signature = <Self as Signer>::Signature> {
+    /// Identifier of the scheme in the wire format.
+    const ID: u16;
+    /// Length (in bytes) of a serialized verification key.
+    const KEY_LEN: usize;
+    /// Length (in bytes) of a serialized signature.
+    const SIG_LEN: usize;
+
+    /// A serialized verification key.
+    type EncodedKey: AsRef<[u8]> + PartialEq + for<'a> TryFrom<&'a [u8]>;
+    /// A serialized signature.
+    type EncodedSignature: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+
+    /// Parse a serialized verification key.
+    fn decode_key(key: &Self::EncodedKey) -> Result<Self::VerificationKey, Error>;
 
-impl Authenticator for Ed25519 {
-    type Signature = [u8; 64];
+    /// Serialize a signature.
+    fn encode_signature(signature: &<Self as Signer>::Signature) -> Self::EncodedSignature;
 
-    type SigningKey = [u8; 32];
+    /// Parse a serialized signature.
+    fn decode_signature(signature: &Self::EncodedSignature) -> <Self as Signer>::Signature;
+}
+
+impl SignatureScheme for libcrux_ed25519::Ed25519 {
+    const ID: u16 = 0x0001;
+    const KEY_LEN: usize = 32;
+    const SIG_LEN: usize = 64;
 
-    type VerificationKey = [u8; 32];
+    type EncodedKey = [u8; 32];
+    type EncodedSignature = [u8; 64];
 
-    type Credential = Self::VerificationKey;
+    /// CAUTION: This does not perform validation of the verification key.
+    fn decode_key(key: &[u8; 32]) -> Result<Self::VerificationKey, Error> {
+        Ok(libcrux_ed25519::VerificationKey::from_bytes(*key))
+    }
 
-    type Certificate = Self::VerificationKey;
+    fn encode_signature(signature: &[u8; 64]) -> [u8; 64] {
+        *signature
+    }
 
-    const CRED_LEN: usize = 32;
+    fn decode_signature(signature: &[u8; 64]) -> [u8; 64] {
+        *signature
+    }
+}
 
+/// Verification keys are serialized as the raw affine coordinates
+/// `X || Y`.
+impl SignatureScheme for libcrux_ecdsa::p256::EcdsaP256Sha256 {
+    const ID: u16 = 0x0002;
+    const KEY_LEN: usize = 64;
     const SIG_LEN: usize = 64;
 
-    fn sign(signing_key: &Self::SigningKey, message: &[u8]) -> Result<Self::Signature, Error> {
-        libcrux_ed25519::sign(message, signing_key).map_err(|_| Error::CredError)
+    type EncodedKey = [u8; 64];
+    type EncodedSignature = [u8; 64];
+
+    fn decode_key(key: &[u8; 64]) -> Result<Self::VerificationKey, Error> {
+        // This also checks that the key is a valid point on the curve.
+        libcrux_ecdsa::p256::PublicKey::try_from(key).map_err(|_| Error::CredError)
+    }
+
+    fn encode_signature(signature: &libcrux_ecdsa::p256::Signature) -> [u8; 64] {
+        let (r, s) = signature.as_bytes();
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(r);
+        out[32..].copy_from_slice(s);
+        out
+    }
+
+    fn decode_signature(signature: &[u8; 64]) -> libcrux_ecdsa::p256::Signature {
+        libcrux_ecdsa::p256::Signature::from_bytes(*signature)
+    }
+}
+
+macro_rules! impl_ml_dsa {
+    ($scheme:ident, $id:literal, $module:ident, $verification_key:ident, $signature:ident) => {
+        impl SignatureScheme for libcrux_ml_dsa::$scheme {
+            const ID: u16 = $id;
+            const KEY_LEN: usize = libcrux_ml_dsa::$module::$verification_key::len();
+            const SIG_LEN: usize = libcrux_ml_dsa::$module::$signature::len();
+
+            type EncodedKey = [u8; libcrux_ml_dsa::$module::$verification_key::len()];
+            type EncodedSignature = [u8; libcrux_ml_dsa::$module::$signature::len()];
+
+            fn decode_key(key: &Self::EncodedKey) -> Result<Self::VerificationKey, Error> {
+                Ok(libcrux_ml_dsa::$module::$verification_key::new(*key))
+            }
+
+            fn encode_signature(
+                signature: &libcrux_ml_dsa::$module::$signature,
+            ) -> Self::EncodedSignature {
+                *signature.as_ref()
+            }
+
+            fn decode_signature(
+                signature: &Self::EncodedSignature,
+            ) -> libcrux_ml_dsa::$module::$signature {
+                libcrux_ml_dsa::$module::$signature::new(*signature)
+            }
+        }
+    };
+}
+
+impl_ml_dsa!(
+    MlDsa44,
+    0x0003,
+    ml_dsa_44,
+    MLDSA44VerificationKey,
+    MLDSA44Signature
+);
+impl_ml_dsa!(
+    MlDsa65,
+    0x0004,
+    ml_dsa_65,
+    MLDSA65VerificationKey,
+    MLDSA65Signature
+);
+impl_ml_dsa!(
+    MlDsa87,
+    0x0005,
+    ml_dsa_87,
+    MLDSA87VerificationKey,
+    MLDSA87Signature
+);
+
+/// An authenticator based on the [`SignatureScheme`] `S`, where it is
+/// assumed that the responder obtains the initiator's verification key
+/// out of band and the serialized verification key itself serves as
+/// the certificate.
+pub struct OutOfBand<S> {
+    _scheme: PhantomData<S>,
+}
+
+impl<S: SignatureScheme> Authenticator for OutOfBand<S> {
+    type Signature = S::EncodedSignature;
+
+    type SigningKey = S::SigningKey;
+
+    type VerificationKey = S::VerificationKey;
+
+    type Credential = S::EncodedKey;
+
+    type Certificate = S::EncodedKey;
+
+    const CRED_LEN: usize = S::KEY_LEN;
+
+    const SIG_LEN: usize = S::SIG_LEN;
+
+    const ID: u16 = S::ID;
+
+    fn sign(
+        signing_key: &Self::SigningKey,
+        message: &[u8],
+        rng: &mut impl CryptoRng,
+    ) -> Result<Self::Signature, Error> {
+        let signature = S::sign(signing_key, message, b"", rng)?;
+        Ok(S::encode_signature(&signature))
     }
 
     fn verify(
@@ -118,11 +290,12 @@ impl Authenticator for Ed25519 {
         signature: &Self::Signature,
         message: &[u8],
     ) -> Result<(), Error> {
-        libcrux_ed25519::verify(message, verification_key, signature).map_err(|_| Error::CredError)
+        let signature = S::decode_signature(signature);
+        <S as Verifier>::verify(verification_key, message, b"", &signature)?;
+        Ok(())
     }
 
-    /// CAUTION: This does not perform validation of the verification key.
-    fn deserialize_credential(bytes: &[u8]) -> Result<Self::VerificationKey, Error> {
+    fn deserialize_credential(bytes: &[u8]) -> Result<Self::Credential, Error> {
         bytes.try_into().map_err(|_| Error::CredError)
     }
 
@@ -133,11 +306,153 @@ impl Authenticator for Ed25519 {
     fn validate_credential(
         credential: Self::Credential,
         cert: &Self::Certificate,
-    ) -> Result<Self::VerificationKey, Error> {
+        _now: Duration,
+    ) -> Result<ValidatedCredential<Self::VerificationKey>, Error> {
         // We only check that the out of band key is the same as the
         // key that is provided as the credential.
-        (credential == *cert)
-            .then_some(credential)
-            .ok_or(Error::CredError)
+        if credential != *cert {
+            return Err(Error::CredError);
+        }
+
+        // The key is the identity.
+        Ok(ValidatedCredential {
+            verification_key: S::decode_key(&credential)?,
+            identity: credential.as_ref().to_vec(),
+        })
+    }
+}
+
+/// An authenticator based on Ed25519 signatures.
+pub type Ed25519 = OutOfBand<libcrux_ed25519::Ed25519>;
+
+/// An authenticator based on ECDSA signatures over P-256 with SHA-256.
+pub type P256 = OutOfBand<libcrux_ecdsa::p256::EcdsaP256Sha256>;
+
+/// An authenticator based on ML-DSA-44 signatures, as specified in FIPS 204.
+pub type MlDsa44 = OutOfBand<libcrux_ml_dsa::MlDsa44>;
+
+/// An authenticator based on ML-DSA-65 signatures, as specified in FIPS 204.
+pub type MlDsa65 = OutOfBand<libcrux_ml_dsa::MlDsa65>;
+
+/// An authenticator based on ML-DSA-87 signatures, as specified in FIPS 204.
+pub type MlDsa87 = OutOfBand<libcrux_ml_dsa::MlDsa87>;
+
+/// A composite authenticator requiring valid signatures under both
+/// `A` and `B`.
+///
+/// Signatures, credentials and identities are the concatenation of
+/// the ones under `A` and `B`, in that order. Both `A` and `B` must
+/// have credentials of exactly [`Authenticator::CRED_LEN`] bytes, so
+/// variable length credentials such as
+/// [`CertChain`](crate::cert::CertChain) can not be used. Combining a
+/// classical and a post-quantum authenticator, e.g. in
+/// [`Ed25519MlDsa65`], keeps the registration authenticated as long
+/// as either of the two schemes remains secure.
+pub struct Hybrid<A, B> {
+    _a: PhantomData<A>,
+    _b: PhantomData<B>,
+}
+
+/// The identifier of a hybrid of two basic authenticators.
+///
+/// Evaluation fails at compile time if either component is itself
+/// composite.
+const fn hybrid_id(a: u16, b: u16) -> u16 {
+    assert!(
+        a < 0x40 && b < 0x40,
+        "hybrid authenticators can not be nested"
+    );
+    0x4000 | (a << 6) | b
+}
+
+/// A hybrid authenticator combining [`Ed25519`] and [`MlDsa65`].
+pub type Ed25519MlDsa65 = Hybrid<Ed25519, MlDsa65>;
+
+impl<A: Authenticator, B: Authenticator> Authenticator for Hybrid<A, B> {
+    type Signature = Vec<u8>;
+
+    type SigningKey = (A::SigningKey, B::SigningKey);
+
+    type VerificationKey = (A::VerificationKey, B::VerificationKey);
+
+    type Credential = Vec<u8>;
+
+    type Certificate = (A::Certificate, B::Certificate);
+
+    const CRED_LEN: usize = A::CRED_LEN + B::CRED_LEN;
+
+    const SIG_LEN: usize = A::SIG_LEN + B::SIG_LEN;
+
+    const ID: u16 = hybrid_id(A::ID, B::ID);
+
+    fn sign(
+        signing_key: &Self::SigningKey,
+        message: &[u8],
+        rng: &mut impl CryptoRng,
+    ) -> Result<Self::Signature, Error> {
+        let signature_a = A::sign(&signing_key.0, message, rng)?;
+        let signature_b = B::sign(&signing_key.1, message, rng)?;
+
+        let mut out = Vec::with_capacity(Self::SIG_LEN);
+        out.extend_from_slice(signature_a.as_ref());
+        out.extend_from_slice(signature_b.as_ref());
+        Ok(out)
+    }
+
+    fn verify(
+        verification_key: &Self::VerificationKey,
+        signature: &Self::Signature,
+        message: &[u8],
+    ) -> Result<(), Error> {
+        if signature.len() != Self::SIG_LEN {
+            return Err(Error::CredError);
+        }
+        let (signature_a, signature_b) = signature.split_at(A::SIG_LEN);
+        let signature_a = A::deserialize_signature(signature_a)?;
+        let signature_b = B::deserialize_signature(signature_b)?;
+
+        // Both signatures are always checked.
+        let result_a = A::verify(&verification_key.0, &signature_a, message);
+        let result_b = B::verify(&verification_key.1, &signature_b, message);
+        result_a.and(result_b)
+    }
+
+    fn deserialize_credential(bytes: &[u8]) -> Result<Self::Credential, Error> {
+        if bytes.len() != Self::CRED_LEN {
+            return Err(Error::CredError);
+        }
+
+        Ok(bytes.to_vec())
+    }
+
+    fn deserialize_signature(bytes: &[u8]) -> Result<Self::Signature, Error> {
+        if bytes.len() != Self::SIG_LEN {
+            return Err(Error::CredError);
+        }
+
+        Ok(bytes.to_vec())
+    }
+
+    fn validate_credential(
+        credential: Self::Credential,
+        cert: &Self::Certificate,
+        now: Duration,
+    ) -> Result<ValidatedCredential<Self::VerificationKey>, Error> {
+        if credential.len() != Self::CRED_LEN {
+            return Err(Error::CredError);
+        }
+        let (credential_a, credential_b) = credential.split_at(A::CRED_LEN);
+        let credential_a = A::deserialize_credential(credential_a)?;
+        let credential_b = B::deserialize_credential(credential_b)?;
+
+        let validated_a = A::validate_credential(credential_a, &cert.0, now)?;
+        let validated_b = B::validate_credential(credential_b, &cert.1, now)?;
+
+        let mut identity = validated_a.identity;
+        identity.extend_from_slice(&validated_b.identity);
+        Ok(ValidatedCredential {
+            verification_key: (validated_a.verification_key, validated_b.verification_key),
+            identity,
+        })
     }
 }
@@ -1,19 +1,25 @@
 //! PSQ implementation backed by `libcrux`.
 //!
 //! This module implements PSQ using the following underlying KEMs:
-//! * `MLKem768`, a lattice-based post-quantum KEM, as specified in FIPS 203 (Draft)
+//! * `MlKem512`, `MlKem768` and `MlKem1024`, lattice-based post-quantum
+//!   KEMs, as specified in FIPS 203
 //! * `XWingKemDraft06`, a hybrid post-quantum KEM combining X25519 and ML-KEM 768
 //! * `X25519`, an elliptic-curve Diffie-Hellman based KEM. This
 //!   implementation is available with feature `test-utils` and using this
 //!   KEM *does not provide post-quantum security*. We include it for
 //!   testing and benchmarking.
 
+use alloc::vec::Vec;
+
 use crate::traits::*;
-use libcrux_kem::{Ct, PrivateKey, PublicKey, Ss};
+use libcrux_curve25519::PK_LEN as X25519_PK_LEN;
+use libcrux_kem::{
+    Ct, MlKem1024Ciphertext, MlKem512Ciphertext, MlKem768Ciphertext, PrivateKey, PublicKey, Ss,
+};
 use libcrux_traits::kem::{KEMError, KeyPair, KEM};
 
 macro_rules! libcrux_impl {
-    ($alg:ident, $docstring:literal) => {
+    ($alg:ident, $ct_len:expr, $id:literal, $docstring:literal) => {
         #[doc = $docstring]
         pub struct $alg;
 
@@ -52,8 +58,21 @@ macro_rules! libcrux_impl {
             }
         }
 
+        impl DecodeCiphertext for $alg {
+            const CIPHERTEXT_LEN: usize = $ct_len;
+
+            fn decode_ciphertext(bytes: &[u8]) -> Result<Self::Ciphertext, crate::Error> {
+                if bytes.len() != Self::CIPHERTEXT_LEN {
+                    return Err(crate::Error::Decoding);
+                }
+                Ct::decode(Self::algorithm(), bytes).map_err(|_| crate::Error::Decoding)
+            }
+        }
+
         impl crate::traits::PSQ for $alg {
             type InnerKEM = Self;
+
+            const KEM_ID: u16 = $id;
         }
     };
 }
@@ -61,14 +80,32 @@ macro_rules! libcrux_impl {
 #[cfg(feature = "test-utils")]
 libcrux_impl!(
     X25519,
+    X25519_PK_LEN,
+    0x0020,
     "An elliptic-curve Diffie-Hellman based KEM (Does not provide post-quantum security)"
 );
+libcrux_impl!(
+    MlKem512,
+    MlKem512Ciphertext::len(),
+    0x0040,
+    "ML-KEM 512, a lattice-based post-quantum KEM, as specified in FIPS 203"
+);
 libcrux_impl!(
     MlKem768,
+    MlKem768Ciphertext::len(),
+    0x0041,
     "ML-KEM 768, a lattice-based post-quantum KEM, as specified in FIPS 203 (Draft)"
 );
+libcrux_impl!(
+    MlKem1024,
+    MlKem1024Ciphertext::len(),
+    0x0042,
+    "ML-KEM 1024, a lattice-based post-quantum KEM, as specified in FIPS 203"
+);
 libcrux_impl!(
     XWingKemDraft06,
+    MlKem768Ciphertext::len() + X25519_PK_LEN,
+    0x647a,
     "A hybrid post-quantum KEM combining X25519 and ML-KEM 768"
 );
 
@@ -115,6 +152,8 @@ mod tests {
 
     #[cfg(feature = "test-utils")]
     libcrux_test!(X25519);
+    libcrux_test!(MlKem512);
     libcrux_test!(MlKem768);
+    libcrux_test!(MlKem1024);
     libcrux_test!(XWingKemDraft06);
 }
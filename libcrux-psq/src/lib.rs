@@ -2,10 +2,21 @@
 //!
 //! This crate implements a protocol for establishing and mutually
 //! registering a PQ-PSK between an initiator and a responder.
+//!
+//! The crate is `no_std` and only requires `alloc`, unless the default
+//! feature `std` is enabled. The current time is supplied by the caller
+//! through a [`clock::Clock`]. With the feature `log`, errors from the
+//! underlying primitives are reported through the [`log`](https://docs.rs/log)
+//! facade.
 
+#![no_std]
 #![deny(missing_docs)]
 
-use std::array::TryFromSliceError;
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::array::TryFromSliceError;
 
 #[derive(Debug)]
 /// PSQ Errors.
@@ -28,45 +39,75 @@ pub enum Error {
     OsError,
     /// An error while decoding bytes
     Decoding,
+    /// A registration message was received more than once
+    Replay,
+    /// No PSK is stored under the given handle
+    UnknownPsk,
+    /// The PSK's TTL has passed
+    PskExpired,
+    /// An error in the PSK storage
+    StorageError,
+    /// A credential is not valid at the current time
+    CredentialExpired,
+    /// A credential has been revoked
+    CredentialRevoked,
+    /// A message uses an unsupported wire format version
+    UnsupportedVersion,
+    /// A message of the wrong type was received
+    UnexpectedMessage,
+    /// A message uses a different KEM than the session
+    KemMismatch,
+    /// A message uses a different authenticator than the session
+    AuthenticatorMismatch,
+    /// A message uses a different AEAD than the session
+    AeadMismatch,
+    /// An input to a PSK derivation is out of range
+    InvalidDerivationInput,
+    /// The first epoch of a rotation schedule has not started yet
+    EpochNotStarted,
 }
 
-#[cfg(debug_assertions)]
-fn print_error(m: &str, e: &impl std::fmt::Debug) {
-    use std::backtrace;
-
-    eprintln!("{m} {e:?}");
-    let bt = backtrace::Backtrace::capture();
-    eprintln!("{bt}");
+#[cfg(feature = "log")]
+fn log_error(m: &str, e: &impl core::fmt::Debug) {
+    log::debug!("{m} {e:?}");
 }
 
 impl From<libcrux_kem::Error> for Error {
     fn from(_e: libcrux_kem::Error) -> Self {
-        #[cfg(debug_assertions)]
-        print_error("KEM error", &_e);
+        #[cfg(feature = "log")]
+        log_error("KEM error", &_e);
         Self::CryptoError
     }
 }
 
-impl From<libcrux_chacha20poly1305::AeadError> for Error {
-    fn from(_e: libcrux_chacha20poly1305::AeadError) -> Self {
-        #[cfg(debug_assertions)]
-        print_error("Chacha20Poly1305 error", &_e);
+impl From<libcrux_traits::aead::AeadError> for Error {
+    fn from(_e: libcrux_traits::aead::AeadError) -> Self {
+        #[cfg(feature = "log")]
+        log_error("AEAD error", &_e);
         Self::CryptoError
     }
 }
 
+impl From<libcrux_traits::signature::SignatureError> for Error {
+    fn from(_e: libcrux_traits::signature::SignatureError) -> Self {
+        #[cfg(feature = "log")]
+        log_error("Signature error", &_e);
+        Self::CredError
+    }
+}
+
 impl From<libcrux_hkdf::Error> for Error {
     fn from(_e: libcrux_hkdf::Error) -> Self {
-        #[cfg(debug_assertions)]
-        print_error("HKDF error", &_e);
+        #[cfg(feature = "log")]
+        log_error("HKDF error", &_e);
         Self::CryptoError
     }
 }
 
 impl From<TryFromSliceError> for Error {
     fn from(_e: TryFromSliceError) -> Self {
-        #[cfg(debug_assertions)]
-        print_error("TryFromSliceError", &_e);
+        #[cfg(feature = "log")]
+        log_error("TryFromSliceError", &_e);
         Self::CryptoError
     }
 }
@@ -74,8 +115,13 @@ impl From<TryFromSliceError> for Error {
 const PSK_LENGTH: usize = 32;
 type Psk = [u8; PSK_LENGTH];
 
+pub mod cert;
+pub mod clock;
 pub mod cred;
+pub mod psk_derivation;
 pub mod psk_registration;
+pub mod psk_store;
+pub mod session;
 
 #[cfg(feature = "classic-mceliece")]
 pub mod classic_mceliece;
@@ -0,0 +1,304 @@
+//! # Protocol PSKs
+//!
+//! A PSK registered with PSQ should not be plugged into another protocol
+//! directly. This module derives protocol-specific keys from it, each
+//! under its own label, so that one registered PSK can be used with
+//! several protocols at once:
+//!
+//! * [`wireguard_psk`] for the `PresharedKey` of a WireGuard peer,
+//! * [`TlsImportedPsk`] for a TLS 1.3 external PSK, imported as specified
+//!   in [RFC 9258](https://www.rfc-editor.org/rfc/rfc9258), and
+//! * [`noise_psk`] for the `psk` token of a Noise handshake.
+//!
+//! An [`EpochSchedule`] rotates a registered PSK in epochs of fixed
+//! length, so that the keys used by the protocols change regularly
+//! without running a new registration.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use libcrux_hkdf::{expand as hkdf_expand, extract as hkdf_extract, Algorithm as HKDF_Algorithm};
+use libcrux_hmac::{hmac, Algorithm as HMAC_Algorithm};
+use libcrux_sha2::sha256;
+
+use crate::{clock::Clock, Error, Psk, PSK_LENGTH};
+
+const WIREGUARD_CONTEXT: &[u8] = b"PSQ-WireGuard-PresharedKey";
+const NOISE_CONTEXT: &[u8] = b"PSQ-Noise-PSK";
+const EPOCH_CONTEXT: &[u8] = b"PSQ-Epoch";
+
+/// The `target_protocol` of an imported PSK, TLS 1.3.
+const TLS13: u16 = 0x0304;
+/// The `target_kdf` of an imported PSK, HKDF-SHA256.
+const HKDF_SHA256: u16 = 0x0001;
+const TLS13_LABEL_PREFIX: &[u8] = b"tls13 ";
+
+fn expand(psk: &Psk, info: &[u8]) -> Result<Psk, Error> {
+    let okm = hkdf_expand(HKDF_Algorithm::Sha256, psk, info, PSK_LENGTH)?;
+
+    Ok(okm.as_slice().try_into()?)
+}
+
+/// Derive the `PresharedKey` of a WireGuard peer from the registered `psk`.
+pub fn wireguard_psk(psk: &Psk) -> Result<Psk, Error> {
+    expand(psk, WIREGUARD_CONTEXT)
+}
+
+/// Derive the `psk` of a Noise handshake from the registered `psk`.
+///
+/// The key is bound to the full `protocol_name` of the handshake, e.g.
+/// `Noise_XXpsk3_25519_ChaChaPoly_SHA256`, so that different handshake
+/// patterns never share a key.
+pub fn noise_psk(psk: &Psk, protocol_name: &[u8]) -> Result<Psk, Error> {
+    let mut info = Vec::from(NOISE_CONTEXT);
+    info.extend_from_slice(protocol_name);
+
+    expand(psk, &info)
+}
+
+/// `HKDF-Expand-Label` of TLS 1.3 with SHA-256, see RFC 8446, Section 7.1.
+fn expand_label(secret: &[u8], label: &[u8], context: &[u8]) -> Result<Psk, Error> {
+    let mut info = Vec::from((PSK_LENGTH as u16).to_be_bytes());
+    info.push((TLS13_LABEL_PREFIX.len() + label.len()) as u8);
+    info.extend_from_slice(TLS13_LABEL_PREFIX);
+    info.extend_from_slice(label);
+    info.push(context.len() as u8);
+    info.extend_from_slice(context);
+
+    let okm = hkdf_expand(HKDF_Algorithm::Sha256, secret, info, PSK_LENGTH)?;
+
+    Ok(okm.as_slice().try_into()?)
+}
+
+/// A TLS 1.3 external PSK, imported from a registered PSK as specified
+/// in RFC 9258.
+///
+/// The import targets TLS 1.3 with HKDF-SHA256, i.e. the PSK can be used
+/// with cipher suites based on SHA-256.
+pub struct TlsImportedPsk {
+    /// The encoded `ImportedIdentity`, to be sent as the PSK identity
+    pub identity: Vec<u8>,
+    /// The imported PSK `ipskx`, the input to the TLS 1.3 key schedule
+    pub ipsk: Psk,
+}
+
+impl TlsImportedPsk {
+    /// Import the registered `psk` under `external_identity`, e.g. its
+    /// PSK handle, and the possibly empty `context`.
+    ///
+    /// Returns [`Error::InvalidDerivationInput`] if `external_identity` is
+    /// empty, or if either input is longer than `u16::MAX` bytes.
+    pub fn new(psk: &Psk, external_identity: &[u8], context: &[u8]) -> Result<Self, Error> {
+        if external_identity.is_empty()
+            || external_identity.len() > u16::MAX as usize
+            || context.len() > u16::MAX as usize
+        {
+            return Err(Error::InvalidDerivationInput);
+        }
+
+        let mut identity = Vec::from((external_identity.len() as u16).to_be_bytes());
+        identity.extend_from_slice(external_identity);
+        identity.extend_from_slice(&(context.len() as u16).to_be_bytes());
+        identity.extend_from_slice(context);
+        identity.extend_from_slice(&TLS13.to_be_bytes());
+        identity.extend_from_slice(&HKDF_SHA256.to_be_bytes());
+
+        let epskx = hkdf_extract(HKDF_Algorithm::Sha256, [0u8; PSK_LENGTH], psk)?;
+        let ipsk = expand_label(&epskx, b"derived psk", &sha256(&identity))?;
+
+        Ok(Self { identity, ipsk })
+    }
+
+    /// The binder key `Derive-Secret(Early Secret, "imp binder", "")`.
+    pub fn binder_key(&self) -> Result<Psk, Error> {
+        let early_secret = hkdf_extract(HKDF_Algorithm::Sha256, [0u8; PSK_LENGTH], self.ipsk)?;
+
+        expand_label(&early_secret, b"imp binder", &sha256(b""))
+    }
+
+    /// Compute the PSK binder over `transcript_hash`, the SHA-256 hash
+    /// of the truncated `ClientHello`, see RFC 8446, Section 4.2.11.2.
+    pub fn binder(&self, transcript_hash: &[u8; 32]) -> Result<[u8; 32], Error> {
+        let finished_key = expand_label(&self.binder_key()?, b"finished", b"")?;
+        let binder = hmac(HMAC_Algorithm::Sha256, &finished_key, transcript_hash, None);
+
+        Ok(binder.as_slice().try_into()?)
+    }
+}
+
+/// A schedule rotating a registered PSK in epochs of fixed length.
+///
+/// Epoch `n` starts at `start + n * period`. Both peers derive the same
+/// key for an epoch without interacting, they only need to agree on the
+/// schedule and roughly on the current time.
+///
+/// The keys of different epochs are independent of each other, but
+/// anyone holding the registered PSK can derive all of them.
+pub struct EpochSchedule {
+    start: Duration,
+    period: Duration,
+}
+
+impl EpochSchedule {
+    /// Create a schedule with epoch `0` starting at `start`, given as the
+    /// [`Duration`] since the UNIX epoch.
+    ///
+    /// Returns [`Error::InvalidDerivationInput`] if `period` is zero.
+    pub fn new(start: Duration, period: Duration) -> Result<Self, Error> {
+        if period.is_zero() {
+            return Err(Error::InvalidDerivationInput);
+        }
+
+        Ok(Self { start, period })
+    }
+
+    /// The epoch at time `now`.
+    ///
+    /// Returns [`Error::EpochNotStarted`] if `now` is before the start of
+    /// the schedule.
+    pub fn epoch_at(&self, now: Duration) -> Result<u64, Error> {
+        let elapsed = now.checked_sub(self.start).ok_or(Error::EpochNotStarted)?;
+
+        (elapsed.as_nanos() / self.period.as_nanos())
+            .try_into()
+            .map_err(|_| Error::InvalidDerivationInput)
+    }
+
+    /// Derive the PSK of `epoch` from the registered `psk`.
+    pub fn epoch_psk(&self, psk: &Psk, epoch: u64) -> Result<Psk, Error> {
+        let mut info = Vec::from(EPOCH_CONTEXT);
+        info.extend_from_slice(&epoch.to_be_bytes());
+
+        expand(psk, &info)
+    }
+
+    /// Derive the PSK of the current epoch from the registered `psk`.
+    ///
+    /// Returns the current epoch together with its PSK.
+    pub fn current_psk(&self, psk: &Psk, clock: &impl Clock) -> Result<(u64, Psk), Error> {
+        let epoch = self.epoch_at(clock.now()?)?;
+
+        Ok((epoch, self.epoch_psk(psk, epoch)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! The expected values are computed independently of libcrux by
+    //! `tests/kats/psk_derivation.py`, using the HKDF of the Python
+    //! `cryptography` package.
+
+    use super::*;
+
+    const PSK: Psk = {
+        let mut psk = [0u8; PSK_LENGTH];
+        let mut i = 0;
+        while i < PSK_LENGTH {
+            psk[i] = i as u8;
+            i += 1;
+        }
+        psk
+    };
+
+    struct FixedClock(Duration);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> Result<Duration, Error> {
+            Ok(self.0)
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> alloc::string::String {
+        bytes.iter().map(|b| alloc::format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn wireguard() {
+        assert_eq!(
+            hex(&wireguard_psk(&PSK).unwrap()),
+            "e79bdbfb08f3e169a7dd1ed63b2acb74374892d5daef0ee89b94166de5fdf2d8"
+        );
+    }
+
+    #[test]
+    fn noise() {
+        let psk = noise_psk(&PSK, b"Noise_XXpsk3_25519_ChaChaPoly_SHA256").unwrap();
+        assert_eq!(
+            hex(&psk),
+            "73e4ffc82f3d008f39031e36a13ddc7c94cf7a702df544e2a6f5c9ef09de5052"
+        );
+
+        let other = noise_psk(&PSK, b"Noise_IKpsk2_25519_ChaChaPoly_BLAKE2s").unwrap();
+        assert_ne!(psk, other);
+        assert_ne!(psk, wireguard_psk(&PSK).unwrap());
+    }
+
+    #[test]
+    fn tls_importer() {
+        let imported = TlsImportedPsk::new(&PSK, b"psk handle", b"example.com").unwrap();
+        assert_eq!(
+            hex(&imported.identity),
+            "000a70736b2068616e646c65000b6578616d706c652e636f6d03040001"
+        );
+        assert_eq!(
+            hex(&imported.ipsk),
+            "87acd236dfd62cf97b030e6709562c11c6c87ca6c355b5563ca15e338147c171"
+        );
+        assert_eq!(
+            hex(&imported.binder_key().unwrap()),
+            "30da8ad0dd0dac63bfa0827be02f34cddf348c73ec01b70644c1f664d7122235"
+        );
+        assert_eq!(
+            hex(&imported.binder(&[0xaa; 32]).unwrap()),
+            "fd41055ba1beb4fb2941b576aff56a3ed02b6a87260553a4ee75ed726a5aa547"
+        );
+
+        assert!(matches!(
+            TlsImportedPsk::new(&PSK, b"", b""),
+            Err(Error::InvalidDerivationInput)
+        ));
+    }
+
+    #[test]
+    fn epochs() {
+        let start = Duration::from_secs(1_700_000_000);
+        let schedule = EpochSchedule::new(start, Duration::from_secs(3600)).unwrap();
+
+        assert_eq!(schedule.epoch_at(start).unwrap(), 0);
+        assert_eq!(
+            schedule
+                .epoch_at(start + Duration::from_secs(3599))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            schedule
+                .epoch_at(start + Duration::from_secs(3600))
+                .unwrap(),
+            1
+        );
+        assert!(matches!(
+            schedule.epoch_at(start - Duration::from_secs(1)),
+            Err(Error::EpochNotStarted)
+        ));
+
+        assert_eq!(
+            hex(&schedule.epoch_psk(&PSK, 0).unwrap()),
+            "f19326d7a579761cba3c3f468a9107424c14782545a5a355013f8ccf77066560"
+        );
+        assert_eq!(
+            hex(&schedule.epoch_psk(&PSK, 1).unwrap()),
+            "0879d3706d5ee7259ebf05624b65bc82e8f21378070a0508738dd17c8d003c1e"
+        );
+
+        let clock = FixedClock(start + Duration::from_secs(5400));
+        let (epoch, psk) = schedule.current_psk(&PSK, &clock).unwrap();
+        assert_eq!(epoch, 1);
+        assert_eq!(psk, schedule.epoch_psk(&PSK, 1).unwrap());
+
+        assert!(matches!(
+            EpochSchedule::new(start, Duration::ZERO),
+            Err(Error::InvalidDerivationInput)
+        ));
+    }
+}
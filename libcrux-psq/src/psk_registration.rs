@@ -2,34 +2,60 @@
 //!
 //! This module implements a protocol for mutual registration of a
 //! PQ-PSK between an initiator and a responder.
-
-use libcrux_chacha20poly1305::{decrypt_detached, encrypt_detached, KEY_LEN, NONCE_LEN, TAG_LEN};
-use libcrux_kem::Ct;
-use libcrux_traits::kem::KEM;
+//!
+//! The protocol messages are protected with an AEAD, which is chosen
+//! through the [`RegistrationAead`] type parameter of the messages and
+//! protocol functions. [`ChaCha20Poly1305`] is the default. Both parties
+//! need to use the same AEAD. The AEAD's identifier is bound into the
+//! derivation of the message keys.
+
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+use core::{marker::PhantomData, time::Duration};
+use libcrux_traits::{aead::Aead, kem::KEM};
 use rand::CryptoRng;
-use std::time::{Duration, SystemTime};
 
-#[cfg(feature = "classic-mceliece")]
-use crate::classic_mceliece::ClassicMcEliece;
+pub use libcrux_chacha20poly1305::ChaCha20Poly1305;
 
-use crate::{cred::Authenticator, impls::MlKem768, traits::*, Error, Psk};
+use crate::{clock::Clock, cred::Authenticator, psk_store::PskStore, traits::*, Error, Psk};
 
 const PSK_REGISTRATION_CONTEXT: &[u8] = b"PSK-Registration";
 const PSK_LENGTH: usize = 32;
 
 const AEAD_RESPONDER: &[u8] = b"AEAD-Responder-Initiator";
 const AEAD_INITIATOR: &[u8] = b"AEAD-Initiator-Responder";
-const AEAD_KEY_NONCE: usize = KEY_LEN + NONCE_LEN;
-const AEAD_KEY_LENGTH: usize = KEY_LEN;
 
 const TS_TTL_LEN: usize = 28;
 
-struct AeadMac {
-    tag: [u8; TAG_LEN],
+/// An [`Aead`] that can protect the registration messages.
+pub trait RegistrationAead: Aead {
+    /// Identifier of the AEAD in the wire format.
+    ///
+    /// Where available, this is the AEAD's HPKE identifier.
+    const AEAD_ID: u16;
+}
+
+impl RegistrationAead for libcrux_aesgcm::aes128::Aes128Gcm {
+    const AEAD_ID: u16 = 0x0001;
+}
+
+impl RegistrationAead for libcrux_aesgcm::aes256::Aes256Gcm {
+    const AEAD_ID: u16 = 0x0002;
+}
+
+impl RegistrationAead for ChaCha20Poly1305 {
+    const AEAD_ID: u16 = 0x0003;
+}
+
+impl RegistrationAead for libcrux_chacha20poly1305::xchacha20_poly1305::XChaCha20Poly1305 {
+    const AEAD_ID: u16 = 0xff00;
+}
+
+pub(crate) struct AeadMac<A: RegistrationAead> {
+    tag: A::Tag,
     ctxt: Vec<u8>,
 }
 
-impl Encode for AeadMac {
+impl<A: RegistrationAead> Encode for AeadMac<A> {
     fn encode(&self) -> Vec<u8> {
         let mut out = vec![];
 
@@ -40,25 +66,29 @@ impl Encode for AeadMac {
     }
 }
 
-impl Decode for AeadMac {
+impl<A: RegistrationAead> Decode for AeadMac<A> {
     fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
-        let tag = <[u8; TAG_LEN]>::try_from(&bytes[0..16]).map_err(|_| Error::Decoding)?;
-        let ctxt = bytes[16..].to_vec();
+        if bytes.len() < A::TAG_LEN {
+            return Err(Error::Decoding);
+        }
+        let (tag, ctxt) = bytes.split_at(A::TAG_LEN);
+        let tag = A::Tag::try_from(tag).map_err(|_| Error::Decoding)?;
+        let ctxt = ctxt.to_vec();
 
         let out = Self { tag, ctxt };
-        let len = 16 + out.ctxt.len();
+        let len = A::TAG_LEN + out.ctxt.len();
 
         Ok((out, len))
     }
 }
 
 /// The Initiator's message to the responder.
-pub struct InitiatorMsg<T: KEM> {
-    encapsulation: Ciphertext<T>,
-    aead_mac: AeadMac,
+pub struct InitiatorMsg<T: KEM, A: RegistrationAead = ChaCha20Poly1305> {
+    pub(crate) encapsulation: Ciphertext<T>,
+    pub(crate) aead_mac: AeadMac<A>,
 }
 
-impl<T: KEM<Ciphertext: Encode>> Encode for InitiatorMsg<T> {
+impl<T: KEM<Ciphertext: Encode>, A: RegistrationAead> Encode for InitiatorMsg<T, A> {
     fn encode(&self) -> Vec<u8> {
         let mut out = vec![];
 
@@ -69,63 +99,32 @@ impl<T: KEM<Ciphertext: Encode>> Encode for InitiatorMsg<T> {
     }
 }
 
-#[cfg(feature = "classic-mceliece")]
-impl Decode for InitiatorMsg<ClassicMcEliece> {
-    fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
-        let mut ct = [0u8; 156];
-        let (ct_bytes, rest) = bytes.split_at(156);
-        let (mac_bytes, aead_bytes) = rest.split_at(32);
-        ct.copy_from_slice(ct_bytes);
-        let ct = classic_mceliece_rust::Ciphertext::from(ct);
-        let mut mac = [0u8; MAC_LENGTH];
-        mac.copy_from_slice(mac_bytes);
-
-        let (aead_mac, _len) = AeadMac::decode(aead_bytes)?;
-
-        let out = Self {
-            encapsulation: Ciphertext::<ClassicMcEliece> {
-                inner_ctxt: ct,
-                mac,
-            },
-            aead_mac,
-        };
-
-        Ok((out, bytes.len()))
-    }
-}
-
-impl Decode for InitiatorMsg<MlKem768> {
+impl<T: DecodeCiphertext, A: RegistrationAead> Decode for InitiatorMsg<T, A> {
     fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
-        let ct = Ct::decode(libcrux_kem::Algorithm::MlKem768, &bytes[0..1088]).unwrap();
-        let mut mac = [0u8; MAC_LENGTH];
-        mac.copy_from_slice(&bytes[1088..1088 + 32]);
-
-        let (aead_mac, _len) = AeadMac::decode(&bytes[1088 + 32..])?;
+        let (encapsulation, read) = Ciphertext::<T>::decode(bytes)?;
+        let (aead_mac, aead_len) = AeadMac::<A>::decode(&bytes[read..])?;
 
         let out = Self {
-            encapsulation: Ciphertext::<MlKem768> {
-                inner_ctxt: ct,
-                mac,
-            },
+            encapsulation,
             aead_mac,
         };
 
-        Ok((out, bytes.len()))
+        Ok((out, read + aead_len))
     }
 }
 
 /// The Responder's message to the initiator.
-pub struct ResponderMsg {
-    aead_mac: AeadMac,
+pub struct ResponderMsg<A: RegistrationAead = ChaCha20Poly1305> {
+    pub(crate) aead_mac: AeadMac<A>,
 }
 
-impl Encode for ResponderMsg {
+impl<A: RegistrationAead> Encode for ResponderMsg<A> {
     fn encode(&self) -> Vec<u8> {
         self.aead_mac.encode()
     }
 }
 
-impl Decode for ResponderMsg {
+impl<A: RegistrationAead> Decode for ResponderMsg<A> {
     fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
         let (aead_mac, read) = AeadMac::decode(bytes)?;
         let out = Self { aead_mac };
@@ -140,19 +139,26 @@ pub struct RegisteredPsk {
     pub psk: Psk,
     /// The PSK's handle for storing
     pub psk_handle: Vec<u8>,
+    /// The initiator's identity, as validated by the responder
+    ///
+    /// This is only known on the responder side.
+    pub identity: Option<Vec<u8>>,
 }
 
 /// The protocol initiator.
-pub struct Initiator {
+pub struct Initiator<A: RegistrationAead = ChaCha20Poly1305> {
     k_pq: [u8; 32],
+    _aead: PhantomData<A>,
 }
 
 /// The protocol responder.
 ///
 /// Is in charge of designating a PSK storage handle.
-pub struct Responder {}
+pub struct Responder<A: RegistrationAead = ChaCha20Poly1305> {
+    _aead: PhantomData<A>,
+}
 
-impl Initiator {
+impl<A: RegistrationAead> Initiator<A> {
     /// Send the initial message encapsulating a PQ-PrePSK.
     pub fn send_initial_message<C: Authenticator, T: PSQ>(
         sctx: &[u8],
@@ -160,39 +166,32 @@ impl Initiator {
         pqpk_responder: &<T::InnerKEM as KEM>::EncapsulationKey,
         signing_key: &C::SigningKey,
         credential: &C::Credential,
+        clock: &impl Clock,
         rng: &mut impl CryptoRng,
-    ) -> Result<(Self, InitiatorMsg<T::InnerKEM>), Error> {
+    ) -> Result<(Self, InitiatorMsg<T::InnerKEM, A>), Error> {
         let (k_pq, enc_pq) = T::encapsulate_psq(pqpk_responder, sctx, rng)?;
-        let (initiator_iv, initiator_key, _receiver_iv, _receiver_key) = derive_cipherstate(&k_pq)?;
+        let (initiator_iv, initiator_key) = derive_key_iv::<A>(&k_pq, AEAD_INITIATOR)?;
 
-        let now = SystemTime::now();
-        let ts = now
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map_err(|_| Error::OsError)?;
+        let ts = clock.now()?;
 
         let ts_ttl = serialize_ts_ttl(&ts, &psk_ttl);
 
-        let signature = C::sign(signing_key, &enc_pq.encode())?;
+        let signature = C::sign(signing_key, &enc_pq.encode(), rng)?;
 
         let mut message = Vec::new();
         message.extend_from_slice(&ts_ttl);
         message.extend_from_slice(signature.as_ref());
         message.extend_from_slice(credential.as_ref());
 
-        let mut ctxt = message.clone();
-        let mut tag = [0u8; TAG_LEN];
-        let _ = encrypt_detached(
-            &initiator_key,
-            &mut message,
-            &mut ctxt,
-            &mut tag,
-            b"",
-            &initiator_iv,
-        )?;
-        let aead_mac = AeadMac { tag, ctxt };
+        let mut tag = A::Tag::default();
+        A::encrypt_in_place(&initiator_key, &mut message, &mut tag, b"", &initiator_iv)?;
+        let aead_mac = AeadMac { tag, ctxt: message };
 
         Ok((
-            Self { k_pq },
+            Self {
+                k_pq,
+                _aead: PhantomData,
+            },
             InitiatorMsg {
                 encapsulation: enc_pq,
                 aead_mac,
@@ -204,16 +203,14 @@ impl Initiator {
     /// given handle.
     pub fn complete_handshake(
         &self,
-        responder_message: &ResponderMsg,
+        responder_message: &ResponderMsg<A>,
     ) -> Result<RegisteredPsk, Error> {
-        let (_initiator_iv, _initiator_key, responder_iv, responder_key) =
-            derive_cipherstate(&self.k_pq)?;
+        let (responder_iv, responder_key) = derive_key_iv::<A>(&self.k_pq, AEAD_RESPONDER)?;
 
         let mut psk_handle = responder_message.aead_mac.ctxt.clone();
-        let _ = decrypt_detached(
+        A::decrypt_in_place(
             &responder_key,
             &mut psk_handle,
-            &responder_message.aead_mac.ctxt,
             &responder_message.aead_mac.tag,
             b"",
             &responder_iv,
@@ -221,7 +218,11 @@ impl Initiator {
 
         let psk = derive_psk(&self.k_pq)?;
 
-        Ok(RegisteredPsk { psk, psk_handle })
+        Ok(RegisteredPsk {
+            psk,
+            psk_handle,
+            identity: None,
+        })
     }
 }
 
@@ -254,91 +255,119 @@ fn deserialize_ts(bytes: &[u8]) -> Result<(u64, u32), Error> {
 fn deserialize_sig_cred<C: Authenticator>(
     bytes: &[u8],
 ) -> Result<(C::Signature, C::Credential), Error> {
+    if bytes.len() < C::SIG_LEN || bytes.len() > C::SIG_LEN + C::CRED_LEN {
+        return Err(Error::CredError);
+    }
     let (sig_bytes, cred_bytes) = bytes.split_at(C::SIG_LEN);
     let signature = C::deserialize_signature(sig_bytes)?;
     let credential = C::deserialize_credential(cred_bytes)?;
     Ok((signature, credential))
 }
 
-impl Responder {
+/// The responder's setup for registering PSKs.
+pub struct ResponderConfig<'a, C: Authenticator, T: PSQ> {
+    /// The session context
+    pub sctx: &'a [u8],
+    /// How long registered PSKs are valid, counted from the initiator's
+    /// timestamp
+    pub psk_ttl: Duration,
+    /// The responder's KEM encapsulation key
+    pub pqpk: &'a <T::InnerKEM as KEM>::EncapsulationKey,
+    /// The responder's KEM decapsulation key
+    pub pqsk: &'a <T::InnerKEM as KEM>::DecapsulationKey,
+    /// The information initiator credentials are validated against
+    pub client_certificate: &'a C::Certificate,
+}
+
+impl<A: RegistrationAead> Responder<A> {
     /// On successful decapsulation of the PQ-PrePSK, send the response.
+    ///
+    /// The registered PSK is stored in `store` under `psk_handle` and
+    /// expires `config.psk_ttl` after the initiator's timestamp. An
+    /// initiator message that has already been accepted by `store` is
+    /// rejected with [`Error::Replay`].
     pub fn send<C: Authenticator, T: PSQ>(
+        config: &ResponderConfig<C, T>,
         psk_handle: &[u8],
-        psk_ttl: Duration,
-        sctxt: &[u8],
-        pqpk: &<T::InnerKEM as KEM>::EncapsulationKey,
-        pqsk: &<T::InnerKEM as KEM>::DecapsulationKey,
-        client_certificate: &C::Certificate,
-        initiator_message: &InitiatorMsg<T::InnerKEM>,
-    ) -> Result<(RegisteredPsk, ResponderMsg), Error> {
-        let k_pq = T::decapsulate_psq(pqsk, pqpk, &initiator_message.encapsulation, sctxt)?;
-        let (initiator_iv, initiator_key, responder_iv, responder_key) = derive_cipherstate(&k_pq)?;
+        initiator_message: &InitiatorMsg<T::InnerKEM, A>,
+        store: &mut impl PskStore,
+        clock: &impl Clock,
+    ) -> Result<(RegisteredPsk, ResponderMsg<A>), Error> {
+        let k_pq = T::decapsulate_psq(
+            config.pqsk,
+            config.pqpk,
+            &initiator_message.encapsulation,
+            config.sctx,
+        )?;
+        let (initiator_iv, initiator_key) = derive_key_iv::<A>(&k_pq, AEAD_INITIATOR)?;
+        let (responder_iv, responder_key) = derive_key_iv::<A>(&k_pq, AEAD_RESPONDER)?;
 
         let mut msg_bytes = initiator_message.aead_mac.ctxt.clone();
-        let _ = decrypt_detached(
+        A::decrypt_in_place(
             &initiator_key,
             &mut msg_bytes,
-            &initiator_message.aead_mac.ctxt,
             &initiator_message.aead_mac.tag,
             b"",
             &initiator_iv,
         )?;
 
+        if msg_bytes.len() < TS_TTL_LEN {
+            return Err(Error::RegistrationError);
+        }
         let (ts_bytes, sig_cred_bytes) = msg_bytes.split_at(TS_TTL_LEN);
         let (signature, credential) = deserialize_sig_cred::<C>(sig_cred_bytes)?;
-        let verification_key = C::validate_credential(credential, client_certificate)?;
-        if C::verify(
-            &verification_key,
-            &signature,
-            &initiator_message.encapsulation.encode(),
-        )
-        .is_err()
-        {
+        let now = clock.now()?;
+        let validated = C::validate_credential(credential, config.client_certificate, now)?;
+        let encapsulation = initiator_message.encapsulation.encode();
+        if C::verify(&validated.verification_key, &signature, &encapsulation).is_err() {
             return Err(Error::RegistrationError);
         }
 
         let (ts_seconds, ts_subsec_millis) = deserialize_ts(ts_bytes)?;
 
         // validate TTL
-        let now = SystemTime::now();
         let ts_since_epoch =
             Duration::from_secs(ts_seconds) + Duration::from_millis((ts_subsec_millis).into());
-        let now = now
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map_err(|_| Error::OsError)?;
 
+        if now < ts_since_epoch {
+            // time seems to have gone backwards
+            return Err(Error::OsError);
+        } else if now - ts_since_epoch >= config.psk_ttl {
+            return Err(Error::RegistrationError);
+        }
+
+        // A replay is rejected before the PSK is stored, also when it
+        // reuses the handle. It is only recorded once the PSK is stored, so
+        // that a message that can not be stored, e.g. because the handle is
+        // taken, can still be retried.
+        if store.is_replay(&encapsulation, now) {
+            return Err(Error::Replay);
+        }
+        let expires_at = ts_since_epoch + config.psk_ttl;
         let psk = derive_psk(&k_pq)?;
+        store.insert(psk_handle, psk, &validated.identity, expires_at)?;
+
+        // Messages older than `psk_ttl` are rejected above, so the replay
+        // cache only needs to remember the encapsulation until then.
+        if let Err(e) = store.record_encapsulation(&encapsulation, now, expires_at) {
+            store.remove(psk_handle);
+            return Err(e);
+        }
 
-        let mut tag = [0u8; TAG_LEN];
+        let mut tag = A::Tag::default();
         let mut ctxt = psk_handle.to_vec();
-        let _ = encrypt_detached(
-            &responder_key,
-            psk_handle,
-            &mut ctxt,
-            &mut tag,
-            b"",
-            &responder_iv,
-        )?;
+        A::encrypt_in_place(&responder_key, &mut ctxt, &mut tag, b"", &responder_iv)?;
 
-        let aead_mac = AeadMac {
-            tag,
-            ctxt: ctxt.to_owned(),
-        };
+        let aead_mac = AeadMac { tag, ctxt };
 
-        if now < ts_since_epoch {
-            // time seems to have gone backwards
-            Err(Error::OsError)
-        } else if now - ts_since_epoch >= psk_ttl {
-            Err(Error::RegistrationError)
-        } else {
-            Ok((
-                RegisteredPsk {
-                    psk,
-                    psk_handle: psk_handle.to_owned(),
-                },
-                ResponderMsg { aead_mac },
-            ))
-        }
+        Ok((
+            RegisteredPsk {
+                psk,
+                psk_handle: psk_handle.to_owned(),
+                identity: Some(validated.identity),
+            },
+            ResponderMsg { aead_mac },
+        ))
     }
 }
 
@@ -355,39 +384,32 @@ fn derive_psk(prk: &[u8; 32]) -> Result<Psk, Error> {
     Ok(psk)
 }
 
-fn derive_cipherstate(
+fn derive_key_iv<A: RegistrationAead>(
     psk: &[u8; 32],
-) -> Result<
-    (
-        [u8; NONCE_LEN],
-        [u8; KEY_LEN],
-        [u8; NONCE_LEN],
-        [u8; KEY_LEN],
-    ),
-    Error,
-> {
-    let (initiator_iv, initiator_key) = derive_key_iv(psk, AEAD_INITIATOR)?;
-    let (receiver_iv, receiver_key) = derive_key_iv(psk, AEAD_RESPONDER)?;
-
-    Ok((initiator_iv, initiator_key, receiver_iv, receiver_key))
-}
+    label: &[u8],
+) -> Result<(A::Nonce, A::Key), Error> {
+    let mut info = label.to_vec();
+    info.extend_from_slice(&A::AEAD_ID.to_be_bytes());
 
-fn derive_key_iv(psk: &[u8; 32], info: &[u8]) -> Result<([u8; NONCE_LEN], [u8; KEY_LEN]), Error> {
-    let key_iv_bytes =
-        libcrux_hkdf::expand(libcrux_hkdf::Algorithm::Sha256, psk, info, AEAD_KEY_NONCE)?;
-    let (key_bytes, iv_bytes) = key_iv_bytes.split_at(AEAD_KEY_LENGTH);
-    let key = <[u8; AEAD_KEY_LENGTH]>::try_from(key_bytes)?;
-    let iv = <[u8; NONCE_LEN]>::try_from(iv_bytes)?;
+    let key_iv_bytes = libcrux_hkdf::expand(
+        libcrux_hkdf::Algorithm::Sha256,
+        psk,
+        &info,
+        A::KEY_LEN + A::NONCE_LEN,
+    )?;
+    let (key_bytes, iv_bytes) = key_iv_bytes.split_at(A::KEY_LEN);
+    let key = A::Key::try_from(key_bytes).map_err(|_| Error::CryptoError)?;
+    let iv = A::Nonce::try_from(iv_bytes).map_err(|_| Error::CryptoError)?;
     Ok((iv, key))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
-
     use crate::{
-        cred::{Ed25519, NoAuth},
+        clock::SystemClock,
+        cred::{Ed25519, Ed25519MlDsa65, MlDsa44, MlDsa65, MlDsa87, NoAuth, P256},
         impls::MlKem768,
+        psk_store::InMemoryPskStore,
     };
 
     use super::*;
@@ -399,24 +421,31 @@ mod tests {
 
         let sctx = b"test context";
         let psk_handle = b"test handle";
-        let (initiator, initiator_msg) = Initiator::send_initial_message::<NoAuth, MlKem768>(
-            sctx,
-            Duration::from_secs(3600),
-            &receiver_pqpk,
-            &[0; 0],
-            &[0; 0],
-            &mut rng,
-        )
-        .unwrap();
-
-        let (handled_psk_responder, respone_msg) = Responder::send::<NoAuth, MlKem768>(
+        let mut store = InMemoryPskStore::new();
+        let (initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<NoAuth, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                &[0; 0],
+                &[0; 0],
+                &SystemClock,
+                &mut rng,
+            )
+            .unwrap();
+
+        let (handled_psk_responder, respone_msg) = Responder::send(
+            &ResponderConfig::<NoAuth, MlKem768> {
+                sctx,
+                psk_ttl: Duration::from_secs(3600),
+                pqpk: &receiver_pqpk,
+                pqsk: &receiver_pqsk,
+                client_certificate: &[],
+            },
             psk_handle,
-            Duration::from_secs(3600),
-            sctx,
-            &receiver_pqpk,
-            &receiver_pqsk,
-            &[],
             &initiator_msg,
+            &mut store,
+            &SystemClock,
         )
         .unwrap();
 
@@ -429,6 +458,12 @@ mod tests {
             handled_psk_responder.psk_handle
         );
         assert_eq!(handled_psk_initiator.psk, handled_psk_responder.psk);
+        assert_eq!(
+            store
+                .lookup(psk_handle, SystemClock.now().unwrap())
+                .unwrap(),
+            handled_psk_responder.psk
+        );
     }
 
     #[test]
@@ -439,24 +474,31 @@ mod tests {
 
         let sctx = b"test context";
         let psk_handle = b"test handle";
-        let (initiator, initiator_msg) = Initiator::send_initial_message::<Ed25519, MlKem768>(
-            sctx,
-            Duration::from_secs(3600),
-            &receiver_pqpk,
-            sk.as_ref(),
-            pk.as_ref(),
-            &mut rng,
-        )
-        .unwrap();
-
-        let (handled_psk_responder, respone_msg) = Responder::send::<Ed25519, MlKem768>(
+        let mut store = InMemoryPskStore::new();
+        let (initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<Ed25519, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                &sk,
+                pk.as_ref(),
+                &SystemClock,
+                &mut rng,
+            )
+            .unwrap();
+
+        let (handled_psk_responder, respone_msg) = Responder::send(
+            &ResponderConfig::<Ed25519, MlKem768> {
+                sctx,
+                psk_ttl: Duration::from_secs(3600),
+                pqpk: &receiver_pqpk,
+                pqsk: &receiver_pqsk,
+                client_certificate: pk.as_ref(),
+            },
             psk_handle,
-            Duration::from_secs(3600),
-            sctx,
-            &receiver_pqpk,
-            &receiver_pqsk,
-            pk.as_ref(),
             &initiator_msg,
+            &mut store,
+            &SystemClock,
         )
         .unwrap();
 
@@ -469,5 +511,449 @@ mod tests {
             handled_psk_responder.psk_handle
         );
         assert_eq!(handled_psk_initiator.psk, handled_psk_responder.psk);
+        assert_eq!(
+            store
+                .lookup(psk_handle, SystemClock.now().unwrap())
+                .unwrap(),
+            handled_psk_responder.psk
+        );
+    }
+
+    fn registration_mlkem768<C: Authenticator>(
+        signing_key: &C::SigningKey,
+        credential: &C::Credential,
+        certificate: &C::Certificate,
+    ) -> Result<(), Error> {
+        let mut rng = rand::rng();
+        let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+
+        let sctx = b"test context";
+        let psk_handle = b"test handle";
+        let mut store = InMemoryPskStore::new();
+        let (initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<C, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                signing_key,
+                credential,
+                &SystemClock,
+                &mut rng,
+            )?;
+
+        let (handled_psk_responder, respone_msg) = Responder::send(
+            &ResponderConfig::<C, MlKem768> {
+                sctx,
+                psk_ttl: Duration::from_secs(3600),
+                pqpk: &receiver_pqpk,
+                pqsk: &receiver_pqsk,
+                client_certificate: certificate,
+            },
+            psk_handle,
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        )?;
+
+        let handled_psk_initiator = initiator.complete_handshake(&respone_msg)?;
+        assert_eq!(handled_psk_initiator.psk, handled_psk_responder.psk);
+
+        Ok(())
+    }
+
+    macro_rules! ml_dsa_test {
+        ($test_name:ident, $auth:ty, $module:ident) => {
+            #[test]
+            fn $test_name() {
+                let mut randomness = [0u8; libcrux_ml_dsa::KEY_GENERATION_RANDOMNESS_SIZE];
+                rand::RngCore::fill_bytes(&mut rand::rng(), &mut randomness);
+                let key_pair = libcrux_ml_dsa::$module::generate_key_pair(randomness);
+                let credential = *key_pair.verification_key.as_ref();
+
+                registration_mlkem768::<$auth>(&key_pair.signing_key, &credential, &credential)
+                    .unwrap();
+
+                let other = libcrux_ml_dsa::$module::generate_key_pair([0xab; 32]);
+                assert!(registration_mlkem768::<$auth>(
+                    &other.signing_key,
+                    &credential,
+                    &credential
+                )
+                .is_err());
+            }
+        };
+    }
+
+    ml_dsa_test!(registration_mldsa44_mlkem768, MlDsa44, ml_dsa_44);
+    ml_dsa_test!(registration_mldsa65_mlkem768, MlDsa65, ml_dsa_65);
+    ml_dsa_test!(registration_mldsa87_mlkem768, MlDsa87, ml_dsa_87);
+
+    #[test]
+    fn registration_p256_mlkem768() {
+        let mut rng = rand::rng();
+        let sk = libcrux_ecdsa::p256::PrivateKey::random(&mut rng).unwrap();
+        let pk: [u8; 64] = libcrux_ecdh::secret_to_public(
+            libcrux_ecdh::Algorithm::P256,
+            AsRef::<[u8]>::as_ref(&sk),
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        registration_mlkem768::<P256>(&sk, &pk, &pk).unwrap();
+
+        let other = libcrux_ecdsa::p256::PrivateKey::random(&mut rng).unwrap();
+        assert!(registration_mlkem768::<P256>(&other, &pk, &pk).is_err());
+    }
+
+    #[test]
+    fn registration_ed25519_mldsa65_mlkem768() {
+        let mut rng = rand::rng();
+        let (ed_sk, ed_pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let mut randomness = [0u8; libcrux_ml_dsa::KEY_GENERATION_RANDOMNESS_SIZE];
+        rand::RngCore::fill_bytes(&mut rng, &mut randomness);
+        let ml_dsa = libcrux_ml_dsa::ml_dsa_65::generate_key_pair(randomness);
+
+        let signing_key = (
+            libcrux_ed25519::SigningKey::from_bytes(*ed_sk.as_ref()),
+            ml_dsa.signing_key.clone(),
+        );
+        let certificate = (*ed_pk.as_ref(), *ml_dsa.verification_key.as_ref());
+        let mut credential = ed_pk.as_ref().to_vec();
+        credential.extend_from_slice(ml_dsa.verification_key.as_slice());
+        assert_eq!(credential.len(), Ed25519MlDsa65::CRED_LEN);
+
+        registration_mlkem768::<Ed25519MlDsa65>(&signing_key, &credential, &certificate).unwrap();
+
+        // A valid Ed25519 signature alone is not sufficient.
+        let other = libcrux_ml_dsa::ml_dsa_65::generate_key_pair([0xab; 32]);
+        let signing_key = (ed_sk, other.signing_key);
+        assert!(
+            registration_mlkem768::<Ed25519MlDsa65>(&signing_key, &credential, &certificate)
+                .is_err()
+        );
+    }
+
+    macro_rules! round_trip_test {
+        ($test_name:ident, $kem:ty) => {
+            #[test]
+            fn $test_name() {
+                let mut rng = rand::rng();
+                let (receiver_pqsk, receiver_pqpk) = <$kem>::generate_key_pair(&mut rng).unwrap();
+                let (sk, pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+
+                let sctx = b"test context";
+                let psk_handle = b"test handle";
+                let mut store = InMemoryPskStore::new();
+                let (initiator, initiator_msg) =
+                    Initiator::<ChaCha20Poly1305>::send_initial_message::<Ed25519, $kem>(
+                        sctx,
+                        Duration::from_secs(3600),
+                        &receiver_pqpk,
+                        &sk,
+                        pk.as_ref(),
+                        &SystemClock,
+                        &mut rng,
+                    )
+                    .unwrap();
+
+                let encoded = initiator_msg.encode();
+                let (decoded, read) = InitiatorMsg::<$kem>::decode(&encoded).unwrap();
+                assert_eq!(read, encoded.len());
+                assert_eq!(decoded.encode(), encoded);
+                assert!(InitiatorMsg::<$kem>::decode(&encoded[..<$kem>::CIPHERTEXT_LEN]).is_err());
+
+                let (handled_psk_responder, respone_msg) = Responder::send(
+                    &ResponderConfig::<Ed25519, $kem> {
+                        sctx,
+                        psk_ttl: Duration::from_secs(3600),
+                        pqpk: &receiver_pqpk,
+                        pqsk: &receiver_pqsk,
+                        client_certificate: pk.as_ref(),
+                    },
+                    psk_handle,
+                    &decoded,
+                    &mut store,
+                    &SystemClock,
+                )
+                .unwrap();
+
+                let encoded = respone_msg.encode();
+                let (decoded, read) = ResponderMsg::decode(&encoded).unwrap();
+                assert_eq!(read, encoded.len());
+
+                let handled_psk_initiator = initiator.complete_handshake(&decoded).unwrap();
+                assert_eq!(handled_psk_initiator.psk_handle, psk_handle);
+                assert_eq!(handled_psk_initiator.psk, handled_psk_responder.psk);
+            }
+        };
+    }
+
+    round_trip_test!(round_trip_mlkem512, crate::impls::MlKem512);
+    round_trip_test!(round_trip_mlkem768, crate::impls::MlKem768);
+    round_trip_test!(round_trip_mlkem1024, crate::impls::MlKem1024);
+    round_trip_test!(round_trip_xwing, crate::impls::XWingKemDraft06);
+    round_trip_test!(round_trip_x25519, crate::impls::X25519);
+    #[cfg(feature = "classic-mceliece")]
+    round_trip_test!(
+        round_trip_classic_mceliece,
+        crate::classic_mceliece::ClassicMcEliece
+    );
+
+    macro_rules! aead_test {
+        ($test_name:ident, $aead:ty) => {
+            #[test]
+            fn $test_name() {
+                let mut rng = rand::rng();
+                let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+
+                let sctx = b"test context";
+                let psk_handle = b"test handle";
+                let mut store = InMemoryPskStore::new();
+                let (initiator, initiator_msg) =
+                    Initiator::<$aead>::send_initial_message::<NoAuth, MlKem768>(
+                        sctx,
+                        Duration::from_secs(3600),
+                        &receiver_pqpk,
+                        &[0; 0],
+                        &[0; 0],
+                        &SystemClock,
+                        &mut rng,
+                    )
+                    .unwrap();
+
+                let encoded = initiator_msg.encode();
+                assert_eq!(
+                    encoded.len(),
+                    initiator_msg.encapsulation.encode().len() + <$aead>::TAG_LEN + TS_TTL_LEN
+                );
+                let (decoded, _) = InitiatorMsg::<MlKem768, $aead>::decode(&encoded).unwrap();
+
+                let (handled_psk_responder, respone_msg) = Responder::send(
+                    &ResponderConfig::<NoAuth, MlKem768> {
+                        sctx,
+                        psk_ttl: Duration::from_secs(3600),
+                        pqpk: &receiver_pqpk,
+                        pqsk: &receiver_pqsk,
+                        client_certificate: &[],
+                    },
+                    psk_handle,
+                    &decoded,
+                    &mut store,
+                    &SystemClock,
+                )
+                .unwrap();
+
+                let encoded = respone_msg.encode();
+                let (decoded, _) = ResponderMsg::<$aead>::decode(&encoded).unwrap();
+
+                let handled_psk_initiator = initiator.complete_handshake(&decoded).unwrap();
+                assert_eq!(handled_psk_initiator.psk_handle, psk_handle);
+                assert_eq!(handled_psk_initiator.psk, handled_psk_responder.psk);
+            }
+        };
+    }
+
+    aead_test!(aead_chacha20poly1305, ChaCha20Poly1305);
+    aead_test!(
+        aead_xchacha20poly1305,
+        libcrux_chacha20poly1305::xchacha20_poly1305::XChaCha20Poly1305
+    );
+    aead_test!(aead_aes128_gcm, libcrux_aesgcm::aes128::Aes128Gcm);
+    aead_test!(aead_aes256_gcm, libcrux_aesgcm::aes256::Aes256Gcm);
+
+    /// Without the header of a [`session`](crate::session), a different
+    /// AEAD is only noticed because the message keys differ.
+    #[test]
+    fn aead_mismatch() {
+        let mut rng = rand::rng();
+        let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+
+        let sctx = b"test context";
+        let mut store = InMemoryPskStore::new();
+        let (_initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<NoAuth, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                &[0; 0],
+                &[0; 0],
+                &SystemClock,
+                &mut rng,
+            )
+            .unwrap();
+
+        let encoded = initiator_msg.encode();
+        let (decoded, _) =
+            InitiatorMsg::<MlKem768, libcrux_aesgcm::aes256::Aes256Gcm>::decode(&encoded).unwrap();
+        let response = Responder::send(
+            &ResponderConfig::<NoAuth, MlKem768> {
+                sctx,
+                psk_ttl: Duration::from_secs(3600),
+                pqpk: &receiver_pqpk,
+                pqsk: &receiver_pqsk,
+                client_certificate: &[],
+            },
+            b"test handle",
+            &decoded,
+            &mut store,
+            &SystemClock,
+        );
+        assert!(matches!(response, Err(Error::CryptoError)));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn registration_replay() {
+        let mut rng = rand::rng();
+        let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+
+        let sctx = b"test context";
+        let mut store = InMemoryPskStore::new();
+        let (_initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<NoAuth, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                &[0; 0],
+                &[0; 0],
+                &SystemClock,
+                &mut rng,
+            )
+            .unwrap();
+
+        let config = ResponderConfig::<NoAuth, MlKem768> {
+            sctx,
+            psk_ttl: Duration::from_secs(3600),
+            pqpk: &receiver_pqpk,
+            pqsk: &receiver_pqsk,
+            client_certificate: &[],
+        };
+        Responder::send(
+            &config,
+            b"first handle",
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        )
+        .unwrap();
+
+        let replayed = Responder::send(
+            &config,
+            b"second handle",
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        );
+        assert!(matches!(replayed, Err(Error::Replay)));
+        assert!(store.get(b"second handle").is_none());
+        assert_eq!(store.len(), 1);
+
+        let psk = store.get(b"first handle").unwrap().psk;
+        let replayed = Responder::send(
+            &config,
+            b"first handle",
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        );
+        assert!(matches!(replayed, Err(Error::Replay)));
+        assert_eq!(store.get(b"first handle").unwrap().psk, psk);
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn registration_taken_handle() {
+        let mut rng = rand::rng();
+        let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+
+        let sctx = b"test context";
+        let mut store = InMemoryPskStore::new();
+        let expires_at = SystemClock.now().unwrap() + Duration::from_secs(3600);
+        store
+            .insert(b"taken handle", [0; 32], b"", expires_at)
+            .unwrap();
+
+        let (_initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<NoAuth, MlKem768>(
+                sctx,
+                Duration::from_secs(3600),
+                &receiver_pqpk,
+                &[0; 0],
+                &[0; 0],
+                &SystemClock,
+                &mut rng,
+            )
+            .unwrap();
+
+        let config = ResponderConfig::<NoAuth, MlKem768> {
+            sctx,
+            psk_ttl: Duration::from_secs(3600),
+            pqpk: &receiver_pqpk,
+            pqsk: &receiver_pqsk,
+            client_certificate: &[],
+        };
+        let response = Responder::send(
+            &config,
+            b"taken handle",
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        );
+        assert!(matches!(response, Err(Error::StorageError)));
+
+        // The failed attempt doesn't count as a replay.
+        Responder::send(
+            &config,
+            b"free handle",
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        )
+        .unwrap();
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn registered_psk_expires() {
+        let mut rng = rand::rng();
+        let (receiver_pqsk, receiver_pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+
+        let sctx = b"test context";
+        let psk_handle = b"test handle";
+        let psk_ttl = Duration::from_secs(60);
+        let mut store = InMemoryPskStore::new();
+        let (_initiator, initiator_msg) =
+            Initiator::<ChaCha20Poly1305>::send_initial_message::<NoAuth, MlKem768>(
+                sctx,
+                psk_ttl,
+                &receiver_pqpk,
+                &[0; 0],
+                &[0; 0],
+                &SystemClock,
+                &mut rng,
+            )
+            .unwrap();
+
+        Responder::send(
+            &ResponderConfig::<NoAuth, MlKem768> {
+                sctx,
+                psk_ttl,
+                pqpk: &receiver_pqpk,
+                pqsk: &receiver_pqsk,
+                client_certificate: &[],
+            },
+            psk_handle,
+            &initiator_msg,
+            &mut store,
+            &SystemClock,
+        )
+        .unwrap();
+
+        assert!(store.lookup(psk_handle, SystemClock.now().unwrap()).is_ok());
+        assert!(matches!(
+            store.lookup(psk_handle, SystemClock.now().unwrap() + psk_ttl),
+            Err(Error::PskExpired)
+        ));
     }
 }
@@ -0,0 +1,236 @@
+//! # PSK Storage
+//!
+//! This module defines the storage interface used by the responder to
+//! keep track of registered PSKs and of the encapsulations it has
+//! already accepted, together with a simple in-memory implementation.
+//!
+//! Points in time are given as the [`Duration`] since the UNIX epoch,
+//! see [`Clock`](crate::clock::Clock).
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+
+use crate::{Error, Psk};
+
+/// A PSK as held by a [`PskStore`].
+#[derive(Clone)]
+pub struct StoredPsk {
+    /// The PSK
+    pub psk: Psk,
+    /// The identity of the initiator that registered the PSK
+    pub identity: Vec<u8>,
+    /// The point in time after which the PSK must no longer be used
+    pub expires_at: Duration,
+}
+
+impl StoredPsk {
+    /// Returns `true` if the PSK is expired at time `now`.
+    pub fn is_expired(&self, now: Duration) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Storage for registered PSKs and for the replay cache of the responder.
+///
+/// Entries in the replay cache only need to be kept until their expiry
+/// time, since the responder rejects messages whose timestamp is older
+/// than the PSK TTL anyway.
+pub trait PskStore {
+    /// Store `psk`, registered by the initiator with `identity`, under
+    /// `psk_handle`, valid until `expires_at`.
+    ///
+    /// Returns [`Error::StorageError`] if a PSK is already stored under
+    /// `psk_handle`.
+    fn insert(
+        &mut self,
+        psk_handle: &[u8],
+        psk: Psk,
+        identity: &[u8],
+        expires_at: Duration,
+    ) -> Result<(), Error>;
+
+    /// Get the PSK stored under `psk_handle`, regardless of its expiry.
+    fn get(&self, psk_handle: &[u8]) -> Option<StoredPsk>;
+
+    /// Remove the PSK stored under `psk_handle`, returning it if present.
+    fn remove(&mut self, psk_handle: &[u8]) -> Option<StoredPsk>;
+
+    /// Whether `encapsulation` has been recorded before and the record has
+    /// not expired at time `now`.
+    fn is_replay(&self, encapsulation: &[u8], now: Duration) -> bool;
+
+    /// Record that `encapsulation` has been accepted at time `now`,
+    /// keeping the record until `expires_at`.
+    ///
+    /// Returns [`Error::Replay`] if `encapsulation` has been recorded
+    /// before and the record has not expired.
+    fn record_encapsulation(
+        &mut self,
+        encapsulation: &[u8],
+        now: Duration,
+        expires_at: Duration,
+    ) -> Result<(), Error>;
+
+    /// Drop all PSKs and replay cache entries that are expired at time `now`.
+    fn purge_expired(&mut self, now: Duration);
+
+    /// Look up the PSK stored under `psk_handle` for use at time `now`.
+    ///
+    /// Returns [`Error::UnknownPsk`] if no PSK is stored under
+    /// `psk_handle` and [`Error::PskExpired`] if its TTL has passed.
+    fn lookup(&self, psk_handle: &[u8], now: Duration) -> Result<Psk, Error> {
+        let stored = self.get(psk_handle).ok_or(Error::UnknownPsk)?;
+        if stored.is_expired(now) {
+            return Err(Error::PskExpired);
+        }
+
+        Ok(stored.psk)
+    }
+}
+
+/// A [`PskStore`] keeping everything in memory.
+#[derive(Default)]
+pub struct InMemoryPskStore {
+    psks: BTreeMap<Vec<u8>, StoredPsk>,
+    seen_encapsulations: BTreeMap<Vec<u8>, Duration>,
+}
+
+impl InMemoryPskStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of stored PSKs, including expired ones that have not
+    /// been purged yet.
+    pub fn len(&self) -> usize {
+        self.psks.len()
+    }
+
+    /// Returns `true` if no PSKs are stored.
+    pub fn is_empty(&self) -> bool {
+        self.psks.is_empty()
+    }
+}
+
+impl PskStore for InMemoryPskStore {
+    fn insert(
+        &mut self,
+        psk_handle: &[u8],
+        psk: Psk,
+        identity: &[u8],
+        expires_at: Duration,
+    ) -> Result<(), Error> {
+        if self.psks.contains_key(psk_handle) {
+            return Err(Error::StorageError);
+        }
+        self.psks.insert(
+            psk_handle.to_vec(),
+            StoredPsk {
+                psk,
+                identity: identity.to_vec(),
+                expires_at,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn get(&self, psk_handle: &[u8]) -> Option<StoredPsk> {
+        self.psks.get(psk_handle).cloned()
+    }
+
+    fn remove(&mut self, psk_handle: &[u8]) -> Option<StoredPsk> {
+        self.psks.remove(psk_handle)
+    }
+
+    fn is_replay(&self, encapsulation: &[u8], now: Duration) -> bool {
+        matches!(
+            self.seen_encapsulations.get(encapsulation),
+            Some(seen_until) if now < *seen_until
+        )
+    }
+
+    fn record_encapsulation(
+        &mut self,
+        encapsulation: &[u8],
+        now: Duration,
+        expires_at: Duration,
+    ) -> Result<(), Error> {
+        if self.is_replay(encapsulation, now) {
+            return Err(Error::Replay);
+        }
+        self.seen_encapsulations
+            .insert(encapsulation.to_vec(), expires_at);
+
+        Ok(())
+    }
+
+    fn purge_expired(&mut self, now: Duration) {
+        self.psks.retain(|_, stored| !stored.is_expired(now));
+        self.seen_encapsulations
+            .retain(|_, expires_at| now < *expires_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: Duration = Duration::from_secs(1_700_000_000);
+
+    #[test]
+    fn lookup_enforces_ttl() {
+        let mut store = InMemoryPskStore::new();
+        let now = NOW;
+        let expires_at = now + Duration::from_secs(60);
+        store
+            .insert(b"handle", [1u8; 32], b"initiator", expires_at)
+            .unwrap();
+
+        assert_eq!(store.get(b"handle").unwrap().identity, b"initiator");
+        assert_eq!(store.lookup(b"handle", now).unwrap(), [1u8; 32]);
+        assert!(matches!(
+            store.lookup(b"handle", expires_at),
+            Err(Error::PskExpired)
+        ));
+        assert!(matches!(
+            store.lookup(b"other", now),
+            Err(Error::UnknownPsk)
+        ));
+
+        store.purge_expired(expires_at);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn duplicate_handle() {
+        let mut store = InMemoryPskStore::new();
+        let expires_at = NOW + Duration::from_secs(60);
+        store
+            .insert(b"handle", [1u8; 32], b"initiator", expires_at)
+            .unwrap();
+        assert!(matches!(
+            store.insert(b"handle", [2u8; 32], b"initiator", expires_at),
+            Err(Error::StorageError)
+        ));
+        assert_eq!(store.get(b"handle").unwrap().psk, [1u8; 32]);
+    }
+
+    #[test]
+    fn replay_cache() {
+        let mut store = InMemoryPskStore::new();
+        let expires_at = NOW + Duration::from_secs(60);
+        assert!(!store.is_replay(b"enc", NOW));
+        store.record_encapsulation(b"enc", NOW, expires_at).unwrap();
+        assert!(store.is_replay(b"enc", NOW));
+        assert!(!store.is_replay(b"enc", expires_at));
+        assert!(matches!(
+            store.record_encapsulation(b"enc", NOW, expires_at),
+            Err(Error::Replay)
+        ));
+        store
+            .record_encapsulation(b"other enc", NOW, expires_at)
+            .unwrap();
+    }
+}
@@ -0,0 +1,503 @@
+//! # Sans-IO Protocol Sessions
+//!
+//! This module wraps the PSK registration protocol in explicit state
+//! machines for the initiator and the responder. The sessions consume
+//! and produce byte messages, and leave transporting them to the caller.
+//!
+//! ```text
+//! InitiatorSession --start--> AwaitingResponse --receive--> RegisteredPsk
+//!                      |                             ^
+//!                      v                             |
+//!               ResponderSession::receive -----------+
+//! ```
+//!
+//! ## Wire format
+//!
+//! Every message starts with a header
+//!
+//! ```text
+//! version (u8) || message type (u8) || KEM id (u16) || authenticator id (u16)
+//!   || AEAD id (u16)
+//! ```
+//!
+//! followed by the message's fields, each prefixed by its length as a
+//! `u32`. All integers are big endian. The initiator's message has the
+//! fields `encapsulation` and `ciphertext`, the responder's message has
+//! the single field `ciphertext`.
+//!
+//! The KEM id is [`PSQ::KEM_ID`], the authenticator id is
+//! [`Authenticator::ID`] and the AEAD id is
+//! [`RegistrationAead::AEAD_ID`]. The AEAD defaults to
+//! [`ChaCha20Poly1305`]. A responder supporting several KEMs,
+//! authenticators or AEADs can use [`Header::peek`] to pick the matching
+//! [`ResponderSession`].
+
+use alloc::vec::Vec;
+use core::{marker::PhantomData, time::Duration};
+
+use libcrux_traits::kem::KEM;
+use rand::CryptoRng;
+
+use crate::{
+    clock::Clock,
+    cred::Authenticator,
+    psk_registration::{
+        AeadMac, ChaCha20Poly1305, Initiator, InitiatorMsg, RegisteredPsk, RegistrationAead,
+        Responder, ResponderConfig, ResponderMsg,
+    },
+    psk_store::PskStore,
+    traits::{Ciphertext, Decode, Encode, PSQ},
+    Error,
+};
+
+/// The version of the wire format.
+pub const VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 8;
+
+/// The type of a protocol message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MessageType {
+    /// The initiator's message
+    Initiator = 1,
+    /// The responder's message
+    Responder = 2,
+}
+
+impl TryFrom<u8> for MessageType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Initiator),
+            2 => Ok(Self::Responder),
+            _ => Err(Error::UnexpectedMessage),
+        }
+    }
+}
+
+/// The header of a protocol message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The type of the message
+    pub message_type: MessageType,
+    /// The identifier of the KEM
+    pub kem: u16,
+    /// The identifier of the authenticator
+    pub authenticator: u16,
+    /// The identifier of the AEAD
+    pub aead: u16,
+}
+
+impl Header {
+    fn new<C: Authenticator, T: PSQ, A: RegistrationAead>(message_type: MessageType) -> Self {
+        Self {
+            message_type,
+            kem: T::KEM_ID,
+            authenticator: C::ID,
+            aead: A::AEAD_ID,
+        }
+    }
+
+    /// Parse the header at the start of `message`.
+    ///
+    /// Returns [`Error::UnsupportedVersion`] if the message uses a
+    /// different wire format version.
+    pub fn peek(message: &[u8]) -> Result<Self, Error> {
+        Self::decode(message).map(|(header, _)| header)
+    }
+
+    /// Returns `true` if the message is meant for a session using `C`,
+    /// `T` and `A`.
+    pub fn matches<C: Authenticator, T: PSQ, A: RegistrationAead>(&self) -> bool {
+        self.kem == T::KEM_ID && self.authenticator == C::ID && self.aead == A::AEAD_ID
+    }
+
+    fn check<C: Authenticator, T: PSQ, A: RegistrationAead>(
+        &self,
+        message_type: MessageType,
+    ) -> Result<(), Error> {
+        if self.message_type != message_type {
+            return Err(Error::UnexpectedMessage);
+        }
+        if self.kem != T::KEM_ID {
+            return Err(Error::KemMismatch);
+        }
+        if self.authenticator != C::ID {
+            return Err(Error::AuthenticatorMismatch);
+        }
+        if self.aead != A::AEAD_ID {
+            return Err(Error::AeadMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+impl Encode for Header {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN);
+
+        out.push(VERSION);
+        out.push(self.message_type as u8);
+        out.extend_from_slice(&self.kem.to_be_bytes());
+        out.extend_from_slice(&self.authenticator.to_be_bytes());
+        out.extend_from_slice(&self.aead.to_be_bytes());
+
+        out
+    }
+}
+
+impl Decode for Header {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let header = bytes.get(..HEADER_LEN).ok_or(Error::Decoding)?;
+        if header[0] != VERSION {
+            return Err(Error::UnsupportedVersion);
+        }
+
+        let out = Self {
+            message_type: header[1].try_into()?,
+            kem: u16::from_be_bytes([header[2], header[3]]),
+            authenticator: u16::from_be_bytes([header[4], header[5]]),
+            aead: u16::from_be_bytes([header[6], header[7]]),
+        };
+
+        Ok((out, HEADER_LEN))
+    }
+}
+
+fn write_field(out: &mut Vec<u8>, field: &[u8]) -> Result<(), Error> {
+    let len = u32::try_from(field.len()).map_err(|_| Error::Decoding)?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(field);
+
+    Ok(())
+}
+
+fn read_field(bytes: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    let len = bytes.get(..4).ok_or(Error::Decoding)?;
+    let len = u32::from_be_bytes(len.try_into().map_err(|_| Error::Decoding)?) as usize;
+
+    let rest = &bytes[4..];
+    if rest.len() < len {
+        return Err(Error::Decoding);
+    }
+
+    Ok(rest.split_at(len))
+}
+
+/// Decode `field` as a `D`, which must consume all of it.
+fn decode_field<D: Decode>(field: &[u8]) -> Result<D, Error> {
+    let (out, read) = D::decode(field)?;
+    if read != field.len() {
+        return Err(Error::Decoding);
+    }
+
+    Ok(out)
+}
+
+/// The initiator, before sending its message.
+pub struct InitiatorSession<'a, C: Authenticator, T: PSQ, A: RegistrationAead = ChaCha20Poly1305> {
+    sctx: &'a [u8],
+    psk_ttl: Duration,
+    pqpk_responder: &'a <T::InnerKEM as KEM>::EncapsulationKey,
+    signing_key: &'a C::SigningKey,
+    credential: &'a C::Credential,
+    _aead: PhantomData<A>,
+}
+
+impl<'a, C: Authenticator, T: PSQ, A: RegistrationAead> InitiatorSession<'a, C, T, A> {
+    /// Set up a registration with the responder holding `pqpk_responder`.
+    pub fn new(
+        sctx: &'a [u8],
+        psk_ttl: Duration,
+        pqpk_responder: &'a <T::InnerKEM as KEM>::EncapsulationKey,
+        signing_key: &'a C::SigningKey,
+        credential: &'a C::Credential,
+    ) -> Self {
+        Self {
+            sctx,
+            psk_ttl,
+            pqpk_responder,
+            signing_key,
+            credential,
+            _aead: PhantomData,
+        }
+    }
+
+    /// Produce the initiator's message for the responder.
+    #[allow(clippy::type_complexity)]
+    pub fn start(
+        self,
+        clock: &impl Clock,
+        rng: &mut impl CryptoRng,
+    ) -> Result<(AwaitingResponse<C, T, A>, Vec<u8>), Error> {
+        let (initiator, message) = Initiator::<A>::send_initial_message::<C, T>(
+            self.sctx,
+            self.psk_ttl,
+            self.pqpk_responder,
+            self.signing_key,
+            self.credential,
+            clock,
+            rng,
+        )?;
+
+        let mut out = Header::new::<C, T, A>(MessageType::Initiator).encode();
+        write_field(&mut out, &message.encapsulation.encode())?;
+        write_field(&mut out, &message.aead_mac.encode())?;
+
+        Ok((
+            AwaitingResponse {
+                initiator,
+                _marker: PhantomData,
+            },
+            out,
+        ))
+    }
+}
+
+/// The initiator, waiting for the responder's message.
+pub struct AwaitingResponse<C: Authenticator, T: PSQ, A: RegistrationAead = ChaCha20Poly1305> {
+    initiator: Initiator<A>,
+    _marker: PhantomData<(C, T)>,
+}
+
+impl<C: Authenticator, T: PSQ, A: RegistrationAead> AwaitingResponse<C, T, A> {
+    /// Process the responder's message and return the registered PSK.
+    pub fn receive(self, message: &[u8]) -> Result<RegisteredPsk, Error> {
+        let (header, read) = Header::decode(message)?;
+        header.check::<C, T, A>(MessageType::Responder)?;
+
+        let (ciphertext, rest) = read_field(&message[read..])?;
+        if !rest.is_empty() {
+            return Err(Error::Decoding);
+        }
+        let aead_mac = decode_field::<AeadMac<A>>(ciphertext)?;
+
+        self.initiator
+            .complete_handshake(&ResponderMsg { aead_mac })
+    }
+}
+
+/// The responder, accepting registrations under a fixed configuration.
+pub struct ResponderSession<'a, C: Authenticator, T: PSQ, A: RegistrationAead = ChaCha20Poly1305> {
+    config: ResponderConfig<'a, C, T>,
+    _aead: PhantomData<A>,
+}
+
+impl<'a, C: Authenticator, T: PSQ, A: RegistrationAead> ResponderSession<'a, C, T, A> {
+    /// Set up a responder with the KEM key pair `pqsk`, `pqpk`.
+    ///
+    /// Initiator credentials are validated against `certificate`.
+    pub fn new(
+        sctx: &'a [u8],
+        psk_ttl: Duration,
+        pqpk: &'a <T::InnerKEM as KEM>::EncapsulationKey,
+        pqsk: &'a <T::InnerKEM as KEM>::DecapsulationKey,
+        certificate: &'a C::Certificate,
+    ) -> Self {
+        Self {
+            config: ResponderConfig {
+                sctx,
+                psk_ttl,
+                pqpk,
+                pqsk,
+                client_certificate: certificate,
+            },
+            _aead: PhantomData,
+        }
+    }
+
+    /// Process an initiator's message, register the PSK under
+    /// `psk_handle` in `store`, and return it with the response for the
+    /// initiator.
+    pub fn receive(
+        &self,
+        message: &[u8],
+        psk_handle: &[u8],
+        store: &mut impl PskStore,
+        clock: &impl Clock,
+    ) -> Result<(RegisteredPsk, Vec<u8>), Error> {
+        let (header, read) = Header::decode(message)?;
+        header.check::<C, T, A>(MessageType::Initiator)?;
+
+        let (encapsulation, rest) = read_field(&message[read..])?;
+        let (ciphertext, rest) = read_field(rest)?;
+        if !rest.is_empty() {
+            return Err(Error::Decoding);
+        }
+        let message = InitiatorMsg::<T::InnerKEM, A> {
+            encapsulation: decode_field::<Ciphertext<T::InnerKEM>>(encapsulation)?,
+            aead_mac: decode_field::<AeadMac<A>>(ciphertext)?,
+        };
+
+        let (psk, response) =
+            Responder::<A>::send(&self.config, psk_handle, &message, store, clock)?;
+
+        let mut out = Header::new::<C, T, A>(MessageType::Responder).encode();
+        write_field(&mut out, &response.aead_mac.encode())?;
+
+        Ok((psk, out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::SystemClock,
+        cred::{Ed25519, NoAuth},
+        impls::{MlKem1024, MlKem768},
+        psk_store::InMemoryPskStore,
+    };
+
+    const SCTX: &[u8] = b"test context";
+    const TTL: Duration = Duration::from_secs(3600);
+
+    fn initiator_message<T: PSQ>(
+        pqpk: &<T::InnerKEM as KEM>::EncapsulationKey,
+    ) -> (AwaitingResponse<NoAuth, T>, Vec<u8>) {
+        InitiatorSession::<NoAuth, T>::new(SCTX, TTL, pqpk, &[], &[])
+            .start(&SystemClock, &mut rand::rng())
+            .unwrap()
+    }
+
+    #[test]
+    fn registration() {
+        let mut rng = rand::rng();
+        let (pqsk, pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+        let (sk, pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let mut store = InMemoryPskStore::new();
+
+        let (initiator, message) =
+            InitiatorSession::<Ed25519, MlKem768>::new(SCTX, TTL, &pqpk, &sk, pk.as_ref())
+                .start(&SystemClock, &mut rng)
+                .unwrap();
+        assert_eq!(
+            Header::peek(&message).unwrap(),
+            Header {
+                message_type: MessageType::Initiator,
+                kem: 0x0041,
+                authenticator: 0x0001,
+                aead: 0x0003,
+            }
+        );
+
+        let responder =
+            ResponderSession::<Ed25519, MlKem768>::new(SCTX, TTL, &pqpk, &pqsk, pk.as_ref());
+        let (responder_psk, response) = responder
+            .receive(&message, b"test handle", &mut store, &SystemClock)
+            .unwrap();
+
+        let initiator_psk = initiator.receive(&response).unwrap();
+        assert_eq!(initiator_psk.psk, responder_psk.psk);
+        assert_eq!(initiator_psk.psk_handle, b"test handle");
+    }
+
+    #[test]
+    fn mismatches() {
+        let mut rng = rand::rng();
+        let (pqsk, pqpk) = MlKem768::generate_key_pair(&mut rng).unwrap();
+        let (_, pk) = libcrux_ed25519::generate_key_pair(&mut rng).unwrap();
+        let mut store = InMemoryPskStore::new();
+        let (_, message) = initiator_message::<MlKem768>(&pqpk);
+
+        let wrong_auth =
+            ResponderSession::<Ed25519, MlKem768>::new(SCTX, TTL, &pqpk, &pqsk, pk.as_ref());
+        assert!(matches!(
+            wrong_auth.receive(&message, b"handle", &mut store, &SystemClock),
+            Err(Error::AuthenticatorMismatch)
+        ));
+
+        let (pqsk_1024, pqpk_1024) = MlKem1024::generate_key_pair(&mut rng).unwrap();
+        let wrong_kem =
+            ResponderSession::<NoAuth, MlKem1024>::new(SCTX, TTL, &pqpk_1024, &pqsk_1024, &[]);
+        assert!(matches!(
+            wrong_kem.receive(&message, b"handle", &mut store, &SystemClock),
+            Err(Error::KemMismatch)
+        ));
+
+        let wrong_aead =
+            ResponderSession::<NoAuth, MlKem768, libcrux_aesgcm::aes256::Aes256Gcm>::new(
+                SCTX,
+                TTL,
+                &pqpk,
+                &pqsk,
+                &[],
+            );
+        assert!(matches!(
+            wrong_aead.receive(&message, b"handle", &mut store, &SystemClock),
+            Err(Error::AeadMismatch)
+        ));
+
+        let responder = ResponderSession::<NoAuth, MlKem768>::new(SCTX, TTL, &pqpk, &pqsk, &[]);
+
+        let mut wrong_version = message.clone();
+        wrong_version[0] = VERSION + 1;
+        assert!(matches!(
+            responder.receive(&wrong_version, b"handle", &mut store, &SystemClock),
+            Err(Error::UnsupportedVersion)
+        ));
+
+        let mut wrong_type = message.clone();
+        wrong_type[1] = MessageType::Responder as u8;
+        assert!(matches!(
+            responder.receive(&wrong_type, b"handle", &mut store, &SystemClock),
+            Err(Error::UnexpectedMessage)
+        ));
+
+        for len in 0..message.len() {
+            assert!(matches!(
+                responder.receive(&message[..len], b"handle", &mut store, &SystemClock),
+                Err(Error::Decoding)
+            ));
+        }
+        let mut trailing = message.clone();
+        trailing.push(0);
+        assert!(matches!(
+            responder.receive(&trailing, b"handle", &mut store, &SystemClock),
+            Err(Error::Decoding)
+        ));
+
+        let (_, response) = responder
+            .receive(&message, b"handle", &mut store, &SystemClock)
+            .unwrap();
+
+        // The initiator's own message is not a valid response.
+        let (initiator, message) = initiator_message::<MlKem768>(&pqpk);
+        assert!(matches!(
+            initiator.receive(&message),
+            Err(Error::UnexpectedMessage)
+        ));
+
+        // A response for a different session does not decrypt.
+        let (initiator, _) = initiator_message::<MlKem768>(&pqpk);
+        assert!(initiator.receive(&response).is_err());
+    }
+
+    #[test]
+    fn negotiation() {
+        let mut rng = rand::rng();
+        let (pqsk_768, pqpk_768) = MlKem768::generate_key_pair(&mut rng).unwrap();
+        let (pqsk_1024, pqpk_1024) = MlKem1024::generate_key_pair(&mut rng).unwrap();
+        let responder_768 =
+            ResponderSession::<NoAuth, MlKem768>::new(SCTX, TTL, &pqpk_768, &pqsk_768, &[]);
+        let responder_1024 =
+            ResponderSession::<NoAuth, MlKem1024>::new(SCTX, TTL, &pqpk_1024, &pqsk_1024, &[]);
+        let mut store = InMemoryPskStore::new();
+
+        let (initiator, message) = initiator_message::<MlKem1024>(&pqpk_1024);
+        let header = Header::peek(&message).unwrap();
+        let (responder_psk, response) = if header.matches::<NoAuth, MlKem768, ChaCha20Poly1305>() {
+            responder_768.receive(&message, b"handle", &mut store, &SystemClock)
+        } else if header.matches::<NoAuth, MlKem1024, ChaCha20Poly1305>() {
+            responder_1024.receive(&message, b"handle", &mut store, &SystemClock)
+        } else {
+            Err(Error::KemMismatch)
+        }
+        .unwrap();
+
+        assert_eq!(initiator.receive(&response).unwrap().psk, responder_psk.psk);
+    }
+}
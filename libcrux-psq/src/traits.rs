@@ -1,4 +1,6 @@
 //! This module provides common traits for PSQ implementations.
+use alloc::vec::Vec;
+
 use libcrux_hkdf::{expand as hkdf_expand, Algorithm as HKDF_Algorithm};
 use libcrux_hmac::{hmac, Algorithm as HMAC_Algorithm};
 use libcrux_traits::kem::KEM;
@@ -45,11 +47,26 @@ pub trait Decode {
 pub(crate) mod private {
     pub trait Seal {}
 }
+
+/// A KEM with fixed-length ciphertexts that can be decoded from bytes.
+pub trait DecodeCiphertext: KEM {
+    /// Length (in bytes) of a serialized ciphertext.
+    const CIPHERTEXT_LEN: usize;
+
+    /// Decode a ciphertext from exactly [`Self::CIPHERTEXT_LEN`] bytes.
+    fn decode_ciphertext(bytes: &[u8]) -> Result<Self::Ciphertext, Error>;
+}
 /// This trait provides the interface for encapsulating a PSQ
 /// component using an underlying KEM.
 pub trait PSQ: private::Seal {
     /// The underlying KEM.
-    type InnerKEM: KEM<Ciphertext: Encode, SharedSecret: Encode, EncapsulationKey: Encode>;
+    type InnerKEM: KEM<Ciphertext: Encode, SharedSecret: Encode, EncapsulationKey: Encode>
+        + DecodeCiphertext;
+
+    /// Identifier of the KEM in the wire format.
+    ///
+    /// Where available, this is the KEM's HPKE identifier.
+    const KEM_ID: u16;
 
     /// Encapsulate a fresh PSQ component.
     fn encapsulate_psq(
@@ -112,6 +129,19 @@ impl<T: KEM<Ciphertext: Encode>> Encode for Ciphertext<T> {
     }
 }
 
+impl<T: DecodeCiphertext> Decode for Ciphertext<T> {
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), Error> {
+        let len = T::CIPHERTEXT_LEN + MAC_LENGTH;
+        let bytes = bytes.get(..len).ok_or(Error::Decoding)?;
+        let (ct_bytes, mac_bytes) = bytes.split_at(T::CIPHERTEXT_LEN);
+
+        let inner_ctxt = T::decode_ciphertext(ct_bytes)?;
+        let mac = mac_bytes.try_into().map_err(|_| Error::Decoding)?;
+
+        Ok((Self { inner_ctxt, mac }, len))
+    }
+}
+
 // TODO: Use functions from `secrets` crate instead once that's merged.
 // See:
 /// Return 1 if `value` is not zero and 0 otherwise.
@@ -0,0 +1,85 @@
+#! /usr/bin/env python3
+
+# Computes the test vectors of src/psk_derivation.rs independently of
+# libcrux, with the OpenSSL backed HKDF and HMAC of the `cryptography` package.
+#
+# The TLS 1.3 HKDF-Expand-Label below is checked against the early secret
+# and the "derived" secret of RFC 8448, Section 3, before the RFC 9258
+# importer vectors are computed with it.
+
+import hashlib
+
+from cryptography.hazmat.primitives import hashes, hmac
+from cryptography.hazmat.primitives.kdf.hkdf import HKDFExpand
+
+PSK = bytes(range(32))
+
+
+def hkdf_extract(salt, ikm):
+    h = hmac.HMAC(salt, hashes.SHA256())
+    h.update(ikm)
+    return h.finalize()
+
+
+def hkdf_expand(prk, info, length=32):
+    return HKDFExpand(hashes.SHA256(), length, info).derive(prk)
+
+
+def expand_label(secret, label, context, length=32):
+    label = b"tls13 " + label
+    info = length.to_bytes(2, "big") + bytes([len(label)]) + label
+    info += bytes([len(context)]) + context
+    return hkdf_expand(secret, info, length)
+
+
+def check_expand_label():
+    early_secret = hkdf_extract(bytes(32), bytes(32))
+    assert early_secret.hex() == (
+        "33ad0a1c607ec03b09e6cd9893680ce210adf300aa1f2660e1b22e10f170f92a"
+    )
+    derived = expand_label(early_secret, b"derived", hashlib.sha256(b"").digest())
+    assert derived.hex() == (
+        "6f2615a108c702c5678f54fc9dbab69716c076189c48250cebeac3576c3611ba"
+    )
+
+
+def tls_importer(psk, external_identity, context):
+    # RFC 9258, Section 4.1, with target_protocol TLS 1.3 and target_kdf
+    # HKDF_SHA256.
+    identity = len(external_identity).to_bytes(2, "big") + external_identity
+    identity += len(context).to_bytes(2, "big") + context
+    identity += (0x0304).to_bytes(2, "big") + (0x0001).to_bytes(2, "big")
+
+    epskx = hkdf_extract(bytes(32), psk)
+    ipsk = expand_label(epskx, b"derived psk", hashlib.sha256(identity).digest())
+
+    # RFC 9258, Section 5 and RFC 8446, Section 4.2.11.2.
+    early_secret = hkdf_extract(bytes(32), ipsk)
+    binder_key = expand_label(early_secret, b"imp binder", hashlib.sha256(b"").digest())
+    finished_key = expand_label(binder_key, b"finished", b"")
+    h = hmac.HMAC(finished_key, hashes.SHA256())
+    h.update(bytes([0xAA] * 32))
+
+    return identity, ipsk, binder_key, h.finalize()
+
+
+if __name__ == "__main__":
+    check_expand_label()
+
+    print("wireguard", hkdf_expand(PSK, b"PSQ-WireGuard-PresharedKey").hex())
+    print(
+        "noise",
+        hkdf_expand(PSK, b"PSQ-Noise-PSK" + b"Noise_XXpsk3_25519_ChaChaPoly_SHA256").hex(),
+    )
+
+    identity, ipsk, binder_key, binder = tls_importer(PSK, b"psk handle", b"example.com")
+    print("tls identity", identity.hex())
+    print("tls ipsk", ipsk.hex())
+    print("tls binder key", binder_key.hex())
+    print("tls binder", binder.hex())
+
+    for epoch in [0, 1]:
+        print(
+            f"epoch {epoch}",
+            hkdf_expand(PSK, b"PSQ-Epoch" + epoch.to_bytes(8, "big")).hex(),
+        )
@@ -0,0 +1,65 @@
+//! Incremental SHA3 hashers implementing [`libcrux_traits::Digest`].
+
+/// Implement an incremental SHA3 hasher on top of the portable
+/// [`KeccakXofState`].
+///
+/// [`KeccakXofState`]: crate::generic_keccak::KeccakXofState
+macro_rules! impl_sha3_digest {
+    ($name:ident, $rate:literal, $len:literal, $oneshot:path, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            state: crate::generic_keccak::KeccakXofState<1, $rate, u64>,
+        }
+
+        impl $name {
+            /// Initialize a new digest state for streaming use.
+            #[inline(always)]
+            pub fn new() -> Self {
+                Self {
+                    state: crate::generic_keccak::KeccakXofState::new(),
+                }
+            }
+        }
+
+        impl Default for $name {
+            #[inline(always)]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl libcrux_traits::Digest<$len> for $name {
+            /// Write the digest of `payload` into `digest`, in immediate mode.
+            #[inline(always)]
+            fn hash(digest: &mut [u8], payload: &[u8]) {
+                $oneshot(digest, payload)
+            }
+
+            /// Add the `payload` to the digest.
+            #[inline(always)]
+            fn update(&mut self, payload: &[u8]) {
+                self.state.absorb(&[payload]);
+            }
+
+            /// Get the digest.
+            ///
+            /// Note that the digest state can be continued to be used, to extend the
+            /// digest.
+            #[inline(always)]
+            fn finish(&self, digest: &mut [u8; $len]) {
+                let mut state = self.state.clone();
+                state.absorb_final::<0x06u8>(&[&[]]);
+                state.squeeze([digest.as_mut_slice()]);
+            }
+
+            /// Reset the digest state.
+            #[inline(always)]
+            fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+    };
+}
+
+pub(crate) use impl_sha3_digest;
@@ -30,11 +30,15 @@ impl<const N: usize, T: KeccakStateItem<N>> KeccakState<N, T> {
 
 /// The internal keccak state that can also buffer inputs to absorb.
 /// This is used in the general xof APIs.
+///
+/// The permutation is Keccak-p[1600, ROUNDS], i.e. Keccak-f[1600] by default.
 #[cfg_attr(hax, hax_lib::opaque)]
+#[derive(Clone)]
 pub(crate) struct KeccakXofState<
     const PARALLEL_LANES: usize,
     const RATE: usize,
     STATE: KeccakStateItem<PARALLEL_LANES>,
+    const ROUNDS: usize = 24,
 > {
     inner: KeccakState<PARALLEL_LANES, STATE>,
 
@@ -48,8 +52,12 @@ pub(crate) struct KeccakXofState<
     sponge: bool,
 }
 
-impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARALLEL_LANES>>
-    KeccakXofState<PARALLEL_LANES, RATE, STATE>
+impl<
+        const PARALLEL_LANES: usize,
+        const RATE: usize,
+        STATE: KeccakStateItem<PARALLEL_LANES>,
+        const ROUNDS: usize,
+    > KeccakXofState<PARALLEL_LANES, RATE, STATE, ROUNDS>
 {
     /// An all zero block
     pub(crate) const fn zero_block() -> [u8; RATE] {
@@ -116,7 +124,7 @@ impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARA
                 borrowed[i] = &self.buf[i];
             }
             STATE::load_block::<RATE>(&mut self.inner.st, &borrowed, 0);
-            keccakf1600(&mut self.inner);
+            keccak_p::<PARALLEL_LANES, STATE, ROUNDS>(&mut self.inner);
 
             // "empty" the local buffer
             self.buf_len = 0;
@@ -131,7 +139,7 @@ impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARA
         for i in 0..num_blocks {
             // We only get in here if `input_len / RATE > 0`.
             STATE::load_block::<RATE>(&mut self.inner.st, &inputs, input_consumed + i * RATE);
-            keccakf1600(&mut self.inner);
+            keccak_p::<PARALLEL_LANES, STATE, ROUNDS>(&mut self.inner);
         }
 
         remainder
@@ -169,6 +177,18 @@ impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARA
     /// `RATE` bytes is ignored.
     #[inline(always)]
     pub(crate) fn absorb_final<const DELIMITER: u8>(&mut self, inputs: &[&[u8]; PARALLEL_LANES]) {
+        self.absorb_final_delimited(inputs, DELIMITER);
+    }
+
+    /// Absorb a final block with a `delimiter` that is only known at runtime.
+    ///
+    /// See [`Self::absorb_final`].
+    #[inline(always)]
+    pub(crate) fn absorb_final_delimited(
+        &mut self,
+        inputs: &[&[u8]; PARALLEL_LANES],
+        delimiter: u8,
+    ) {
         let input_remainder_len = self.absorb_full(inputs);
 
         // Consume the remaining bytes.
@@ -183,12 +203,22 @@ impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARA
                 blocks[i][self.buf_len..self.buf_len + input_remainder_len]
                     .copy_from_slice(&inputs[i][input_len - input_remainder_len..]);
             }
-            blocks[i][self.buf_len + input_remainder_len] = DELIMITER;
+            blocks[i][self.buf_len + input_remainder_len] = delimiter;
             blocks[i][RATE - 1] |= 0x80;
         }
 
         STATE::load_block_full::<RATE>(&mut self.inner.st, &blocks, 0);
-        keccakf1600(&mut self.inner);
+        keccak_p::<PARALLEL_LANES, STATE, ROUNDS>(&mut self.inner);
+    }
+
+    /// Squeeze the first block after [`Self::absorb_final_delimited`].
+    ///
+    /// Unlike [`Self::squeeze`] this works for all `STATE`s.
+    #[inline(always)]
+    pub(crate) fn squeeze_first_block(&self) -> [[u8; 200]; PARALLEL_LANES] {
+        let mut out = [[0u8; 200]; PARALLEL_LANES];
+        STATE::store_block_full::<RATE>(&self.inner.st, &mut out);
+        out
     }
 
     /// Squeeze `N` x `LEN` bytes.
@@ -198,7 +228,7 @@ impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARA
             // If we called `squeeze` before, call f1600 first.
             // We do it this way around so that we don't call f1600 at the end
             // when we don't need it.
-            keccakf1600(&mut self.inner);
+            keccak_p::<PARALLEL_LANES, STATE, ROUNDS>(&mut self.inner);
         }
 
         // How many blocks do we need to squeeze out?
@@ -217,14 +247,14 @@ impl<const PARALLEL_LANES: usize, const RATE: usize, STATE: KeccakStateItem<PARA
         for _ in 1..blocks {
             // Here we know that we always have full blocks to write out.
             let (out0, tmp) = STATE::split_at_mut_n(out_rest, RATE);
-            keccakf1600(&mut self.inner);
+            keccak_p::<PARALLEL_LANES, STATE, ROUNDS>(&mut self.inner);
             STATE::store::<RATE>(&self.inner.st, out0);
             out_rest = tmp;
         }
 
         if last < out_len {
             // Squeeze out the last partial block
-            keccakf1600(&mut self.inner);
+            keccak_p::<PARALLEL_LANES, STATE, ROUNDS>(&mut self.inner);
             STATE::store::<RATE>(&self.inner.st, out_rest);
         }
 
@@ -404,7 +434,18 @@ pub(crate) fn iota<const N: usize, T: KeccakStateItem<N>>(s: &mut KeccakState<N,
 
 #[inline(always)]
 pub(crate) fn keccakf1600<const N: usize, T: KeccakStateItem<N>>(s: &mut KeccakState<N, T>) {
-    for i in 0..24 {
+    keccak_p::<N, T, 24>(s)
+}
+
+/// Keccak-p[1600, ROUNDS], i.e. the last `ROUNDS` rounds of Keccak-f[1600].
+///
+/// This is used with 12 rounds in TurboSHAKE.
+#[inline(always)]
+pub(crate) fn keccak_p<const N: usize, T: KeccakStateItem<N>, const ROUNDS: usize>(
+    s: &mut KeccakState<N, T>,
+) {
+    debug_assert!(ROUNDS <= 24);
+    for i in 24 - ROUNDS..24 {
         theta_rho(s);
         pi(s);
         chi(s);
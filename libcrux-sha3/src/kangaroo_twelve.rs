@@ -0,0 +1,210 @@
+//! # KangarooTwelve
+//!
+//! KT128 and KT256 from [RFC 9861], the tree hashes on top of TurboSHAKE128
+//! and TurboSHAKE256.
+//!
+//! Inputs longer than 8192 bytes are split into chunks that are hashed
+//! independently. These chunks are hashed with the AVX2 (4 lanes) or Neon
+//! (2 lanes) Keccak when they are available.
+//!
+//! [RFC 9861]: https://www.rfc-editor.org/rfc/rfc9861.html
+
+use crate::{
+    generic_keccak::KeccakXofState,
+    traits::KeccakStateItem,
+    turboshake::{TurboShake, RATE_128, RATE_256, ROUNDS},
+};
+
+/// The length of the chunks.
+const CHUNK_LEN: usize = 8192;
+
+/// The domain separation byte for inputs of a single chunk.
+const SINGLE_NODE: u8 = 0x07;
+
+/// The domain separation byte for the final node.
+const FINAL_NODE: u8 = 0x06;
+
+/// The domain separation byte for the leaves.
+const LEAF: u8 = 0x0B;
+
+/// `length_encode(x)`: the big-endian encoding of `x` without leading zeroes,
+/// followed by its length.
+#[inline(always)]
+fn length_encode(x: usize) -> ([u8; 9], usize) {
+    let x = x as u64;
+    let n = 8 - x.leading_zeros() as usize / 8;
+    let mut encoded = [0u8; 9];
+    encoded[..n].copy_from_slice(&x.to_be_bytes()[8 - n..]);
+    encoded[n] = n as u8;
+    (encoded, n + 1)
+}
+
+/// The input `S = M || C || length_encode(|C|)`, without copying it.
+struct Input<'a> {
+    parts: [&'a [u8]; 3],
+}
+
+impl<'a> Input<'a> {
+    fn len(&self) -> usize {
+        self.parts.iter().map(|part| part.len()).sum()
+    }
+
+    /// Absorb the bytes `start..end` of the input.
+    fn absorb<const RATE: usize>(
+        &self,
+        state: &mut TurboShake<RATE>,
+        mut start: usize,
+        mut end: usize,
+    ) {
+        for part in self.parts {
+            let part_end = core::cmp::min(end, part.len());
+            if start < part_end {
+                state.absorb(&part[start..part_end]);
+            }
+            start = start.saturating_sub(part.len());
+            end = end.saturating_sub(part.len());
+        }
+    }
+
+    /// The bytes `start..end` of the input if they are in a single part.
+    fn contiguous(&self, mut start: usize, mut end: usize) -> Option<&'a [u8]> {
+        for part in self.parts {
+            if end <= part.len() {
+                return Some(&part[start..end]);
+            }
+            if start < part.len() {
+                return None;
+            }
+            start -= part.len();
+            end -= part.len();
+        }
+        None
+    }
+
+    /// The `N` full leaf chunks starting at leaf `first`, if they are all in a
+    /// single part.
+    fn full_leaves<const N: usize>(&self, first: usize) -> Option<[&'a [u8]; N]> {
+        let mut chunks = [&[][..]; N];
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            // The first chunk is not a leaf.
+            let start = (first + i + 1) * CHUNK_LEN;
+            *chunk = self.contiguous(start, start + CHUNK_LEN)?;
+        }
+        Some(chunks)
+    }
+}
+
+/// The chaining values of `N` leaves of the same length, using the `N` lane
+/// Keccak.
+#[inline(always)]
+fn chaining_values<
+    const N: usize,
+    T: KeccakStateItem<N>,
+    const RATE: usize,
+    const CV_LEN: usize,
+>(
+    leaves: &[&[u8]; N],
+) -> [[u8; CV_LEN]; N] {
+    let mut state = KeccakXofState::<N, RATE, T, ROUNDS>::new();
+    state.absorb(leaves);
+    state.absorb_final_delimited(&[&[]; N], LEAF);
+
+    // The chaining values are shorter than the rate.
+    let blocks = state.squeeze_first_block();
+    let mut cvs = [[0u8; CV_LEN]; N];
+    for (cv, block) in cvs.iter_mut().zip(blocks.iter()) {
+        cv.copy_from_slice(&block[..CV_LEN]);
+    }
+    cvs
+}
+
+/// Hash the leaves starting at leaf `first` and absorb their chaining values
+/// into the final `node`.
+///
+/// Returns the number of leaves that were hashed.
+fn absorb_leaves<const RATE: usize, const CV_LEN: usize>(
+    node: &mut TurboShake<RATE>,
+    input: &Input,
+    first: usize,
+) -> usize {
+    #[cfg(feature = "simd256")]
+    if libcrux_platform::simd256_support() {
+        if let Some(leaves) = input.full_leaves::<4>(first) {
+            let cvs = chaining_values::<4, libcrux_intrinsics::avx2::Vec256, RATE, CV_LEN>(&leaves);
+            for cv in cvs {
+                node.absorb(&cv);
+            }
+            return 4;
+        }
+    }
+
+    #[cfg(feature = "simd128")]
+    if libcrux_platform::simd128_support() {
+        if let Some(leaves) = input.full_leaves::<2>(first) {
+            let cvs = chaining_values::<2, crate::simd::arm64::uint64x2_t, RATE, CV_LEN>(&leaves);
+            for cv in cvs {
+                node.absorb(&cv);
+            }
+            return 2;
+        }
+    }
+
+    // A single leaf, which may be shorter or span multiple parts.
+    let start = (first + 1) * CHUNK_LEN;
+    let end = core::cmp::min(start + CHUNK_LEN, input.len());
+    let mut leaf = TurboShake::<RATE>::init(LEAF);
+    input.absorb(&mut leaf, start, end);
+
+    let mut cv = [0u8; CV_LEN];
+    leaf.finalize(&mut cv);
+    node.absorb(&cv);
+    1
+}
+
+fn kangaroo_twelve<const RATE: usize, const CV_LEN: usize>(
+    out: &mut [u8],
+    message: &[u8],
+    customization: &[u8],
+) {
+    let (encoded_len, encoded_len_len) = length_encode(customization.len());
+    let input = Input {
+        parts: [message, customization, &encoded_len[..encoded_len_len]],
+    };
+    let len = input.len();
+
+    if len <= CHUNK_LEN {
+        let mut node = TurboShake::<RATE>::init(SINGLE_NODE);
+        input.absorb(&mut node, 0, len);
+        node.finalize(out);
+        return;
+    }
+
+    let mut node = TurboShake::<RATE>::init(FINAL_NODE);
+    input.absorb(&mut node, 0, CHUNK_LEN);
+    node.absorb(&[0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+    let leaves = (len - 1) / CHUNK_LEN;
+    let mut leaf = 0;
+    while leaf < leaves {
+        leaf += absorb_leaves::<RATE, CV_LEN>(&mut node, &input, leaf);
+    }
+
+    let (encoded_leaves, encoded_leaves_len) = length_encode(leaves);
+    node.absorb(&encoded_leaves[..encoded_leaves_len]);
+    node.absorb(&[0xFF, 0xFF]);
+    node.finalize(out);
+}
+
+/// KT128 (KangarooTwelve) of `message` with the `customization` string.
+///
+/// Writes `out.len()` bytes.
+pub fn kt128(out: &mut [u8], message: &[u8], customization: &[u8]) {
+    kangaroo_twelve::<RATE_128, 32>(out, message, customization)
+}
+
+/// KT256 of `message` with the `customization` string.
+///
+/// Writes `out.len()` bytes.
+pub fn kt256(out: &mut [u8], message: &[u8], customization: &[u8]) {
+    kangaroo_twelve::<RATE_256, 64>(out, message, customization)
+}
@@ -6,12 +6,19 @@
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
+pub mod kangaroo_twelve;
 pub mod simd;
+pub mod sp800_185;
+pub mod turboshake;
 
+mod digest;
 mod generic_keccak;
 mod portable_keccak;
 mod traits;
 
+/// Re-export the `Digest` trait.
+pub use libcrux_traits::Digest;
+
 /// A SHA3 224 Digest
 pub type Sha3_224Digest = [u8; 28];
 
@@ -341,6 +348,35 @@ pub mod portable {
             }
         }
 
+        crate::digest::impl_sha3_digest!(
+            Sha3_224,
+            144,
+            28,
+            crate::portable::sha224,
+            "Incremental portable SHA3 224"
+        );
+        crate::digest::impl_sha3_digest!(
+            Sha3_256,
+            136,
+            32,
+            crate::portable::sha256,
+            "Incremental portable SHA3 256"
+        );
+        crate::digest::impl_sha3_digest!(
+            Sha3_384,
+            104,
+            48,
+            crate::portable::sha384,
+            "Incremental portable SHA3 384"
+        );
+        crate::digest::impl_sha3_digest!(
+            Sha3_512,
+            72,
+            64,
+            crate::portable::sha512,
+            "Incremental portable SHA3 512"
+        );
+
         /// Create a new SHAKE-128 state object.
         #[inline(always)]
         pub fn shake128_init() -> KeccakState {
@@ -470,6 +506,14 @@ pub mod neon {
     pub mod x2 {
         use super::*;
 
+        /// Run SHAKE128 on both inputs in parallel.
+        ///
+        /// Writes the two results into `out0` and `out1`
+        #[inline(always)]
+        pub fn shake128(input0: &[u8], input1: &[u8], out0: &mut [u8], out1: &mut [u8]) {
+            keccakx2::<168, 0x1fu8>(&[input0, input1], [out0, out1]);
+        }
+
         /// Run SHAKE256 on both inputs in parallel.
         ///
         /// Writes the two results into `out0` and `out1`
@@ -808,6 +852,25 @@ pub mod avx2 {
         use crate::generic_keccak::keccak;
         use libcrux_intrinsics::avx2::*;
 
+        /// Perform 4 SHAKE128 operations in parallel
+        #[allow(clippy::too_many_arguments)]
+        #[inline(always)]
+        pub fn shake128(
+            input0: &[u8],
+            input1: &[u8],
+            input2: &[u8],
+            input3: &[u8],
+            out0: &mut [u8],
+            out1: &mut [u8],
+            out2: &mut [u8],
+            out3: &mut [u8],
+        ) {
+            keccak::<4, Vec256, 168, 0x1fu8>(
+                &[input0, input1, input2, input3],
+                [out0, out1, out2, out3],
+            );
+        }
+
         /// Perform 4 SHAKE256 operations in parallel
         #[allow(clippy::too_many_arguments)]
         #[inline(always)]
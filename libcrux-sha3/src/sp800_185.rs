@@ -0,0 +1,330 @@
+//! # SHA-3 Derived Functions
+//!
+//! The functions from [NIST SP 800-185]: cSHAKE, KMAC, TupleHash and
+//! ParallelHash, each in the 128 and 256 bit security variants.
+//!
+//! All functions write `out.len()` bytes. The fixed-length functions bind the
+//! output length into the result. The `_xof` variants do not, i.e. a shorter
+//! output is a prefix of a longer one.
+//!
+//! ParallelHash hashes the input blocks with the AVX2 (4 lanes) or Neon
+//! (2 lanes) Keccak when they are available.
+//!
+//! [NIST SP 800-185]: https://doi.org/10.6028/NIST.SP.800-185
+
+use crate::generic_keccak::{keccak, KeccakXofState};
+
+/// The rate of cSHAKE128.
+const RATE_128: usize = 168;
+
+/// The rate of cSHAKE256.
+const RATE_256: usize = 136;
+
+/// Zero bytes for padding to the rate.
+const ZEROS: [u8; RATE_128] = [0u8; RATE_128];
+
+/// The length of the input in bits, as used in the encodings.
+#[inline(always)]
+fn bit_len(len: usize) -> u128 {
+    (len as u128) * 8
+}
+
+/// The big-endian encoding of `x` and the number of significant bytes, which
+/// is at least one.
+#[inline(always)]
+fn be_bytes(x: u128) -> ([u8; 16], usize) {
+    let n = 16 - (x.leading_zeros() as usize / 8);
+    (x.to_be_bytes(), core::cmp::max(n, 1))
+}
+
+/// A cSHAKE state with `RATE` bytes.
+struct CShake<const RATE: usize> {
+    state: KeccakXofState<1, RATE, u64>,
+    /// cSHAKE with an empty function name and customization string is SHAKE.
+    shake: bool,
+}
+
+impl<const RATE: usize> CShake<RATE> {
+    /// `cSHAKE(·, L, N, S)` before absorbing the input.
+    fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        let mut cshake = Self {
+            state: KeccakXofState::new(),
+            shake: function_name.is_empty() && customization.is_empty(),
+        };
+        if !cshake.shake {
+            cshake.bytepad(&[function_name, customization]);
+        }
+        cshake
+    }
+
+    #[inline(always)]
+    fn absorb(&mut self, data: &[u8]) {
+        self.state.absorb(&[data]);
+    }
+
+    /// Absorb `left_encode(x)` and return its length.
+    fn left_encode(&mut self, x: u128) -> usize {
+        let (bytes, n) = be_bytes(x);
+        self.absorb(&[n as u8]);
+        self.absorb(&bytes[16 - n..]);
+        n + 1
+    }
+
+    /// Absorb `right_encode(x)`.
+    fn right_encode(&mut self, x: u128) {
+        let (bytes, n) = be_bytes(x);
+        self.absorb(&bytes[16 - n..]);
+        self.absorb(&[n as u8]);
+    }
+
+    /// Absorb `encode_string(s)` and return its length.
+    fn encode_string(&mut self, s: &[u8]) -> usize {
+        let n = self.left_encode(bit_len(s.len()));
+        self.absorb(s);
+        n + s.len()
+    }
+
+    /// Absorb `bytepad(encode_string(s_0) || ... || encode_string(s_n), RATE)`.
+    fn bytepad(&mut self, strings: &[&[u8]]) {
+        let mut len = self.left_encode(RATE as u128);
+        for s in strings {
+            len += self.encode_string(s);
+        }
+        let padding = (RATE - len % RATE) % RATE;
+        self.absorb(&ZEROS[..padding]);
+    }
+
+    /// Pad and squeeze `out.len()` bytes.
+    fn squeeze(mut self, out: &mut [u8]) {
+        if self.shake {
+            self.state.absorb_final::<0x1fu8>(&[&[]]);
+        } else {
+            self.state.absorb_final::<0x04u8>(&[&[]]);
+        }
+        self.state.squeeze([out]);
+    }
+}
+
+#[inline(always)]
+fn cshake<const RATE: usize>(
+    out: &mut [u8],
+    data: &[u8],
+    function_name: &[u8],
+    customization: &[u8],
+) {
+    let mut cshake = CShake::<RATE>::new(function_name, customization);
+    cshake.absorb(data);
+    cshake.squeeze(out);
+}
+
+/// cSHAKE128 of `data` with the `function_name` and `customization` strings.
+///
+/// With empty `function_name` and `customization` this is SHAKE128.
+pub fn cshake128(out: &mut [u8], data: &[u8], function_name: &[u8], customization: &[u8]) {
+    cshake::<RATE_128>(out, data, function_name, customization)
+}
+
+/// cSHAKE256 of `data` with the `function_name` and `customization` strings.
+///
+/// With empty `function_name` and `customization` this is SHAKE256.
+pub fn cshake256(out: &mut [u8], data: &[u8], function_name: &[u8], customization: &[u8]) {
+    cshake::<RATE_256>(out, data, function_name, customization)
+}
+
+#[inline(always)]
+fn kmac<const RATE: usize>(
+    out: &mut [u8],
+    key: &[u8],
+    data: &[u8],
+    customization: &[u8],
+    xof: bool,
+) {
+    let mut cshake = CShake::<RATE>::new(b"KMAC", customization);
+    cshake.bytepad(&[key]);
+    cshake.absorb(data);
+    cshake.right_encode(if xof { 0 } else { bit_len(out.len()) });
+    cshake.squeeze(out);
+}
+
+/// KMAC128 of `data` under `key`.
+pub fn kmac128(out: &mut [u8], key: &[u8], data: &[u8], customization: &[u8]) {
+    kmac::<RATE_128>(out, key, data, customization, false)
+}
+
+/// KMACXOF128 of `data` under `key`.
+pub fn kmac128_xof(out: &mut [u8], key: &[u8], data: &[u8], customization: &[u8]) {
+    kmac::<RATE_128>(out, key, data, customization, true)
+}
+
+/// KMAC256 of `data` under `key`.
+pub fn kmac256(out: &mut [u8], key: &[u8], data: &[u8], customization: &[u8]) {
+    kmac::<RATE_256>(out, key, data, customization, false)
+}
+
+/// KMACXOF256 of `data` under `key`.
+pub fn kmac256_xof(out: &mut [u8], key: &[u8], data: &[u8], customization: &[u8]) {
+    kmac::<RATE_256>(out, key, data, customization, true)
+}
+
+#[inline(always)]
+fn tuple_hash<const RATE: usize>(out: &mut [u8], tuple: &[&[u8]], customization: &[u8], xof: bool) {
+    let mut cshake = CShake::<RATE>::new(b"TupleHash", customization);
+    for s in tuple {
+        cshake.encode_string(s);
+    }
+    cshake.right_encode(if xof { 0 } else { bit_len(out.len()) });
+    cshake.squeeze(out);
+}
+
+/// TupleHash128 of the strings in `tuple`.
+pub fn tuple_hash128(out: &mut [u8], tuple: &[&[u8]], customization: &[u8]) {
+    tuple_hash::<RATE_128>(out, tuple, customization, false)
+}
+
+/// TupleHashXOF128 of the strings in `tuple`.
+pub fn tuple_hash128_xof(out: &mut [u8], tuple: &[&[u8]], customization: &[u8]) {
+    tuple_hash::<RATE_128>(out, tuple, customization, true)
+}
+
+/// TupleHash256 of the strings in `tuple`.
+pub fn tuple_hash256(out: &mut [u8], tuple: &[&[u8]], customization: &[u8]) {
+    tuple_hash::<RATE_256>(out, tuple, customization, false)
+}
+
+/// TupleHashXOF256 of the strings in `tuple`.
+pub fn tuple_hash256_xof(out: &mut [u8], tuple: &[&[u8]], customization: &[u8]) {
+    tuple_hash::<RATE_256>(out, tuple, customization, true)
+}
+
+/// Hash groups of 4 blocks with the AVX2 Keccak and absorb the results.
+///
+/// Returns the remaining input.
+#[cfg(feature = "simd256")]
+fn absorb_leaves_x4<'a, const RATE: usize, const LEAF_LEN: usize>(
+    cshake: &mut CShake<RATE>,
+    data: &'a [u8],
+    block_size: usize,
+) -> &'a [u8] {
+    use crate::avx2::x4;
+
+    let mut groups = data.chunks_exact(4 * block_size);
+    for group in groups.by_ref() {
+        let (b0, rest) = group.split_at(block_size);
+        let (b1, rest) = rest.split_at(block_size);
+        let (b2, b3) = rest.split_at(block_size);
+
+        let [mut l0, mut l1, mut l2, mut l3] = [[0u8; LEAF_LEN]; 4];
+        if RATE == RATE_128 {
+            x4::shake128(b0, b1, b2, b3, &mut l0, &mut l1, &mut l2, &mut l3);
+        } else {
+            x4::shake256(b0, b1, b2, b3, &mut l0, &mut l1, &mut l2, &mut l3);
+        }
+        for leaf in [l0, l1, l2, l3] {
+            cshake.absorb(&leaf);
+        }
+    }
+    groups.remainder()
+}
+
+/// Hash pairs of blocks with the Neon Keccak and absorb the results.
+///
+/// Returns the remaining input.
+#[cfg(feature = "simd128")]
+fn absorb_leaves_x2<'a, const RATE: usize, const LEAF_LEN: usize>(
+    cshake: &mut CShake<RATE>,
+    data: &'a [u8],
+    block_size: usize,
+) -> &'a [u8] {
+    use crate::neon::x2;
+
+    let mut groups = data.chunks_exact(2 * block_size);
+    for group in groups.by_ref() {
+        let (b0, b1) = group.split_at(block_size);
+
+        let [mut l0, mut l1] = [[0u8; LEAF_LEN]; 2];
+        if RATE == RATE_128 {
+            x2::shake128(b0, b1, &mut l0, &mut l1);
+        } else {
+            x2::shake256(b0, b1, &mut l0, &mut l1);
+        }
+        cshake.absorb(&l0);
+        cshake.absorb(&l1);
+    }
+    groups.remainder()
+}
+
+/// Hash every `block_size` block of `data` to `LEAF_LEN` bytes and absorb the
+/// results.
+///
+/// The leaves are `cSHAKE(block, 8 * LEAF_LEN, "", "")`, i.e. SHAKE.
+fn absorb_leaves<const RATE: usize, const LEAF_LEN: usize>(
+    cshake: &mut CShake<RATE>,
+    data: &[u8],
+    block_size: usize,
+) {
+    #[cfg(feature = "simd256")]
+    let data = if libcrux_platform::simd256_support() {
+        absorb_leaves_x4::<RATE, LEAF_LEN>(cshake, data, block_size)
+    } else {
+        data
+    };
+
+    #[cfg(feature = "simd128")]
+    let data = if libcrux_platform::simd128_support() {
+        absorb_leaves_x2::<RATE, LEAF_LEN>(cshake, data, block_size)
+    } else {
+        data
+    };
+
+    for block in data.chunks(block_size) {
+        let mut leaf = [0u8; LEAF_LEN];
+        keccak::<1, u64, RATE, 0x1fu8>(&[block], [&mut leaf]);
+        cshake.absorb(&leaf);
+    }
+}
+
+#[inline(always)]
+fn parallel_hash<const RATE: usize, const LEAF_LEN: usize>(
+    out: &mut [u8],
+    data: &[u8],
+    block_size: usize,
+    customization: &[u8],
+    xof: bool,
+) {
+    assert!(block_size > 0, "The ParallelHash block size must not be 0.");
+
+    let mut cshake = CShake::<RATE>::new(b"ParallelHash", customization);
+    cshake.left_encode(block_size as u128);
+    absorb_leaves::<RATE, LEAF_LEN>(&mut cshake, data, block_size);
+    cshake.right_encode(data.len().div_ceil(block_size) as u128);
+    cshake.right_encode(if xof { 0 } else { bit_len(out.len()) });
+    cshake.squeeze(out);
+}
+
+/// ParallelHash128 of `data`, split into blocks of `block_size` bytes.
+///
+/// **PANICS** when `block_size` is 0.
+pub fn parallel_hash128(out: &mut [u8], data: &[u8], block_size: usize, customization: &[u8]) {
+    parallel_hash::<RATE_128, 32>(out, data, block_size, customization, false)
+}
+
+/// ParallelHashXOF128 of `data`, split into blocks of `block_size` bytes.
+///
+/// **PANICS** when `block_size` is 0.
+pub fn parallel_hash128_xof(out: &mut [u8], data: &[u8], block_size: usize, customization: &[u8]) {
+    parallel_hash::<RATE_128, 32>(out, data, block_size, customization, true)
+}
+
+/// ParallelHash256 of `data`, split into blocks of `block_size` bytes.
+///
+/// **PANICS** when `block_size` is 0.
+pub fn parallel_hash256(out: &mut [u8], data: &[u8], block_size: usize, customization: &[u8]) {
+    parallel_hash::<RATE_256, 64>(out, data, block_size, customization, false)
+}
+
+/// ParallelHashXOF256 of `data`, split into blocks of `block_size` bytes.
+///
+/// **PANICS** when `block_size` is 0.
+pub fn parallel_hash256_xof(out: &mut [u8], data: &[u8], block_size: usize, customization: &[u8]) {
+    parallel_hash::<RATE_256, 64>(out, data, block_size, customization, true)
+}
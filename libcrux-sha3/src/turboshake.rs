@@ -0,0 +1,105 @@
+//! # TurboSHAKE
+//!
+//! TurboSHAKE128 and TurboSHAKE256 from [RFC 9861]. These are SHAKE128 and
+//! SHAKE256 on the 12 round permutation Keccak-p[1600, 12], with a domain
+//! separation byte `D` in `0x01..=0x7F`.
+//!
+//! [RFC 9861]: https://www.rfc-editor.org/rfc/rfc9861.html
+
+use crate::generic_keccak::KeccakXofState;
+
+/// The number of rounds of the permutation.
+pub(crate) const ROUNDS: usize = 12;
+
+/// The rate of TurboSHAKE128.
+pub(crate) const RATE_128: usize = 168;
+
+/// The rate of TurboSHAKE256.
+pub(crate) const RATE_256: usize = 136;
+
+/// The domain separation byte to use when there is no need for multiple
+/// instances.
+pub const DEFAULT_DOMAIN_SEPARATION: u8 = 0x1F;
+
+/// An incremental TurboSHAKE state with `RATE` bytes.
+///
+/// Use [`TurboShake128`] or [`TurboShake256`].
+#[derive(Clone)]
+pub struct TurboShake<const RATE: usize> {
+    state: KeccakXofState<1, RATE, u64, ROUNDS>,
+    domain_separation: u8,
+}
+
+/// An incremental TurboSHAKE128 state.
+pub type TurboShake128 = TurboShake<RATE_128>;
+
+/// An incremental TurboSHAKE256 state.
+pub type TurboShake256 = TurboShake<RATE_256>;
+
+impl<const RATE: usize> TurboShake<RATE> {
+    /// **PANICS** when `domain_separation` is not in `0x01..=0x7F`.
+    pub(crate) fn init(domain_separation: u8) -> Self {
+        assert!(
+            (0x01..=0x7F).contains(&domain_separation),
+            "The TurboSHAKE domain separation byte must be in 0x01..=0x7F."
+        );
+        Self {
+            state: KeccakXofState::new(),
+            domain_separation,
+        }
+    }
+
+    /// Absorb `data`.
+    #[inline(always)]
+    pub fn absorb(&mut self, data: &[u8]) {
+        self.state.absorb(&[data]);
+    }
+
+    /// Pad and write `out.len()` bytes of output.
+    #[inline(always)]
+    pub fn finalize(mut self, out: &mut [u8]) {
+        self.state
+            .absorb_final_delimited(&[&[]], self.domain_separation);
+        self.state.squeeze([out]);
+    }
+}
+
+impl TurboShake128 {
+    /// Create a new TurboSHAKE128 state with the `domain_separation` byte.
+    ///
+    /// **PANICS** when `domain_separation` is not in `0x01..=0x7F`.
+    pub fn new(domain_separation: u8) -> Self {
+        Self::init(domain_separation)
+    }
+}
+
+impl TurboShake256 {
+    /// Create a new TurboSHAKE256 state with the `domain_separation` byte.
+    ///
+    /// **PANICS** when `domain_separation` is not in `0x01..=0x7F`.
+    pub fn new(domain_separation: u8) -> Self {
+        Self::init(domain_separation)
+    }
+}
+
+/// TurboSHAKE128 of `data` with the `domain_separation` byte.
+///
+/// Writes `out.len()` bytes.
+///
+/// **PANICS** when `domain_separation` is not in `0x01..=0x7F`.
+pub fn turboshake128(out: &mut [u8], data: &[u8], domain_separation: u8) {
+    let mut state = TurboShake128::new(domain_separation);
+    state.absorb(data);
+    state.finalize(out);
+}
+
+/// TurboSHAKE256 of `data` with the `domain_separation` byte.
+///
+/// Writes `out.len()` bytes.
+///
+/// **PANICS** when `domain_separation` is not in `0x01..=0x7F`.
+pub fn turboshake256(out: &mut [u8], data: &[u8], domain_separation: u8) {
+    let mut state = TurboShake256::new(domain_separation);
+    state.absorb(data);
+    state.finalize(out);
+}
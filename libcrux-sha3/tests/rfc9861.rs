@@ -0,0 +1,323 @@
+//! The test vectors for TurboSHAKE and KangarooTwelve from
+//! [RFC 9861, Section 5](https://www.rfc-editor.org/rfc/rfc9861.html#section-5).
+
+use libcrux_sha3::{kangaroo_twelve::*, turboshake::*};
+
+/// `ptn(n)`: the bytes `0x00, ..., 0xFA` repeated and truncated to `n` bytes.
+fn ptn(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
+
+fn turboshake128_hex(data: &[u8], domain_separation: u8, len: usize) -> String {
+    let mut out = vec![0u8; len];
+    turboshake128(&mut out, data, domain_separation);
+    hex::encode(out)
+}
+
+fn turboshake256_hex(data: &[u8], domain_separation: u8, len: usize) -> String {
+    let mut out = vec![0u8; len];
+    turboshake256(&mut out, data, domain_separation);
+    hex::encode(out)
+}
+
+fn kt128_hex(message: &[u8], customization: &[u8], len: usize) -> String {
+    let mut out = vec![0u8; len];
+    kt128(&mut out, message, customization);
+    hex::encode(out)
+}
+
+fn kt256_hex(message: &[u8], customization: &[u8], len: usize) -> String {
+    let mut out = vec![0u8; len];
+    kt256(&mut out, message, customization);
+    hex::encode(out)
+}
+
+#[test]
+fn turboshake128_empty() {
+    assert_eq!(
+        turboshake128_hex(b"", 0x07, 32),
+        "5a223ad30b3b8c66a243048cfced430f54e7529287d15150b973133adfac6a2f"
+    );
+    assert_eq!(
+        turboshake128_hex(b"", 0x07, 64),
+        "5a223ad30b3b8c66a243048cfced430f54e7529287d15150b973133adfac6a2f\
+         fe2708e73061e09a4000168ba9c8ca1813198f7bbed4984b4185f2c2580ee623"
+    );
+    assert_eq!(
+        &turboshake128_hex(b"", 0x07, 10032)[2 * 10000..],
+        "7593a28020a3c4ae0d605fd61f5eb56eccd27cc3d12ff09f78369772a460c55d"
+    );
+}
+
+#[test]
+fn turboshake128_ptn() {
+    let expected = [
+        "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5",
+        "acbd4aa57507043bcee55ad3f48504d815e707fe82ee3dad6d5852c8920b905e",
+        "7a4de8b1d927a682b929610103f0e964559bd74542cfad740ee3d9b036469e0a",
+        "7452ed0ed860aa8fe8e79699ece324f8d93271463610da76801ebcee4fcafe42",
+        "ca5f1f3eeac992cdc2abebca0e216765dbf779c3c10946055a94ab3272573522",
+        "e988193fb9119f11cd34467914e2a26da9bdf96c8bef076aeead1a897b866383",
+        "9c0ffb987eeeedadfa55948987756d090b67ccb61236e306ac8a24de1d0af774",
+    ];
+    for (i, expected) in expected.into_iter().enumerate() {
+        let data = ptn(17usize.pow(i as u32));
+        assert_eq!(turboshake128_hex(&data, 0x07, 32), expected, "ptn(17^{i})");
+    }
+}
+
+#[test]
+fn turboshake128_domain_separation() {
+    assert_eq!(
+        turboshake128_hex(&[0xFF; 3], 0x06, 32),
+        "3d03988bb59e681851a192f429ae03988e8f444bc06036a3f1a7d2ccd758d174"
+    );
+    assert_eq!(
+        turboshake128_hex(&[0xFF; 7], 0x06, 32),
+        "05d9ae673d5f0e48bb2b57e88021a1a83d70ba85923aa04c12e8f65ba1f94595"
+    );
+}
+
+#[test]
+fn turboshake256_empty() {
+    assert_eq!(
+        turboshake256_hex(b"", 0x07, 64),
+        "4a555b06ecf8f1538ccf5c9515d0d04970181563a62381c7f0c807a6d1bd9e81\
+         97804bfde2428bf72961eb52b4189c391cef6fee663a3c1ce78b88255bc1acc3"
+    );
+    assert_eq!(
+        &turboshake256_hex(b"", 0x07, 10032)[2 * 10000..],
+        "40221ad734f3edc1b106bad50a72949315b352ba39ad98b5b3c2301163adaad0"
+    );
+}
+
+#[test]
+fn turboshake256_ptn() {
+    let expected = [
+        "66d378dfe4e902ac4eb78f7c2e5a14f02bc1c849e621bae665796fb3346e6c79\
+         75705bb93c00f3ca8f83bca479f06977ab3a60f39796b136538aaae8bcac8544",
+        "c52174abf28295e15dfb37b946ac36bd3a6bcc98c074fc25199e0530425cc5ed\
+         d4dfd43dc3e7e6491a13179830c3c750c9237e83fd9a3fec4603ff57e4222ef2",
+        "62a5a0bff06426d71a7a3e9e3f2fd6e252ff3fc188a6a536eca45a49a3437cb3\
+         bc3a0f8149c850e6e7f4747a70627fd2303041c6c33630f943ad92f8e1ff4390",
+        "523c0647182d8941f0dd5c5c0ab62d4fc29561615396bb5b9a9deb022b80c5bf\
+         2d83a3bb36ffc04fac58cf1149c66dec4a59526e51f29596d824421a4b84b44d",
+        "d114a1c1a208ff05fd49d09ee035465d86547ebad8e9af4f8e875370573d6b7b\
+         b20ab960635ab574e22195ef9d171c9a2801044b6e2edf272e2302554b3a77c9",
+        "1e513495d6169875b59453a594e08ae271ca20e05643c88a987b5b6ab423ede7\
+         240f34f2b335fa94bc4b0d70e31fb633b079844331fea42a9c4d79bb8c5f9e73",
+    ];
+    for (i, expected) in expected.into_iter().enumerate() {
+        let data = ptn(17usize.pow(i as u32 + 1));
+        assert_eq!(
+            turboshake256_hex(&data, 0x07, 64),
+            expected,
+            "ptn(17^{})",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn turboshake256_domain_separation() {
+    assert_eq!(
+        turboshake256_hex(&[0xFF], 0x06, 64),
+        "738d7b4e37d18b7f22ad1b5313e357e3dd7d07056a26a303c433fa3533455280\
+         f4f5a7d4f700efb437fe6d281405e07be32a0a972e22e63adc1b090daefe004b"
+    );
+    assert_eq!(
+        turboshake256_hex(&[0xFF; 3], 0x06, 64),
+        "e5538cdd28302a2e81e41f65fd2a4052014d0cd463df671d1e510a9d95c37d71\
+         35ef2728430a9e317004f836c9a238ef35370280d03dce7f0612f0315b3cbf63"
+    );
+    assert_eq!(
+        turboshake256_hex(&[0xFF; 7], 0x06, 64),
+        "b38b8c15f4a6e80cd3ec645f999f6498aad7a59a489c1dee29708b4f8a59e124\
+         99a96f89372256fe522b1b97472add736915bd4df93b21ffe597217eb3c2c6d9"
+    );
+}
+
+#[test]
+fn turboshake_incremental() {
+    let data = ptn(1000);
+    let mut expected128 = [0u8; 200];
+    let mut expected256 = [0u8; 200];
+    turboshake128(&mut expected128, &data, DEFAULT_DOMAIN_SEPARATION);
+    turboshake256(&mut expected256, &data, DEFAULT_DOMAIN_SEPARATION);
+
+    for chunk_len in [1, 7, 135, 136, 137, 167, 168, 169, 500] {
+        let mut state128 = TurboShake128::new(DEFAULT_DOMAIN_SEPARATION);
+        let mut state256 = TurboShake256::new(DEFAULT_DOMAIN_SEPARATION);
+        for chunk in data.chunks(chunk_len) {
+            state128.absorb(chunk);
+            state256.absorb(chunk);
+        }
+
+        let mut out = [0u8; 200];
+        state128.finalize(&mut out);
+        assert_eq!(out, expected128, "chunk length {chunk_len}");
+        state256.finalize(&mut out);
+        assert_eq!(out, expected256, "chunk length {chunk_len}");
+    }
+}
+
+#[test]
+#[should_panic]
+fn turboshake_domain_separation_zero() {
+    turboshake128(&mut [0u8; 32], b"", 0x00);
+}
+
+#[test]
+#[should_panic]
+fn turboshake_domain_separation_too_large() {
+    turboshake256(&mut [0u8; 32], b"", 0x80);
+}
+
+#[test]
+fn kt128_empty() {
+    assert_eq!(
+        kt128_hex(b"", b"", 32),
+        "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5"
+    );
+    assert_eq!(
+        kt128_hex(b"", b"", 64),
+        "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5\
+         4269c056b8c82e48276038b6d292966cc07a3d4645272e31ff38508139eb0a71"
+    );
+    assert_eq!(
+        &kt128_hex(b"", b"", 10032)[2 * 10000..],
+        "e8dc563642f7228c84684c898405d3a834799158c079b12880277a1d28e2ff6d"
+    );
+}
+
+#[test]
+fn kt128_ptn_message() {
+    let expected = [
+        "2bda92450e8b147f8a7cb629e784a058efca7cf7d8218e02d345dfaa65244a1f",
+        "6bf75fa2239198db4772e36478f8e19b0f371205f6a9a93a273f51df37122888",
+        "0c315ebcdedbf61426de7dcf8fb725d1e74675d7f5327a5067f367b108ecb67c",
+        "cb552e2ec77d9910701d578b457ddf772c12e322e4ee7fe417f92c758f0d59d0",
+        "8701045e22205345ff4dda05555cbb5c3af1a771c2b89baef37db43d9998b9fe",
+        "844d610933b1b9963cbdeb5ae3b6b05cc7cbd67ceedf883eb678a0a8e0371682",
+        "3c390782a8a4e89fa6367f72feaaf13255c8d95878481d3cd8ce85f58e880af8",
+    ];
+    for (i, expected) in expected.into_iter().enumerate() {
+        let message = ptn(17usize.pow(i as u32));
+        assert_eq!(kt128_hex(&message, b"", 32), expected, "ptn(17^{i})");
+    }
+}
+
+#[test]
+fn kt128_ptn_customization() {
+    let expected = [
+        "fab658db63e94a246188bf7af69a133045f46ee984c56e3c3328caaf1aa1a583",
+        "d848c5068ced736f4462159b9867fd4c20b808acc3d5bc48e0b06ba0a3762ec4",
+        "c389e5009ae57120854c2e8c64670ac01358cf4c1baf89447a724234dc7ced74",
+        "75d2f86a2e644566726b4fbcfc5657b9dbcf070c7b0dca06450ab291d7443bcf",
+    ];
+    for (i, expected) in expected.into_iter().enumerate() {
+        let message = vec![0xFF; 2usize.pow(i as u32) - 1];
+        let customization = ptn(41usize.pow(i as u32));
+        assert_eq!(
+            kt128_hex(&message, &customization, 32),
+            expected,
+            "ptn(41^{i})"
+        );
+    }
+}
+
+#[test]
+fn kt128_chunk_boundaries() {
+    // Not from the RFC, but from the reference implementation.
+    let expected = [
+        (
+            "1b577636f723643e990cc7d6a659837436fd6a103626600eb8301cd1dbe553d6",
+            "48f256f6772f9edfb6a8b661ec92dc93b95ebd05a08a17b39ae3490870c926c3",
+        ),
+        (
+            "e3ded52118ea64eaf04c7531c6ccb95e32924b7c2b87b2ce68ff2f2ee46e84ef",
+            "82778f7f7234c83352e76837b721fbdbb5270b88010d84fa5ab0b61ec8ce0956",
+        ),
+        (
+            "daacf62e434bdd126fbe9e61fae38d1429e9dddfaf8f999095585c3cbf366a4a",
+            "f4082a8fe7d1635aa042cd1da63bf235f91c231886c29896f9fe3818c60cd360",
+        ),
+        (
+            "eac3722b4b7db10af973ed7ca60e113a19fab895b46476a9aac51ead099e6ba4",
+            "d14f8dc243c206004ca8a996997e5ae16a8bdda288f6c90d20d7c43c1a408618",
+        ),
+    ];
+    for (i, (minus_one, multiple)) in expected.into_iter().enumerate() {
+        let len = 8192 * (i + 1);
+        assert_eq!(kt128_hex(&ptn(len - 1), b"", 32), minus_one, "{}", len - 1);
+        assert_eq!(kt128_hex(&ptn(len), b"", 32), multiple, "{len}");
+    }
+}
+
+#[test]
+fn kt256_empty() {
+    assert_eq!(
+        kt256_hex(b"", b"", 64),
+        "b23d2e9cea9f4904e02bec06817fc10ce38ce8e93ef4c89e6537076af8646404\
+         e3e8b68107b8833a5d30490aa33482353fd4adc7148ecb782855003aaebde4a9"
+    );
+    assert_eq!(
+        kt256_hex(b"", b"", 128),
+        "b23d2e9cea9f4904e02bec06817fc10ce38ce8e93ef4c89e6537076af8646404\
+         e3e8b68107b8833a5d30490aa33482353fd4adc7148ecb782855003aaebde4a9\
+         b0925319d8ea1e121a609821ec19efea89e6d08daee1662b69c840289f188ba8\
+         60f55760b61f82114c030c97e5178449608ccd2cd2d919fc7829ff69931ac4d0"
+    );
+    assert_eq!(
+        &kt256_hex(b"", b"", 10064)[2 * 10000..],
+        "ad4a1d718cf950506709a4c33396139b4449041fc79a05d68da35f1e453522e0\
+         56c64fe94958e7085f2964888259b9932752f3ccd855288efee5fcbb8b563069"
+    );
+}
+
+#[test]
+fn kt256_ptn_message() {
+    let expected = [
+        "0d005a194085360217128cf17f91e1f71314efa5564539d444912e3437efa17f\
+         82db6f6ffe76e781eaa068bce01f2bbf81eacb983d7230f2fb02834a21b1ddd0",
+        "1ba3c02b1fc514474f06c8979978a9056c8483f4a1b63d0dccefe3a28a2f323e\
+         1cdcca40ebf006ac76ef0397152346837b1277d3e7faa9c9653b19075098527b",
+        "de8ccbc63e0f133ebb4416814d4c66f691bbf8b6a61ec0a7700f836b086cb029\
+         d54f12ac7159472c72db118c35b4e6aa213c6562caaa9dcc518959e69b10f3ba",
+        "647efb49fe9d717500171b41e7f11bd491544443209997ce1c2530d15eb1ffbb\
+         598935ef954528ffc152b1e4d731ee2683680674365cd191d562bae753b84aa5",
+        "b06275d284cd1cf205bcbe57dccd3ec1ff6686e3ed15776383e1f2fa3c6ac8f0\
+         8bf8a162829db1a44b2a43ff83dd89c3cf1ceb61ede659766d5ccf817a62ba8d",
+        "9473831d76a4c7bf77ace45b59f1458b1673d64bcd877a7c66b2664aa6dd149e\
+         60eab71b5c2bab858c074ded81ddce2b4022b5215935c0d4d19bf511aeeb0772",
+        "0652b740d78c5e1f7c8dcc1777097382768b7ff38f9a7a20f29f413bb1b3045b\
+         31a5578f568f911e09cf44746da84224a5266e96a4a535e871324e4f9c7004da",
+    ];
+    for (i, expected) in expected.into_iter().enumerate() {
+        let message = ptn(17usize.pow(i as u32));
+        assert_eq!(kt256_hex(&message, b"", 64), expected, "ptn(17^{i})");
+    }
+}
+
+#[test]
+fn kt256_ptn_customization() {
+    let expected = [
+        "9280f5cc39b54a5a594ec63de0bb99371e4609d44bf845c2f5b8c316d72b1598\
+         11f748f23e3fabbe5c3226ec96c62186df2d33e9df74c5069ceecbb4dd10eff6",
+        "47ef96dd616f200937aa7847e34ec2feae8087e3761dc0f8c1a154f51dc9ccf8\
+         45d7adbce57ff64b639722c6a1672e3bf5372d87e00aff89be97240756998853",
+        "3b48667a5051c5966c53c5d42b95de451e05584e7806e2fb765eda959074172c\
+         b438a9e91dde337c98e9c41bed94c4e0aef431d0b64ef2324f7932caa6f54969",
+        "e0911cc00025e1540831e266d94add9b98712142b80d2629e643aac4efaf5a3a\
+         30a88cbf4ac2a91a2432743054fbcc9897670e86ba8cec2fc2ace9c966369724",
+    ];
+    for (i, expected) in expected.into_iter().enumerate() {
+        let message = vec![0xFF; 2usize.pow(i as u32) - 1];
+        let customization = ptn(41usize.pow(i as u32));
+        assert_eq!(
+            kt256_hex(&message, &customization, 64),
+            expected,
+            "ptn(41^{i})"
+        );
+    }
+}
@@ -333,4 +333,94 @@ mod portable {
         state.squeeze(&mut digest);
         assert_eq!(hex::encode(digest), expected);
     }
+
+    #[test]
+    fn sha3_incremental() {
+        use libcrux_sha3::Digest;
+
+        fn check<const LEN: usize, D: Digest<LEN> + Default>(expected: [&str; 3]) {
+            let inputs = [test_vectors::EMPTY, test_vectors::HELLO, test_vectors::FOX];
+            for (input, expected) in inputs.iter().zip(expected) {
+                let mut one_shot = [0u8; LEN];
+                D::hash(&mut one_shot, input);
+                assert_eq!(hex::encode(one_shot), expected);
+
+                for chunk_len in [1, 3, 7, 64] {
+                    let mut state = D::default();
+                    for chunk in input.chunks(chunk_len) {
+                        state.update(chunk);
+                    }
+                    let mut digest = [0u8; LEN];
+                    state.finish(&mut digest);
+                    assert_eq!(hex::encode(digest), expected);
+                }
+            }
+        }
+
+        check::<28, incremental::Sha3_224>([
+            test_vectors::sha3_224::EMPTY,
+            test_vectors::sha3_224::HELLO,
+            test_vectors::sha3_224::FOX,
+        ]);
+        check::<32, incremental::Sha3_256>([
+            test_vectors::sha3_256::EMPTY,
+            test_vectors::sha3_256::HELLO,
+            test_vectors::sha3_256::FOX,
+        ]);
+        check::<48, incremental::Sha3_384>([
+            test_vectors::sha3_384::EMPTY,
+            test_vectors::sha3_384::HELLO,
+            test_vectors::sha3_384::FOX,
+        ]);
+        check::<64, incremental::Sha3_512>([
+            test_vectors::sha3_512::EMPTY,
+            test_vectors::sha3_512::HELLO,
+            test_vectors::sha3_512::FOX,
+        ]);
+    }
+
+    #[test]
+    fn sha3_incremental_rate_boundaries() {
+        use libcrux_sha3::Digest;
+
+        // Cover inputs around the 136 byte rate of SHA3 256.
+        let msg: Vec<u8> = (0..600u32).map(|i| (i * 13) as u8).collect();
+        for len in [135, 136, 137, 271, 272, 273, 600] {
+            let mut expected = [0u8; 32];
+            sha256(&mut expected, &msg[..len]);
+
+            for chunk_len in [1, 17, 135, 136, 137] {
+                let mut state = incremental::Sha3_256::new();
+                for chunk in msg[..len].chunks(chunk_len) {
+                    state.update(chunk);
+                }
+                let mut digest = [0u8; 32];
+                state.finish(&mut digest);
+                assert_eq!(digest, expected, "length {len}, chunk length {chunk_len}");
+            }
+        }
+    }
+
+    #[test]
+    fn sha3_incremental_continue_and_reset() {
+        use libcrux_sha3::Digest;
+
+        let mut state = incremental::Sha3_256::new();
+        let mut digest = [0u8; 32];
+        state.update(&test_vectors::HELLO[..3]);
+        state.finish(&mut digest);
+
+        let mut expected = [0u8; 32];
+        sha256(&mut expected, &test_vectors::HELLO[..3]);
+        assert_eq!(digest, expected);
+
+        // The state can be extended after `finish`.
+        state.update(&test_vectors::HELLO[3..]);
+        state.finish(&mut digest);
+        assert_eq!(hex::encode(digest), test_vectors::sha3_256::HELLO);
+
+        state.reset();
+        state.finish(&mut digest);
+        assert_eq!(hex::encode(digest), test_vectors::sha3_256::EMPTY);
+    }
 }
@@ -0,0 +1,315 @@
+//! The sample vectors for [NIST SP 800-185](https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values).
+
+use libcrux_sha3::sp800_185::*;
+
+/// The bytes `0x00, ..., 0x03`.
+fn short_data() -> Vec<u8> {
+    (0x00..0x04).collect()
+}
+
+/// The bytes `0x00, ..., 0xC7`.
+fn long_data() -> Vec<u8> {
+    (0x00..0xC8).collect()
+}
+
+/// The KMAC key `0x40, ..., 0x5F`.
+fn kmac_key() -> Vec<u8> {
+    (0x40..0x60).collect()
+}
+
+/// The TupleHash inputs.
+const TE3: &[u8] = &[0x00, 0x01, 0x02];
+const TE6: &[u8] = &[0x10, 0x11, 0x12, 0x13, 0x14, 0x15];
+const TE9: &[u8] = &[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28];
+
+/// The ParallelHash input, with a block size of 8.
+const PARALLEL_DATA: &[u8] = &[
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+    0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+];
+
+fn check(f: impl Fn(&mut [u8]), expected: &str) {
+    let mut out = vec![0u8; expected.len() / 2];
+    f(&mut out);
+    assert_eq!(hex::encode(out), expected);
+}
+
+#[test]
+fn cshake() {
+    let s = b"Email Signature";
+    check(
+        |out| cshake128(out, &short_data(), b"", s),
+        "c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5",
+    );
+    check(
+        |out| cshake128(out, &long_data(), b"", s),
+        "c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b",
+    );
+    check(
+        |out| cshake256(out, &short_data(), b"", s),
+        "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1\
+         64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c",
+    );
+    check(
+        |out| cshake256(out, &long_data(), b"", s),
+        "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac864302730917\
+         27f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb",
+    );
+}
+
+#[test]
+fn cshake_without_strings_is_shake() {
+    let data = long_data();
+    let mut cshake = [0u8; 200];
+    let mut shake = [0u8; 200];
+
+    cshake128(&mut cshake, &data, b"", b"");
+    libcrux_sha3::shake128_ema(&mut shake, &data);
+    assert_eq!(cshake, shake);
+
+    cshake256(&mut cshake, &data, b"", b"");
+    libcrux_sha3::shake256_ema(&mut shake, &data);
+    assert_eq!(cshake, shake);
+}
+
+#[test]
+fn kmac() {
+    let key = kmac_key();
+    let s = b"My Tagged Application";
+    check(
+        |out| kmac128(out, &key, &short_data(), b""),
+        "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e",
+    );
+    check(
+        |out| kmac128(out, &key, &short_data(), s),
+        "3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5",
+    );
+    check(
+        |out| kmac128(out, &key, &long_data(), s),
+        "1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230",
+    );
+    check(
+        |out| kmac256(out, &key, &short_data(), s),
+        "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7\
+         f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd",
+    );
+    check(
+        |out| kmac256(out, &key, &long_data(), b""),
+        "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691\
+         589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69",
+    );
+    check(
+        |out| kmac256(out, &key, &long_data(), s),
+        "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d9\
+         70fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965",
+    );
+}
+
+#[test]
+fn kmac_xof() {
+    let key = kmac_key();
+    let s = b"My Tagged Application";
+    check(
+        |out| kmac128_xof(out, &key, &short_data(), b""),
+        "cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35",
+    );
+    check(
+        |out| kmac128_xof(out, &key, &short_data(), s),
+        "31a44527b4ed9f5c6101d11de6d26f0620aa5c341def41299657fe9df1a3b16c",
+    );
+    check(
+        |out| kmac128_xof(out, &key, &long_data(), s),
+        "47026c7cd793084aa0283c253ef658490c0db61438b8326fe9bddf281b83ae0f",
+    );
+    check(
+        |out| kmac256_xof(out, &key, &short_data(), s),
+        "1755133f1534752aad0748f2c706fb5c784512cab835cd15676b16c0c6647fa9\
+         6faa7af634a0bf8ff6df39374fa00fad9a39e322a7c92065a64eb1fb0801eb2b",
+    );
+    check(
+        |out| kmac256_xof(out, &key, &long_data(), b""),
+        "ff7b171f1e8a2b24683eed37830ee797538ba8dc563f6da1e667391a75edc02c\
+         a633079f81ce12a25f45615ec89972031d18337331d24ceb8f8ca8e6a19fd98b",
+    );
+    check(
+        |out| kmac256_xof(out, &key, &long_data(), s),
+        "d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce\
+         67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d",
+    );
+}
+
+#[test]
+fn tuple_hash() {
+    let s = b"My Tuple App";
+    check(
+        |out| tuple_hash128(out, &[TE3, TE6], b""),
+        "c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1",
+    );
+    check(
+        |out| tuple_hash128(out, &[TE3, TE6], s),
+        "75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb",
+    );
+    check(
+        |out| tuple_hash128(out, &[TE3, TE6, TE9], s),
+        "e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84",
+    );
+    check(
+        |out| tuple_hash256(out, &[TE3, TE6], b""),
+        "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec6073\
+         11ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194",
+    );
+    check(
+        |out| tuple_hash256(out, &[TE3, TE6], s),
+        "147c2191d5ed7efd98dbd96d7ab5a11692576f5fe2a5065f3e33de6bba9f3aa1\
+         c4e9a068a289c61c95aab30aee1e410b0b607de3620e24a4e3bf9852a1d4367e",
+    );
+    check(
+        |out| tuple_hash256(out, &[TE3, TE6, TE9], s),
+        "45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7\
+         d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce",
+    );
+}
+
+#[test]
+fn tuple_hash_xof() {
+    let s = b"My Tuple App";
+    check(
+        |out| tuple_hash128_xof(out, &[TE3, TE6], b""),
+        "2f103cd7c32320353495c68de1a8129245c6325f6f2a3d608d92179c96e68488",
+    );
+    check(
+        |out| tuple_hash128_xof(out, &[TE3, TE6], s),
+        "3fc8ad69453128292859a18b6c67d7ad85f01b32815e22ce839c49ec374e9b9a",
+    );
+    check(
+        |out| tuple_hash128_xof(out, &[TE3, TE6, TE9], s),
+        "900fe16cad098d28e74d632ed852f99daab7f7df4d99e775657885b4bf76d6f8",
+    );
+    check(
+        |out| tuple_hash256_xof(out, &[TE3, TE6], b""),
+        "03ded4610ed6450a1e3f8bc44951d14fbc384ab0efe57b000df6b6df5aae7cd5\
+         68e77377daf13f37ec75cf5fc598b6841d51dd207c991cd45d210ba60ac52eb9",
+    );
+    check(
+        |out| tuple_hash256_xof(out, &[TE3, TE6], s),
+        "6483cb3c9952eb20e830af4785851fc597ee3bf93bb7602c0ef6a65d741aeca7\
+         e63c3b128981aa05c6d27438c79d2754bb1b7191f125d6620fca12ce658b2442",
+    );
+    check(
+        |out| tuple_hash256_xof(out, &[TE3, TE6, TE9], s),
+        "0c59b11464f2336c34663ed51b2b950bec743610856f36c28d1d088d8a244628\
+         4dd09830a6a178dc752376199fae935d86cfdee5913d4922dfd369b66a53c897",
+    );
+}
+
+#[test]
+fn tuple_hash_is_unambiguous() {
+    let mut a = [0u8; 32];
+    let mut b = [0u8; 32];
+    tuple_hash128(&mut a, &[b"ab", b"c"], b"");
+    tuple_hash128(&mut b, &[b"a", b"bc"], b"");
+    assert_ne!(a, b);
+}
+
+#[test]
+fn parallel_hash() {
+    let s = b"Parallel Data";
+    check(
+        |out| parallel_hash128(out, PARALLEL_DATA, 8, b""),
+        "ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5",
+    );
+    check(
+        |out| parallel_hash128(out, PARALLEL_DATA, 8, s),
+        "fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206",
+    );
+    check(
+        |out| parallel_hash256(out, PARALLEL_DATA, 8, b""),
+        "bc1ef124da34495e948ead207dd9842235da432d2bbc54b4c110e64c45110553\
+         1b7f2a3e0ce055c02805e7c2de1fb746af97a1dd01f43b824e31b87612410429",
+    );
+    check(
+        |out| parallel_hash256(out, PARALLEL_DATA, 8, s),
+        "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb\
+         33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110",
+    );
+}
+
+#[test]
+fn parallel_hash_xof() {
+    let s = b"Parallel Data";
+    check(
+        |out| parallel_hash128_xof(out, PARALLEL_DATA, 8, b""),
+        "fe47d661e49ffe5b7d999922c062356750caf552985b8e8ce6667f2727c3c8d3",
+    );
+    check(
+        |out| parallel_hash128_xof(out, PARALLEL_DATA, 8, s),
+        "ea2a793140820f7a128b8eb70a9439f93257c6e6e79b4a540d291d6dae7098d7",
+    );
+    check(
+        |out| parallel_hash256_xof(out, PARALLEL_DATA, 8, b""),
+        "c10a052722614684144d28474850b410757e3cba87651ba167a5cbddff7f4666\
+         75fbf84bcae7378ac444be681d729499afca667fb879348bfdda427863c82f1c",
+    );
+    check(
+        |out| parallel_hash256_xof(out, PARALLEL_DATA, 8, s),
+        "538e105f1a22f44ed2f5cc1674fbd40be803d9c99bf5f8d90a2c8193f3fe6ea7\
+         68e5c1a20987e2c9c65febed03887a51d35624ed12377594b5585541dc377efc",
+    );
+}
+
+/// The result does not depend on how many blocks are hashed in parallel.
+#[test]
+fn parallel_hash_block_counts() {
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+    for blocks in 0..=9 {
+        let input = &data[..blocks * 8];
+
+        // The same blocks hashed one by one with SHAKE128.
+        let mut tuple = Vec::new();
+        for block in input.chunks(8) {
+            let mut leaf = [0u8; 32];
+            libcrux_sha3::shake128_ema(&mut leaf, block);
+            tuple.push(leaf);
+        }
+
+        let mut expected = [0u8; 32];
+        parallel_hash128(&mut expected, input, 8, b"");
+        let mut reference = [0u8; 32];
+        parallel_hash_reference(&mut reference, &tuple, 8);
+        assert_eq!(expected, reference, "{blocks} blocks");
+    }
+}
+
+/// ParallelHash128 with an empty customization string on the SHAKE128 leaves,
+/// computed with cSHAKE128.
+fn parallel_hash_reference(out: &mut [u8], leaves: &[[u8; 32]], block_size: u8) {
+    let mut z = vec![1, block_size];
+    for leaf in leaves {
+        z.extend_from_slice(leaf);
+    }
+    z.extend_from_slice(&[leaves.len() as u8, 1]);
+    let bits = out.len() * 8;
+    z.extend_from_slice(&[(bits >> 8) as u8, bits as u8, 2]);
+    cshake128(out, &z, b"ParallelHash", b"");
+}
+
+#[test]
+fn xof_prefix() {
+    let key = kmac_key();
+    let data = long_data();
+    let mut short = [0u8; 17];
+    let mut long = [0u8; 300];
+
+    kmac256_xof(&mut short, &key, &data, b"");
+    kmac256_xof(&mut long, &key, &data, b"");
+    assert_eq!(short, long[..17]);
+
+    parallel_hash128_xof(&mut short, &data, 16, b"");
+    parallel_hash128_xof(&mut long, &data, 16, b"");
+    assert_eq!(short, long[..17]);
+
+    // The fixed-length outputs depend on the length.
+    kmac256(&mut short, &key, &data, b"");
+    kmac256(&mut long, &key, &data, b"");
+    assert_ne!(short, long[..17]);
+}
@@ -417,6 +417,7 @@ pub fn poly1305_finish(tag: &mut [u8], key: &[u8], ctx: &mut [u64]) {
     lowstar::endianness::store64_le(&mut tag[8usize..], f312)
 }
 
+#[cfg(feature = "alloc")]
 #[derive(PartialEq, Clone)]
 pub struct state_t {
     pub block_state: Box<[u64]>,
@@ -425,6 +426,7 @@ pub struct state_t {
     pub p_key: Box<[u8]>,
 }
 
+#[cfg(feature = "alloc")]
 pub fn malloc(key: &[u8]) -> Box<[crate::hacl::mac_poly1305::state_t]> {
     let buf: Box<[u8]> = vec![0u8; 16usize].into_boxed_slice();
     let mut r1: Box<[u64]> = vec![0u64; 25usize].into_boxed_slice();
@@ -443,6 +445,7 @@ pub fn malloc(key: &[u8]) -> Box<[crate::hacl::mac_poly1305::state_t]> {
     p
 }
 
+#[cfg(feature = "alloc")]
 pub fn reset(state: &mut [crate::hacl::mac_poly1305::state_t], key: &[u8]) {
     let block_state: &mut [u64] = &mut (state[0usize]).block_state;
     let k·: &mut [u8] = &mut (state[0usize]).p_key;
@@ -457,6 +460,7 @@ pub fn reset(state: &mut [crate::hacl::mac_poly1305::state_t], key: &[u8]) {
 /**
 0 = success, 1 = max length exceeded
 */
+#[cfg(feature = "alloc")]
 pub fn update(
     state: &mut [crate::hacl::mac_poly1305::state_t],
     chunk: &[u8],
@@ -576,6 +580,7 @@ pub fn update(
     }
 }
 
+#[cfg(feature = "alloc")]
 pub fn digest(state: &[crate::hacl::mac_poly1305::state_t], output: &mut [u8]) {
     let block_state: &[u64] = &(state[0usize]).block_state;
     let buf_: &[u8] = &(state[0usize]).buf;
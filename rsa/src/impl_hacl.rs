@@ -359,6 +359,89 @@ pub fn verify_varlen(
     }
 }
 
+fn traits_error(e: Error) -> libcrux_traits::signature::SignatureError {
+    use libcrux_traits::signature::SignatureError;
+
+    match e {
+        Error::SaltTooLarge => SignatureError::InvalidRandomness,
+        Error::MessageTooLarge => SignatureError::MessageTooLarge,
+        Error::VerificationFailed | Error::InvalidSignatureLength => {
+            SignatureError::InvalidSignature
+        }
+        Error::SigningFailed => SignatureError::Signing,
+        Error::KeyLengthMismatch | Error::InvalidKeyLength => SignatureError::InvalidKey,
+    }
+}
+
+macro_rules! impl_signature_traits {
+    ($name:ident, $alg:ident, $doc:literal) => {
+        #[doc = concat!("RSA-PSS with ", $doc, " and keys of `LEN` bytes as a")]
+        /// [`Signer`](libcrux_traits::signature::Signer) and
+        /// [`Verifier`](libcrux_traits::signature::Verifier).
+        ///
+        /// The signing randomness is the salt, which is as long as the hash.
+        /// Contexts are not supported.
+        pub struct $name<const LEN: usize>;
+
+        impl<const LEN: usize> libcrux_traits::signature::Signer for $name<LEN> {
+            const RANDOMNESS_LEN: usize = crate::DigestAlgorithm::$alg.hash_len() as usize;
+
+            type SigningKey = PrivateKey<LEN>;
+            type Signature = [u8; LEN];
+
+            fn sign_with_randomness(
+                key: &PrivateKey<LEN>,
+                msg: &[u8],
+                ctx: &[u8],
+                randomness: &[u8],
+            ) -> Result<[u8; LEN], libcrux_traits::signature::SignatureError> {
+                use libcrux_traits::signature::SignatureError;
+
+                if !ctx.is_empty() {
+                    return Err(SignatureError::InvalidContext);
+                }
+                if randomness.len() != Self::RANDOMNESS_LEN {
+                    return Err(SignatureError::InvalidRandomness);
+                }
+
+                let sk = VarLenPrivateKey {
+                    pk: VarLenPublicKey { n: &key.pk.n },
+                    d: &key.d,
+                };
+                let mut sig = [0u8; LEN];
+                sign_varlen(crate::DigestAlgorithm::$alg, &sk, msg, randomness, &mut sig)
+                    .map_err(traits_error)?;
+
+                Ok(sig)
+            }
+        }
+
+        impl<const LEN: usize> libcrux_traits::signature::Verifier for $name<LEN> {
+            type VerificationKey = PublicKey<LEN>;
+            type Signature = [u8; LEN];
+
+            fn verify(
+                key: &PublicKey<LEN>,
+                msg: &[u8],
+                ctx: &[u8],
+                signature: &[u8; LEN],
+            ) -> Result<(), libcrux_traits::signature::SignatureError> {
+                if !ctx.is_empty() {
+                    return Err(libcrux_traits::signature::SignatureError::InvalidContext);
+                }
+
+                let alg = crate::DigestAlgorithm::$alg;
+                let pk = VarLenPublicKey { n: &key.n };
+                verify_varlen(alg, &pk, msg, alg.hash_len() as u32, signature).map_err(traits_error)
+            }
+        }
+    };
+}
+
+impl_signature_traits!(RsaPssSha2_256, Sha2_256, "SHA-256");
+impl_signature_traits!(RsaPssSha2_384, Sha2_384, "SHA-384");
+impl_signature_traits!(RsaPssSha2_512, Sha2_512, "SHA-512");
+
 #[cfg(test)]
 mod tests {
     use super::*;
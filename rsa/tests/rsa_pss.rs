@@ -318,3 +318,36 @@ fn run_wycheproof() {
         println!("Ran {} out of {} tests.", tests_run, num_tests);
     }
 }
+
+#[test]
+fn signature_traits() {
+    use libcrux_rsa::{RsaPssSha2_256, RsaPssSha2_384};
+    use libcrux_traits::signature::{SignatureError, Signer, Verifier};
+
+    let sk = PrivateKey::<256>::from_components(MODULUS, PRIVATE_EXPONENT);
+    let pk = PublicKey::from(MODULUS);
+    let msg = [7, 8, 9, 10];
+
+    let signature = RsaPssSha2_256::sign(&sk, &msg, b"", &mut rand::rng()).unwrap();
+    RsaPssSha2_256::verify(&pk, &msg, b"", &signature).unwrap();
+    assert_eq!(
+        RsaPssSha2_384::verify(&pk, &msg, b"", &signature),
+        Err(SignatureError::InvalidSignature)
+    );
+
+    // The randomness is the salt.
+    let salt = [0x2a; 32];
+    let signature = RsaPssSha2_256::sign_with_randomness(&sk, &msg, b"", &salt).unwrap();
+    let mut expected = [0u8; 256];
+    sign_2048(DigestAlgorithm::Sha2_256, &sk, &msg, &salt, &mut expected).unwrap();
+    assert_eq!(signature, expected);
+
+    assert_eq!(
+        RsaPssSha2_256::sign_with_randomness(&sk, &msg, b"", &[1, 2, 3]),
+        Err(SignatureError::InvalidRandomness)
+    );
+    assert_eq!(
+        RsaPssSha2_256::verify(&pk, &msg, b"context", &signature),
+        Err(SignatureError::InvalidContext)
+    );
+}
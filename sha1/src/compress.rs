@@ -0,0 +1,141 @@
+//! The SHA-1 compression function with collision detection.
+
+use crate::{
+    dvs::{DISTURBANCE_VECTORS, STEPS},
+    ubc::ubc_check,
+};
+
+/// The SHA-1 block length.
+pub(crate) const BLOCK_LEN: usize = 64;
+
+/// The intermediate hash value.
+pub(crate) type Ihv = [u32; 5];
+
+/// The working state `a, b, c, d, e`.
+type State = [u32; 5];
+
+const K: [u32; 4] = [0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xca62c1d6];
+
+/// The boolean function and round constant of step `i`.
+#[inline(always)]
+fn f(i: usize, b: u32, c: u32, d: u32) -> u32 {
+    match i / 20 {
+        0 => (b & c) | (!b & d),
+        2 => (b & c) | (b & d) | (c & d),
+        _ => b ^ c ^ d,
+    }
+    .wrapping_add(K[i / 20])
+}
+
+#[inline(always)]
+fn step(i: usize, s: &mut State, w: &[u32; STEPS]) {
+    let [a, b, c, d, e] = *s;
+    let t = a
+        .rotate_left(5)
+        .wrapping_add(f(i, b, c, d))
+        .wrapping_add(e)
+        .wrapping_add(w[i]);
+    *s = [t, a, b.rotate_left(30), c, d];
+}
+
+/// Undo step `i`, i.e. compute the state before step `i` from the one after.
+#[inline(always)]
+fn step_back(i: usize, s: &mut State, w: &[u32; STEPS]) {
+    let [t, a, b, c, d] = *s;
+    let b = b.rotate_right(30);
+    let e = t
+        .wrapping_sub(a.rotate_left(5))
+        .wrapping_sub(f(i, b, c, d))
+        .wrapping_sub(w[i]);
+    *s = [a, b, c, d, e];
+}
+
+fn add_assign(ihv: &mut Ihv, s: &State) {
+    for (h, s) in ihv.iter_mut().zip(s.iter()) {
+        *h = h.wrapping_add(*s);
+    }
+}
+
+fn expand(block: &[u8; BLOCK_LEN]) -> [u32; STEPS] {
+    let mut w = [0u32; STEPS];
+    for (w, chunk) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..STEPS {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+    w
+}
+
+/// Plain SHA-1 compression of the expanded message `w`.
+fn compress_expanded(ihv: &mut Ihv, w: &[u32; STEPS]) {
+    let mut s = *ihv;
+    for i in 0..STEPS {
+        step(i, &mut s, w);
+    }
+    add_assign(ihv, &s);
+}
+
+/// Compress `block` into `ihv`.
+///
+/// Returns `true` if the block is a near-collision block of one of the known
+/// attacks. In that case the block is compressed two more times, such that the
+/// result differs from SHA-1 and the collision does not carry over.
+pub(crate) fn compress(ihv: &mut Ihv, block: &[u8; BLOCK_LEN]) -> bool {
+    let w = expand(block);
+
+    // Compute SHA-1 and store the intermediate states needed below.
+    let mut s = *ihv;
+    let mut state_58 = s;
+    let mut state_65 = s;
+    for i in 0..STEPS {
+        if i == 58 {
+            state_58 = s;
+        } else if i == 65 {
+            state_65 = s;
+        }
+        step(i, &mut s, &w);
+    }
+    add_assign(ihv, &s);
+
+    // For every disturbance vector whose unavoidable bit conditions hold,
+    // compute the input that the second block of an attack would have to use,
+    // starting from the step where the attack has no state difference. If it
+    // compresses to the same output, this is a collision.
+    let dv_mask = ubc_check(&w);
+    for (i, dv) in DISTURBANCE_VECTORS.iter().enumerate() {
+        if dv_mask & (1 << i) == 0 {
+            continue;
+        }
+
+        let mut w2 = w;
+        for (w2, dm) in w2.iter_mut().zip(dv.dm.iter()) {
+            *w2 ^= dm;
+        }
+
+        let mut s = if dv.testt == 58 { state_58 } else { state_65 };
+        for i in (0..dv.testt).rev() {
+            step_back(i, &mut s, &w2);
+        }
+        let mut ihv2 = s;
+
+        let mut s = if dv.testt == 58 { state_58 } else { state_65 };
+        for i in dv.testt..STEPS {
+            step(i, &mut s, &w2);
+        }
+        add_assign(&mut ihv2, &s);
+
+        // Compare without early abort, as the state may depend on secrets.
+        let diff = ihv2
+            .iter()
+            .zip(ihv.iter())
+            .fold(0, |diff, (a, b)| diff | (a ^ b));
+        if diff == 0 {
+            compress_expanded(ihv, &w);
+            compress_expanded(ihv, &w);
+            return true;
+        }
+    }
+
+    false
+}
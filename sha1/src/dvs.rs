@@ -0,0 +1,151 @@
+//! The disturbance vectors of the known SHA-1 collision attacks.
+//!
+//! A disturbance vector (DV) describes where the local collisions of a
+//! differential path start. It satisfies the SHA-1 message expansion and is
+//! defined by 16 consecutive words `K..K+15`. We use the classification of
+//! [Manuel](https://eprint.iacr.org/2008/469) as in SHA-1DC:
+//!
+//! - `I(K, b)`: all words are zero, except for `W[K + 15] = rotl(1, b)`.
+//! - `II(K, b)`: as `I(K, b)`, with additionally
+//!   `W[K + 1] = W[K + 3] = rotl(2^31, b)`.
+//!
+//! The message difference `dm` used for the recompression is derived from the
+//! DV by adding the corrections of every local collision.
+
+/// The number of steps of the SHA-1 compression function.
+pub(crate) const STEPS: usize = 80;
+
+/// A disturbance vector, prepared for the collision check.
+pub(crate) struct DisturbanceVector {
+    /// The step to recompress from. The state difference of the attack is
+    /// zero before this step.
+    pub(crate) testt: usize,
+    /// The expanded message XOR difference.
+    pub(crate) dm: [u32; STEPS],
+}
+
+#[derive(Clone, Copy)]
+enum DvType {
+    I,
+    II,
+}
+
+/// Compute the message difference of the DV of `dv_type` with `k` and `b`.
+const fn dv(dv_type: DvType, k: usize, b: u32, testt: usize) -> DisturbanceVector {
+    // The DV is needed in steps -5..80 to compute the corrections.
+    const OFFSET: usize = 5;
+    let mut w = [0u32; STEPS + OFFSET];
+
+    w[OFFSET + k + 15] = 1u32.rotate_left(b);
+    if let DvType::II = dv_type {
+        w[OFFSET + k + 1] = (1u32 << 31).rotate_left(b);
+        w[OFFSET + k + 3] = (1u32 << 31).rotate_left(b);
+    }
+
+    // Forward message expansion.
+    let mut i = OFFSET + k + 16;
+    while i < STEPS + OFFSET {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        i += 1;
+    }
+
+    // Backward message expansion.
+    let mut i = OFFSET + k;
+    while i > 0 {
+        i -= 1;
+        w[i] = w[i + 16].rotate_right(1) ^ w[i + 13] ^ w[i + 8] ^ w[i + 2];
+    }
+
+    // A disturbance in step i is corrected in the steps i + 1 to i + 5.
+    let mut dm = [0u32; STEPS];
+    let mut i = 0;
+    while i < STEPS {
+        let j = i + OFFSET;
+        dm[i] = w[j]
+            ^ w[j - 1].rotate_left(5)
+            ^ w[j - 2]
+            ^ w[j - 3].rotate_left(30)
+            ^ w[j - 4].rotate_left(30)
+            ^ w[j - 5].rotate_left(30);
+        i += 1;
+    }
+
+    DisturbanceVector { testt, dm }
+}
+
+/// The disturbance vectors that are checked, in the order of SHA-1DC.
+pub(crate) const DISTURBANCE_VECTORS: [DisturbanceVector; 32] = [
+    dv(DvType::I, 43, 0, 58),
+    dv(DvType::I, 44, 0, 58),
+    dv(DvType::I, 45, 0, 58),
+    dv(DvType::I, 46, 0, 58),
+    dv(DvType::I, 46, 2, 58),
+    dv(DvType::I, 47, 0, 58),
+    dv(DvType::I, 47, 2, 58),
+    dv(DvType::I, 48, 0, 58),
+    dv(DvType::I, 48, 2, 58),
+    dv(DvType::I, 49, 0, 58),
+    dv(DvType::I, 49, 2, 58),
+    dv(DvType::I, 50, 0, 65),
+    dv(DvType::I, 50, 2, 65),
+    dv(DvType::I, 51, 0, 65),
+    dv(DvType::I, 51, 2, 65),
+    dv(DvType::I, 52, 0, 65),
+    dv(DvType::II, 45, 0, 58),
+    dv(DvType::II, 46, 0, 58),
+    dv(DvType::II, 46, 2, 58),
+    dv(DvType::II, 47, 0, 58),
+    dv(DvType::II, 48, 0, 58),
+    dv(DvType::II, 49, 0, 58),
+    dv(DvType::II, 49, 2, 58),
+    dv(DvType::II, 50, 0, 65),
+    dv(DvType::II, 50, 2, 65),
+    dv(DvType::II, 51, 0, 65),
+    dv(DvType::II, 51, 2, 65),
+    dv(DvType::II, 52, 0, 65),
+    dv(DvType::II, 53, 0, 65),
+    dv(DvType::II, 54, 0, 65),
+    dv(DvType::II, 55, 0, 65),
+    dv(DvType::II, 56, 0, 65),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compare against the tables of the SHA-1DC reference implementation.
+    #[test]
+    fn reference_message_differences() {
+        let dv_i_43_0 = &DISTURBANCE_VECTORS[0].dm;
+        assert_eq!(
+            dv_i_43_0[..8],
+            [
+                0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000, 0x60000000,
+                0x00000008
+            ]
+        );
+        assert_eq!(
+            dv_i_43_0[72..],
+            [
+                0x00000018, 0x00000164, 0x00000408, 0x800000e6, 0x8000004c, 0x00000803, 0x80000161,
+                0x80000599
+            ]
+        );
+
+        let dv_ii_56_0 = &DISTURBANCE_VECTORS[31].dm;
+        assert_eq!(
+            dv_ii_56_0[..8],
+            [
+                0x2600001a, 0x00000010, 0x0400001c, 0xcc000014, 0x0c000002, 0xc0000010, 0xb400001c,
+                0x3c000004
+            ]
+        );
+        assert_eq!(
+            dv_ii_56_0[72..],
+            [
+                0x00000020, 0x00000001, 0x40000002, 0x40000041, 0x40000022, 0x80000005, 0xc0000082,
+                0xc0000046
+            ]
+        );
+    }
+}
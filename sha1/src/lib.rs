@@ -0,0 +1,158 @@
+//! # SHA-1
+//!
+//! SHA-1 with collision detection ([SHA-1DC](https://github.com/cr-marcstevens/sha1collisiondetection)).
+//!
+//! **SHA-1 is broken and must only be used for interoperability with legacy
+//! protocols**, e.g. HMAC-SHA1 based one-time passwords, git object ids or
+//! certificate fingerprints.
+//!
+//! Every block is checked for being part of one of the known collision attacks.
+//! If an attack block is found, the digest is altered such that the colliding
+//! inputs hash to different values. The digest of all other inputs is the
+//! SHA-1 digest. Use [`Sha1::collision_detected`] to find out whether an
+//! attack was detected.
+//!
+//! Like SHA-1DC, the collision check first tests the unavoidable bit
+//! conditions of the 32 disturbance vectors on every block, and only
+//! recompresses the block for the vectors whose conditions hold. Hashing is
+//! still slower than plain SHA-1, and its running time depends on the input.
+//!
+//! **NOTE:** This code has not been formally verified yet.
+
+#![no_std]
+
+mod compress;
+mod dvs;
+mod ubc;
+
+use compress::{compress, Ihv, BLOCK_LEN};
+
+/// Re-export the `Digest` trait.
+pub use libcrux_traits::Digest;
+
+/// The length of a SHA-1 hash in bytes.
+pub const SHA1_LENGTH: usize = 20;
+
+const IV: Ihv = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// SHA-1
+///
+/// Returns the SHA-1 digest of `payload`, or the altered digest if a collision
+/// attack was detected.
+#[inline(always)]
+pub fn sha1(payload: &[u8]) -> [u8; SHA1_LENGTH] {
+    let mut digest = [0u8; SHA1_LENGTH];
+    Sha1::hash(&mut digest, payload);
+    digest
+}
+
+/// The streaming SHA-1 state.
+#[derive(Clone)]
+pub struct Sha1 {
+    ihv: Ihv,
+    buffer: [u8; BLOCK_LEN],
+    buffer_len: usize,
+    total_len: u64,
+    collision_detected: bool,
+}
+
+impl Sha1 {
+    /// Initialize a new digest state for streaming use.
+    pub fn new() -> Self {
+        Self {
+            ihv: IV,
+            buffer: [0u8; BLOCK_LEN],
+            buffer_len: 0,
+            total_len: 0,
+            collision_detected: false,
+        }
+    }
+
+    /// Returns `true` if one of the full blocks hashed so far is part of a
+    /// collision attack.
+    ///
+    /// If this is the case, the digest differs from the SHA-1 digest of the
+    /// input.
+    pub fn collision_detected(&self) -> bool {
+        self.collision_detected
+    }
+
+    fn compress(&mut self, block: &[u8; BLOCK_LEN]) {
+        self.collision_detected |= compress(&mut self.ihv, block);
+    }
+}
+
+impl Digest<SHA1_LENGTH> for Sha1 {
+    /// Write the digest for the given input byte slice into `digest`, in
+    /// immediate mode.
+    ///
+    /// Panics if `digest` is not [`SHA1_LENGTH`] bytes long.
+    fn hash(digest: &mut [u8], payload: &[u8]) {
+        let mut state = Self::new();
+        state.update(payload);
+        state.finish(digest.try_into().unwrap());
+    }
+
+    /// Add the `payload` to the digest.
+    fn update(&mut self, mut payload: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(payload.len() as u64);
+
+        if self.buffer_len > 0 {
+            let take = core::cmp::min(BLOCK_LEN - self.buffer_len, payload.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&payload[..take]);
+            self.buffer_len += take;
+            payload = &payload[take..];
+
+            if self.buffer_len < BLOCK_LEN {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        let mut blocks = payload.chunks_exact(BLOCK_LEN);
+        for block in blocks.by_ref() {
+            self.compress(block.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    /// Get the digest.
+    ///
+    /// Note that the digest state can be continued to be used, to extend the
+    /// digest.
+    fn finish(&self, digest: &mut [u8; SHA1_LENGTH]) {
+        let mut state = self.clone();
+        let bit_len = state.total_len.wrapping_mul(8);
+
+        let mut block = [0u8; BLOCK_LEN];
+        block[..state.buffer_len].copy_from_slice(&state.buffer[..state.buffer_len]);
+        block[state.buffer_len] = 0x80;
+        if state.buffer_len >= BLOCK_LEN - 8 {
+            state.compress(&block);
+            block = [0u8; BLOCK_LEN];
+        }
+        block[BLOCK_LEN - 8..].copy_from_slice(&bit_len.to_be_bytes());
+        state.compress(&block);
+
+        for (out, h) in digest.chunks_exact_mut(4).zip(state.ihv.iter()) {
+            out.copy_from_slice(&h.to_be_bytes());
+        }
+    }
+
+    /// Reset the digest state.
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Sha1 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,120 @@
+use libcrux_sha1::{sha1, Digest, Sha1, SHA1_LENGTH};
+
+/// Test vectors from FIPS 180-2, Appendix A and
+/// <https://www.di-mgt.com.au/sha_testvectors.html>.
+const KATS: [(&[u8], &str); 4] = [
+    (b"", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+    (b"abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+    (
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+    ),
+    (
+        b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+        "a49b2446a02c645bf419f995b67091253a04a259",
+    ),
+];
+
+#[test]
+fn sha1_kat_oneshot() {
+    for (msg, expected) in KATS {
+        assert_eq!(hex::encode(sha1(msg)), expected);
+    }
+}
+
+#[test]
+fn sha1_kat_million_a() {
+    let mut digest = Sha1::new();
+    for _ in 0..1000 {
+        digest.update(&[b'a'; 1000]);
+    }
+    let mut d = [0u8; SHA1_LENGTH];
+    digest.finish(&mut d);
+
+    assert_eq!(hex::encode(d), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    assert!(!digest.collision_detected());
+}
+
+#[test]
+fn sha1_streaming_chunks() {
+    let msg: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+    let expected = sha1(&msg);
+
+    for chunk_len in [1, 3, 55, 56, 63, 64, 65, 200] {
+        let mut digest = Sha1::new();
+        for chunk in msg.chunks(chunk_len) {
+            digest.update(chunk);
+        }
+        let mut d = [0u8; SHA1_LENGTH];
+        digest.finish(&mut d);
+        assert_eq!(d, expected, "chunk length {chunk_len}");
+    }
+}
+
+#[test]
+fn sha1_continue_and_reset() {
+    let mut digest = Sha1::new();
+    let mut d = [0u8; SHA1_LENGTH];
+    digest.update(b"ab");
+    digest.finish(&mut d);
+    assert_eq!(d, sha1(b"ab"));
+
+    // The state can be extended after `finish`.
+    digest.update(b"c");
+    digest.finish(&mut d);
+    assert_eq!(hex::encode(d), KATS[1].1);
+
+    digest.reset();
+    digest.finish(&mut d);
+    assert_eq!(hex::encode(d), KATS[0].1);
+}
+
+/// Hash `input` and check the detection result and the (altered) digest.
+fn collision_test(input: &[u8], expected: &str) {
+    let mut digest = Sha1::new();
+    digest.update(input);
+    assert!(digest.collision_detected());
+
+    let mut d = [0u8; SHA1_LENGTH];
+    digest.finish(&mut d);
+    assert_eq!(hex::encode(d), expected);
+}
+
+/// The first 320 bytes of the SHAttered PDFs, which contain the two colliding
+/// blocks (<https://shattered.io>). Their SHA-1 digest is
+/// `f92d74e3874587aaf443d1db961d4e26dde13e9c`.
+#[test]
+fn shattered() {
+    collision_test(
+        include_bytes!("data/shattered-1-prefix.bin"),
+        "7117b3cb9225aaf0d8ef1a40e493957b0bf8693d",
+    );
+    collision_test(
+        include_bytes!("data/shattered-2-prefix.bin"),
+        "29f38ae9fd98e2931120fa0bf213e024250d3f6a",
+    );
+}
+
+/// The chosen-prefix collision of <https://sha-mbles.github.io>. Their SHA-1
+/// digest is `8ac60ba76f1999a1ab70223f225aefdc78d4ddc0`.
+#[test]
+fn shambles() {
+    collision_test(
+        include_bytes!("data/sha-mbles-1.bin"),
+        "4f3d9be4a472c4dae83c6314aa6c36a064c1fd14",
+    );
+    collision_test(
+        include_bytes!("data/sha-mbles-2.bin"),
+        "9ed5d77a4f48be1dbf3e9e15650733eb850897f2",
+    );
+}
+
+#[test]
+fn no_false_positive() {
+    // The identical PDF header in front of the collision blocks.
+    let prefix = &include_bytes!("data/shattered-1-prefix.bin")[..192];
+
+    let mut digest = Sha1::new();
+    digest.update(prefix);
+    assert!(!digest.collision_detected());
+}
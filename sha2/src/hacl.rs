@@ -371,7 +371,7 @@ pub fn sha256_finish(st: &[u32], h: &mut [u8]) {
 }
 
 #[inline]
-fn sha224_init(hash: &mut [u32]) {
+pub fn sha224_init(hash: &mut [u32]) {
     krml::unroll_for!(8, "i", 0u32, 1u32, {
         let x: u32 = (&crate::hacl::h224)[i as usize];
         let os: (&mut [u32], &mut [u32]) = hash.split_at_mut(0usize);
@@ -380,16 +380,16 @@ fn sha224_init(hash: &mut [u32]) {
 }
 
 #[inline]
-fn sha224_update_nblocks(len: u32, b: &[u8], st: &mut [u32]) {
+pub fn sha224_update_nblocks(len: u32, b: &[u8], st: &mut [u32]) {
     crate::hacl::sha256_update_nblocks(len, b, st)
 }
 
-fn sha224_update_last(totlen: u64, len: u32, b: &[u8], st: &mut [u32]) {
+pub fn sha224_update_last(totlen: u64, len: u32, b: &[u8], st: &mut [u32]) {
     crate::hacl::sha256_update_last(totlen, len, b, st)
 }
 
 #[inline]
-fn sha224_finish(st: &[u32], h: &mut [u8]) {
+pub fn sha224_finish(st: &[u32], h: &mut [u8]) {
     let mut hbuf: [u8; 32] = [0u8; 32usize];
     krml::unroll_for!(
         8,
@@ -600,18 +600,23 @@ pub fn sha384_finish(st: &[u64], h: &mut [u8]) {
     (h[0usize..48usize]).copy_from_slice(&(&(&hbuf)[0usize..])[0usize..48usize])
 }
 
+#[cfg(feature = "alloc")]
 pub type state_t_224 = streaming_types::state_32;
 
+#[cfg(feature = "alloc")]
 pub type state_t_256 = streaming_types::state_32;
 
+#[cfg(feature = "alloc")]
 pub type state_t_384 = streaming_types::state_64;
 
+#[cfg(feature = "alloc")]
 pub type state_t_512 = streaming_types::state_64;
 
 /**
 Allocate initial state for the SHA2_256 hash. The state is to be freed by
 calling `free_256`.
 */
+#[cfg(feature = "alloc")]
 pub fn malloc_256() -> Box<[streaming_types::state_32]> {
     let buf: Box<[u8]> = vec![0u8; 64usize].into_boxed_slice();
     let mut block_state: Box<[u32]> = vec![0u32; 8usize].into_boxed_slice();
@@ -631,6 +636,7 @@ The state is to be freed by calling `free_256`. Cloning the state this way is
 useful, for instance, if your control-flow diverges and you need to feed
 more (different) data into the hash in each branch.
 */
+#[cfg(feature = "alloc")]
 pub fn copy_256(state: &[streaming_types::state_32]) -> Box<[streaming_types::state_32]> {
     let block_state0: &[u32] = &(state[0usize]).block_state;
     let buf0: &[u8] = &(state[0usize]).buf;
@@ -651,6 +657,7 @@ pub fn copy_256(state: &[streaming_types::state_32]) -> Box<[streaming_types::st
 /**
 Reset an existing state to the initial hash state with empty data.
 */
+#[cfg(feature = "alloc")]
 pub fn reset_256(state: &mut [streaming_types::state_32]) {
     let block_state: &mut [u32] = &mut (state[0usize]).block_state;
     crate::hacl::sha256_init(block_state);
@@ -659,6 +666,7 @@ pub fn reset_256(state: &mut [streaming_types::state_32]) {
 }
 
 #[inline]
+#[cfg(feature = "alloc")]
 fn update_224_256(
     state: &mut [streaming_types::state_32],
     chunk: &[u8],
@@ -783,6 +791,7 @@ success, or 1 if the combined length of all of the data passed to `update_256`
 
 This function is identical to the update function for SHA2_224.
 */
+#[cfg(feature = "alloc")]
 pub fn update_256(
     state: &mut [streaming_types::state_32],
     input: &[u8],
@@ -797,6 +806,7 @@ valid after a call to `digest_256`, meaning the user may feed more data into
 the hash via `update_256`. (The digest_256 function operates on an internal copy of
 the state and therefore does not invalidate the client-held state `p`.)
 */
+#[cfg(feature = "alloc")]
 pub fn digest_256(state: &[streaming_types::state_32], output: &mut [u8]) {
     let block_state: &[u32] = &(state[0usize]).block_state;
     let buf_: &[u8] = &(state[0usize]).buf;
@@ -845,6 +855,7 @@ pub fn hash_256(output: &mut [u8], input: &[u8], input_len: u32) {
     crate::hacl::sha256_finish(&st, rb)
 }
 
+#[cfg(feature = "alloc")]
 pub fn malloc_224() -> Box<[streaming_types::state_32]> {
     let buf: Box<[u8]> = vec![0u8; 64usize].into_boxed_slice();
     let mut block_state: Box<[u32]> = vec![0u32; 8usize].into_boxed_slice();
@@ -858,6 +869,7 @@ pub fn malloc_224() -> Box<[streaming_types::state_32]> {
     p
 }
 
+#[cfg(feature = "alloc")]
 pub fn reset_224(state: &mut [streaming_types::state_32]) {
     let block_state: &mut [u32] = &mut (state[0usize]).block_state;
     crate::hacl::sha224_init(block_state);
@@ -865,6 +877,7 @@ pub fn reset_224(state: &mut [streaming_types::state_32]) {
     (state[0usize]).total_len = total_len
 }
 
+#[cfg(feature = "alloc")]
 pub fn update_224(
     state: &mut [streaming_types::state_32],
     input: &[u8],
@@ -878,6 +891,7 @@ Write the resulting hash into `output`, an array of 28 bytes. The state remains
 valid after a call to `digest_224`, meaning the user may feed more data into
 the hash via `update_224`.
 */
+#[cfg(feature = "alloc")]
 pub fn digest_224(state: &[streaming_types::state_32], output: &mut [u8]) {
     let block_state: &[u32] = &(state[0usize]).block_state;
     let buf_: &[u8] = &(state[0usize]).buf;
@@ -926,6 +940,7 @@ pub fn hash_224(output: &mut [u8], input: &[u8], input_len: u32) {
     crate::hacl::sha224_finish(&st, rb)
 }
 
+#[cfg(feature = "alloc")]
 pub fn malloc_512() -> Box<[streaming_types::state_64]> {
     let buf: Box<[u8]> = vec![0u8; 128usize].into_boxed_slice();
     let mut block_state: Box<[u64]> = vec![0u64; 8usize].into_boxed_slice();
@@ -945,6 +960,7 @@ The state is to be freed by calling `free_512`. Cloning the state this way is
 useful, for instance, if your control-flow diverges and you need to feed
 more (different) data into the hash in each branch.
 */
+#[cfg(feature = "alloc")]
 pub fn copy_512(state: &[streaming_types::state_64]) -> Box<[streaming_types::state_64]> {
     let block_state0: &[u64] = &(state[0usize]).block_state;
     let buf0: &[u8] = &(state[0usize]).buf;
@@ -962,6 +978,7 @@ pub fn copy_512(state: &[streaming_types::state_64]) -> Box<[streaming_types::st
     p
 }
 
+#[cfg(feature = "alloc")]
 pub fn reset_512(state: &mut [streaming_types::state_64]) {
     let block_state: &mut [u64] = &mut (state[0usize]).block_state;
     crate::hacl::sha512_init(block_state);
@@ -970,6 +987,7 @@ pub fn reset_512(state: &mut [streaming_types::state_64]) {
 }
 
 #[inline]
+#[cfg(feature = "alloc")]
 fn update_384_512(
     state: &mut [streaming_types::state_64],
     chunk: &[u8],
@@ -1094,6 +1112,7 @@ success, or 1 if the combined length of all of the data passed to `update_512`
 
 This function is identical to the update function for SHA2_384.
 */
+#[cfg(feature = "alloc")]
 pub fn update_512(
     state: &mut [streaming_types::state_64],
     input: &[u8],
@@ -1108,6 +1127,7 @@ valid after a call to `digest_512`, meaning the user may feed more data into
 the hash via `update_512`. (The digest_512 function operates on an internal copy of
 the state and therefore does not invalidate the client-held state `p`.)
 */
+#[cfg(feature = "alloc")]
 pub fn digest_512(state: &[streaming_types::state_64], output: &mut [u8]) {
     let block_state: &[u64] = &(state[0usize]).block_state;
     let buf_: &[u8] = &(state[0usize]).buf;
@@ -1159,6 +1179,7 @@ pub fn hash_512(output: &mut [u8], input: &[u8], input_len: u32) {
     crate::hacl::sha512_finish(&st, rb)
 }
 
+#[cfg(feature = "alloc")]
 pub fn malloc_384() -> Box<[streaming_types::state_64]> {
     let buf: Box<[u8]> = vec![0u8; 128usize].into_boxed_slice();
     let mut block_state: Box<[u64]> = vec![0u64; 8usize].into_boxed_slice();
@@ -1172,6 +1193,7 @@ pub fn malloc_384() -> Box<[streaming_types::state_64]> {
     p
 }
 
+#[cfg(feature = "alloc")]
 pub fn reset_384(state: &mut [streaming_types::state_64]) {
     let block_state: &mut [u64] = &mut (state[0usize]).block_state;
     crate::hacl::sha384_init(block_state);
@@ -1179,6 +1201,7 @@ pub fn reset_384(state: &mut [streaming_types::state_64]) {
     (state[0usize]).total_len = total_len
 }
 
+#[cfg(feature = "alloc")]
 pub fn update_384(
     state: &mut [streaming_types::state_64],
     input: &[u8],
@@ -1192,6 +1215,7 @@ Write the resulting hash into `output`, an array of 48 bytes. The state remains
 valid after a call to `digest_384`, meaning the user may feed more data into
 the hash via `update_384`.
 */
+#[cfg(feature = "alloc")]
 pub fn digest_384(state: &[streaming_types::state_64], output: &mut [u8]) {
     let block_state: &[u64] = &(state[0usize]).block_state;
     let buf_: &[u8] = &(state[0usize]).buf;
@@ -1,6 +1,6 @@
 use super::*;
+#[cfg(feature = "alloc")]
 use libcrux_hacl_rs::prelude::*;
-use libcrux_traits::Digest;
 
 /// The different Sha2 algorithms.
 #[derive(Clone, Copy, Debug)]
@@ -29,10 +29,18 @@ impl Algorithm {
     /// Write the Sha2 hash of `payload` into `digest`.
     pub fn hash(&self, payload: &[u8], digest: &mut [u8]) {
         match self {
-            Algorithm::Sha224 => Sha224::hash(digest, payload),
-            Algorithm::Sha256 => Sha256::hash(digest, payload),
-            Algorithm::Sha384 => Sha384::hash(digest, payload),
-            Algorithm::Sha512 => Sha512::hash(digest, payload),
+            Algorithm::Sha224 => {
+                crate::hacl::hash_224(digest, payload, payload.len().try_into().unwrap())
+            }
+            Algorithm::Sha256 => {
+                crate::hacl::hash_256(digest, payload, payload.len().try_into().unwrap())
+            }
+            Algorithm::Sha384 => {
+                crate::hacl::hash_384(digest, payload, payload.len().try_into().unwrap())
+            }
+            Algorithm::Sha512 => {
+                crate::hacl::hash_512(digest, payload, payload.len().try_into().unwrap())
+            }
         }
     }
 }
@@ -43,7 +51,7 @@ impl Algorithm {
 #[inline(always)]
 pub fn sha224(payload: &[u8]) -> [u8; SHA224_LENGTH] {
     let mut digest = [0u8; SHA224_LENGTH];
-    Sha224::hash(&mut digest, payload);
+    crate::hacl::hash_224(&mut digest, payload, payload.len().try_into().unwrap());
     digest
 }
 
@@ -53,7 +61,7 @@ pub fn sha224(payload: &[u8]) -> [u8; SHA224_LENGTH] {
 #[inline(always)]
 pub fn sha256(payload: &[u8]) -> [u8; SHA256_LENGTH] {
     let mut digest = [0u8; SHA256_LENGTH];
-    Sha256::hash(&mut digest, payload);
+    crate::hacl::hash_256(&mut digest, payload, payload.len().try_into().unwrap());
     digest
 }
 
@@ -63,7 +71,7 @@ pub fn sha256(payload: &[u8]) -> [u8; SHA256_LENGTH] {
 #[inline(always)]
 pub fn sha384(payload: &[u8]) -> [u8; SHA384_LENGTH] {
     let mut digest = [0u8; SHA384_LENGTH];
-    Sha384::hash(&mut digest, payload);
+    crate::hacl::hash_384(&mut digest, payload, payload.len().try_into().unwrap());
     digest
 }
 
@@ -73,12 +81,15 @@ pub fn sha384(payload: &[u8]) -> [u8; SHA384_LENGTH] {
 #[inline(always)]
 pub fn sha512(payload: &[u8]) -> [u8; SHA512_LENGTH] {
     let mut digest = [0u8; SHA512_LENGTH];
-    Sha512::hash(&mut digest, payload);
+    crate::hacl::hash_512(&mut digest, payload, payload.len().try_into().unwrap());
     digest
 }
 
 // Streaming API - This is the recommended one.
 // For implementations based on hacl_rs (over hacl-c)
+// The hacl streaming states allocate, see [`crate::no_alloc`] for streaming
+// without `alloc`.
+#[cfg(feature = "alloc")]
 macro_rules! impl_hash {
     ($name:ident, $digest_size:literal, $state:ty, $malloc:expr, $reset:expr, $update:expr, $finish:expr, $copy:expr, $hash:expr) => {
         #[allow(non_camel_case_types)]
@@ -105,12 +116,14 @@ macro_rules! impl_hash {
             }
 
             /// Add the `payload` to the digest.
-            /// Will panic if `payload` is longer than `u32::MAX` to ensure that hacl-rs can
-            /// process it.
+            ///
+            /// The `payload` is passed to hacl-rs in chunks of at most
+            /// `u32::MAX` bytes.
             #[inline(always)]
             fn update(&mut self, payload: &[u8]) {
-                let payload_len = payload.len().try_into().unwrap();
-                $update(self.state.as_mut(), payload, payload_len);
+                for chunk in payload.chunks(u32::MAX as usize) {
+                    $update(self.state.as_mut(), chunk, chunk.len() as u32);
+                }
             }
 
             /// Get the digest.
@@ -147,6 +160,7 @@ macro_rules! impl_hash {
     };
 }
 
+#[cfg(feature = "alloc")]
 impl_hash!(
     Sha256,
     32,
@@ -158,6 +172,7 @@ impl_hash!(
     crate::hacl::copy_256,
     crate::hacl::hash_256
 );
+#[cfg(feature = "alloc")]
 impl_hash!(
     Sha224,
     28,
@@ -170,6 +185,7 @@ impl_hash!(
     crate::hacl::hash_224
 );
 
+#[cfg(feature = "alloc")]
 impl_hash!(
     Sha512,
     64,
@@ -181,6 +197,7 @@ impl_hash!(
     crate::hacl::copy_512,
     crate::hacl::hash_512
 );
+#[cfg(feature = "alloc")]
 impl_hash!(
     Sha384,
     48,
@@ -26,5 +26,8 @@ mod impl_hacl;
 /// use it if we want to use hacl
 pub use impl_hacl::*;
 
+/// Streaming types that don't allocate
+pub mod no_alloc;
+
 /// Re-export the `Digest` trait.
 pub use libcrux_traits::Digest;
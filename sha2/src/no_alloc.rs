@@ -0,0 +1,158 @@
+//! Streaming SHA-2 that doesn't allocate.
+//!
+//! The streaming types in the crate root use the hacl streaming states, which
+//! keep the hash state and the buffered input on the heap. The types here keep
+//! both inline, and process full blocks with the hacl block functions.
+//!
+//! Unlike the hacl streaming states, the buffering and the length tracking
+//! here are not verified. Only use these types where `alloc` is not available.
+
+use libcrux_traits::Digest;
+
+macro_rules! impl_hash {
+    ($name:ident, $word:ty, $block_len:literal, $digest_size:literal, $totlen:ty, $init:path, $update_nblocks:path, $update_last:path, $finish:path) => {
+        #[doc = concat!("Streaming ", stringify!($name), " that doesn't allocate.")]
+        #[derive(Clone)]
+        pub struct $name {
+            hash: [$word; 8],
+            buf: [u8; $block_len],
+            buf_len: usize,
+            total_len: u64,
+        }
+
+        impl $name {
+            /// The largest number of bytes that the hacl block functions
+            /// process at once.
+            const MAX_BLOCKS_LEN: usize = (u32::MAX as usize / $block_len) * $block_len;
+
+            /// Initialize a new digest state for streaming use.
+            pub fn new() -> $name {
+                let mut hash = [0; 8];
+                $init(&mut hash);
+                $name {
+                    hash,
+                    buf: [0u8; $block_len],
+                    buf_len: 0,
+                    total_len: 0,
+                }
+            }
+
+            fn compress(&mut self, blocks: &[u8]) {
+                for blocks in blocks.chunks(Self::MAX_BLOCKS_LEN) {
+                    $update_nblocks(blocks.len() as u32, blocks, &mut self.hash);
+                }
+            }
+        }
+
+        impl Digest<$digest_size> for $name {
+            /// Return the digest for the given input byte slice, in immediate mode.
+            #[inline(always)]
+            fn hash(digest: &mut [u8], payload: &[u8]) {
+                debug_assert!(digest.len() == $digest_size);
+                let mut state = Self::new();
+                state.update(payload);
+                state.finish(digest.try_into().unwrap());
+            }
+
+            /// Add the `payload` to the digest.
+            fn update(&mut self, mut payload: &[u8]) {
+                self.total_len = self.total_len.wrapping_add(payload.len() as u64);
+
+                if self.buf_len > 0 {
+                    let take = core::cmp::min($block_len - self.buf_len, payload.len());
+                    self.buf[self.buf_len..self.buf_len + take].copy_from_slice(&payload[..take]);
+                    self.buf_len += take;
+                    payload = &payload[take..];
+
+                    if self.buf_len < $block_len {
+                        return;
+                    }
+                    let block = self.buf;
+                    self.compress(&block);
+                    self.buf_len = 0;
+                }
+
+                let full_len = payload.len() - payload.len() % $block_len;
+                self.compress(&payload[..full_len]);
+
+                let rest = &payload[full_len..];
+                self.buf[..rest.len()].copy_from_slice(rest);
+                self.buf_len = rest.len();
+            }
+
+            /// Get the digest.
+            ///
+            /// Note that the digest state can be continued to be used, to extend the
+            /// digest.
+            fn finish(&self, digest: &mut [u8; $digest_size]) {
+                let mut hash = self.hash;
+                $update_last(
+                    <$totlen>::from(self.total_len),
+                    self.buf_len as u32,
+                    &self.buf,
+                    &mut hash,
+                );
+                $finish(&hash, digest);
+            }
+
+            /// Reset the digest state.
+            #[inline(always)]
+            fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl Default for $name {
+            #[inline(always)]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+impl_hash!(
+    Sha256,
+    u32,
+    64,
+    32,
+    u64,
+    crate::hacl::sha256_init,
+    crate::hacl::sha256_update_nblocks,
+    crate::hacl::sha256_update_last,
+    crate::hacl::sha256_finish
+);
+impl_hash!(
+    Sha224,
+    u32,
+    64,
+    28,
+    u64,
+    crate::hacl::sha224_init,
+    crate::hacl::sha224_update_nblocks,
+    crate::hacl::sha224_update_last,
+    crate::hacl::sha224_finish
+);
+
+impl_hash!(
+    Sha512,
+    u64,
+    128,
+    64,
+    u128,
+    crate::hacl::sha512_init,
+    crate::hacl::sha512_update_nblocks,
+    crate::hacl::sha512_update_last,
+    crate::hacl::sha512_finish
+);
+impl_hash!(
+    Sha384,
+    u64,
+    128,
+    48,
+    u128,
+    crate::hacl::sha384_init,
+    crate::hacl::sha384_update_nblocks,
+    crate::hacl::sha384_update_last,
+    crate::hacl::sha384_finish
+);
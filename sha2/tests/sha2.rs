@@ -77,3 +77,113 @@ fn shaclone() {
     assert_eq!(digest, digest_2);
     assert_eq!(digest, libcrux_sha2::sha512(b"test 512more 512"));
 }
+
+#[test]
+fn streaming_chunks() {
+    // One million repetitions of "a", from FIPS 180-2.
+    let input = [b'a'; 1_000_000];
+
+    fn check<const N: usize, D: libcrux_traits::Digest<N> + Default>(input: &[u8], expected: &str) {
+        for chunk_len in [1, 3, 63, 64, 65, 127, 128, 129, 1000, input.len()] {
+            let mut state = D::default();
+            for chunk in input.chunks(chunk_len) {
+                state.update(chunk);
+            }
+            let mut digest = [0u8; N];
+            state.finish(&mut digest);
+            assert_eq!(hex::encode(digest), expected, "chunk length {chunk_len}");
+        }
+
+        let mut digest = [0u8; N];
+        D::hash(&mut digest, input);
+        assert_eq!(hex::encode(digest), expected);
+    }
+
+    const SHA224: &str = "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67";
+    const SHA256: &str = "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0";
+    const SHA384: &str = "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b07b8b3dc38ecc4ebae97ddd87f3d8985";
+    const SHA512: &str = "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b";
+
+    check::<28, libcrux_sha2::Sha224>(&input, SHA224);
+    check::<32, libcrux_sha2::Sha256>(&input, SHA256);
+    check::<48, libcrux_sha2::Sha384>(&input, SHA384);
+    check::<64, libcrux_sha2::Sha512>(&input, SHA512);
+
+    check::<28, libcrux_sha2::no_alloc::Sha224>(&input, SHA224);
+    check::<32, libcrux_sha2::no_alloc::Sha256>(&input, SHA256);
+    check::<48, libcrux_sha2::no_alloc::Sha384>(&input, SHA384);
+    check::<64, libcrux_sha2::no_alloc::Sha512>(&input, SHA512);
+}
+
+/// The `no_alloc` states must match the hacl streaming states for every way of
+/// splitting the input around the block boundaries.
+#[test]
+fn no_alloc_chunk_boundaries() {
+    let input: Vec<u8> = (0..400u32).map(|i| i as u8).collect();
+
+    fn check<
+        const N: usize,
+        Hacl: libcrux_traits::Digest<N> + Default + Clone,
+        NoAlloc: libcrux_traits::Digest<N> + Default + Clone,
+    >(
+        input: &[u8],
+    ) {
+        for len in 0..input.len() {
+            let input = &input[..len];
+            for split in [0, 1, len / 3, len / 2, len.saturating_sub(1), len] {
+                let (first, second) = input.split_at(split.min(len));
+
+                let mut hacl = Hacl::default();
+                let mut no_alloc = NoAlloc::default();
+                hacl.update(first);
+                no_alloc.update(first);
+
+                // Finishing must not change the state.
+                let mut expected = [0u8; N];
+                let mut digest = [0u8; N];
+                hacl.finish(&mut expected);
+                no_alloc.finish(&mut digest);
+                assert_eq!(digest, expected, "length {}", first.len());
+
+                hacl.update(second);
+                no_alloc.update(second);
+                hacl.finish(&mut expected);
+                no_alloc.finish(&mut digest);
+                assert_eq!(digest, expected, "length {len}, split at {split}");
+            }
+        }
+    }
+
+    check::<28, libcrux_sha2::Sha224, libcrux_sha2::no_alloc::Sha224>(&input);
+    check::<32, libcrux_sha2::Sha256, libcrux_sha2::no_alloc::Sha256>(&input);
+    check::<48, libcrux_sha2::Sha384, libcrux_sha2::no_alloc::Sha384>(&input);
+    check::<64, libcrux_sha2::Sha512, libcrux_sha2::no_alloc::Sha512>(&input);
+}
+
+/// A single update with more than `u32::MAX` bytes.
+///
+/// The digests of 2^32 + 100 zero bytes are from Python's `hashlib`.
+/// This takes minutes in debug builds, run it with
+/// `cargo test --release -- --ignored`.
+#[test]
+#[ignore = "slow, hashes more than 4 GiB"]
+fn streaming_over_u32_max() {
+    const LEN: usize = (1 << 32) + 100;
+    const SHA256: &str = "577d1bdcfb357ff6b5cfa8d863aba0847fea65faa1ff00f6daf1caedb30a7b3f";
+    const SHA512: &str = "0c9121eeb489de8cbcd2c42be05b7ec959803cbfd5ddb2b2ed0c6b6867506797d8d5f45c9b181bc650509aada23002b62eda5508562b1a642313fe951458d7a5";
+
+    let input = vec![0u8; LEN];
+
+    fn check<const N: usize, D: libcrux_traits::Digest<N> + Default>(input: &[u8], expected: &str) {
+        let mut state = D::default();
+        state.update(input);
+        let mut digest = [0u8; N];
+        state.finish(&mut digest);
+        assert_eq!(hex::encode(digest), expected);
+    }
+
+    check::<32, libcrux_sha2::Sha256>(&input, SHA256);
+    check::<64, libcrux_sha2::Sha512>(&input, SHA512);
+    check::<32, libcrux_sha2::no_alloc::Sha256>(&input, SHA256);
+    check::<64, libcrux_sha2::no_alloc::Sha512>(&input, SHA512);
+}
@@ -10,6 +10,19 @@
 //! On CPUs with a 128-bit SIMD unit (arm64, or SSE3, SSE4.1, and AVX on x64), the
 //! 128-bit SIMD implementation is used.
 //! In any other case the portable implementation is used.
+//!
+//! ## AES-GCM
+//!
+//! On x64 CPUs with AES-NI and CLMUL support the HACL implementation is used.
+//! On all other platforms the portable, constant-time implementation from
+//! `libcrux-aesgcm` is used. It is considerably slower.
+//!
+//! ## AES-GCM-SIV
+//!
+//! The nonce misuse-resistant AES-GCM-SIV ([RFC 8452](https://www.rfc-editor.org/rfc/rfc8452))
+//! from `libcrux-aesgcm` is used on all platforms. On x64 CPUs with AES-NI and
+//! CLMUL support it uses the hardware instructions, otherwise the portable,
+//! constant-time implementation.
 
 #[cfg(aes_ni)]
 use crate::hacl::aesgcm;
@@ -115,19 +128,39 @@ pub enum Algorithm {
 
     /// ChaCha20 Poly1305
     Chacha20Poly1305 = 3,
+
+    /// AES GCM SIV 128
+    Aes128GcmSiv = 4,
+
+    /// AES GCM SIV 256
+    Aes256GcmSiv = 5,
 }
 
 impl From<u8> for Algorithm {
     fn from(v: u8) -> Algorithm {
         match v {
-            0 => Algorithm::Aes128Gcm,
-            1 => Algorithm::Aes256Gcm,
-            2 => Algorithm::Chacha20Poly1305,
+            1 => Algorithm::Aes128Gcm,
+            2 => Algorithm::Aes256Gcm,
+            3 => Algorithm::Chacha20Poly1305,
+            4 => Algorithm::Aes128GcmSiv,
+            5 => Algorithm::Aes256GcmSiv,
             _ => panic!("Unknown AEAD mode {}", v),
         }
     }
 }
 
+impl From<Algorithm> for u8 {
+    fn from(v: Algorithm) -> u8 {
+        match v {
+            Algorithm::Aes128Gcm => 1,
+            Algorithm::Aes256Gcm => 2,
+            Algorithm::Chacha20Poly1305 => 3,
+            Algorithm::Aes128GcmSiv => 4,
+            Algorithm::Aes256GcmSiv => 5,
+        }
+    }
+}
+
 impl Algorithm {
     /// Get the key size of the `Algorithm` in bytes.
     #[inline]
@@ -136,6 +169,8 @@ impl Algorithm {
             Algorithm::Aes128Gcm => 16,
             Algorithm::Aes256Gcm => 32,
             Algorithm::Chacha20Poly1305 => 32,
+            Algorithm::Aes128GcmSiv => 16,
+            Algorithm::Aes256GcmSiv => 32,
         }
     }
 
@@ -146,6 +181,8 @@ impl Algorithm {
             Algorithm::Aes128Gcm => 16,
             Algorithm::Aes256Gcm => 16,
             Algorithm::Chacha20Poly1305 => 16,
+            Algorithm::Aes128GcmSiv => 16,
+            Algorithm::Aes256GcmSiv => 16,
         }
     }
 
@@ -156,17 +193,8 @@ impl Algorithm {
             Algorithm::Aes128Gcm => 12,
             Algorithm::Aes256Gcm => 12,
             Algorithm::Chacha20Poly1305 => 12,
-        }
-    }
-
-    /// Make sure the algorithm is supported by the hardware.
-    /// Returns `Ok(())` or `Err(InvalidArgumentError::UnsupportedAlgorithm)`.
-    #[inline]
-    fn supported(self) -> Result<(), InvalidArgumentError> {
-        if matches!(self, Algorithm::Aes128Gcm | Algorithm::Aes256Gcm) && !aes_ni_support() {
-            Err(InvalidArgumentError::UnsupportedAlgorithm)
-        } else {
-            Ok(())
+            Algorithm::Aes128GcmSiv => 12,
+            Algorithm::Aes256GcmSiv => 12,
         }
     }
 }
@@ -180,6 +208,33 @@ pub struct Aes256Key(pub [u8; Algorithm::key_size(Algorithm::Aes256Gcm)]);
 #[derive(Default)]
 pub struct Chacha20Key(pub [u8; Algorithm::key_size(Algorithm::Chacha20Poly1305)]);
 
+/// An AES key, i.e. an [`Aes128Key`] or an [`Aes256Key`].
+///
+/// This allows the AES based primitives in [`crate::aes_kw`] and
+/// [`crate::cmac`] to take the same typed keys as the AEADs.
+pub trait AesKey: sealed::Sealed {
+    /// The raw key bytes.
+    fn as_bytes(&self) -> &[u8];
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Aes128Key {}
+    impl Sealed for super::Aes256Key {}
+}
+
+impl AesKey for Aes128Key {
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AesKey for Aes256Key {
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 mod keygen {
     use super::*;
     use rand::CryptoRng;
@@ -205,6 +260,8 @@ mod keygen {
                 Algorithm::Aes128Gcm => Self::Aes128(Aes128Key::generate(rng)),
                 Algorithm::Aes256Gcm => Self::Aes256(Aes256Key::generate(rng)),
                 Algorithm::Chacha20Poly1305 => Self::Chacha20Poly1305(Chacha20Key::generate(rng)),
+                Algorithm::Aes128GcmSiv => Self::Aes128GcmSiv(Aes128Key::generate(rng)),
+                Algorithm::Aes256GcmSiv => Self::Aes256GcmSiv(Aes256Key::generate(rng)),
             }
         }
     }
@@ -232,13 +289,13 @@ pub enum Key {
     Aes128(Aes128Key),
     Aes256(Aes256Key),
     Chacha20Poly1305(Chacha20Key),
+    Aes128GcmSiv(Aes128Key),
+    Aes256GcmSiv(Aes256Key),
 }
 
 impl Key {
     /// Generate a [`Key`] for the [`Algorithm`] from the raw `bytes`.
     pub fn from_bytes(alg: Algorithm, bytes: Vec<u8>) -> Result<Self, InvalidArgumentError> {
-        alg.supported()?;
-
         fn to_array<const N: usize>(bytes: Vec<u8>) -> Result<[u8; N], InvalidArgumentError> {
             bytes
                 .try_into()
@@ -249,6 +306,8 @@ impl Key {
             Algorithm::Aes128Gcm => Self::Aes128(Aes128Key(to_array(bytes)?)),
             Algorithm::Aes256Gcm => Self::Aes256(Aes256Key(to_array(bytes)?)),
             Algorithm::Chacha20Poly1305 => Self::Chacha20Poly1305(Chacha20Key(to_array(bytes)?)),
+            Algorithm::Aes128GcmSiv => Self::Aes128GcmSiv(Aes128Key(to_array(bytes)?)),
+            Algorithm::Aes256GcmSiv => Self::Aes256GcmSiv(Aes256Key(to_array(bytes)?)),
         };
 
         Ok(key)
@@ -259,8 +318,6 @@ impl Key {
         alg: Algorithm,
         bytes: impl AsRef<[u8]>,
     ) -> Result<Self, InvalidArgumentError> {
-        alg.supported()?;
-
         fn to_array<const N: usize>(
             bytes: impl AsRef<[u8]>,
         ) -> Result<[u8; N], InvalidArgumentError> {
@@ -274,6 +331,8 @@ impl Key {
             Algorithm::Aes128Gcm => Self::Aes128(Aes128Key(to_array(bytes)?)),
             Algorithm::Aes256Gcm => Self::Aes256(Aes256Key(to_array(bytes)?)),
             Algorithm::Chacha20Poly1305 => Self::Chacha20Poly1305(Chacha20Key(to_array(bytes)?)),
+            Algorithm::Aes128GcmSiv => Self::Aes128GcmSiv(Aes128Key(to_array(bytes)?)),
+            Algorithm::Aes256GcmSiv => Self::Aes256GcmSiv(Aes256Key(to_array(bytes)?)),
         };
 
         Ok(key)
@@ -424,13 +483,6 @@ fn aes_encrypt_128(
         .map(|t| t.into())
 }
 
-#[cfg(not(aes_ni))]
-fn aes_encrypt_128(_: &Aes128Key, _: &mut [u8], _v: Iv, _: &[u8]) -> Result<Tag, EncryptError> {
-    Err(EncryptError::InvalidArgument(
-        InvalidArgumentError::UnsupportedAlgorithm,
-    ))
-}
-
 #[cfg(aes_ni)]
 fn aes_encrypt_256(
     key: &Aes256Key,
@@ -451,13 +503,6 @@ fn aes_encrypt_256(
         .map(|t| t.into())
 }
 
-#[cfg(not(aes_ni))]
-fn aes_encrypt_256(_: &Aes256Key, _: &mut [u8], _: Iv, _: &[u8]) -> Result<Tag, EncryptError> {
-    Err(EncryptError::InvalidArgument(
-        InvalidArgumentError::UnsupportedAlgorithm,
-    ))
-}
-
 #[cfg(aes_ni)]
 fn aes_decrypt_128(
     key: &Aes128Key,
@@ -475,19 +520,6 @@ fn aes_decrypt_128(
     })
 }
 
-#[cfg(not(aes_ni))]
-fn aes_decrypt_128(
-    _: &Aes128Key,
-    _: &mut [u8],
-    _: Iv,
-    _: &[u8],
-    _: &Tag,
-) -> Result<(), DecryptError> {
-    Err(DecryptError::InvalidArgument(
-        InvalidArgumentError::UnsupportedAlgorithm,
-    ))
-}
-
 #[cfg(aes_ni)]
 fn aes_decrypt_256(
     key: &Aes256Key,
@@ -505,18 +537,80 @@ fn aes_decrypt_256(
     })
 }
 
+// Without the `aes_ni` cfg, which `build.rs` only sets for native x86-64
+// builds on a CPU with AES-NI, the HACL AES-NI code isn't compiled in and the
+// hardware path uses the portable implementation as well.
 #[cfg(not(aes_ni))]
-fn aes_decrypt_256(
-    _: &Aes256Key,
-    _: &mut [u8],
-    _: Iv,
-    _: &[u8],
-    _: &Tag,
-) -> Result<(), DecryptError> {
-    Err(DecryptError::InvalidArgument(
-        InvalidArgumentError::UnsupportedAlgorithm,
-    ))
-}
+use self::{
+    portable_aes_decrypt_128 as aes_decrypt_128, portable_aes_decrypt_256 as aes_decrypt_256,
+    portable_aes_encrypt_128 as aes_encrypt_128, portable_aes_encrypt_256 as aes_encrypt_256,
+};
+
+macro_rules! impl_libcrux_aesgcm {
+    ($encrypt:ident, $decrypt:ident, $key:ty, $module:ident) => {
+        fn $encrypt(
+            key: &$key,
+            msg_ctxt: &mut [u8],
+            iv: Iv,
+            aad: &[u8],
+        ) -> Result<Tag, EncryptError> {
+            let mut tag = Tag::default();
+            libcrux_aesgcm::$module::encrypt_in_place(&key.0, msg_ctxt, &mut tag.0, aad, &iv.0)
+                .map_err(|e| match e {
+                    libcrux_aesgcm::AeadError::PlaintextTooLarge
+                    | libcrux_aesgcm::AeadError::AadTooLarge => {
+                        EncryptError::InvalidArgument(InvalidArgumentError::Unknown)
+                    }
+                    _ => EncryptError::InternalError,
+                })?;
+
+            Ok(tag)
+        }
+
+        fn $decrypt(
+            key: &$key,
+            ctxt_msg: &mut [u8],
+            iv: Iv,
+            aad: &[u8],
+            tag: &Tag,
+        ) -> Result<(), DecryptError> {
+            libcrux_aesgcm::$module::decrypt_in_place(&key.0, ctxt_msg, &tag.0, aad, &iv.0)
+                .map_err(|e| match e {
+                    libcrux_aesgcm::AeadError::InvalidCiphertext
+                    | libcrux_aesgcm::AeadError::CiphertextTooLarge
+                    | libcrux_aesgcm::AeadError::AadTooLarge => DecryptError::DecryptionFailed,
+                    _ => DecryptError::InternalError,
+                })?;
+
+            Ok(())
+        }
+    };
+}
+
+impl_libcrux_aesgcm!(
+    portable_aes_encrypt_128,
+    portable_aes_decrypt_128,
+    Aes128Key,
+    aes128
+);
+impl_libcrux_aesgcm!(
+    portable_aes_encrypt_256,
+    portable_aes_decrypt_256,
+    Aes256Key,
+    aes256
+);
+impl_libcrux_aesgcm!(
+    aes_gcm_siv_encrypt_128,
+    aes_gcm_siv_decrypt_128,
+    Aes128Key,
+    aes128_gcm_siv
+);
+impl_libcrux_aesgcm!(
+    aes_gcm_siv_encrypt_256,
+    aes_gcm_siv_decrypt_256,
+    Aes256Key,
+    aes256_gcm_siv
+);
 
 /// AEAD encrypt the message in `msg_ctxt` with the `key`, `iv` and `aad`.
 ///
@@ -527,18 +621,14 @@ pub fn encrypt(key: &Key, msg_ctxt: &mut [u8], iv: Iv, aad: &[u8]) -> Result<Tag
             if aes_ni_support() {
                 aes_encrypt_128(key, msg_ctxt, iv, aad)
             } else {
-                Err(EncryptError::InvalidArgument(
-                    InvalidArgumentError::UnsupportedAlgorithm,
-                ))
+                portable_aes_encrypt_128(key, msg_ctxt, iv, aad)
             }
         }
         Key::Aes256(key) => {
             if aes_ni_support() {
                 aes_encrypt_256(key, msg_ctxt, iv, aad)
             } else {
-                Err(EncryptError::InvalidArgument(
-                    InvalidArgumentError::UnsupportedAlgorithm,
-                ))
+                portable_aes_encrypt_256(key, msg_ctxt, iv, aad)
             }
         }
         Key::Chacha20Poly1305(key) => Ok(if simd256_support() {
@@ -548,6 +638,8 @@ pub fn encrypt(key: &Key, msg_ctxt: &mut [u8], iv: Iv, aad: &[u8]) -> Result<Tag
         } else {
             encrypt_32(key, msg_ctxt, iv, aad)
         }),
+        Key::Aes128GcmSiv(key) => aes_gcm_siv_encrypt_128(key, msg_ctxt, iv, aad),
+        Key::Aes256GcmSiv(key) => aes_gcm_siv_encrypt_256(key, msg_ctxt, iv, aad),
     }
 }
 
@@ -581,18 +673,14 @@ pub fn decrypt(
             if aes_ni_support() {
                 aes_decrypt_128(key, ctxt_msg, iv, aad, tag)
             } else {
-                Err(DecryptError::InvalidArgument(
-                    InvalidArgumentError::UnsupportedAlgorithm,
-                ))
+                portable_aes_decrypt_128(key, ctxt_msg, iv, aad, tag)
             }
         }
         Key::Aes256(key) => {
             if aes_ni_support() {
                 aes_decrypt_256(key, ctxt_msg, iv, aad, tag)
             } else {
-                Err(DecryptError::InvalidArgument(
-                    InvalidArgumentError::UnsupportedAlgorithm,
-                ))
+                portable_aes_decrypt_256(key, ctxt_msg, iv, aad, tag)
             }
         }
         Key::Chacha20Poly1305(key) => {
@@ -604,6 +692,8 @@ pub fn decrypt(
                 decrypt_32(key, ctxt_msg, iv, aad, tag)
             }
         }
+        Key::Aes128GcmSiv(key) => aes_gcm_siv_decrypt_128(key, ctxt_msg, iv, aad, tag),
+        Key::Aes256GcmSiv(key) => aes_gcm_siv_decrypt_256(key, ctxt_msg, iv, aad, tag),
     }
 }
 
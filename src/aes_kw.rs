@@ -0,0 +1,83 @@
+//! # AES Key Wrap
+//!
+//! AES-KW ([RFC 3394](https://www.rfc-editor.org/rfc/rfc3394)) and AES-KWP
+//! ([RFC 5649](https://www.rfc-editor.org/rfc/rfc5649)) with AES-128 and
+//! AES-256 key-encryption keys.
+//!
+//! On x64 CPUs with AES-NI the hardware instructions are used, otherwise the
+//! portable, constant-time AES from `libcrux-aesgcm`.
+
+use crate::aead::AesKey;
+use crate::std::{vec, vec::Vec};
+
+use libcrux_aesgcm::{aes128_kw, aes256_kw};
+
+pub use libcrux_aesgcm::KeyWrapError as Error;
+
+type WrapFn = for<'a> fn(&[u8], &[u8], &'a mut [u8]) -> Result<&'a [u8], Error>;
+
+/// Call `f128` or `f256`, depending on the size of `key`, and return the
+/// output as a vector.
+fn run(
+    key: &impl AesKey,
+    input: &[u8],
+    output_len: usize,
+    f128: WrapFn,
+    f256: WrapFn,
+) -> Result<Vec<u8>, Error> {
+    let mut output = vec![0u8; output_len];
+    let key = key.as_bytes();
+    let len = match key.len() {
+        16 => f128(key, input, &mut output)?.len(),
+        _ => f256(key, input, &mut output)?.len(),
+    };
+    output.truncate(len);
+
+    Ok(output)
+}
+
+/// Wrap `key_data` with AES-KW.
+///
+/// The length of `key_data` must be a multiple of 8 and at least 16.
+pub fn wrap(kek: &impl AesKey, key_data: &[u8]) -> Result<Vec<u8>, Error> {
+    run(
+        kek,
+        key_data,
+        key_data.len() + 8,
+        |k, i, o| aes128_kw::wrap(k.try_into().unwrap(), i, o),
+        |k, i, o| aes256_kw::wrap(k.try_into().unwrap(), i, o),
+    )
+}
+
+/// Unwrap the AES-KW `wrapped` key.
+pub fn unwrap(kek: &impl AesKey, wrapped: &[u8]) -> Result<Vec<u8>, Error> {
+    run(
+        kek,
+        wrapped,
+        wrapped.len().saturating_sub(8),
+        |k, i, o| aes128_kw::unwrap(k.try_into().unwrap(), i, o),
+        |k, i, o| aes256_kw::unwrap(k.try_into().unwrap(), i, o),
+    )
+}
+
+/// Wrap `key_data` of any non-zero length with AES-KWP.
+pub fn wrap_with_padding(kek: &impl AesKey, key_data: &[u8]) -> Result<Vec<u8>, Error> {
+    run(
+        kek,
+        key_data,
+        key_data.len().next_multiple_of(8) + 8,
+        |k, i, o| aes128_kw::wrap_with_padding(k.try_into().unwrap(), i, o),
+        |k, i, o| aes256_kw::wrap_with_padding(k.try_into().unwrap(), i, o),
+    )
+}
+
+/// Unwrap the AES-KWP `wrapped` key.
+pub fn unwrap_with_padding(kek: &impl AesKey, wrapped: &[u8]) -> Result<Vec<u8>, Error> {
+    run(
+        kek,
+        wrapped,
+        wrapped.len().saturating_sub(8),
+        |k, i, o| aes128_kw::unwrap_with_padding(k.try_into().unwrap(), i, o),
+        |k, i, o| aes256_kw::unwrap_with_padding(k.try_into().unwrap(), i, o),
+    )
+}
@@ -0,0 +1,33 @@
+//! # AES-CMAC
+//!
+//! AES-CMAC ([NIST SP 800-38B](https://csrc.nist.gov/pubs/sp/800/38/b/upd1/final),
+//! [RFC 4493](https://www.rfc-editor.org/rfc/rfc4493)) with AES-128 and
+//! AES-256 keys.
+//!
+//! On x64 CPUs with AES-NI the hardware instructions are used, otherwise the
+//! portable, constant-time AES from `libcrux-aesgcm`.
+
+use crate::aead::AesKey;
+
+use libcrux_aesgcm::{aes128_cmac, aes256_cmac};
+
+pub use libcrux_aesgcm::CmacError as Error;
+pub use libcrux_aesgcm::CMAC_TAG_LEN as TAG_LEN;
+
+/// Compute the AES-CMAC tag of `msg`.
+pub fn cmac(key: &impl AesKey, msg: &[u8]) -> [u8; TAG_LEN] {
+    let key = key.as_bytes();
+    match key.len() {
+        16 => aes128_cmac::mac(key.try_into().unwrap(), msg),
+        _ => aes256_cmac::mac(key.try_into().unwrap(), msg),
+    }
+}
+
+/// Check the AES-CMAC `tag` of `msg` in constant time.
+pub fn verify(key: &impl AesKey, msg: &[u8], tag: &[u8; TAG_LEN]) -> Result<(), Error> {
+    let key = key.as_bytes();
+    match key.len() {
+        16 => aes128_cmac::verify(key.try_into().unwrap(), msg, tag),
+        _ => aes256_cmac::verify(key.try_into().unwrap(), msg, tag),
+    }
+}
@@ -12,6 +12,15 @@
 //! The portable HACL implementations are used unless running on an x64 CPU.
 //! On x64 CPUs the libjade implementation is used and if AVX2 is available, the
 //! optimised libjade implementation is used.
+//!
+//! The incremental SHA3 hashers [`Sha3_224`], [`Sha3_256`], [`Sha3_384`] and
+//! [`Sha3_512`] are provided by `libcrux-sha3`. Only the portable implementation
+//! is available, there are no SIMD incremental SHA3 hashers.
+//!
+//! ## BLAKE3
+//!
+//! [`blake3`] and the incremental [`Blake3`] hasher are provided by
+//! `libcrux-blake3`, which uses AVX2 or Neon for long inputs when available.
 
 use crate::hacl::{blake2, sha3};
 
@@ -40,6 +49,7 @@ pub enum Algorithm {
     Sha3_256 = 9,
     Sha3_384 = 10,
     Sha3_512 = 11,
+    Blake3 = 12,
 }
 
 impl From<u32> for Algorithm {
@@ -56,6 +66,7 @@ impl From<u32> for Algorithm {
             9 => Algorithm::Sha3_256,
             10 => Algorithm::Sha3_384,
             11 => Algorithm::Sha3_512,
+            12 => Algorithm::Blake3,
             _ => panic!("Unknown Digest mode {}", v),
         }
     }
@@ -75,6 +86,7 @@ impl From<Algorithm> for u32 {
             Algorithm::Sha3_256 => 9,
             Algorithm::Sha3_384 => 10,
             Algorithm::Sha3_512 => 11,
+            Algorithm::Blake3 => 12,
         }
     }
 }
@@ -93,6 +105,7 @@ pub const fn digest_size(mode: Algorithm) -> usize {
         Algorithm::Sha3_256 => 32,
         Algorithm::Sha3_384 => 48,
         Algorithm::Sha3_512 => 64,
+        Algorithm::Blake3 => 32,
     }
 }
 
@@ -102,6 +115,8 @@ impl Algorithm {
     }
 }
 
+pub type Sha1Digest = [u8; digest_size(Algorithm::Sha1)];
+
 pub type Sha2_224Digest = [u8; digest_size(Algorithm::Sha224)];
 pub type Sha2_256Digest = [u8; digest_size(Algorithm::Sha256)];
 pub type Sha2_384Digest = [u8; digest_size(Algorithm::Sha384)];
@@ -112,6 +127,8 @@ pub type Sha3_256Digest = [u8; digest_size(Algorithm::Sha3_256)];
 pub type Sha3_384Digest = [u8; digest_size(Algorithm::Sha3_384)];
 pub type Sha3_512Digest = [u8; digest_size(Algorithm::Sha3_512)];
 
+pub type Blake3Digest = [u8; digest_size(Algorithm::Blake3)];
+
 // Single-shot API
 
 macro_rules! sha3_impl {
@@ -173,7 +190,7 @@ pub fn hash(alg: Algorithm, payload: &[u8]) -> Vec<u8> {
     // Note that one-shot hacl functions are slower than streaming.
     // So we only use streaming.
     match alg {
-        Algorithm::Sha1 => todo!(),
+        Algorithm::Sha1 => sha1(payload).into(),
         Algorithm::Sha224 => sha2_224(payload).into(),
         Algorithm::Sha256 => sha2_256(payload).into(),
         Algorithm::Sha384 => sha2_384(payload).into(),
@@ -184,6 +201,7 @@ pub fn hash(alg: Algorithm, payload: &[u8]) -> Vec<u8> {
         Algorithm::Sha3_256 => sha3_256(payload).into(),
         Algorithm::Sha3_384 => sha3_384(payload).into(),
         Algorithm::Sha3_512 => sha3_512(payload).into(),
+        Algorithm::Blake3 => blake3(payload).into(),
     }
 }
 
@@ -229,6 +247,10 @@ fn blake2b(payload: &[u8], key: &[u8]) -> Vec<u8> {
     .into()
 }
 
+// import SHA1
+pub use libcrux_sha1::sha1;
+pub use libcrux_sha1::Sha1;
+
 // import SHA2
 pub use libcrux_sha2::sha224 as sha2_224;
 pub use libcrux_sha2::sha256 as sha2_256;
@@ -240,6 +262,13 @@ pub use libcrux_sha2::Sha256 as Sha2_256;
 pub use libcrux_sha2::Sha384 as Sha2_384;
 pub use libcrux_sha2::Sha512 as Sha2_512;
 
+// import incremental SHA3
+pub use libcrux_sha3::portable::incremental::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+
+// import BLAKE3
+pub use libcrux_blake3::hash as blake3;
+pub use libcrux_blake3::Hasher as Blake3;
+
 // SHAKE messages from SHA 3
 
 #[cfg(simd256)]
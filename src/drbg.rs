@@ -78,7 +78,8 @@ impl Drbg {
             | crate::digest::Algorithm::Sha3_224
             | crate::digest::Algorithm::Sha3_256
             | crate::digest::Algorithm::Sha3_384
-            | crate::digest::Algorithm::Sha3_512 => return Err(Error::UnsupportedAlgorithm),
+            | crate::digest::Algorithm::Sha3_512
+            | crate::digest::Algorithm::Blake3 => return Err(Error::UnsupportedAlgorithm),
         };
 
         let state = drbg::Drbg::new(algorithm, entropy, nonce, personalization)
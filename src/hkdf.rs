@@ -1,6 +1,6 @@
 //! HKDF
 //!
-//! This module implements HKDF on SHA 1 and SHA 2 (except for SHA 224).
+//! This module implements HKDF on SHA 2 (except for SHA 224), SHA 3 and BLAKE2.
 
 pub use libcrux_hkdf::expand;
 pub use libcrux_hkdf::extract;
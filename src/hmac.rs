@@ -1,7 +1,14 @@
 //! HMAC
 //!
-//! This module implements HMAC on SHA 1 and SHA 2 (except for SHA 224).
+//! This module implements HMAC on SHA 1, SHA 2 (except for SHA 224), SHA 3 and
+//! BLAKE2.
 
 pub use libcrux_hmac::hmac;
 pub use libcrux_hmac::tag_size;
 pub use libcrux_hmac::Algorithm;
+
+pub use libcrux_hmac::Error;
+pub use libcrux_hmac::{
+    HmacBlake2b, HmacBlake2s, HmacSha1, HmacSha256, HmacSha384, HmacSha3_224, HmacSha3_256,
+    HmacSha3_384, HmacSha3_512, HmacSha512,
+};
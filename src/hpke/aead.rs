@@ -21,6 +21,17 @@
 //! All AEADs MUST be IND-CCA2-secure, as is currently true for all AEADs
 //! listed in [`AEAD`].
 //!
+//! ## Implementations
+//!
+//! ChaCha20Poly1305 uses the portable, formally verified [`libcrux_chacha20poly1305`]
+//! crate and AES-GCM the portable, constant-time [`libcrux_aesgcm`] crate.
+//! Both are therefore available on all platforms.
+//!
+//! The export-only [`AEAD::Export_only`] can only be used with the
+//! [secret export](`super::Context_Export`) interface. [`AeadSeal`] and
+//! [`AeadOpen`] return an [`UnsupportedAlgorithm`](`HpkeError::UnsupportedAlgorithm`)
+//! error for it.
+//!
 //! [hpke rfc]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-hpke/
 //! [publication queue]: https://www.rfc-editor.org/current_queue.php
 #![allow(
@@ -30,17 +41,10 @@
     non_upper_case_globals
 )]
 
-use crate::{
-    aead::{self, *},
-    hmac::tag_size,
-};
-
 use crate::std::{vec, vec::Vec};
 
 use super::errors::*;
 
-type AeadAlgResult = Result<Algorithm, HpkeError>;
-
 /// ## Authenticated Encryption with Associated Data (AEAD) Functions
 ///
 /// The `0xFFFF` AEAD ID is reserved for applications which only use the Export
@@ -52,7 +56,7 @@ type AeadAlgResult = Result<Algorithm, HpkeError>;
 /// | 0x0001 | AES-128-GCM      | 16  | 12  | 16  | [GCM]     |
 /// | 0x0002 | AES-256-GCM      | 32  | 12  | 16  | [GCM]     |
 /// | 0x0003 | ChaCha20Poly1305 | 32  | 12  | 16  | [RFC8439] |
-/// | 0xFFFF | Export-only      | N/A | N/A | N/A | RFCXXXX   |
+/// | 0xFFFF | Export-only      | N/A | N/A | N/A | [RFC9180] |
 ///
 /// The "HPKE AEAD Identifiers" registry lists identifiers for authenticated
 /// encryption with associated data (AEAD) algorithms defined for use with HPKE.
@@ -70,6 +74,7 @@ type AeadAlgResult = Result<Algorithm, HpkeError>;
 ///
 /// [GCM]: https://doi.org/10.6028/nist.sp.800-38d
 /// [RFC8439]: https://www.rfc-editor.org/info/rfc8439
+/// [RFC9180]: https://www.rfc-editor.org/info/rfc9180
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum AEAD {
     /// 0x0001
@@ -124,27 +129,14 @@ pub type Key = Vec<u8>;
 /// An AEAD nonce is a sequence of bytes.
 pub type Nonce = Vec<u8>;
 
-fn alg_for_aead(aead_id: AEAD) -> AeadAlgResult {
-    match aead_id {
-        AEAD::AES_128_GCM => AeadAlgResult::Ok(Algorithm::Aes128Gcm),
-        AEAD::AES_256_GCM => AeadAlgResult::Ok(Algorithm::Aes256Gcm),
-        AEAD::ChaCha20Poly1305 => AeadAlgResult::Ok(Algorithm::Chacha20Poly1305),
-        AEAD::Export_only => AeadAlgResult::Err(HpkeError::UnsupportedAlgorithm),
-    }
-}
-
 /// Encrypt and authenticate plaintext `pt` with associated data `aad` using
 /// symmetric key `key` and nonce `nonce`, yielding ciphertext and tag `ct`.
 /// This function can raise a [`MessageLimitReachedError`](`HpkeError::MessageLimitReachedError`) upon failure.
 pub fn AeadSeal(aead_id: AEAD, key: &Key, nonce: &Nonce, aad: &[u8], pt: &[u8]) -> HpkeBytesResult {
-    let algorithm = alg_for_aead(aead_id)?;
-    let key = aead::Key::from_slice(algorithm, key)?;
-    match encrypt_detached(&key, pt, Iv::new(nonce)?, aad) {
-        Ok((tag, mut ct)) => {
-            ct.extend_from_slice(tag.as_ref());
-            HpkeBytesResult::Ok(ct)
-        }
-        Err(_) => HpkeBytesResult::Err(HpkeError::CryptoError),
+    match aead_id {
+        AEAD::ChaCha20Poly1305 => chacha20poly1305_seal(key, nonce, aad, pt),
+        AEAD::AES_128_GCM | AEAD::AES_256_GCM => aes_gcm_seal(aead_id, key, nonce, aad, pt),
+        AEAD::Export_only => HpkeBytesResult::Err(HpkeError::UnsupportedAlgorithm),
     }
 }
 
@@ -153,12 +145,123 @@ pub fn AeadSeal(aead_id: AEAD, key: &Key, nonce: &Nonce, aad: &[u8], pt: &[u8])
 /// returning plaintext message `pt`. This function can raise an
 /// [`OpenError`](`HpkeError::OpenError`) or [`MessageLimitReachedError`](`HpkeError::MessageLimitReachedError`) upon failure.
 pub fn AeadOpen(aead_id: AEAD, key: &Key, nonce: &Nonce, aad: &[u8], ct: &[u8]) -> HpkeBytesResult {
-    let algorithm = alg_for_aead(aead_id)?;
-    let key = aead::Key::from_slice(algorithm, key)?;
-    let mut ct = ct.to_vec();
-    let tag = ct.split_off(ct.len() - Nt(aead_id));
-    match decrypt_detached(&key, ct, Iv::new(nonce)?, aad, &Tag::from_slice(tag)?) {
-        Ok(pt) => HpkeBytesResult::Ok(pt.into()),
-        Err(_) => HpkeBytesResult::Err(HpkeError::CryptoError),
+    match aead_id {
+        AEAD::ChaCha20Poly1305 => chacha20poly1305_open(key, nonce, aad, ct),
+        AEAD::AES_128_GCM | AEAD::AES_256_GCM => aes_gcm_open(aead_id, key, nonce, aad, ct),
+        AEAD::Export_only => HpkeBytesResult::Err(HpkeError::UnsupportedAlgorithm),
     }
 }
+
+/// ChaCha20Poly1305 `Seal()` with the portable [`libcrux_chacha20poly1305`].
+fn chacha20poly1305_seal(key: &Key, nonce: &Nonce, aad: &[u8], pt: &[u8]) -> HpkeBytesResult {
+    let key: &[u8; libcrux_chacha20poly1305::KEY_LEN] = key
+        .as_slice()
+        .try_into()
+        .map_err(|_| HpkeError::InvalidParameters)?;
+    let nonce: &[u8; libcrux_chacha20poly1305::NONCE_LEN] = nonce
+        .as_slice()
+        .try_into()
+        .map_err(|_| HpkeError::InvalidParameters)?;
+
+    let mut ct = vec![0u8; pt.len() + libcrux_chacha20poly1305::TAG_LEN];
+    libcrux_chacha20poly1305::encrypt(key, pt, &mut ct, aad, nonce)
+        .map_err(|_| HpkeError::InvalidParameters)?;
+
+    HpkeBytesResult::Ok(ct)
+}
+
+/// ChaCha20Poly1305 `Open()` with the portable [`libcrux_chacha20poly1305`].
+fn chacha20poly1305_open(key: &Key, nonce: &Nonce, aad: &[u8], ct: &[u8]) -> HpkeBytesResult {
+    let key: &[u8; libcrux_chacha20poly1305::KEY_LEN] = key
+        .as_slice()
+        .try_into()
+        .map_err(|_| HpkeError::InvalidParameters)?;
+    let nonce: &[u8; libcrux_chacha20poly1305::NONCE_LEN] = nonce
+        .as_slice()
+        .try_into()
+        .map_err(|_| HpkeError::InvalidParameters)?;
+    if ct.len() < libcrux_chacha20poly1305::TAG_LEN {
+        return HpkeBytesResult::Err(HpkeError::OpenError);
+    }
+
+    let mut pt = vec![0u8; ct.len() - libcrux_chacha20poly1305::TAG_LEN];
+    libcrux_chacha20poly1305::decrypt(key, &mut pt, ct, aad, nonce)
+        .map_err(|_| HpkeError::OpenError)?;
+
+    HpkeBytesResult::Ok(pt)
+}
+
+/// AES-GCM `Seal()` with the portable [`libcrux_aesgcm`].
+fn aes_gcm_seal(aead_id: AEAD, key: &Key, nonce: &Nonce, aad: &[u8], pt: &[u8]) -> HpkeBytesResult {
+    let nonce: &[u8; libcrux_aesgcm::NONCE_LEN] = nonce
+        .as_slice()
+        .try_into()
+        .map_err(|_| HpkeError::InvalidParameters)?;
+
+    let mut ct = vec![0u8; pt.len() + libcrux_aesgcm::TAG_LEN];
+    let result = match aead_id {
+        AEAD::AES_128_GCM => libcrux_aesgcm::aes128::encrypt(
+            key.as_slice()
+                .try_into()
+                .map_err(|_| HpkeError::InvalidParameters)?,
+            pt,
+            &mut ct,
+            aad,
+            nonce,
+        )
+        .map(|_| ()),
+        AEAD::AES_256_GCM => libcrux_aesgcm::aes256::encrypt(
+            key.as_slice()
+                .try_into()
+                .map_err(|_| HpkeError::InvalidParameters)?,
+            pt,
+            &mut ct,
+            aad,
+            nonce,
+        )
+        .map(|_| ()),
+        _ => return HpkeBytesResult::Err(HpkeError::UnsupportedAlgorithm),
+    };
+    result.map_err(|_| HpkeError::InvalidParameters)?;
+
+    HpkeBytesResult::Ok(ct)
+}
+
+/// AES-GCM `Open()` with the portable [`libcrux_aesgcm`].
+fn aes_gcm_open(aead_id: AEAD, key: &Key, nonce: &Nonce, aad: &[u8], ct: &[u8]) -> HpkeBytesResult {
+    let nonce: &[u8; libcrux_aesgcm::NONCE_LEN] = nonce
+        .as_slice()
+        .try_into()
+        .map_err(|_| HpkeError::InvalidParameters)?;
+    if ct.len() < libcrux_aesgcm::TAG_LEN {
+        return HpkeBytesResult::Err(HpkeError::OpenError);
+    }
+
+    let mut pt = vec![0u8; ct.len() - libcrux_aesgcm::TAG_LEN];
+    let result = match aead_id {
+        AEAD::AES_128_GCM => libcrux_aesgcm::aes128::decrypt(
+            key.as_slice()
+                .try_into()
+                .map_err(|_| HpkeError::InvalidParameters)?,
+            &mut pt,
+            ct,
+            aad,
+            nonce,
+        )
+        .map(|_| ()),
+        AEAD::AES_256_GCM => libcrux_aesgcm::aes256::decrypt(
+            key.as_slice()
+                .try_into()
+                .map_err(|_| HpkeError::InvalidParameters)?,
+            &mut pt,
+            ct,
+            aad,
+            nonce,
+        )
+        .map(|_| ()),
+        _ => return HpkeBytesResult::Err(HpkeError::UnsupportedAlgorithm),
+    };
+    result.map_err(|_| HpkeError::OpenError)?;
+
+    HpkeBytesResult::Ok(pt)
+}
@@ -354,22 +354,28 @@ pub fn KeySchedule(
 
     let secret = LabeledExtract(kdf, suite_id(config), shared_secret, secret_label(), psk)?;
 
-    let key = LabeledExpand(
-        kdf,
-        suite_id(config),
-        &secret,
-        key_label(),
-        &key_schedule_context,
-        Nk(aead),
-    )?;
-    let base_nonce = LabeledExpand(
-        kdf,
-        suite_id(config),
-        &secret,
-        base_nonce_label(),
-        &key_schedule_context,
-        Nn(aead),
-    )?;
+    // The export-only AEAD has no key and base nonce.
+    let (key, base_nonce) = if aead == AEAD::Export_only {
+        (Vec::new(), Vec::new())
+    } else {
+        let key = LabeledExpand(
+            kdf,
+            suite_id(config),
+            &secret,
+            key_label(),
+            &key_schedule_context,
+            Nk(aead),
+        )?;
+        let base_nonce = LabeledExpand(
+            kdf,
+            suite_id(config),
+            &secret,
+            base_nonce_label(),
+            &key_schedule_context,
+            Nn(aead),
+        )?;
+        (key, base_nonce)
+    };
     let exporter_secret = LabeledExpand(
         kdf,
         suite_id(config),
@@ -409,7 +415,11 @@ pub fn SetupBaseS(
         | KEM::DHKEM_P384_HKDF_SHA384
         | KEM::DHKEM_P521_HKDF_SHA512
         | KEM::DHKEM_X25519_HKDF_SHA256
-        | KEM::DHKEM_X448_HKDF_SHA512 => Encap(kem(config), pkR, randomness)?,
+        | KEM::DHKEM_X448_HKDF_SHA512
+        | KEM::ML_KEM_512
+        | KEM::ML_KEM_768
+        | KEM::ML_KEM_1024
+        | KEM::XWing => Encap(kem(config), pkR, randomness)?,
         KEM::X25519Kyber768Draft00 => {
             // FIXME: clean up
             // Decode the public key
@@ -477,7 +487,11 @@ pub fn SetupBaseR(
         | KEM::DHKEM_P384_HKDF_SHA384
         | KEM::DHKEM_P521_HKDF_SHA512
         | KEM::DHKEM_X25519_HKDF_SHA256
-        | KEM::DHKEM_X448_HKDF_SHA512 => Decap(kem(config), enc, skR)?,
+        | KEM::DHKEM_X448_HKDF_SHA512
+        | KEM::ML_KEM_512
+        | KEM::ML_KEM_768
+        | KEM::ML_KEM_1024
+        | KEM::XWing => Decap(kem(config), enc, skR)?,
         KEM::X25519Kyber768Draft00 => {
             // FIXME: clean up
             // Decode the public key
@@ -796,12 +810,18 @@ pub fn ComputeNonce(aead_id: AEAD, base_nonce: &Nonce, seq: SequenceCounter) ->
 /// which causes [`ContextS_Seal()`] and [`ContextR_Open()`] to fail accordingly.)
 /// Note that the internal `Seal()` and `Open()`
 /// calls inside correspond to the context's [`AEAD`] algorithm.
+///
+/// Contexts for the [`AEAD::Export_only`] AEAD can not be used for encryption
+/// and return an [`UnsupportedAlgorithm`](`HpkeError::UnsupportedAlgorithm`) error.
 pub fn ContextS_Seal(
     aead_id: AEAD,
     context: Context,
     aad: &[u8],
     pt: &[u8],
 ) -> Result<(Ciphertext, Context), HpkeError> {
+    if aead_id == AEAD::Export_only {
+        return Err(HpkeError::UnsupportedAlgorithm);
+    }
     let (key, base_nonce, seq, exp) = context;
     let nonce = ComputeNonce(aead_id, &base_nonce, seq);
     let ct = AeadSeal(aead_id, &key, &nonce, aad, pt)?;
@@ -824,12 +844,18 @@ pub fn ContextS_Seal(
 ///   self.IncrementSeq()
 ///   return pt
 /// ```
+///
+/// Contexts for the [`AEAD::Export_only`] AEAD can not be used for decryption
+/// and return an [`UnsupportedAlgorithm`](`HpkeError::UnsupportedAlgorithm`) error.
 pub fn ContextR_Open(
     aead_id: AEAD,
     context: Context,
     aad: &[u8],
     ct: &[u8],
 ) -> Result<(Vec<u8>, Context), HpkeError> {
+    if aead_id == AEAD::Export_only {
+        return Err(HpkeError::UnsupportedAlgorithm);
+    }
     let (key, base_nonce, seq, exp) = context;
     let nonce = ComputeNonce(aead_id, &base_nonce, seq);
     let pt = AeadOpen(aead_id, &key, &nonce, aad, ct)?;
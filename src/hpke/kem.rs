@@ -9,7 +9,7 @@ use crate::std::{vec, vec::Vec};
 use super::errors::*;
 use super::kdf::*;
 use libcrux_kem::{Algorithm, X25519MlKem768Draft00PrivateKey, X25519MlKem768Draft00PublicKey};
-use libcrux_ml_kem::mlkem768;
+use libcrux_ml_kem::{mlkem1024, mlkem512, mlkem768};
 
 /// ## Key Encapsulation Mechanisms (KEMs)
 ///
@@ -21,13 +21,24 @@ use libcrux_ml_kem::mlkem768;
 /// | 0x0012 | DHKEM(P-521, HKDF-SHA512)  | 64       | 133  | 133  | 66   | yes  | [NISTCurves], [RFC5869] |
 /// | 0x0020 | DHKEM(X25519, HKDF-SHA256) | 32       | 32   | 32   | 32   | yes  | [RFC7748], [RFC5869]    |
 /// | 0x0021 | DHKEM(X448, HKDF-SHA512)   | 64       | 56   | 56   | 56   | yes  | [RFC7748], [RFC5869]    |
-/// | 0x0021 | DHKEM(X448, HKDF-SHA512)   | 64       | 1120 | 1216 | 2432 | no   | [xkyber]                |
+/// | 0x0030 | X25519Kyber768Draft00      | 64       | 1120 | 1216 | 2432 | no   | [xkyber]                |
+/// | 0x0040 | ML-KEM-512                 | 32       | 768  | 800  | 64   | no   | [FIPS203], [hpke-mlkem] |
+/// | 0x0041 | ML-KEM-768                 | 32       | 1088 | 1184 | 64   | no   | [FIPS203], [hpke-mlkem] |
+/// | 0x0042 | ML-KEM-1024                | 32       | 1568 | 1568 | 64   | no   | [FIPS203], [hpke-mlkem] |
+/// | 0x647a | X-Wing                     | 32       | 1120 | 1216 | 32   | no   | [xwing]                 |
 ///
 /// The `Auth` column indicates if the KEM algorithm provides the [`AuthEncap()`]/[`AuthDecap()`]
 /// interface and is therefore suitable for the Auth and AuthPSK modes. The meaning of all
 /// other columns is explained below. All algorithms are suitable for the
 /// PSK mode.
 ///
+/// DHKEM(P-384, HKDF-SHA384), DHKEM(P-521, HKDF-SHA512) and
+/// DHKEM(X448, HKDF-SHA512) are **not supported**, because libcrux has no ECDH
+/// on these curves. [`DeriveKeyPair()`], [`GenerateKeyPair()`] and everything
+/// that encapsulates with them return
+/// [`UnsupportedAlgorithm`](`HpkeError::UnsupportedAlgorithm`), and
+/// decapsulating fails.
+///
 /// ### KEM Identifiers
 ///
 /// The "HPKE KEM Identifiers" registry lists identifiers for key encapsulation
@@ -48,23 +59,34 @@ use libcrux_ml_kem::mlkem768;
 /// [NISTCurves]: https://doi.org/10.6028/nist.fips.186-4
 /// [RFC7748]: https://www.rfc-editor.org/info/rfc7748
 /// [RFC5869]: https://www.rfc-editor.org/info/rfc5869
-/// [xkyer]: https://datatracker.ietf.org/doc/html/draft-westerbaan-cfrg-hpke-xyber768d00-02
+/// [xkyber]: https://datatracker.ietf.org/doc/html/draft-westerbaan-cfrg-hpke-xyber768d00-02
+/// [FIPS203]: https://doi.org/10.6028/NIST.FIPS.203
+/// [hpke-mlkem]: https://datatracker.ietf.org/doc/draft-connolly-cfrg-hpke-mlkem/
+/// [xwing]: https://datatracker.ietf.org/doc/draft-connolly-cfrg-xwing-kem/
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum KEM {
     /// 0x0010
     DHKEM_P256_HKDF_SHA256,
-    /// 0x0011
+    /// 0x0011, not supported
     DHKEM_P384_HKDF_SHA384,
-    /// 0x0012
+    /// 0x0012, not supported
     DHKEM_P521_HKDF_SHA512,
     /// 0x0020
     DHKEM_X25519_HKDF_SHA256,
-    /// 0x0021
+    /// 0x0021, not supported
     DHKEM_X448_HKDF_SHA512,
     /// 0x0030
     X25519Kyber768Draft00,
     /// 0x004D
     XWingDraft06,
+    /// 0x0040
+    ML_KEM_512,
+    /// 0x0041
+    ML_KEM_768,
+    /// 0x0042
+    ML_KEM_1024,
+    /// 0x647a
+    XWing,
 }
 
 /// [`u16`] value of the `kem_id`.
@@ -79,6 +101,10 @@ pub fn kem_value(kem_id: KEM) -> u16 {
         KEM::DHKEM_X448_HKDF_SHA512 => 0x0021,
         KEM::X25519Kyber768Draft00 => 0x0030,
         KEM::XWingDraft06 => 0x004D,
+        KEM::ML_KEM_512 => 0x0040,
+        KEM::ML_KEM_768 => 0x0041,
+        KEM::ML_KEM_1024 => 0x0042,
+        KEM::XWing => 0x647a,
     }
 }
 
@@ -94,6 +120,7 @@ fn kdf_for_kem(kem_id: KEM) -> KDF {
         KEM::DHKEM_X448_HKDF_SHA512 => KDF::HKDF_SHA512,
         KEM::X25519Kyber768Draft00 => KDF::HKDF_SHA256,
         KEM::XWingDraft06 => KDF::HKDF_SHA256,
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => KDF::HKDF_SHA256,
     }
 }
 
@@ -107,6 +134,10 @@ fn kem_to_named_group(alg: KEM) -> Algorithm {
         KEM::DHKEM_X448_HKDF_SHA512 => Algorithm::X448,
         KEM::X25519Kyber768Draft00 => Algorithm::X25519, // This is only used for DH operations
         KEM::XWingDraft06 => Algorithm::X25519,          // This is only used for DH operations
+        KEM::ML_KEM_512 => Algorithm::MlKem512,
+        KEM::ML_KEM_768 => Algorithm::MlKem768,
+        KEM::ML_KEM_1024 => Algorithm::MlKem1024,
+        KEM::XWing => Algorithm::XWingKemDraft06,
     }
 }
 
@@ -122,6 +153,7 @@ pub fn Nsecret(kem_id: KEM) -> usize {
         KEM::DHKEM_X448_HKDF_SHA512 => 64,
         KEM::X25519Kyber768Draft00 => 64,
         KEM::XWingDraft06 => 32,
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => 32,
     }
 }
 
@@ -137,6 +169,10 @@ pub fn Nenc(kem_id: KEM) -> usize {
         KEM::DHKEM_X448_HKDF_SHA512 => 56,
         KEM::X25519Kyber768Draft00 => 1120,
         KEM::XWingDraft06 => 1120,
+        KEM::ML_KEM_512 => 768,
+        KEM::ML_KEM_768 => 1088,
+        KEM::ML_KEM_1024 => 1568,
+        KEM::XWing => 1120,
     }
 }
 
@@ -152,6 +188,8 @@ pub fn Nsk(kem_id: KEM) -> usize {
         KEM::DHKEM_X448_HKDF_SHA512 => 56,
         KEM::X25519Kyber768Draft00 => 2432,
         KEM::XWingDraft06 => 2464,
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 => 64,
+        KEM::XWing => 32,
     }
 }
 
@@ -167,6 +205,10 @@ pub fn Npk(kem_id: KEM) -> usize {
         KEM::DHKEM_X448_HKDF_SHA512 => 56,
         KEM::X25519Kyber768Draft00 => 1216,
         KEM::XWingDraft06 => 1216,
+        KEM::ML_KEM_512 => 800,
+        KEM::ML_KEM_768 => 1184,
+        KEM::ML_KEM_1024 => 1568,
+        KEM::XWing => 1216,
     }
 }
 
@@ -180,6 +222,10 @@ pub fn Npk(kem_id: KEM) -> usize {
 /// | X25519         | 32      |
 /// | X448           | 56      |
 /// | X448Kyber768   | 32      |
+/// | ML-KEM         | 0       |
+/// | X-Wing         | 0       |
+///
+/// ML-KEM and X-Wing are not Diffie-Hellman based and never call [`DH()`].
 pub fn Ndh(kem_id: KEM) -> usize {
     match kem_id {
         KEM::DHKEM_P256_HKDF_SHA256 => 32,
@@ -189,6 +235,7 @@ pub fn Ndh(kem_id: KEM) -> usize {
         KEM::DHKEM_X448_HKDF_SHA512 => 56,
         KEM::X25519Kyber768Draft00 => 32,
         KEM::XWingDraft06 => 32,
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => 0,
     }
 }
 
@@ -269,6 +316,9 @@ fn shared_secret_from_dh(alg: KEM, mut secret: Vec<u8>) -> Result<SharedSecret,
             // This is only the x25519 part.
             Ok(secret)
         }
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => {
+            Err(HpkeError::UnsupportedAlgorithm)
+        }
     }
 }
 
@@ -321,6 +371,7 @@ pub fn SerializePublicKey(alg: KEM, pk: PublicKey) -> PublicKey {
         KEM::DHKEM_X448_HKDF_SHA512 => pk,
         KEM::X25519Kyber768Draft00 => pk, // This must have been encoded before
         KEM::XWingDraft06 => pk,          // This must have been encoded before
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => pk,
     }
 }
 
@@ -345,6 +396,7 @@ pub fn DeserializePublicKey(alg: KEM, enc: &[u8]) -> HpkeBytesResult {
         KEM::DHKEM_X448_HKDF_SHA512 => enc.to_vec(),
         KEM::X25519Kyber768Draft00 => enc.to_vec(), // Deserialization must be done later
         KEM::XWingDraft06 => enc.to_vec(),          // Deserialization must be done later
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => enc.to_vec(),
     })
 }
 
@@ -456,14 +508,34 @@ pub fn DeriveKeyPairX(alg: KEM, ikm: &InputKeyMaterial) -> Result<KeyPair, HpkeE
 ///   return (sk, pk(sk))
 /// ```
 ///
+/// For ML-KEM, the private key is the 64 byte seed `d || z` of `ML-KEM.KeyGen_internal`,
+/// which is expanded from the input key material with SHAKE256:
+///
+/// ```text
+/// def DeriveKeyPair(ikm):
+///   seed = SHAKE256(ikm, 64)
+///   (ek, dk) = ML-KEM.KeyGen_internal(seed[0:32], seed[32:64])
+///   return (seed, ek)
+/// ```
+///
+/// For X-Wing, the private key is the 32 byte X-Wing seed:
+///
+/// ```text
+/// def DeriveKeyPair(ikm):
+///   sk = SHAKE256(ikm, 32)
+///   return (sk, X-Wing.GenerateKeyPairDerand(sk).pk)
+/// ```
+///
+/// DHKEM(P-384), DHKEM(P-521) and DHKEM(X448) are not implemented and return
+/// an [`UnsupportedAlgorithm`](`HpkeError::UnsupportedAlgorithm`) error.
+///
 /// [NISTCurves]: https://doi.org/10.6028/nist.fips.186-4
 pub fn DeriveKeyPair(alg: KEM, ikm: &InputKeyMaterial) -> Result<KeyPair, HpkeError> {
     match alg {
-        KEM::DHKEM_P256_HKDF_SHA256
-        | KEM::DHKEM_P384_HKDF_SHA384
-        | KEM::DHKEM_P521_HKDF_SHA512
-        | KEM::DHKEM_X25519_HKDF_SHA256
-        | KEM::DHKEM_X448_HKDF_SHA512 => {
+        KEM::DHKEM_P384_HKDF_SHA384 | KEM::DHKEM_P521_HKDF_SHA512 | KEM::DHKEM_X448_HKDF_SHA512 => {
+            Err(HpkeError::UnsupportedAlgorithm)
+        }
+        KEM::DHKEM_P256_HKDF_SHA256 | KEM::DHKEM_X25519_HKDF_SHA256 => {
             let kdf = kdf_for_kem(alg);
             let dkp_prk = LabeledExtract(kdf, suite_id(alg), &empty(), dkp_prk_label(), ikm)?;
 
@@ -513,6 +585,23 @@ pub fn DeriveKeyPair(alg: KEM, ikm: &InputKeyMaterial) -> Result<KeyPair, HpkeEr
 
             Ok((sk.encode(), pk.encode()))
         }
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 => {
+            let seed: [u8; 64] = crate::digest::shake256(ikm);
+            let pk = match alg {
+                KEM::ML_KEM_512 => mlkem512::generate_key_pair(seed).pk().to_vec(),
+                KEM::ML_KEM_768 => mlkem768::generate_key_pair(seed).pk().to_vec(),
+                _ => mlkem1024::generate_key_pair(seed).pk().to_vec(),
+            };
+
+            Ok((seed.to_vec(), pk))
+        }
+        KEM::XWing => {
+            let seed: [u8; 32] = crate::digest::shake256(ikm);
+            let (sk, pk) = libcrux_kem::key_gen_derand(Algorithm::XWingKemDraft06, &seed)
+                .map_err(|_| HpkeError::DeriveKeyPairError)?;
+
+            Ok((sk.encode(), pk.encode()))
+        }
     }
 }
 
@@ -564,6 +653,9 @@ pub fn GenerateKeyPair(alg: KEM, randomness: Randomness) -> Result<KeyPair, Hpke
                 Ok((private.encode(), public.encode()))
             }
             KEM::XWingDraft06 => DeriveKeyPair(alg, &randomness),
+            KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing => {
+                DeriveKeyPair(alg, &randomness)
+            }
         }
     }
 }
@@ -580,6 +672,10 @@ pub fn GenerateKeyPair(alg: KEM, randomness: Randomness) -> Result<KeyPair, Hpke
 ///   shared_secret = ExtractAndExpand(dh, kem_context)
 /// ```
 pub fn Encap(alg: KEM, pkR: &PublicKeyIn, randomness: Randomness) -> EncapResult {
+    if is_post_quantum(alg) {
+        return PqEncap(alg, pkR, randomness);
+    }
+
     let (skE, pkE) = GenerateKeyPair(alg, randomness)?;
     let dh = DH(alg, &skE, pkR)?;
     let enc = SerializePublicKey(alg, pkE);
@@ -592,6 +688,54 @@ pub fn Encap(alg: KEM, pkR: &PublicKeyIn, randomness: Randomness) -> EncapResult
     EncapResult::Ok((shared_secret, enc))
 }
 
+/// Returns `true` for the ML-KEM and X-Wing KEMs, which are not built from a
+/// Diffie-Hellman group and therefore use [`PqEncap()`] and [`PqDecap()`].
+fn is_post_quantum(alg: KEM) -> bool {
+    matches!(
+        alg,
+        KEM::ML_KEM_512 | KEM::ML_KEM_768 | KEM::ML_KEM_1024 | KEM::XWing
+    )
+}
+
+/// ML-KEM and X-Wing Encap
+///
+/// The `randomness` is the 32 byte message `m` of `ML-KEM.Encaps_internal` for
+/// ML-KEM, and the 64 byte `eseed` for X-Wing.
+///
+/// ```text
+/// def Encap(pkR):
+///   (shared_secret, enc) = KEM.Encaps(pkR)
+///   return shared_secret, enc
+/// ```
+pub fn PqEncap(alg: KEM, pkR: &PublicKeyIn, randomness: Randomness) -> EncapResult {
+    let pk = libcrux_kem::PublicKey::decode(kem_to_named_group(alg), pkR)
+        .map_err(|_| HpkeError::DeserializeError)?;
+    let (ss, ct) = pk
+        .encapsulate_derand(&randomness)
+        .map_err(|_| HpkeError::EncapError)?;
+
+    EncapResult::Ok((ss.encode(), ct.encode()))
+}
+
+/// ML-KEM and X-Wing Decap
+///
+/// The private key `skR` is the seed produced by [`DeriveKeyPair()`]. It is
+/// expanded to the full decapsulation key before decapsulating `enc`.
+///
+/// ```text
+/// def Decap(enc, skR):
+///   return KEM.Decaps(skR, enc)
+/// ```
+pub fn PqDecap(alg: KEM, enc: &[u8], skR: &PrivateKeyIn) -> Result<SharedSecret, HpkeError> {
+    let named_group = kem_to_named_group(alg);
+    let (sk, _) =
+        libcrux_kem::key_gen_derand(named_group, skR).map_err(|_| HpkeError::DeserializeError)?;
+    let ct = libcrux_kem::Ct::decode(named_group, enc).map_err(|_| HpkeError::DecapError)?;
+    let ss = ct.decapsulate(&sk).map_err(|_| HpkeError::DecapError)?;
+
+    Ok(ss.encode())
+}
+
 /// Kyber Encap
 ///
 /// FIXME: vec conversions and unwraps
@@ -620,6 +764,10 @@ pub fn Kyber768Draft00_Decap(skR: &PrivateKeyIn, enc: &[u8]) -> Result<SharedSec
 ///   return shared_secret
 /// ```
 pub fn Decap(alg: KEM, enc: &[u8], skR: &PrivateKeyIn) -> Result<SharedSecret, HpkeError> {
+    if is_post_quantum(alg) {
+        return PqDecap(alg, enc, skR);
+    }
+
     let pkE = DeserializePublicKey(alg, enc)?;
     let dh = DH(alg, skR, &pkE)?;
 
@@ -650,6 +798,10 @@ pub fn AuthEncap(
     skS: &PrivateKeyIn,
     randomness: Randomness,
 ) -> EncapResult {
+    if is_post_quantum(alg) {
+        return EncapResult::Err(HpkeError::UnsupportedAlgorithm);
+    }
+
     let (skE, pkE) = GenerateKeyPair(alg, randomness)?;
     let dhE = DH(alg, &skE, pkR)?;
     let dhS = DH(alg, skS, pkR)?;
@@ -686,6 +838,10 @@ pub fn AuthDecap(
     skR: &PrivateKeyIn,
     pkS: &PublicKeyIn,
 ) -> Result<SharedSecret, HpkeError> {
+    if is_post_quantum(alg) {
+        return Err(HpkeError::UnsupportedAlgorithm);
+    }
+
     let pkE = DeserializePublicKey(alg, enc)?;
     let dhE = DH(alg, skR, &pkE)?;
     let dhS = DH(alg, skR, pkS)?;
@@ -31,3 +31,5 @@ pub use libcrux_kem::Ss;
 pub use libcrux_kem::secret_to_public;
 
 pub use libcrux_kem::key_gen;
+
+pub use libcrux_kem::key_gen_derand;
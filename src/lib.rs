@@ -28,11 +28,13 @@ pub(crate) mod hacl;
 
 // libcrux
 pub mod aead;
+pub mod aes_kw;
 // The BLS code requires a 64 bit system.
 #[cfg(all(not(target_arch = "wasm32"), not(target_arch = "x86")))]
 // Generated code: don't let clippy touch it.
 #[allow(clippy::all)]
 pub mod bls12;
+pub mod cmac;
 pub mod digest;
 // XXX: Looks like the bindings are broken for drbg for some reason.
 #[cfg(not(target_arch = "wasm32"))]
@@ -0,0 +1,97 @@
+use libcrux::{
+    aead::{Aes128Key, Aes256Key, AesKey},
+    aes_kw::{self, Error},
+};
+use wycheproof::keywrap::{TestName, TestSet};
+
+fn run_wycheproof(test_name: TestName, padding: bool) {
+    let test_set = TestSet::load(test_name).expect("error loading wycheproof test");
+
+    let mut skipped_tests = 0;
+    let mut tests_run = 0;
+    for test_group in test_set.test_groups {
+        if test_group.key_size != 128 && test_group.key_size != 256 {
+            // Only AES-128 and AES-256 are supported.
+            skipped_tests += test_group.tests.len();
+            continue;
+        }
+
+        for test in test_group.tests {
+            println!("Test {}: {}", test.tc_id, test.comment);
+            let (wrapped, unwrapped) = match test_group.key_size {
+                128 => run(&Aes128Key(test.key[..].try_into().unwrap()), &test, padding),
+                _ => run(&Aes256Key(test.key[..].try_into().unwrap()), &test, padding),
+            };
+
+            match test.result {
+                wycheproof::TestResult::Valid => {
+                    assert_eq!(wrapped.unwrap(), &test.ct[..]);
+                    assert_eq!(unwrapped.unwrap(), &test.pt[..]);
+                }
+                // Wrapping 8 byte keys with AES-KW is not supported.
+                wycheproof::TestResult::Acceptable | wycheproof::TestResult::Invalid => {
+                    assert!(wrapped.map_or(true, |ct| ct != &test.ct[..]));
+                    assert!(unwrapped.is_err());
+                }
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    assert_eq!(test_set.number_of_tests - skipped_tests, tests_run);
+}
+
+type Results = (Result<Vec<u8>, Error>, Result<Vec<u8>, Error>);
+
+fn run(kek: &impl AesKey, test: &wycheproof::keywrap::Test, padding: bool) -> Results {
+    if padding {
+        (
+            aes_kw::wrap_with_padding(kek, &test.pt),
+            aes_kw::unwrap_with_padding(kek, &test.ct),
+        )
+    } else {
+        (aes_kw::wrap(kek, &test.pt), aes_kw::unwrap(kek, &test.ct))
+    }
+}
+
+#[test]
+fn wycheproof_kw() {
+    run_wycheproof(TestName::AesKeyWrap, false);
+}
+
+#[test]
+fn wycheproof_kwp() {
+    run_wycheproof(TestName::AesKeyWrapWithPadding, true);
+}
+
+#[test]
+fn kwp_round_trip() {
+    let kek = Aes128Key(
+        hex::decode("5840df6e29b02af1ab493b705bf16ea1")
+            .unwrap()
+            .try_into()
+            .unwrap(),
+    );
+
+    for key_data in [&[0x42u8; 1][..], &[0x42; 8], &[0x42; 9], &[0x42; 20]] {
+        let wrapped = aes_kw::wrap_with_padding(&kek, key_data).unwrap();
+        assert_eq!(wrapped.len(), key_data.len().next_multiple_of(8) + 8);
+        assert_eq!(
+            aes_kw::unwrap_with_padding(&kek, &wrapped).unwrap(),
+            key_data
+        );
+
+        let mut modified = wrapped.clone();
+        modified[0] ^= 1;
+        assert_eq!(
+            aes_kw::unwrap_with_padding(&kek, &modified),
+            Err(Error::InvalidCiphertext)
+        );
+    }
+
+    assert_eq!(
+        aes_kw::wrap_with_padding(&kek, &[]),
+        Err(Error::InvalidInputLength)
+    );
+}
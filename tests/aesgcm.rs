@@ -7,18 +7,12 @@ use libcrux::{
     aead::{
         decrypt, encrypt, Aes128Key, Aes256Key,
         Algorithm::{Aes128Gcm, Aes256Gcm},
-        EncryptError, InvalidArgumentError, Iv, Key,
+        Iv, Key,
     },
-    aes_ni_support,
 };
 
 #[test]
 fn aesgcm_self_test() {
-    if !aes_ni_support() {
-        eprintln!("AES not supported on this architecture.");
-        return;
-    }
-
     let _ = pretty_env_logger::try_init();
 
     let orig_msg = b"hacspec rulez";
@@ -31,20 +25,7 @@ fn aesgcm_self_test() {
     let key = Key::Aes256(raw_key);
     let iv = Iv([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
 
-    let tag = match encrypt(&key, &mut msg, iv, aad) {
-        Ok(t) => t,
-        Err(e) => {
-            if matches!(
-                e,
-                EncryptError::InvalidArgument(InvalidArgumentError::UnsupportedAlgorithm)
-            ) {
-                eprintln!("AES not supported on this architecture.");
-                return;
-            } else {
-                panic!("{:?}", e);
-            }
-        }
-    };
+    let tag = encrypt(&key, &mut msg, iv, aad).unwrap();
 
     let iv = Iv([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
     assert!(decrypt(&key, &mut msg, iv, aad, &tag).is_ok());
@@ -63,11 +44,6 @@ fn aesgcm_self_test() {
 
 #[test]
 fn aesgcm_self_test_rand() {
-    if !aes_ni_support() {
-        eprintln!("AES not supported on this architecture.");
-        return;
-    }
-
     let _ = pretty_env_logger::try_init();
 
     let orig_msg = b"hacspec rulez";
@@ -83,20 +59,7 @@ fn aesgcm_self_test_rand() {
     let iv = Iv::generate(&mut rng);
     let iv2 = Iv(iv.0);
 
-    let tag = match encrypt(&key, &mut msg, iv, aad) {
-        Ok(t) => t,
-        Err(e) => {
-            if matches!(
-                e,
-                EncryptError::InvalidArgument(InvalidArgumentError::UnsupportedAlgorithm)
-            ) {
-                eprintln!("AES not supported on this architecture.");
-                return;
-            } else {
-                panic!("{:?}", e);
-            }
-        }
-    };
+    let tag = encrypt(&key, &mut msg, iv, aad).unwrap();
     assert!(decrypt(&key, &mut msg, iv2, aad, &tag).is_ok());
 
     assert_eq!(orig_msg, &msg);
@@ -0,0 +1,138 @@
+use libcrux::aead::{
+    decrypt, encrypt, Algorithm,
+    Algorithm::{Aes128GcmSiv, Aes256GcmSiv},
+    DecryptError, Iv, Key, Tag,
+};
+
+#[test]
+fn aesgcm_siv_self_test() {
+    let orig_msg = b"hacspec rulez";
+    let aad = b"associated data" as &[u8];
+
+    for alg in [Aes128GcmSiv, Aes256GcmSiv] {
+        let raw_key: Vec<u8> = (1..=alg.key_size() as u8).collect();
+        let key = Key::from_slice(alg, &raw_key).unwrap();
+        let mut msg = *orig_msg;
+
+        let tag = encrypt(&key, &mut msg, Iv([1; 12]), aad).unwrap();
+        assert_ne!(orig_msg, &msg);
+
+        // Encryption is deterministic.
+        let mut msg2 = *orig_msg;
+        let tag2 = encrypt(&key, &mut msg2, Iv([1; 12]), aad).unwrap();
+        assert_eq!(tag, tag2);
+        assert_eq!(msg, msg2);
+
+        assert!(decrypt(&key, &mut msg, Iv([1; 12]), aad, &tag).is_ok());
+        assert_eq!(orig_msg, &msg);
+
+        let mut modified_tag = Tag::from_slice(tag.as_ref()).unwrap();
+        modified_tag.as_mut()[0] ^= 1;
+        assert_eq!(
+            decrypt(&key, &mut msg2, Iv([1; 12]), aad, &modified_tag),
+            Err(DecryptError::DecryptionFailed)
+        );
+    }
+}
+
+/// Known answer tests from RFC 8452, Appendix C.1 and C.2.
+#[test]
+fn rfc8452() {
+    const TESTS: &[(Algorithm, &str, &str, &str, &str, &str)] = &[
+        (
+            Aes128GcmSiv,
+            "01000000000000000000000000000000",
+            "030000000000000000000000",
+            "01",
+            "0200000000000000",
+            "1e6daba35669f4273b0a1a2560969cdf790d99759abd1508",
+        ),
+        (
+            Aes256GcmSiv,
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            "030000000000000000000000",
+            "01",
+            "0200000000000000",
+            "1de22967237a813291213f267e3b452f02d01ae33e4ec854",
+        ),
+    ];
+
+    for (alg, key, nonce, aad, msg, expected) in TESTS {
+        let key = Key::from_slice(*alg, hex::decode(key).unwrap()).unwrap();
+        let aad = hex::decode(aad).unwrap();
+        let mut msg = hex::decode(msg).unwrap();
+        let expected = hex::decode(expected).unwrap();
+
+        let tag = encrypt(
+            &key,
+            &mut msg,
+            Iv::new(hex::decode(nonce).unwrap()).unwrap(),
+            &aad,
+        )
+        .unwrap();
+        assert_eq!(msg, expected[..msg.len()]);
+        assert_eq!(tag.as_ref(), &expected[msg.len()..]);
+    }
+}
+
+#[test]
+fn wycheproof() {
+    let test_set = wycheproof::aead::TestSet::load(wycheproof::aead::TestName::AesGcmSiv)
+        .expect("error loading wycheproof aes_gcm_siv tests");
+
+    let mut tests_run = 0;
+    for test_group in test_set.test_groups {
+        let alg = match test_group.key_size {
+            128 => Aes128GcmSiv,
+            256 => Aes256GcmSiv,
+            _ => unreachable!(),
+        };
+
+        for test in test_group.tests {
+            println!("Test {}: {}", test.tc_id, test.comment);
+            let key = Key::from_slice(alg, &test.key).unwrap();
+            let valid = test.result == wycheproof::TestResult::Valid;
+
+            let mut msg = test.pt.to_vec();
+            let tag = encrypt(&key, &mut msg, Iv::new(&test.nonce).unwrap(), &test.aad).unwrap();
+            if valid {
+                assert_eq!(tag.as_ref(), &test.tag[..]);
+                assert_eq!(msg, &test.ct[..]);
+            }
+
+            let mut ctxt = test.ct.to_vec();
+            let result = decrypt(
+                &key,
+                &mut ctxt,
+                Iv::new(&test.nonce).unwrap(),
+                &test.aad,
+                &Tag::from_slice(&test.tag).unwrap(),
+            );
+            if valid {
+                result.unwrap();
+                assert_eq!(ctxt, &test.pt[..]);
+            } else {
+                assert_eq!(result, Err(DecryptError::DecryptionFailed));
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    assert_eq!(test_set.number_of_tests, tests_run);
+}
+
+#[test]
+fn algorithm_ids() {
+    for alg in [
+        Algorithm::Aes128Gcm,
+        Algorithm::Aes256Gcm,
+        Algorithm::Chacha20Poly1305,
+        Aes128GcmSiv,
+        Aes256GcmSiv,
+    ] {
+        let id = u8::from(alg);
+        assert_eq!(id as u32, alg as u32);
+        assert_eq!(Algorithm::from(id), alg);
+    }
+}
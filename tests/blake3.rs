@@ -0,0 +1,23 @@
+use libcrux::digest::{self, Algorithm};
+use libcrux_traits::Digest as _;
+
+#[test]
+fn blake3_kat() {
+    assert_eq!(digest::digest_size(Algorithm::Blake3), 32);
+    assert_eq!(Algorithm::from(12u32), Algorithm::Blake3);
+
+    let d = digest::hash(Algorithm::Blake3, b"");
+    assert_eq!(
+        hex::encode(d),
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+
+    let payload: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+    let mut digest = digest::Blake3::new();
+    let mut d: digest::Blake3Digest = [0u8; 32];
+    digest.update(&payload[..1234]);
+    digest.update(&payload[1234..]);
+    digest.finish(&mut d);
+    assert_eq!(d, digest::blake3(&payload));
+    assert_eq!(digest::hash(Algorithm::Blake3, &payload), d);
+}
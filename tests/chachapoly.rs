@@ -12,9 +12,8 @@ use libcrux::{
     aead::{
         self, decrypt, encrypt,
         Algorithm::{self, Chacha20Poly1305},
-        Chacha20Key, EncryptError, InvalidArgumentError, Iv, Key, Tag,
+        Chacha20Key, Iv, Key, Tag,
     },
-    aes_ni_support,
 };
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -138,13 +137,6 @@ fn wycheproof() {
                 }
                 _ => panic!("Unknown algorithm {:?}", test_vec.algorithm),
             };
-            if !aes_ni_support()
-                && (algorithm == Algorithm::Aes128Gcm || algorithm == Algorithm::Aes256Gcm)
-            {
-                println!("⚠️  AES NOT AVAILABLE ON THIS PLATFORM!");
-                *skipped_tests += testGroup.tests.len();
-                continue;
-            }
             let invalid_iv = testGroup.ivSize != 96;
 
             for test in testGroup.tests.iter() {
@@ -164,32 +156,9 @@ fn wycheproof() {
                 let exp_tag: Tag = hex_str_to_array(&test.tag);
                 let key = hex_str_to_bytes(&test.key);
 
-                let aead_key = match Key::from_bytes(algorithm, key) {
-                    Ok(c) => c,
-                    Err(_) => {
-                        println!("⚠️  Skipping {:?} because it's not available.", algorithm);
-                        *skipped_tests += 1;
-                        continue;
-                    }
-                };
+                let aead_key = Key::from_bytes(algorithm, key).unwrap();
                 let mut msg_ctxt = msg.clone();
-                let tag = match aead::encrypt(&aead_key, &mut msg_ctxt, Iv(nonce), &aad) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        if matches!(
-                            e,
-                            EncryptError::InvalidArgument(
-                                InvalidArgumentError::UnsupportedAlgorithm
-                            )
-                        ) {
-                            eprintln!("AES not supported on this architecture.");
-                            *skipped_tests += 1;
-                            continue;
-                        } else {
-                            panic!("Encrypt failed unexpectedly {:?}", e);
-                        }
-                    }
-                };
+                let tag = aead::encrypt(&aead_key, &mut msg_ctxt, Iv(nonce), &aad).unwrap();
                 if valid {
                     assert_eq!(tag, exp_tag);
                 } else {
@@ -0,0 +1,57 @@
+use libcrux::{
+    aead::{Aes128Key, Aes256Key},
+    cmac::{self, Error, TAG_LEN},
+};
+use wycheproof::mac::{TestName, TestSet};
+
+#[test]
+fn wycheproof() {
+    let test_set = TestSet::load(TestName::AesCmac).expect("error loading wycheproof test");
+
+    let mut skipped_tests = 0;
+    let mut tests_run = 0;
+    for test_group in test_set.test_groups {
+        assert_eq!(test_group.tag_size, 128);
+        if test_group.key_size != 128 && test_group.key_size != 256 {
+            // Only AES-128 and AES-256 are supported.
+            skipped_tests += test_group.tests.len();
+            continue;
+        }
+
+        for test in test_group.tests {
+            println!("Test {}: {}", test.tc_id, test.comment);
+            let tag: &[u8; TAG_LEN] = test.tag[..].try_into().unwrap();
+            let (computed, verified) = match test_group.key_size {
+                128 => {
+                    let key = Aes128Key(test.key[..].try_into().unwrap());
+                    (
+                        cmac::cmac(&key, &test.msg),
+                        cmac::verify(&key, &test.msg, tag),
+                    )
+                }
+                _ => {
+                    let key = Aes256Key(test.key[..].try_into().unwrap());
+                    (
+                        cmac::cmac(&key, &test.msg),
+                        cmac::verify(&key, &test.msg, tag),
+                    )
+                }
+            };
+
+            match test.result {
+                wycheproof::TestResult::Valid => {
+                    assert_eq!(&computed, tag);
+                    verified.unwrap();
+                }
+                _ => {
+                    assert_ne!(&computed, tag);
+                    assert_eq!(verified, Err(Error::InvalidTag));
+                }
+            }
+
+            tests_run += 1;
+        }
+    }
+
+    assert_eq!(test_set.number_of_tests - skipped_tests, tests_run);
+}
@@ -33,3 +33,163 @@ fn run_wycheproof() {
         }
     }
 }
+
+/// The `ikm`, `salt`, `info` and output length of a test case.
+type Inputs = (Vec<u8>, Vec<u8>, Vec<u8>, usize);
+
+/// The inputs of the SHA-256 test cases 1, 2 and 3 of RFC 5869.
+fn rfc5869_inputs() -> [Inputs; 3] {
+    [
+        (
+            vec![0x0b; 22],
+            (0x00..=0x0c).collect(),
+            (0xf0..=0xf9).collect(),
+            42,
+        ),
+        (
+            (0x00..=0x4f).collect(),
+            (0x60..=0xaf).collect(),
+            (0xb0..=0xff).collect(),
+            82,
+        ),
+        (vec![0x0b; 22], vec![], vec![], 42),
+    ]
+}
+
+/// HKDF with SHA-3 and BLAKE2 on the inputs of RFC 5869.
+///
+/// There are no official test vectors for these modes. The expected values
+/// were computed with Python's `hmac` and `hashlib` modules.
+#[test]
+fn sha3_blake2() {
+    let expected: [(Algorithm, [(&str, &str); 3]); 6] = [
+        (
+            Algorithm::Sha3_224,
+            [
+                ("af44657dfc9946f90d9ff007d083fb106c289171021aad2be48801fb", "5058867fc7bdb118ce6a703add6edbf8e2ce21f5766cfc2e662e1a36ff6922fa96fc149517cf1e451fe6"),
+                ("c8e3e6f40e881054e0bc79a5f5425e07cf16014a22639f1f3ec15fa1", "62ef98372be6fa88db279467801bd5b2b36606a5117d18c82a2b115a6b92817a10a8a486e3dc0a100d6579f513703a779e2514b2729182b78645a9e469e3d63dbff6ccb279186c33308a04432b15d0c4ad2f"),
+                ("8a0fecdd370347d42bd4361026a737b5092be4e71b504cae330ab155", "6b761c8491972d1a7f85178a5a833ceb90bf501e3ff0d9c94ac8848847271571475f53b85da693a5f4aa"),
+            ],
+        ),
+        (
+            Algorithm::Sha3_256,
+            [
+                ("7d4194836f7a113a44677abc825640ade07af1c1d69a9a4b109b280a8fe54ef0", "0c5160501d65021deaf2c14f5abce04c5bd2635abceeba61c2edb6e8ed72674900557728f2c9f2c4c179"),
+                ("addf31835b49366ac27734104d9f1865c1c2e7c8a2ebc1fed712808e4eab677c", "3dc251e66c75da6560405ec5ac10e17d851eedfbfdc13feafbec16964c25d021bd971465a3e9c615f27769019e3f0407d84986fb0ba24e729c99834624baa21cb623dc0098f430d52e18bbdf694df4edd8b2"),
+                ("b899e6e4b88a35f9f5d618f48b424c313f9704012763eb6295414d673365928a", "bc1342cdd75c05e8b0c3ae609ce4410684d197232875073499b30cdfe2de2853c1c1bed63d725e885e78"),
+            ],
+        ),
+        (
+            Algorithm::Sha3_384,
+            [
+                ("7855bc9300a4db532c9cab2593796e1a4bbb77a24d417e66822beaa36fabd412515dcf388810adf27fa23d3d7def84ca", "138d8521e5a346a9cb770f762b9c04d9ca317409fb6a3ef9cb905228385589ae883bbe8b07b009f0e08b"),
+                ("8f45c2056d61f9b7f7dbb828c950b3b2a35f119f3b7732e02b1cdcc5ab4d57b803533bda6b890e2072f20765d6de0ac9", "db2ad19e69d1e3318a7e2d0d3ef63d5e637757e1b160c622413c72db61df1e067c785cd25b9621e69922fbed6e8ddc6a12c72555ce0dbb9dac3b2d4dfa01bfaf230f0f527f3f088361e58bd3515b6159ea44"),
+                ("973d6a2e551b6531e6e65be94e1999da8c89f2561e57ef52b16c69eb961aa67411cfb559dad173f072cbd465032b1732", "9d1cb657955fb4f2ddf1a416ba946427495d1fa052d279d02628faf40854707916e255415c91ebdc4a1b"),
+            ],
+        ),
+        (
+            Algorithm::Sha3_512,
+            [
+                ("e1c543094f64f3d6c6658a94a94e3818ba13d0b3e77074b80f88f32e6b8433b703536cb500753967fae2ea977e11e4dd4f45389807cdf255b395e46807c87d5d", "40e9f17e9bf2ef99425c2b23ccdf20a018ea5513f9ae68e1ea8c626deb57dfa4d56c27ccf2a2a24488a5"),
+                ("bc138b5ec5f398198e333105a8ed3c2e775016e53c8de21aaddc2d776964e14e9e1fd19bf5678aa97c2a57427d1eeac6e8ca44ddbae018a47dc18fe8201efdc6", "3adf31011245f82cc6b5c3b2ea31fe2a9b855b425c3ecdd8da4a3fc5d0c3563f63bbdedf7ca912d2e98cbc853d978066ab177f19a7349e3982549b82a307e2113891691f2536ce45eb5ddf9b5175859ce8d5"),
+                ("37a48c72dce8c34bf1a08356c929133ea60a20c6c2eb3ce26d2c3ce6b0e2385572e82fc77418ace2f6df0419eacafc847fdf283b0324163d7d88265a8e7e4992", "38bd71e45b397b775b563365a33258a6fd83abc1e86acf042f0723c2b68ebf073a75c34c69328835ee4c"),
+            ],
+        ),
+        (
+            Algorithm::Blake2s,
+            [
+                ("57e878130679f9ea85900980b52df2643d043b82f290eb7dd62175dbb04cca4e", "1472c31f2ff768c71b19f8803683ee3b13c1a5fb3ea59c0c3bf0d44a4a40dcd4329d9cd85bbe35a1b3e7"),
+                ("67ba3b32417ecf634cce8b62ef78dd621c3d5c6f40615b7fa6759a5c54c13745", "9a4d17a2953c7331fecfb23ff13578c4758b4f5ebaac2dae59dd0d2c8457e2f0075434cf17d98cadf4fcfdcd6d818a3f4f99679d3265bd9da9955b901661a974b146aa7e89fbdcc114e6de28b28b38b87c51"),
+                ("ca62915d4a8508e2c993341d6cd4221d9152f2582c263e0335c6cfab4ebf1937", "064c0f0b9d9148a2e5ac797e5ef23d1b39b422f1ec37b57b45065ff2b607527143b9b9f8ba59db392663"),
+            ],
+        ),
+        (
+            Algorithm::Blake2b,
+            [
+                ("02fbaa4ced1e659fe2eb8ae358de5be0edc0fd4526dbc7cc68d2ab9273e1b230ab9d6860f65dc7bad92a483c0f90e019ace68b5e4fe65251666eb1e71e57a812", "8815e1a85b5e90e6174323fdd180248887a7138af6dc5c8320fde21a60a078808267d6a41b6a938d7b30"),
+                ("9eddca55c30bd643eb09b91a6a7b2763096333338d4fd2145899d746b415c07422c9ff0aecdefa0fb1cae869492a499ad01f9e0a1a29b13067a97805455ad54f", "bb19eccde3ff3f41a8b3a147eb4fa640599a4e2194aadd98c0458f7ec05ac995238f9b6002d5bcbfa7c67975965de73ff014e55acff823162d6d5c04498501b1acb5e99058466d1a440ffc544f408e89f555"),
+                ("39db468c9289015fd163c86a299ef5a95942835aed7103a2b9f30cc796e2d0586fc284a024c25fb18699c78e47273346fa35f076ac315e5906fe170ba1877c63", "817520332f597bd8f557a4b40fddfe7674f1edac6c8a1a36fa0546b649bfae4a2ed3f34d03fdef572d51"),
+            ],
+        ),
+    ];
+
+    for (alg, cases) in expected {
+        for ((ikm, salt, info, len), (prk, okm)) in rfc5869_inputs().into_iter().zip(cases) {
+            let computed_prk = libcrux_hkdf::extract(alg, &salt, &ikm).unwrap();
+            assert_eq!(hex::encode(&computed_prk), prk);
+            assert_eq!(
+                hex::encode(libcrux_hkdf::expand(alg, &computed_prk, &info, len).unwrap()),
+                okm
+            );
+            assert_eq!(
+                hex::encode(libcrux_hkdf::hkdf(alg, &salt, &ikm, &info, len).unwrap()),
+                okm
+            );
+        }
+
+        let too_long = 255 * alg.hash_len() + 1;
+        assert_eq!(
+            libcrux_hkdf::hkdf(alg, b"", b"ikm", b"", too_long),
+            Err(libcrux_hkdf::Error::OkmTooLarge)
+        );
+    }
+}
+
+#[test]
+fn hkdf_mode_trait() {
+    use libcrux_hkdf::{HkdfBlake2b, HkdfMode, HkdfSha3_256};
+
+    let mut okm = [0u8; 42];
+    HkdfSha3_256::hkdf(
+        &mut okm,
+        &(0x00..=0x0c).collect::<Vec<u8>>(),
+        &[0x0b; 22],
+        &(0xf0..=0xf9).collect::<Vec<u8>>(),
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(okm),
+        "0c5160501d65021deaf2c14f5abce04c5bd2635abceeba61c2edb6e8ed72674900557728f2c9f2c4c179"
+    );
+    assert_eq!(HkdfSha3_256::MODE, Algorithm::Sha3_256);
+
+    let mut okm = [0u8; 255 * 64 + 1];
+    assert_eq!(
+        HkdfBlake2b::expand(&mut okm, &[0u8; 64], b""),
+        Err(libcrux_hkdf::Error::OkmTooLarge)
+    );
+}
+
+#[test]
+fn kdf_trait() {
+    use libcrux_hkdf::{HkdfSha2_256, HkdfSha3_256};
+    use libcrux_traits::kdf::{Kdf, KdfError};
+
+    let (ikm, salt, info, len) = rfc5869_inputs()[0].clone();
+
+    // RFC 5869, test case 1.
+    let prk = <HkdfSha2_256 as Kdf>::extract(&salt, &ikm).unwrap();
+    assert_eq!(
+        hex::encode(prk),
+        "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"
+    );
+    let mut okm = vec![0u8; len];
+    <HkdfSha2_256 as Kdf>::derive(&mut okm, &salt, &ikm, &info).unwrap();
+    assert_eq!(
+        hex::encode(&okm),
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+    );
+
+    let mut okm = vec![0u8; len];
+    <HkdfSha3_256 as Kdf>::derive(&mut okm, &salt, &ikm, &info).unwrap();
+    assert_eq!(
+        hex::encode(&okm),
+        "0c5160501d65021deaf2c14f5abce04c5bd2635abceeba61c2edb6e8ed72674900557728f2c9f2c4c179"
+    );
+
+    let mut okm = [0u8; 255 * 32 + 1];
+    assert_eq!(
+        <HkdfSha2_256 as Kdf>::expand(&mut okm, &[0u8; 32], b""),
+        Err(KdfError::OkmTooLarge)
+    );
+}
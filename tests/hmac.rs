@@ -8,6 +8,10 @@ fn run_wycheproof() {
             wycheproof::mac::TestName::HmacSha256 => Algorithm::Sha256,
             wycheproof::mac::TestName::HmacSha384 => Algorithm::Sha384,
             wycheproof::mac::TestName::HmacSha512 => Algorithm::Sha512,
+            wycheproof::mac::TestName::HmacSha3_224 => Algorithm::Sha3_224,
+            wycheproof::mac::TestName::HmacSha3_256 => Algorithm::Sha3_256,
+            wycheproof::mac::TestName::HmacSha3_384 => Algorithm::Sha3_384,
+            wycheproof::mac::TestName::HmacSha3_512 => Algorithm::Sha3_512,
             _ => continue,
         };
         let test_set = wycheproof::mac::TestSet::load(test_name)
@@ -32,3 +36,73 @@ fn run_wycheproof() {
         }
     }
 }
+
+#[test]
+fn run_wycheproof_streaming() {
+    fn check<const N: usize>(
+        valid: bool,
+        tag: &[u8],
+        finalize: impl Fn() -> [u8; N],
+        verify: impl Fn(&[u8]) -> Result<(), libcrux_hmac::Error>,
+    ) {
+        let expected = finalize();
+        if valid {
+            assert_eq!(&expected[..tag.len()], tag);
+        } else {
+            assert_ne!(&expected[..tag.len()], tag);
+        }
+
+        // Tags that are too short are rejected by the verification.
+        if tag.len() >= core::cmp::max(N / 2, 10) {
+            assert_eq!(verify(tag).is_ok(), valid);
+        } else {
+            assert!(verify(tag).is_err());
+        }
+    }
+
+    for test_name in wycheproof::mac::TestName::all() {
+        let test_set = match test_name {
+            wycheproof::mac::TestName::HmacSha1
+            | wycheproof::mac::TestName::HmacSha256
+            | wycheproof::mac::TestName::HmacSha384
+            | wycheproof::mac::TestName::HmacSha512
+            | wycheproof::mac::TestName::HmacSha3_224
+            | wycheproof::mac::TestName::HmacSha3_256
+            | wycheproof::mac::TestName::HmacSha3_384
+            | wycheproof::mac::TestName::HmacSha3_512 => wycheproof::mac::TestSet::load(test_name)
+                .expect("error loading wycheproof test for name {test_name}"),
+            _ => continue,
+        };
+
+        for test_group in test_set.test_groups {
+            for test in test_group.tests {
+                let valid = match test.result {
+                    wycheproof::TestResult::Valid => true,
+                    wycheproof::TestResult::Invalid => false,
+                    _ => unreachable!(),
+                };
+                let (key, msg, tag) = (&test.key[..], &test.msg[..], &test.tag[..]);
+
+                // Add the message in two parts.
+                let split = msg.len() / 3;
+                macro_rules! run {
+                    ($hmac:ty) => {{
+                        let mut mac = <$hmac>::new(key);
+                        mac.update(&msg[..split]).update(&msg[split..]);
+                        check(valid, tag, || mac.finalize(), |t| mac.verify_truncated(t));
+                    }};
+                }
+                match test_name {
+                    wycheproof::mac::TestName::HmacSha1 => run!(libcrux::hmac::HmacSha1),
+                    wycheproof::mac::TestName::HmacSha256 => run!(libcrux::hmac::HmacSha256),
+                    wycheproof::mac::TestName::HmacSha384 => run!(libcrux::hmac::HmacSha384),
+                    wycheproof::mac::TestName::HmacSha512 => run!(libcrux::hmac::HmacSha512),
+                    wycheproof::mac::TestName::HmacSha3_224 => run!(libcrux::hmac::HmacSha3_224),
+                    wycheproof::mac::TestName::HmacSha3_256 => run!(libcrux::hmac::HmacSha3_256),
+                    wycheproof::mac::TestName::HmacSha3_384 => run!(libcrux::hmac::HmacSha3_384),
+                    _ => run!(libcrux::hmac::HmacSha3_512),
+                }
+            }
+        }
+    }
+}
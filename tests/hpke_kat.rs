@@ -1,6 +1,5 @@
 #![allow(non_snake_case)]
 
-use libcrux::aes_ni_support;
 use serde::{Deserialize, Serialize};
 use std::{fs::File, io::BufReader, time::Instant};
 
@@ -8,6 +7,7 @@ mod test_util;
 use test_util::*;
 
 use libcrux::hpke::aead::AEAD::{self};
+use libcrux::hpke::errors::HpkeError;
 use libcrux::hpke::kdf::KDF::{self};
 use libcrux::hpke::kem::{DeriveKeyPair, DeserializePublicKey, SerializePublicKey, KEM};
 use libcrux::hpke::{Mode::*, *};
@@ -178,32 +178,15 @@ fn kat(tests: Vec<HpkeTestVector>) {
         let aead_id = aead(test.aead_id);
         let config = HPKEConfig(mode, kem_id, kdf_id, aead_id);
 
+        // The other DHKEMs are not implemented and must be rejected.
         if !(kem_id == KEM::DHKEM_X25519_HKDF_SHA256 || kem_id == KEM::DHKEM_P256_HKDF_SHA256) {
-            println!(
-                " > Only DHKEM_X25519_HKDF_SHA256 and DHKEM_P256_HKDF_SHA256 implemented ({:?})",
-                kem_id
+            assert_eq!(
+                DeriveKeyPair(kem_id, &hex_str_to_bytes(&test.ikmR)),
+                Err(HpkeError::UnsupportedAlgorithm)
             );
             return;
         }
 
-        if !(aead_id == AEAD::ChaCha20Poly1305 || aead_id == AEAD::AES_128_GCM) {
-            println!(
-                " > Only ChaCha20Poly1305 and Aes128Gcm implemented ({:?})",
-                aead_id
-            );
-            return;
-        }
-        if !aes_ni_support() || cfg!(not(target_arch = "x86_64")) {
-            // libcrux AES only works on x64 (and there only with the necessary extensions)
-            eprintln!("skipping AES because we're missing features.");
-            return;
-        }
-
-        if kdf_id != KDF::HKDF_SHA256 {
-            println!(" > Only HKDF_SHA256 implemented ({:?})", kdf_id);
-            return;
-        }
-
         println!(
             "Testing mode {:?} with ciphersuite {:?}_{:?}_{:?}",
             mode, kem_id, kdf_id, aead_id
@@ -0,0 +1,372 @@
+mod test_util;
+use test_util::*;
+
+use libcrux::{
+    digest,
+    hpke::{
+        self,
+        aead::AEAD,
+        errors::HpkeError,
+        kdf::KDF,
+        kem::{DeriveKeyPair, GenerateKeyPair, Nenc, Npk, Nsk, KEM},
+        HPKECiphertext, HPKEConfig, HpkeOpen, HpkeSeal, ReceiveExport, SendExport,
+    },
+    kem,
+};
+use serde::Deserialize;
+
+#[cfg(not(target_arch = "wasm32"))]
+use libcrux::drbg;
+#[cfg(target_arch = "wasm32")]
+use rand_core::OsRng;
+use rand_core::RngCore;
+
+const PQ_KEMS: [KEM; 4] = [
+    KEM::ML_KEM_512,
+    KEM::ML_KEM_768,
+    KEM::ML_KEM_1024,
+    KEM::XWing,
+];
+
+/// Length of the encapsulation randomness.
+fn encap_randomness_len(kem: KEM) -> usize {
+    match kem {
+        KEM::XWing => 64,
+        _ => 32,
+    }
+}
+
+fn rng() -> impl RngCore {
+    #[cfg(not(target_arch = "wasm32"))]
+    let rng = drbg::Drbg::new(digest::Algorithm::Sha256).unwrap();
+    #[cfg(target_arch = "wasm32")]
+    let rng = OsRng;
+    rng
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn pq_hpke_selftest() {
+    let _ = pretty_env_logger::try_init();
+    let mut rng = rng();
+
+    for kem in PQ_KEMS {
+        for mode in [hpke::Mode::mode_base, hpke::Mode::mode_psk] {
+            let config = HPKEConfig(mode, kem, KDF::HKDF_SHA256, AEAD::ChaCha20Poly1305);
+
+            let mut randomness = vec![0u8; 32];
+            rng.fill_bytes(&mut randomness);
+            let (sk_r, pk_r) = GenerateKeyPair(kem, randomness).unwrap();
+            assert_eq!(sk_r.len(), Nsk(kem));
+            assert_eq!(pk_r.len(), Npk(kem));
+
+            let info = b"pq hpke selftest info";
+            let aad = b"pq hpke selftest aad";
+            let ptxt = b"pq hpke selftest plaintext";
+            let (psk, psk_id) = match mode {
+                hpke::Mode::mode_psk => (Some(&[0x42u8; 32][..]), Some(&b"psk id"[..])),
+                _ => (None, None),
+            };
+
+            let mut randomness = vec![0u8; encap_randomness_len(kem)];
+            rng.fill_bytes(&mut randomness);
+
+            let HPKECiphertext(enc, ct) = HpkeSeal(
+                config, &pk_r, info, aad, ptxt, psk, psk_id, None, randomness,
+            )
+            .expect("Error in hpke seal");
+            assert_eq!(enc.len(), Nenc(kem));
+
+            let decrypted_ptxt = HpkeOpen(
+                config,
+                &HPKECiphertext(enc, ct),
+                &sk_r,
+                info,
+                aad,
+                psk,
+                psk_id,
+                None,
+            )
+            .expect("Error opening hpke ciphertext");
+
+            assert_eq!(ptxt, decrypted_ptxt.as_slice());
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn pq_hpke_export() {
+    let mut rng = rng();
+
+    for kem in PQ_KEMS {
+        let config = HPKEConfig(
+            hpke::Mode::mode_base,
+            kem,
+            KDF::HKDF_SHA256,
+            AEAD::Export_only,
+        );
+
+        let (sk_r, pk_r) = DeriveKeyPair(kem, b"pq hpke export ikm").unwrap();
+
+        let mut randomness = vec![0u8; encap_randomness_len(kem)];
+        rng.fill_bytes(&mut randomness);
+
+        let HPKECiphertext(enc, exported) = SendExport(
+            config,
+            &pk_r,
+            b"info",
+            b"context".to_vec(),
+            42,
+            None,
+            None,
+            None,
+            randomness,
+        )
+        .unwrap();
+        let received = ReceiveExport(
+            config,
+            &enc,
+            &sk_r,
+            b"info",
+            b"context".to_vec(),
+            42,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(exported.len(), 42);
+        assert_eq!(exported, received);
+    }
+}
+
+#[derive(Deserialize)]
+struct DeriveKeyPairVector {
+    kem_id: u16,
+    ikm: String,
+    sk: String,
+    pk: String,
+}
+
+impl ReadFromFile for DeriveKeyPairVector {}
+
+/// Check `DeriveKeyPair` against the vectors in `tests/hpke_pq_kats`, which
+/// are produced by `generate_kats.py` from the Python ML-KEM in
+/// `libcrux-ml-kem/tests/kats` and a Python X25519.
+///
+/// These are not vectors from the drafts. [`pq_interop`] checks the key
+/// generation from the seed against pyca/cryptography.
+#[test]
+fn pq_derive_key_pair() {
+    let vectors: Vec<DeriveKeyPairVector> =
+        DeriveKeyPairVector::from_file("tests/hpke_pq_kats/derive_key_pair.json");
+    assert_eq!(vectors.len(), 3 * PQ_KEMS.len());
+
+    for vector in vectors {
+        let kem = match vector.kem_id {
+            0x0040 => KEM::ML_KEM_512,
+            0x0041 => KEM::ML_KEM_768,
+            0x0042 => KEM::ML_KEM_1024,
+            0x647a => KEM::XWing,
+            _ => panic!("Invalid KEM"),
+        };
+        let (sk, pk) = DeriveKeyPair(kem, &hex_str_to_bytes(&vector.ikm)).unwrap();
+        assert_eq!(sk.len(), Nsk(kem));
+        assert_eq!(pk.len(), Npk(kem));
+        assert_eq!(sk, hex_str_to_bytes(&vector.sk));
+        assert_eq!(pk, hex_str_to_bytes(&vector.pk));
+    }
+}
+
+#[test]
+fn pq_auth_unsupported() {
+    for kem in PQ_KEMS {
+        let config = HPKEConfig(
+            hpke::Mode::mode_auth,
+            kem,
+            KDF::HKDF_SHA256,
+            AEAD::ChaCha20Poly1305,
+        );
+        let (sk, pk) = DeriveKeyPair(kem, b"pq hpke auth ikm").unwrap();
+
+        let result = HpkeSeal(
+            config,
+            &pk,
+            b"info",
+            b"aad",
+            b"plaintext",
+            None,
+            None,
+            Some(&sk),
+            vec![0u8; encap_randomness_len(kem)],
+        );
+        assert!(matches!(result, Err(HpkeError::UnsupportedAlgorithm)));
+    }
+}
+
+#[test]
+fn pq_modified_enc() {
+    let mut rng = rng();
+
+    for kem in PQ_KEMS {
+        let config = HPKEConfig(
+            hpke::Mode::mode_base,
+            kem,
+            KDF::HKDF_SHA256,
+            AEAD::ChaCha20Poly1305,
+        );
+        let (sk_r, pk_r) = DeriveKeyPair(kem, b"pq hpke modified enc ikm").unwrap();
+
+        let mut randomness = vec![0u8; encap_randomness_len(kem)];
+        rng.fill_bytes(&mut randomness);
+        let HPKECiphertext(mut enc, ct) = HpkeSeal(
+            config,
+            &pk_r,
+            b"info",
+            b"aad",
+            b"plaintext",
+            None,
+            None,
+            None,
+            randomness,
+        )
+        .unwrap();
+
+        // ML-KEM implicitly rejects, so this has to fail when opening.
+        enc[0] ^= 1;
+        let result = HpkeOpen(
+            config,
+            &HPKECiphertext(enc, ct),
+            &sk_r,
+            b"info",
+            b"aad",
+            None,
+            None,
+            None,
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[derive(Deserialize)]
+struct InteropCiphertext {
+    enc: String,
+    ct: String,
+}
+
+#[derive(Deserialize)]
+struct InteropDerandCiphertext {
+    randomness: String,
+    enc: String,
+    ct: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct InteropExport {
+    exporter_context: String,
+    L: usize,
+    exported_value: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct InteropVector {
+    kem_id: u16,
+    kdf_id: u16,
+    aead_id: u16,
+    info: String,
+    skRm: String,
+    pkRm: String,
+    aad: String,
+    pt: String,
+    sealed: InteropCiphertext,
+    derand: InteropDerandCiphertext,
+    exports: Vec<InteropExport>,
+}
+
+impl ReadFromFile for InteropVector {}
+
+/// Check ML-KEM-768, ML-KEM-1024 and X-Wing against pyca/cryptography's HPKE,
+/// with the vectors that `generate_interop.py` writes to `tests/hpke_pq_kats`.
+#[test]
+fn pq_interop() {
+    let vectors: Vec<InteropVector> = InteropVector::from_file("tests/hpke_pq_kats/interop.json");
+    assert_eq!(vectors.len(), 27);
+
+    for vector in vectors {
+        let (kem, algorithm) = match vector.kem_id {
+            0x0041 => (KEM::ML_KEM_768, kem::Algorithm::MlKem768),
+            0x0042 => (KEM::ML_KEM_1024, kem::Algorithm::MlKem1024),
+            0x647a => (KEM::XWing, kem::Algorithm::XWingKemDraft06),
+            _ => panic!("Invalid KEM"),
+        };
+        let kdf = match vector.kdf_id {
+            0x0001 => KDF::HKDF_SHA256,
+            0x0002 => KDF::HKDF_SHA384,
+            0x0003 => KDF::HKDF_SHA512,
+            _ => panic!("Invalid KDF"),
+        };
+        let aead = match vector.aead_id {
+            0x0001 => AEAD::AES_128_GCM,
+            0x0002 => AEAD::AES_256_GCM,
+            0x0003 => AEAD::ChaCha20Poly1305,
+            _ => panic!("Invalid AEAD"),
+        };
+        let config = HPKEConfig(hpke::Mode::mode_base, kem, kdf, aead);
+
+        let sk_r = hex_str_to_bytes(&vector.skRm);
+        let pk_r = hex_str_to_bytes(&vector.pkRm);
+        let info = hex_str_to_bytes(&vector.info);
+        let aad = hex_str_to_bytes(&vector.aad);
+        let pt = hex_str_to_bytes(&vector.pt);
+        assert_eq!(sk_r.len(), Nsk(kem));
+
+        // The public key for the private key, the seed, is the same.
+        let (_, pk) = kem::key_gen_derand(algorithm, &sk_r).unwrap();
+        assert_eq!(pk.encode(), pk_r);
+
+        // Decap
+        let sealed = HPKECiphertext(
+            hex_str_to_bytes(&vector.sealed.enc),
+            hex_str_to_bytes(&vector.sealed.ct),
+        );
+        let opened = HpkeOpen(config, &sealed, &sk_r, &info, &aad, None, None, None).unwrap();
+        assert_eq!(opened, pt);
+
+        // Encap
+        let enc = hex_str_to_bytes(&vector.derand.enc);
+        let HPKECiphertext(derand_enc, derand_ct) = HpkeSeal(
+            config,
+            &pk_r,
+            &info,
+            &aad,
+            &pt,
+            None,
+            None,
+            None,
+            hex_str_to_bytes(&vector.derand.randomness),
+        )
+        .unwrap();
+        assert_eq!(derand_enc, enc);
+        assert_eq!(derand_ct, hex_str_to_bytes(&vector.derand.ct));
+
+        for export in vector.exports {
+            let exported = ReceiveExport(
+                config,
+                &enc,
+                &sk_r,
+                &info,
+                hex_str_to_bytes(&export.exporter_context),
+                export.L,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            assert_eq!(exported, hex_str_to_bytes(&export.exported_value));
+        }
+    }
+}
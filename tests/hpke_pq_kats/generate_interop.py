@@ -0,0 +1,231 @@
+#! /usr/bin/env python3
+
+# Generates interop.json, the HPKE vectors for ML-KEM-768, ML-KEM-1024 and
+# X-Wing (MLKEM768-X25519, 0x647a), from pyca/cryptography's HPKE
+# (cryptography >= 48).
+#
+# The vectors from the ML-KEM and X-Wing HPKE drafts are not vendored here yet.
+# Until they are, these check libcrux against an implementation that is not
+# ours. ML-KEM-512 is not covered because cryptography doesn't support it in
+# HPKE.
+#
+# For every KEM, KDF and AEAD combination a vector has
+#
+# - skRm, pkRm: the private key is the seed (d || z for ML-KEM, the 32 byte
+#   X-Wing seed), the public key is the one cryptography derives from it.
+# - sealed: a message sealed by cryptography's HPKE.
+# - derand: a message sealed with the given encapsulation randomness, using
+#   libcrux-ml-kem/tests/kats/mlkem.py for ML-KEM.Encaps_internal and
+#   cryptography for X25519. cryptography has to open it before it is written.
+# - exports: exporter values for derand.enc.
+#
+# cryptography has no export API. The exports use the RFC 9180 key schedule
+# below, which has to derive the key and nonce that open cryptography's
+# ciphertext before any value is written.
+
+import hashlib
+import hmac
+import json
+import os
+import sys
+
+from cryptography.hazmat.primitives import hpke
+from cryptography.hazmat.primitives.asymmetric import mlkem, x25519
+from cryptography.hazmat.primitives.ciphers.aead import AESGCM, ChaCha20Poly1305
+
+here = os.path.dirname(os.path.abspath(__file__))
+sys.path.insert(0, here)
+from generate_kats import mlkem as mlkem_spec  # noqa: E402
+
+# (KEM, Nsk, Nenc)
+KEMS = {
+    0x0041: (hpke.KEM.MLKEM768, 64, 1088),
+    0x0042: (hpke.KEM.MLKEM1024, 64, 1568),
+    0x647A: (hpke.KEM.MLKEM768_X25519, 32, 1120),
+}
+
+KDFS = {
+    0x0001: (hpke.KDF.HKDF_SHA256, "sha256"),
+    0x0002: (hpke.KDF.HKDF_SHA384, "sha384"),
+    0x0003: (hpke.KDF.HKDF_SHA512, "sha512"),
+}
+
+AEADS = {
+    0x0001: (hpke.AEAD.AES_128_GCM, AESGCM, 16),
+    0x0002: (hpke.AEAD.AES_256_GCM, AESGCM, 32),
+    0x0003: (hpke.AEAD.CHACHA20_POLY1305, ChaCha20Poly1305, 32),
+}
+
+# ML-KEM-768 ciphertext length, the X-Wing enc is ct_M || ct_X.
+XWING_CT_M_LEN = 1088
+
+
+def private_key(kem_id, sk):
+    if kem_id == 0x0041:
+        return mlkem.MLKEM768PrivateKey.from_seed_bytes(sk)
+    if kem_id == 0x0042:
+        return mlkem.MLKEM1024PrivateKey.from_seed_bytes(sk)
+    expanded = hashlib.shake_256(sk).digest(96)
+    return hpke.MLKEM768X25519PrivateKey(
+        mlkem.MLKEM768PrivateKey.from_seed_bytes(expanded[:64]),
+        x25519.X25519PrivateKey.from_private_bytes(expanded[64:]),
+    )
+
+
+def public_key_bytes(kem_id, sk):
+    if kem_id != 0x647A:
+        return private_key(kem_id, sk).public_key().public_bytes_raw()
+    expanded = hashlib.shake_256(sk).digest(96)
+    pk_m = mlkem.MLKEM768PrivateKey.from_seed_bytes(expanded[:64]).public_key()
+    pk_x = x25519.X25519PrivateKey.from_private_bytes(expanded[64:]).public_key()
+    return pk_m.public_bytes_raw() + pk_x.public_bytes_raw()
+
+
+def xwing_combiner(ss_m, ss_x, ct_x, pk_x):
+    return hashlib.sha3_256(ss_m + ss_x + ct_x + pk_x + b"\\./" + b"/^\\").digest()
+
+
+def decap(kem_id, sk, enc):
+    """The shared secret, with cryptography's ML-KEM and X25519."""
+    if kem_id != 0x647A:
+        return private_key(kem_id, sk).decapsulate(enc)
+    expanded = hashlib.shake_256(sk).digest(96)
+    ss_m = mlkem.MLKEM768PrivateKey.from_seed_bytes(expanded[:64]).decapsulate(
+        enc[:XWING_CT_M_LEN]
+    )
+    sk_x = x25519.X25519PrivateKey.from_private_bytes(expanded[64:])
+    ct_x = enc[XWING_CT_M_LEN:]
+    ss_x = sk_x.exchange(x25519.X25519PublicKey.from_public_bytes(ct_x))
+    pk_x = sk_x.public_key().public_bytes_raw()
+    return xwing_combiner(ss_m, ss_x, ct_x, pk_x)
+
+
+def encap_derand(kem_id, pk, randomness):
+    """The encapsulation for the given randomness, returns (enc, ss)."""
+    if kem_id == 0x0041:
+        return mlkem_spec.Enc(pk, randomness, mlkem_spec.params768)
+    if kem_id == 0x0042:
+        return mlkem_spec.Enc(pk, randomness, mlkem_spec.params1024)
+    pk_m, pk_x = pk[:-32], pk[-32:]
+    ct_m, ss_m = mlkem_spec.Enc(pk_m, randomness[:32], mlkem_spec.params768)
+    ek_x = x25519.X25519PrivateKey.from_private_bytes(randomness[32:])
+    ct_x = ek_x.public_key().public_bytes_raw()
+    ss_x = ek_x.exchange(x25519.X25519PublicKey.from_public_bytes(pk_x))
+    return ct_m + ct_x, xwing_combiner(ss_m, ss_x, ct_x, pk_x)
+
+
+class KeySchedule:
+    """RFC 9180, Section 5.1, in mode_base."""
+
+    def __init__(self, kem_id, kdf_id, aead_id, shared_secret, info):
+        self.hash = KDFS[kdf_id][1]
+        self.suite_id = (
+            b"HPKE"
+            + kem_id.to_bytes(2, "big")
+            + kdf_id.to_bytes(2, "big")
+            + aead_id.to_bytes(2, "big")
+        )
+        nh = hashlib.new(self.hash).digest_size
+        _, _, nk = AEADS[aead_id]
+
+        psk_id_hash = self.labeled_extract(b"", b"psk_id_hash", b"")
+        info_hash = self.labeled_extract(b"", b"info_hash", info)
+        context = b"\x00" + psk_id_hash + info_hash
+        secret = self.labeled_extract(shared_secret, b"secret", b"")
+
+        self.key = self.labeled_expand(secret, b"key", context, nk)
+        self.base_nonce = self.labeled_expand(secret, b"base_nonce", context, 12)
+        self.exporter_secret = self.labeled_expand(secret, b"exp", context, nh)
+
+    def labeled_extract(self, salt, label, ikm):
+        ikm = b"HPKE-v1" + self.suite_id + label + ikm
+        if not salt:
+            salt = bytes(hashlib.new(self.hash).digest_size)
+        return hmac.new(salt, ikm, self.hash).digest()
+
+    def labeled_expand(self, prk, label, info, length):
+        info = length.to_bytes(2, "big") + b"HPKE-v1" + self.suite_id + label + info
+        out, t = b"", b""
+        for i in range(1, -(-length // len(prk)) + 1):
+            t = hmac.new(prk, t + info + bytes([i]), self.hash).digest()
+            out += t
+        return out[:length]
+
+    def export(self, context, length):
+        return self.labeled_expand(self.exporter_secret, b"sec", context, length)
+
+
+def check_key_schedule(kem_id, kdf_id, aead_id, sk, info, aad, pt, enc, ct):
+    """The key schedule has to open cryptography's ciphertext."""
+    ks = KeySchedule(kem_id, kdf_id, aead_id, decap(kem_id, sk, enc), info)
+    aead = AEADS[aead_id][1]
+    assert aead(ks.key).decrypt(ks.base_nonce, ct, aad) == pt
+
+
+EXPORTER_CONTEXTS = [b"", b"\x00", b"TestContext"]
+
+if __name__ == "__main__":
+    vectors = []
+    counter = 0
+    for kem_id, (kem, sk_len, enc_len) in KEMS.items():
+        for kdf_id, (kdf, _) in KDFS.items():
+            for aead_id, (aead, _, _) in AEADS.items():
+                counter += 1
+                label = f"{kem_id:04x} {kdf_id:04x} {aead_id:04x}".encode()
+                sk = hashlib.shake_256(b"skRm " + label).digest(sk_len)
+                pk = public_key_bytes(kem_id, sk)
+                info = b"pq hpke interop info " + label
+                aad = b"Count-" + str(counter).encode()
+                pt = b"Beauty is truth, truth beauty"
+                suite = hpke.Suite(kem, kdf, aead)
+
+                sealed = hpke.rust_openssl.hpke._encrypt_with_aad(
+                    suite, pt, private_key(kem_id, sk).public_key(), info, aad
+                )
+                sealed_enc, sealed_ct = sealed[:enc_len], sealed[enc_len:]
+                check_key_schedule(
+                    kem_id, kdf_id, aead_id, sk, info, aad, pt, sealed_enc, sealed_ct
+                )
+
+                randomness = hashlib.shake_256(b"randomness " + label).digest(
+                    64 if kem_id == 0x647A else 32
+                )
+                enc, ss = encap_derand(kem_id, pk, randomness)
+                assert ss == decap(kem_id, sk, enc)
+                ks = KeySchedule(kem_id, kdf_id, aead_id, ss, info)
+                ct = AEADS[aead_id][1](ks.key).encrypt(ks.base_nonce, pt, aad)
+                opened = hpke.rust_openssl.hpke._decrypt_with_aad(
+                    suite, enc + ct, private_key(kem_id, sk), info, aad
+                )
+                assert opened == pt
+
+                vectors.append(
+                    {
+                        "kem_id": kem_id,
+                        "kdf_id": kdf_id,
+                        "aead_id": aead_id,
+                        "info": info.hex(),
+                        "skRm": sk.hex(),
+                        "pkRm": pk.hex(),
+                        "aad": aad.hex(),
+                        "pt": pt.hex(),
+                        "sealed": {"enc": sealed_enc.hex(), "ct": sealed_ct.hex()},
+                        "derand": {
+                            "randomness": randomness.hex(),
+                            "enc": enc.hex(),
+                            "ct": ct.hex(),
+                        },
+                        "exports": [
+                            {
+                                "exporter_context": context.hex(),
+                                "L": 32,
+                                "exported_value": ks.export(context, 32).hex(),
+                            }
+                            for context in EXPORTER_CONTEXTS
+                        ],
+                    }
+                )
+
+    with open(os.path.join(here, "interop.json"), "w") as f:
+        json.dump(vectors, f, indent=2)
+        f.write("\n")
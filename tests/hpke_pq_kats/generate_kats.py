@@ -0,0 +1,163 @@
+#! /usr/bin/env python3
+
+# Generates the DeriveKeyPair vectors for the post-quantum HPKE KEMs in
+# derive_key_pair.json, independently of libcrux:
+#
+# - SHAKE256 is Python's hashlib,
+# - ML-KEM is libcrux-ml-kem/tests/kats/mlkem.py,
+# - X25519 is the RFC 7748 ladder below.
+#
+# ML-KEM is checked against the FIPS 203 KATs in libcrux-ml-kem/tests/kats and
+# X25519 against the RFC 7748 vectors before any vectors are written. X-Wing is
+# not checked as a whole: ../xwing_test_vectors.json is for an older draft.
+# generate_interop.py checks the key generation against pyca/cryptography.
+#
+# DeriveKeyPair(ikm) is
+#
+#   ML-KEM: sk = SHAKE256(ikm, 64), pk = ML-KEM.KeyGen_internal(sk[:32], sk[32:])
+#   X-Wing: sk = SHAKE256(ikm, 32), pk = X-Wing.GenerateKeyPairDerand(sk)
+
+import hashlib
+import json
+import os
+import sys
+import types
+
+
+class _Shake:
+    """The part of the pycryptodome SHAKE interface that mlkem.py uses."""
+
+    def __init__(self, new):
+        self.new_hash = new
+        self.data = b""
+        self.read_len = 0
+
+    def update(self, data):
+        self.data += data
+
+    def read(self, n):
+        out = self.new_hash(self.data).digest(self.read_len + n)[self.read_len :]
+        self.read_len += n
+        return out
+
+
+crypto_hash = types.ModuleType("Crypto.Hash")
+crypto_hash.SHAKE128 = types.SimpleNamespace(new=lambda: _Shake(hashlib.shake_128))
+crypto_hash.SHAKE256 = types.SimpleNamespace(new=lambda: _Shake(hashlib.shake_256))
+sys.modules["Crypto"] = types.ModuleType("Crypto")
+sys.modules["Crypto.Hash"] = crypto_hash
+
+here = os.path.dirname(os.path.abspath(__file__))
+mlkem_kats = os.path.join(here, "..", "..", "libcrux-ml-kem", "tests", "kats")
+sys.path.insert(0, mlkem_kats)
+import mlkem  # noqa: E402
+
+
+def mlkem_keygen(seed, params):
+    pk, _ = mlkem.KeyGen(seed, params)
+    return pk
+
+
+P = 2**255 - 19
+
+
+def x25519(k, u):
+    k = bytearray(k)
+    k[0] &= 248
+    k[31] &= 127
+    k[31] |= 64
+    k = int.from_bytes(k, "little")
+    u = int.from_bytes(u, "little") & ((1 << 255) - 1)
+
+    x1, x2, z2, x3, z3, swap = u, 1, 0, u, 1, 0
+    for t in reversed(range(255)):
+        k_t = (k >> t) & 1
+        swap ^= k_t
+        if swap:
+            x2, x3, z2, z3 = x3, x2, z3, z2
+        swap = k_t
+        A, B = (x2 + z2) % P, (x2 - z2) % P
+        AA, BB = A * A % P, B * B % P
+        E = (AA - BB) % P
+        C, D = (x3 + z3) % P, (x3 - z3) % P
+        DA, CB = D * A % P, C * B % P
+        x3 = (DA + CB) ** 2 % P
+        z3 = x1 * (DA - CB) ** 2 % P
+        x2 = AA * BB % P
+        z2 = E * (AA + 121665 * E) % P
+    if swap:
+        x2, z2 = x3, z3
+    return (x2 * pow(z2, P - 2, P) % P).to_bytes(32, "little")
+
+
+def xwing_keygen(sk):
+    expanded = hashlib.shake_256(sk).digest(96)
+    pk_m = mlkem_keygen(expanded[:64], mlkem.params768)
+    pk_x = x25519(expanded[64:], (9).to_bytes(32, "little"))
+    return pk_m + pk_x
+
+
+def check_mlkem():
+    for name, params in [
+        ("512", mlkem.params512),
+        ("768", mlkem.params768),
+        ("1024", mlkem.params1024),
+    ]:
+        with open(os.path.join(mlkem_kats, f"nistkats_mlkem_{name}.json")) as f:
+            kats = json.load(f)
+        for kat in kats[:3]:
+            pk = mlkem_keygen(bytes.fromhex(kat["key_generation_seed"]), params)
+            assert hashlib.sha3_256(pk).hexdigest() == kat["sha3_256_hash_of_public_key"]
+
+
+def check_x25519():
+    # RFC 7748, Section 5.2 and Section 6.1.
+    vectors = [
+        (
+            "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+            "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+            "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+        ),
+        (
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+            "09" + "00" * 31,
+            "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a",
+        ),
+    ]
+    for k, u, out in vectors:
+        assert x25519(bytes.fromhex(k), bytes.fromhex(u)).hex() == out
+
+
+KEMS = [
+    (0x0040, lambda sk: mlkem_keygen(sk, mlkem.params512), 64),
+    (0x0041, lambda sk: mlkem_keygen(sk, mlkem.params768), 64),
+    (0x0042, lambda sk: mlkem_keygen(sk, mlkem.params1024), 64),
+    (0x647A, xwing_keygen, 32),
+]
+
+IKMS = [
+    bytes(range(32)),
+    bytes(range(64)),
+    b"pq hpke derive key pair ikm, at least 32 bytes long",
+]
+
+if __name__ == "__main__":
+    check_mlkem()
+    check_x25519()
+
+    vectors = []
+    for kem_id, keygen, sk_len in KEMS:
+        for ikm in IKMS:
+            sk = hashlib.shake_256(ikm).digest(sk_len)
+            vectors.append(
+                {
+                    "kem_id": kem_id,
+                    "ikm": ikm.hex(),
+                    "sk": sk.hex(),
+                    "pk": keygen(sk).hex(),
+                }
+            )
+
+    with open(os.path.join(here, "derive_key_pair.json"), "w") as f:
+        json.dump(vectors, f, indent=2)
+        f.write("\n")
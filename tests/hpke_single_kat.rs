@@ -51,6 +51,51 @@ fn single_kat_base() {
     assert_eq!(ptxt, decrypted_ptxt);
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn single_kat_base_aes_gcm() {
+    let _ = pretty_env_logger::try_init();
+
+    // A.1.1.
+    let config = HPKEConfig(
+        mode_base,
+        DHKEM_X25519_HKDF_SHA256,
+        HKDF_SHA256,
+        AES_128_GCM,
+    );
+    let pk_r = hex_str_to_bytes("3948cfe0ad1ddb695d780e59077195da6c56506b027329794ab02bca80815c4d");
+    let info = hex_str_to_bytes("4f6465206f6e2061204772656369616e2055726e");
+    let aad = hex_str_to_bytes("436f756e742d30");
+    let ptxt = hex_str_to_bytes("4265617574792069732074727574682c20747275746820626561757479");
+    let randomness =
+        hex_str_to_bytes("7268600d403fce431561aef583ee1613527cff655c1343f29812e66706df3234");
+
+    let HPKECiphertext(enc, ct) = HpkeSeal(
+        config, &pk_r, &info, &aad, &ptxt, None, None, None, randomness,
+    )
+    .expect("Error in hpke seal");
+    assert_eq!(
+        hex_str_to_bytes(
+            "f938558b5d72f1a23810b4be2ab4f84331acc02fc97babc53a52ae8218a355a96d8770ac83d07bea87e13c512a"
+        ),
+        ct,
+    );
+
+    let sk_r = hex_str_to_bytes("4612c550263fc8ad58375df3f557aac531d26850903e55a9f23f21d8534e8ac8");
+    let decrypted_ptxt = HpkeOpen(
+        config,
+        &HPKECiphertext(enc, ct),
+        &sk_r,
+        &info,
+        &aad,
+        None,
+        None,
+        None,
+    )
+    .expect("Error opening hpke ciphertext");
+    assert_eq!(ptxt, decrypted_ptxt);
+}
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
 fn single_kat_psk() {
@@ -217,3 +262,115 @@ fn single_kat_auth_psk() {
     .expect("Error opening hpke ciphertext");
     assert_eq!(ptxt, decrypted_ptxt);
 }
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn single_kat_open_error() {
+    // A.2.1.1 with a modified tag
+    let config = HPKEConfig(
+        mode_base,
+        DHKEM_X25519_HKDF_SHA256,
+        HKDF_SHA256,
+        ChaCha20Poly1305,
+    );
+    let pk_r = hex_str_to_bytes("4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a");
+    let info = hex_str_to_bytes("4f6465206f6e2061204772656369616e2055726e");
+    let aad = hex_str_to_bytes("436f756e742d30");
+    let ptxt = hex_str_to_bytes("4265617574792069732074727574682c20747275746820626561757479");
+    let randomness =
+        hex_str_to_bytes("909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b");
+
+    let HPKECiphertext(enc, mut ct) = HpkeSeal(
+        config, &pk_r, &info, &aad, &ptxt, None, None, None, randomness,
+    )
+    .expect("Error in hpke seal");
+    let last = ct.len() - 1;
+    ct[last] ^= 1;
+
+    let sk_r = hex_str_to_bytes("8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb");
+    let result = HpkeOpen(
+        config,
+        &HPKECiphertext(enc, ct),
+        &sk_r,
+        &info,
+        &aad,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(result, Err(errors::HpkeError::OpenError));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn export_only() {
+    let config = HPKEConfig(
+        mode_base,
+        DHKEM_X25519_HKDF_SHA256,
+        HKDF_SHA256,
+        Export_only,
+    );
+    let info = hex_str_to_bytes("4f6465206f6e2061204772656369616e2055726e");
+    let randomness =
+        hex_str_to_bytes("909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b");
+    let pk_r = hex_str_to_bytes("4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a");
+    let sk_r = hex_str_to_bytes("8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb");
+
+    // The key schedule doesn't produce a key or nonce.
+    let (enc, context) = SetupBaseS(config, &pk_r, &info, randomness.clone()).unwrap();
+    let (key, base_nonce, seq, exporter_secret) = context.clone();
+    assert!(key.is_empty());
+    assert!(base_nonce.is_empty());
+    assert_eq!(seq, 0);
+    assert_eq!(exporter_secret.len(), 32);
+
+    // Encryption is not possible.
+    assert_eq!(
+        ContextS_Seal(Export_only, context, b"aad", b"plaintext").unwrap_err(),
+        errors::HpkeError::UnsupportedAlgorithm
+    );
+    assert_eq!(
+        HpkeSeal(
+            config,
+            &pk_r,
+            &info,
+            b"aad",
+            b"plaintext",
+            None,
+            None,
+            None,
+            randomness.clone()
+        )
+        .err(),
+        Some(errors::HpkeError::UnsupportedAlgorithm)
+    );
+
+    // But exporting secrets is.
+    let HPKECiphertext(send_enc, exported) = SendExport(
+        config,
+        &pk_r,
+        &info,
+        b"TestContext".to_vec(),
+        32,
+        None,
+        None,
+        None,
+        randomness,
+    )
+    .unwrap();
+    assert_eq!(enc, send_enc);
+
+    let received = ReceiveExport(
+        config,
+        &enc,
+        &sk_r,
+        &info,
+        b"TestContext".to_vec(),
+        32,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(exported, received);
+}
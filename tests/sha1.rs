@@ -0,0 +1,43 @@
+use libcrux::digest::{self, Algorithm};
+use libcrux_traits::Digest as _;
+
+#[test]
+fn sha1_kat() {
+    let d = digest::hash(Algorithm::Sha1, b"abc");
+    assert_eq!(hex::encode(d), "a9993e364706816aba3e25717850c26c9cd0d89d");
+
+    let mut digest = digest::Sha1::new();
+    let mut d = [0u8; 20];
+    digest.update(b"ab");
+    digest.update(b"c");
+    digest.finish(&mut d);
+    assert_eq!(d, digest::sha1(b"abc"));
+}
+
+/// The SHA-1 test vectors of RFC 6238, Appendix B.
+#[test]
+fn totp_sha1() {
+    let key = b"12345678901234567890";
+
+    for (time, expected) in [
+        (59u64, 94287082),
+        (1111111109, 7081804),
+        (1111111111, 14050471),
+        (1234567890, 89005924),
+        (2000000000, 69279037),
+        (20000000000, 65353130),
+    ] {
+        let counter = time / 30;
+        let tag = libcrux::hmac::hmac(
+            libcrux::hmac::Algorithm::Sha1,
+            key,
+            &counter.to_be_bytes(),
+            None,
+        );
+
+        // Dynamic truncation (RFC 4226, Section 5.3).
+        let offset = (tag[19] & 0xf) as usize;
+        let code = u32::from_be_bytes(tag[offset..offset + 4].try_into().unwrap()) & 0x7fffffff;
+        assert_eq!(code % 100_000_000, expected);
+    }
+}
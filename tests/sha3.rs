@@ -0,0 +1,56 @@
+use libcrux_traits::Digest as _;
+
+#[test]
+fn sha3_256_kat_streaming() {
+    let mut digest = libcrux::digest::Sha3_256::new();
+    let mut d = [0u8; 32];
+    digest.update(b"Hello, ");
+    digest.update(b"World!");
+    digest.finish(&mut d);
+
+    let expected = "1af17a664e3fa8e419b8ba05c2a173169df76162a5a286e0c405b460d478f7ef";
+    assert_eq!(hex::encode(d), expected);
+    assert_eq!(d, libcrux::digest::sha3_256(b"Hello, World!"));
+}
+
+#[test]
+fn sha3_clone() {
+    let mut hasher_224 = libcrux::digest::Sha3_224::new();
+    hasher_224.update(b"test 224");
+    let mut hasher224_2 = hasher_224.clone();
+    hasher_224.update(b"more 224");
+    hasher224_2.update(b"more 224");
+    let mut digest = [0u8; 28];
+    let mut digest_2 = [0u8; 28];
+    hasher_224.finish(&mut digest);
+    hasher224_2.finish(&mut digest_2);
+
+    assert_eq!(digest, digest_2);
+    assert_eq!(digest, libcrux::digest::sha3_224(b"test 224more 224"));
+
+    let mut hasher_384 = libcrux::digest::Sha3_384::new();
+    hasher_384.update(b"test 384");
+    let mut hasher384_2 = hasher_384.clone();
+    hasher_384.update(b"more 384");
+    hasher384_2.update(b"more 384");
+    let mut digest = [0u8; 48];
+    let mut digest_2 = [0u8; 48];
+    hasher_384.finish(&mut digest);
+    hasher384_2.finish(&mut digest_2);
+
+    assert_eq!(digest, digest_2);
+    assert_eq!(digest, libcrux::digest::sha3_384(b"test 384more 384"));
+
+    let mut hasher_512 = libcrux::digest::Sha3_512::new();
+    hasher_512.update(b"test 512");
+    let mut hasher512_2 = hasher_512.clone();
+    hasher_512.update(b"more 512");
+    hasher512_2.update(b"more 512");
+    let mut digest = [0u8; 64];
+    let mut digest_2 = [0u8; 64];
+    hasher_512.finish(&mut digest);
+    hasher512_2.finish(&mut digest_2);
+
+    assert_eq!(digest, digest_2);
+    assert_eq!(digest, libcrux::digest::sha3_512(b"test 512more 512"));
+}
@@ -0,0 +1,152 @@
+//! This module provides a common interface trait for authenticated
+//! encryption with associated data (AEAD).
+//!
+//! All operations write into caller-provided buffers and never allocate.
+//! Arguments are always given in the order key, input, output, tag,
+//! associated data, nonce.
+
+/// Errors during AEAD operations.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AeadError {
+    /// Indicates that the plaintext argument is too large for the AEAD.
+    PlaintextTooLarge,
+    /// Indicates that the ciphertext argument is too large for the AEAD.
+    CiphertextTooLarge,
+    /// Indicates that the associated data argument is too large for the AEAD.
+    AadTooLarge,
+    /// This indicates that the provided destination ciphertext does not fit the ciphertext and tag.
+    CiphertextTooShort,
+    /// This indicates that the provided destination plaintext is shorter than `ctxt.len() - TAG_LEN`
+    /// and thus will not fit the decrypted plaintext
+    PlaintextTooShort,
+    /// Indicates that the ciphertext is not a valid encryption under the given key and nonce.
+    InvalidCiphertext,
+}
+
+impl core::fmt::Display for AeadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            AeadError::PlaintextTooLarge => "The plaintext argument is too large for the AEAD",
+            AeadError::CiphertextTooLarge => "The ciphertext argument is too large for the AEAD",
+            AeadError::AadTooLarge => "The associated data argument is too large for the AEAD",
+            AeadError::CiphertextTooShort => {
+                "The provided destination ciphertext does not fit the ciphertext and tag"
+            }
+            AeadError::PlaintextTooShort => {
+                "The provided destination plaintext is too short to fit the decrypted plaintext"
+            }
+            AeadError::InvalidCiphertext => {
+                "The ciphertext is not a valid encryption under the given key and nonce."
+            }
+        };
+
+        f.write_str(msg)
+    }
+}
+
+/// This trait captures the required interface of an AEAD.
+///
+/// The [`Key`](Aead::Key), [`Nonce`](Aead::Nonce) and [`Tag`](Aead::Tag)
+/// types are byte arrays of length [`KEY_LEN`](Aead::KEY_LEN),
+/// [`NONCE_LEN`](Aead::NONCE_LEN) and [`TAG_LEN`](Aead::TAG_LEN).
+pub trait Aead {
+    /// The length of keys.
+    const KEY_LEN: usize;
+    /// The length of nonces.
+    const NONCE_LEN: usize;
+    /// The length of tags.
+    const TAG_LEN: usize;
+
+    /// The AEAD's key.
+    type Key: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+    /// The AEAD's nonce.
+    type Nonce: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+    /// The AEAD's tag.
+    type Tag: AsRef<[u8]> + AsMut<[u8]> + Default + for<'a> TryFrom<&'a [u8]>;
+
+    /// Encrypt `ptxt` into the first `ptxt.len()` bytes of `ctxt` and write
+    /// the tag to `tag`.
+    fn encrypt_detached(
+        key: &Self::Key,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        tag: &mut Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), AeadError>;
+
+    /// Decrypt `ctxt` with the detached `tag` into the first `ctxt.len()`
+    /// bytes of `ptxt`.
+    ///
+    /// If the tag is invalid, no plaintext is left in `ptxt`.
+    fn decrypt_detached(
+        key: &Self::Key,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        tag: &Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), AeadError>;
+
+    /// Encrypt `buffer` in place and write the tag to `tag`.
+    fn encrypt_in_place(
+        key: &Self::Key,
+        buffer: &mut [u8],
+        tag: &mut Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), AeadError>;
+
+    /// Decrypt `buffer` in place with the detached `tag`.
+    ///
+    /// If the tag is invalid, no plaintext is left in `buffer`.
+    fn decrypt_in_place(
+        key: &Self::Key,
+        buffer: &mut [u8],
+        tag: &Self::Tag,
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), AeadError>;
+
+    /// Encrypt `ptxt` and write the concatenation of ciphertext and tag to
+    /// the first `ptxt.len() + TAG_LEN` bytes of `ctxt`.
+    fn encrypt(
+        key: &Self::Key,
+        ptxt: &[u8],
+        ctxt: &mut [u8],
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), AeadError> {
+        if (ctxt.len() as u64) < (ptxt.len() as u64) + (Self::TAG_LEN as u64) {
+            return Err(AeadError::CiphertextTooShort);
+        }
+
+        let (ctxt, tag_bytes) = ctxt[..ptxt.len() + Self::TAG_LEN].split_at_mut(ptxt.len());
+        let mut tag = Self::Tag::default();
+        Self::encrypt_detached(key, ptxt, ctxt, &mut tag, aad, nonce)?;
+        tag_bytes.copy_from_slice(tag.as_ref());
+
+        Ok(())
+    }
+
+    /// Decrypt the concatenation of ciphertext and tag in `ctxt` into the
+    /// first `ctxt.len() - TAG_LEN` bytes of `ptxt`.
+    ///
+    /// If the tag is invalid, no plaintext is left in `ptxt`.
+    fn decrypt(
+        key: &Self::Key,
+        ptxt: &mut [u8],
+        ctxt: &[u8],
+        aad: &[u8],
+        nonce: &Self::Nonce,
+    ) -> Result<(), AeadError> {
+        if ctxt.len() < Self::TAG_LEN {
+            return Err(AeadError::InvalidCiphertext);
+        }
+
+        let (ctxt, tag) = ctxt.split_at(ctxt.len() - Self::TAG_LEN);
+        let tag = Self::Tag::try_from(tag).map_err(|_| AeadError::InvalidCiphertext)?;
+
+        Self::decrypt_detached(key, ptxt, ctxt, &tag, aad, nonce)
+    }
+}
@@ -0,0 +1,48 @@
+//! This module provides a common interface trait for extract-and-expand
+//! key derivation functions (KDFs).
+
+/// Errors during key derivation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum KdfError {
+    /// Indicates that the requested output is too long for the KDF.
+    OkmTooLarge,
+    /// Indicates that at least one argument is too large for the KDF.
+    ArgumentsTooLarge,
+}
+
+impl core::fmt::Display for KdfError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            KdfError::OkmTooLarge => "The requested output is too long for the KDF",
+            KdfError::ArgumentsTooLarge => "At least one argument is too large for the KDF",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+/// This trait captures the required interface of an extract-and-expand KDF.
+pub trait Kdf {
+    /// The length of pseudorandom keys.
+    const PRK_LEN: usize;
+
+    /// The KDF's pseudorandom key, a byte array of length
+    /// [`PRK_LEN`](Kdf::PRK_LEN).
+    type Prk: AsRef<[u8]>;
+
+    /// Extract a pseudorandom key from the input key material `ikm` and the
+    /// `salt`.
+    fn extract(salt: &[u8], ikm: &[u8]) -> Result<Self::Prk, KdfError>;
+
+    /// Expand the pseudorandom key `prk` with `info` into `okm`.
+    ///
+    /// The output length is the length of `okm`.
+    fn expand(okm: &mut [u8], prk: &[u8], info: &[u8]) -> Result<(), KdfError>;
+
+    /// Derive `okm` from `salt`, the input key material `ikm` and `info`,
+    /// by calling [`extract`](Kdf::extract) and [`expand`](Kdf::expand).
+    fn derive(okm: &mut [u8], salt: &[u8], ikm: &[u8], info: &[u8]) -> Result<(), KdfError> {
+        let prk = Self::extract(salt, ikm)?;
+        Self::expand(okm, prk.as_ref(), info)
+    }
+}
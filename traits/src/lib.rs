@@ -1,5 +1,6 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 /// A Hash algorithm returning hashes of length `HASH_LEN`.
@@ -20,4 +21,8 @@ pub trait Digest<const HASH_LEN: usize> {
     fn reset(&mut self);
 }
 
+pub mod aead;
+pub mod kdf;
 pub mod kem;
+pub mod mac;
+pub mod signature;
@@ -0,0 +1,32 @@
+//! This module provides a common interface trait for message
+//! authentication codes (MACs).
+
+/// Errors during MAC operations.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MacError {
+    /// Indicates that the tag is not valid for the data and key.
+    InvalidTag,
+}
+
+impl core::fmt::Display for MacError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MacError::InvalidTag => f.write_str("The tag is not valid for the data and key"),
+        }
+    }
+}
+
+/// This trait captures the required interface of a MAC.
+pub trait Mac {
+    /// The length of tags.
+    const TAG_LEN: usize;
+
+    /// The MAC's tag, a byte array of length [`TAG_LEN`](Mac::TAG_LEN).
+    type Tag: AsRef<[u8]>;
+
+    /// Compute the tag on `data` under `key`.
+    fn mac(key: &[u8], data: &[u8]) -> Self::Tag;
+
+    /// Check the `tag` on `data` under `key` in constant time.
+    fn verify(key: &[u8], data: &[u8], tag: &Self::Tag) -> Result<(), MacError>;
+}
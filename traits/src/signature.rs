@@ -0,0 +1,104 @@
+//! This module provides common interface traits for digital signature
+//! schemes.
+//!
+//! Signing and verification take an optional context string. Schemes
+//! without support for contexts reject a non-empty context with
+//! [`SignatureError::InvalidContext`].
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use rand::CryptoRng;
+
+/// Errors during signing or verification.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SignatureError {
+    /// Indicates that the key is not valid for the signature scheme.
+    InvalidKey,
+    /// Indicates that the context is too long or not supported by the signature scheme.
+    InvalidContext,
+    /// Indicates that the message is too large for the signature scheme.
+    MessageTooLarge,
+    /// Indicates that the signing randomness has the wrong length or can not be used.
+    InvalidRandomness,
+    /// Indicates that signing failed.
+    Signing,
+    /// Indicates that the signature is not valid for the message and key.
+    InvalidSignature,
+}
+
+impl core::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            SignatureError::InvalidKey => "The key is not valid for the signature scheme",
+            SignatureError::InvalidContext => {
+                "The context is too long or not supported by the signature scheme"
+            }
+            SignatureError::MessageTooLarge => "The message is too large for the signature scheme",
+            SignatureError::InvalidRandomness => {
+                "The signing randomness has the wrong length or can not be used"
+            }
+            SignatureError::Signing => "Signing failed",
+            SignatureError::InvalidSignature => {
+                "The signature is not valid for the message and key"
+            }
+        };
+
+        f.write_str(msg)
+    }
+}
+
+/// This trait captures the signing interface of a signature scheme.
+pub trait Signer {
+    /// The length of the randomness consumed when signing.
+    ///
+    /// This is zero for deterministic signature schemes.
+    const RANDOMNESS_LEN: usize;
+
+    /// The scheme's signing key.
+    type SigningKey;
+    /// The scheme's signature.
+    type Signature;
+
+    /// Sign `msg` in the context `ctx` with `key`, using the given
+    /// `randomness` of length [`RANDOMNESS_LEN`](Signer::RANDOMNESS_LEN).
+    fn sign_with_randomness(
+        key: &Self::SigningKey,
+        msg: &[u8],
+        ctx: &[u8],
+        randomness: &[u8],
+    ) -> Result<Self::Signature, SignatureError>;
+
+    /// Sign `msg` in the context `ctx` with `key`, drawing the randomness
+    /// from `rng`.
+    ///
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn sign(
+        key: &Self::SigningKey,
+        msg: &[u8],
+        ctx: &[u8],
+        rng: &mut impl CryptoRng,
+    ) -> Result<Self::Signature, SignatureError> {
+        let mut randomness = vec![0u8; Self::RANDOMNESS_LEN];
+        rng.fill_bytes(&mut randomness);
+
+        Self::sign_with_randomness(key, msg, ctx, &randomness)
+    }
+}
+
+/// This trait captures the verification interface of a signature scheme.
+pub trait Verifier {
+    /// The scheme's verification key.
+    type VerificationKey;
+    /// The scheme's signature.
+    type Signature;
+
+    /// Verify that `signature` is a valid signature on `msg` in the context
+    /// `ctx` under `key`.
+    fn verify(
+        key: &Self::VerificationKey,
+        msg: &[u8],
+        ctx: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<(), SignatureError>;
+}